@@ -8,6 +8,8 @@ import * as DropdownMenu from '@radix-ui/react-dropdown-menu';
 import { Dialog, DialogRoot, DialogTitle } from '~/components/ui/Dialog';
 import { jsPDF } from 'jspdf';
 import { toast } from 'react-toastify';
+import { db, getMcpAuditRecords } from '~/lib/persistence';
+import type { McpAuditRecord } from '~/lib/persistence/types';
 
 interface SelectOption {
   value: string;
@@ -35,6 +37,12 @@ const logLevelOptions: SelectOption[] = [
     icon: 'i-ph:cloud',
     color: '#3b82f6',
   },
+  {
+    value: 'mcp',
+    label: 'MCP Tools',
+    icon: 'i-ph:plugs-connected',
+    color: '#8b5cf6',
+  },
   {
     value: 'error',
     label: 'Errors',
@@ -61,6 +69,21 @@ const logLevelOptions: SelectOption[] = [
   },
 ];
 
+// MCP tool calls live in their own append-only log, they are shown alongside the event logs but never cleared
+function toAuditLogEntry(record: McpAuditRecord): LogEntry {
+  const approval = record.outcome === 'denied' ? 'denied' : 'approved';
+
+  return {
+    id: `mcp-${record.id}`,
+    timestamp: new Date(record.timestamp).toISOString(),
+    level: record.outcome === 'executed' ? 'info' : 'warning',
+    message: `${record.serverName}/${record.toolName} ${record.outcome} (${approval} by ${record.approver})`,
+    category: 'mcp',
+    duration: record.duration,
+    details: record,
+  };
+}
+
 interface LogEntryItemProps {
   log: LogEntry;
   isExpanded: boolean;
@@ -90,6 +113,15 @@ const LogEntryItem = ({ log, isExpanded: forceExpanded, use24Hour, showTimestamp
       };
     }
 
+    if (log.category === 'mcp') {
+      return {
+        icon: 'i-ph:plugs-connected',
+        color: 'text-violet-500 dark:text-violet-400',
+        bg: 'hover:bg-violet-500/10 dark:hover:bg-violet-500/20',
+        badge: 'text-violet-500 bg-violet-50 dark:bg-violet-500/10',
+      };
+    }
+
     if (log.category === 'api') {
       return {
         icon: 'i-ph:cloud',
@@ -162,6 +194,26 @@ const LogEntryItem = ({ log, isExpanded: forceExpanded, use24Hour, showTimestamp
       );
     }
 
+    if (log.category === 'mcp') {
+      return (
+        <div className="flex flex-col gap-2">
+          <div className="flex items-center gap-2 text-xs text-gray-500 dark:text-gray-400">
+            <span>Approver: {details.approver}</span>
+            <span>•</span>
+            <span>Result: {details.resultSize.toLocaleString()} chars</span>
+            <span>•</span>
+            <span>Duration: {details.duration}ms</span>
+          </div>
+          <div className="flex flex-col gap-1">
+            <div className="text-xs font-medium text-gray-700 dark:text-gray-300">Arguments:</div>
+            <pre className="text-xs text-gray-600 dark:text-gray-400 bg-gray-50 dark:bg-gray-800/50 rounded p-2 whitespace-pre-wrap">
+              {JSON.stringify(details.args, null, 2)}
+            </pre>
+          </div>
+        </div>
+      );
+    }
+
     if (log.category === 'api') {
       return (
         <div className="flex flex-col gap-2">
@@ -272,9 +324,25 @@ export function EventLogsTab() {
   const [showLevelFilter, setShowLevelFilter] = useState(false);
   const [isRefreshing, setIsRefreshing] = useState(false);
   const levelFilterRef = useRef<HTMLDivElement>(null);
+  const [auditLogs, setAuditLogs] = useState<LogEntry[]>([]);
+
+  const loadAuditLogs = useCallback(async () => {
+    if (!db) {
+      return;
+    }
+
+    const records = await getMcpAuditRecords(db);
+    setAuditLogs(records.map(toAuditLogEntry));
+  }, []);
+
+  useEffect(() => {
+    loadAuditLogs().catch((error) => logStore.logError('Failed to load MCP audit log', error));
+  }, [loadAuditLogs]);
 
   const filteredLogs = useMemo(() => {
-    const allLogs = Object.values(logs);
+    const allLogs = [...Object.values(logs), ...auditLogs].sort(
+      (a, b) => new Date(b.timestamp).getTime() - new Date(a.timestamp).getTime(),
+    );
 
     if (selectedLevel === 'all') {
       return allLogs.filter((log) =>
@@ -288,7 +356,7 @@ export function EventLogsTab() {
 
       return matchesType && matchesSearch;
     });
-  }, [logs, selectedLevel, searchQuery]);
+  }, [logs, auditLogs, selectedLevel, searchQuery]);
 
   // Add performance tracking on mount
   useEffect(() => {
@@ -345,7 +413,7 @@ export function EventLogsTab() {
     setIsRefreshing(true);
 
     try {
-      await logStore.refreshLogs();
+      await Promise.all([logStore.refreshLogs(), loadAuditLogs()]);
 
       const duration = performance.now() - startTime;
 
@@ -365,7 +433,7 @@ export function EventLogsTab() {
     } finally {
       setTimeout(() => setIsRefreshing(false), 500);
     }
-  }, [logs]);
+  }, [logs, loadAuditLogs]);
 
   // Log preference changes
   const handlePreferenceChange = useCallback((type: string, value: boolean) => {
@@ -5,7 +5,10 @@ import { Switch } from '~/components/ui/Switch';
 import { useSettings } from '~/lib/hooks/useSettings';
 import { classNames } from '~/utils/classNames';
 import { toast } from 'react-toastify';
+import { useStore } from '@nanostores/react';
 import { PromptLibrary } from '~/lib/common/prompt-library';
+import { customPromptsStore } from '~/lib/stores/prompts';
+import PromptTemplates from './PromptTemplates';
 
 interface FeatureToggle {
   id: string;
@@ -110,14 +113,19 @@ export default function FeaturesTab() {
     autoSelectTemplate,
     isLatestBranch,
     contextOptimizationEnabled,
+    lexicalContextEnabled,
     eventLogs,
     setAutoSelectTemplate,
     enableLatestBranch,
     enableContextOptimization,
+    enableLexicalContext,
+    visualSnapshotsEnabled,
+    enableVisualSnapshots,
     setEventLogs,
     setPromptId,
     promptId,
   } = useSettings();
+  const customPrompts = useStore(customPromptsStore);
 
   // Enable features by default on first load
   React.useEffect(() => {
@@ -164,6 +172,18 @@ export default function FeaturesTab() {
           break;
         }
 
+        case 'lexicalContext': {
+          enableLexicalContext(enabled);
+          toast.success(`Lexical context selection ${enabled ? 'enabled' : 'disabled'}`);
+          break;
+        }
+
+        case 'visualSnapshots': {
+          enableVisualSnapshots(enabled);
+          toast.success(`Visual snapshots ${enabled ? 'enabled' : 'disabled'}`);
+          break;
+        }
+
         case 'eventLogs': {
           setEventLogs(enabled);
           toast.success(`Event logging ${enabled ? 'enabled' : 'disabled'}`);
@@ -174,7 +194,14 @@ export default function FeaturesTab() {
           break;
       }
     },
-    [enableLatestBranch, setAutoSelectTemplate, enableContextOptimization, setEventLogs],
+    [
+      enableLatestBranch,
+      setAutoSelectTemplate,
+      enableContextOptimization,
+      enableLexicalContext,
+      enableVisualSnapshots,
+      setEventLogs,
+    ],
   );
 
   const features = {
@@ -212,7 +239,25 @@ export default function FeaturesTab() {
         tooltip: 'Enabled by default to record detailed logs of system events and user actions',
       },
     ],
-    beta: [],
+    beta: [
+      {
+        id: 'lexicalContext',
+        title: 'Lexical Context Selection',
+        description: 'Pick context files by the words they share with your message',
+        icon: 'i-ph:graph',
+        enabled: lexicalContextEnabled,
+        tooltip:
+          'Needs context optimization. Matching is by words and identifiers, not meaning, so "login page" does not find AuthForm.tsx; the model is asked when the ranking is unsure',
+      },
+      {
+        id: 'visualSnapshots',
+        title: 'Visual Snapshots',
+        description: 'Capture the preview after every artifact and diff it with the previous one',
+        icon: 'i-ph:images',
+        enabled: visualSnapshotsEnabled,
+        tooltip: 'The viewports are configured in the visual snapshots of the preview',
+      },
+    ],
   };
 
   return (
@@ -282,7 +327,7 @@ export default function FeaturesTab() {
               'transition-all duration-200',
             )}
           >
-            {PromptLibrary.getList().map((x) => (
+            {PromptLibrary.getList(Object.values(customPrompts)).map((x) => (
               <option key={x.id} value={x.id}>
                 {x.label}
               </option>
@@ -290,6 +335,8 @@ export default function FeaturesTab() {
           </select>
         </div>
       </motion.div>
+
+      <PromptTemplates />
     </div>
   );
 }
@@ -0,0 +1,339 @@
+import { useEffect, useMemo, useRef, useState } from 'react';
+import { useStore } from '@nanostores/react';
+import { toast } from 'react-toastify';
+import { ConfirmationDialog, Dialog, DialogButton, DialogRoot, DialogTitle } from '~/components/ui/Dialog';
+import {
+  PROMPT_TEMPLATE_VARIABLES,
+  renderPromptTemplate,
+  type CustomPrompt,
+  type PromptOptions,
+} from '~/lib/common/prompt-library';
+import {
+  customPromptsStore,
+  deleteCustomPrompt,
+  exportCustomPrompts,
+  importCustomPrompts,
+  loadCustomPrompts,
+  restorePromptVersion,
+  saveCustomPrompt,
+} from '~/lib/stores/prompts';
+import { supabaseConnection } from '~/lib/stores/supabase';
+import { defaultDesignScheme } from '~/types/design-scheme';
+import { MODIFICATIONS_TAG_NAME, WORK_DIR } from '~/utils/constants';
+import { allowedHTMLElements } from '~/utils/markdown';
+import { classNames } from '~/utils/classNames';
+
+const inputClassName = classNames(
+  'w-full px-3 py-2 rounded-lg text-sm',
+  'bg-smack-elements-background-depth-3 border border-smack-elements-borderColor',
+  'text-smack-elements-textPrimary',
+  'focus:outline-none focus:ring-1 focus:ring-smack-elements-focus',
+);
+
+const buttonClassName = classNames(
+  'flex items-center gap-1.5 px-3 py-1.5 rounded-lg text-sm',
+  'bg-smack-elements-background-depth-3 text-smack-elements-textPrimary',
+  'hover:bg-smack-elements-background-depth-4 transition-colors',
+);
+
+function downloadJson(json: string, fileName: string) {
+  const blob = new Blob([json], { type: 'application/json' });
+  const url = window.URL.createObjectURL(blob);
+  const a = document.createElement('a');
+  a.href = url;
+  a.download = fileName;
+  document.body.appendChild(a);
+  a.click();
+  window.URL.revokeObjectURL(url);
+  document.body.removeChild(a);
+}
+
+type Draft = Pick<CustomPrompt, 'label' | 'description' | 'template'> & { id?: string };
+
+function PromptEditor({ draft, onClose }: { draft: Draft; onClose: () => void }) {
+  const prompt = useStore(customPromptsStore)[draft.id ?? ''];
+  const supabase = useStore(supabaseConnection);
+  const [label, setLabel] = useState(draft.label);
+  const [description, setDescription] = useState(draft.description);
+  const [template, setTemplate] = useState(draft.template);
+  const [showPreview, setShowPreview] = useState(false);
+  const [isSaving, setIsSaving] = useState(false);
+  const textareaRef = useRef<HTMLTextAreaElement>(null);
+
+  // the options of the current workspace, the server fills in the same values when the prompt is used
+  const previewOptions: PromptOptions = useMemo(
+    () => ({
+      cwd: WORK_DIR,
+      allowedHtmlElements: allowedHTMLElements,
+      modificationTagName: MODIFICATIONS_TAG_NAME,
+      designScheme: defaultDesignScheme,
+      supabase: {
+        isConnected: supabase.isConnected,
+        hasSelectedProject: !!supabase.selectedProjectId,
+        credentials: { supabaseUrl: supabase.credentials?.supabaseUrl },
+      },
+    }),
+    [supabase],
+  );
+
+  const insertVariable = (name: string) => {
+    const textarea = textareaRef.current;
+    const placeholder = `{{${name}}}`;
+    const start = textarea?.selectionStart ?? template.length;
+    const end = textarea?.selectionEnd ?? template.length;
+
+    setTemplate(template.slice(0, start) + placeholder + template.slice(end));
+  };
+
+  const handleSave = async () => {
+    setIsSaving(true);
+
+    try {
+      await saveCustomPrompt({ id: draft.id, label: label.trim(), description: description.trim(), template });
+      toast.success(`Prompt "${label}" saved`);
+      onClose();
+    } catch (error) {
+      toast.error(`Failed to save the prompt: ${error instanceof Error ? error.message : String(error)}`);
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleRestore = async (version: number) => {
+    try {
+      const restored = await restorePromptVersion(prompt.id, version);
+      setTemplate(restored.template);
+      toast.success(`Restored version ${version} as version ${restored.version}`);
+    } catch (error) {
+      toast.error(`Failed to restore the version: ${error instanceof Error ? error.message : String(error)}`);
+    }
+  };
+
+  return (
+    <Dialog className="max-w-3xl w-full p-6" onClose={onClose}>
+      <div className="space-y-4">
+        <DialogTitle>{draft.id ? `Edit ${draft.label}` : 'New prompt'}</DialogTitle>
+
+        <div className="grid grid-cols-2 gap-3">
+          <input
+            value={label}
+            onChange={(e) => setLabel(e.target.value)}
+            placeholder="Name"
+            className={inputClassName}
+          />
+          <input
+            value={description}
+            onChange={(e) => setDescription(e.target.value)}
+            placeholder="Description"
+            className={inputClassName}
+          />
+        </div>
+
+        <div className="flex flex-wrap gap-1">
+          {PROMPT_TEMPLATE_VARIABLES.map((variable) => (
+            <button
+              key={variable.name}
+              title={variable.description}
+              onClick={() => insertVariable(variable.name)}
+              className="px-2 py-0.5 rounded text-xs font-mono bg-smack-elements-background-depth-3 text-smack-elements-textSecondary hover:text-smack-elements-textPrimary"
+            >
+              {`{{${variable.name}}}`}
+            </button>
+          ))}
+        </div>
+
+        {showPreview ? (
+          <pre className="h-80 overflow-auto p-3 rounded-lg text-xs whitespace-pre-wrap bg-smack-elements-background-depth-3 text-smack-elements-textPrimary">
+            {renderPromptTemplate(template, previewOptions)}
+          </pre>
+        ) : (
+          <textarea
+            ref={textareaRef}
+            value={template}
+            onChange={(e) => setTemplate(e.target.value)}
+            placeholder="You are a senior engineer working in {{cwd}}..."
+            className={classNames(inputClassName, 'h-80 font-mono text-xs resize-none')}
+          />
+        )}
+
+        <div className="flex items-center justify-between gap-2">
+          <div className="flex items-center gap-2">
+            <button onClick={() => setShowPreview(!showPreview)} className={buttonClassName}>
+              <div className={showPreview ? 'i-ph:pencil-simple' : 'i-ph:eye'} />
+              {showPreview ? 'Edit' : 'Preview'}
+            </button>
+            {!!prompt?.history.length && (
+              <select
+                value=""
+                onChange={(e) => handleRestore(Number(e.target.value))}
+                className={classNames(inputClassName, 'w-auto py-1.5')}
+              >
+                <option value="" disabled>
+                  Version {prompt.version}
+                </option>
+                {[...prompt.history].reverse().map((entry) => (
+                  <option key={entry.version} value={entry.version}>
+                    Restore version {entry.version} ({new Date(entry.updatedAt).toLocaleString()})
+                  </option>
+                ))}
+              </select>
+            )}
+          </div>
+          <div className="flex gap-2">
+            <DialogButton type="secondary" onClick={onClose}>
+              Cancel
+            </DialogButton>
+            <DialogButton type="primary" disabled={isSaving || !label.trim() || !template.trim()} onClick={handleSave}>
+              {isSaving ? 'Saving...' : 'Save'}
+            </DialogButton>
+          </div>
+        </div>
+      </div>
+    </Dialog>
+  );
+}
+
+/**
+ * Lets users write their own system prompts, keep versions of them and share them as JSON files.
+ */
+export default function PromptTemplates() {
+  const customPrompts = useStore(customPromptsStore);
+  const [draft, setDraft] = useState<Draft | null>(null);
+  const [promptToDelete, setPromptToDelete] = useState<CustomPrompt | null>(null);
+  const fileInputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    loadCustomPrompts();
+  }, []);
+
+  const prompts = Object.values(customPrompts).sort((a, b) => a.label.localeCompare(b.label));
+
+  const handleImport = async (file: File) => {
+    try {
+      const imported = await importCustomPrompts(await file.text());
+      toast.success(`Imported ${imported.length} prompt${imported.length === 1 ? '' : 's'}`);
+    } catch (error) {
+      toast.error(`Failed to import prompts: ${error instanceof Error ? error.message : String(error)}`);
+    }
+  };
+
+  const handleDelete = async () => {
+    if (!promptToDelete) {
+      return;
+    }
+
+    try {
+      await deleteCustomPrompt(promptToDelete.id);
+      toast.success(`Prompt "${promptToDelete.label}" deleted`);
+    } catch (error) {
+      toast.error(`Failed to delete the prompt: ${error instanceof Error ? error.message : String(error)}`);
+    } finally {
+      setPromptToDelete(null);
+    }
+  };
+
+  return (
+    <div className="rounded-lg p-4 bg-smack-elements-background-depth-2">
+      <div className="flex items-center justify-between gap-4 mb-3">
+        <div>
+          <h4 className="text-sm font-medium text-smack-elements-textPrimary">Custom Prompts</h4>
+          <p className="text-xs text-smack-elements-textSecondary mt-0.5">
+            Write your own system prompts, they show up in the prompt library once saved
+          </p>
+        </div>
+        <div className="flex gap-2">
+          <button onClick={() => fileInputRef.current?.click()} className={buttonClassName}>
+            <div className="i-ph:upload-simple" />
+            Import
+          </button>
+          {prompts.length > 0 && (
+            <button
+              onClick={() => downloadJson(exportCustomPrompts(), 'smack-prompts.json')}
+              className={buttonClassName}
+            >
+              <div className="i-ph:download-simple" />
+              Export all
+            </button>
+          )}
+          <button onClick={() => setDraft({ label: '', description: '', template: '' })} className={buttonClassName}>
+            <div className="i-ph:plus" />
+            New prompt
+          </button>
+          <input
+            ref={fileInputRef}
+            type="file"
+            accept="application/json,.json"
+            className="hidden"
+            onChange={(e) => {
+              const file = e.target.files?.[0];
+
+              if (file) {
+                handleImport(file);
+              }
+
+              e.target.value = '';
+            }}
+          />
+        </div>
+      </div>
+
+      {prompts.length === 0 ? (
+        <p className="text-sm text-smack-elements-textSecondary">No custom prompts yet</p>
+      ) : (
+        <div className="space-y-1">
+          {prompts.map((prompt) => (
+            <div
+              key={prompt.id}
+              className="flex items-center gap-3 p-2 rounded-md bg-smack-elements-background-depth-3 text-sm"
+            >
+              <div className="flex-1 min-w-0">
+                <div className="text-smack-elements-textPrimary truncate">
+                  {prompt.label}{' '}
+                  <span className="text-xs text-smack-elements-textTertiary">v{prompt.version}</span>
+                </div>
+                {prompt.description && (
+                  <div className="text-xs text-smack-elements-textSecondary truncate">{prompt.description}</div>
+                )}
+              </div>
+              <button
+                title="Edit"
+                onClick={() => setDraft(prompt)}
+                className="p-1 text-smack-elements-textSecondary hover:text-smack-elements-textPrimary"
+              >
+                <div className="i-ph:pencil-simple w-4 h-4" />
+              </button>
+              <button
+                title="Export to share"
+                onClick={() => downloadJson(exportCustomPrompts([prompt.id]), `${prompt.label}.prompt.json`)}
+                className="p-1 text-smack-elements-textSecondary hover:text-smack-elements-textPrimary"
+              >
+                <div className="i-ph:share-network w-4 h-4" />
+              </button>
+              <button
+                title="Delete"
+                onClick={() => setPromptToDelete(prompt)}
+                className="p-1 text-smack-elements-textSecondary hover:text-red-500"
+              >
+                <div className="i-ph:trash w-4 h-4" />
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <DialogRoot open={draft !== null} onOpenChange={(open) => !open && setDraft(null)}>
+        {draft && <PromptEditor draft={draft} onClose={() => setDraft(null)} />}
+      </DialogRoot>
+
+      <ConfirmationDialog
+        isOpen={promptToDelete !== null}
+        onClose={() => setPromptToDelete(null)}
+        onConfirm={handleDelete}
+        title="Delete prompt"
+        description={`Delete "${promptToDelete?.label}" and all its versions?`}
+        confirmLabel="Delete"
+        variant="destructive"
+      />
+    </div>
+  );
+}
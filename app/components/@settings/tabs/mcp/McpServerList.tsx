@@ -31,6 +31,7 @@ export default function McpServerList({
         const isAvailable = mcpServer.status === 'available';
         const isExpanded = expandedServer === serverName;
         const serverTools = isAvailable ? Object.entries(mcpServer.tools) : [];
+        const retry = mcpServer.status === 'unavailable' ? mcpServer.retry : undefined;
 
         return (
           <div key={serverName} className="flex flex-col p-2 rounded-md bg-smack-elements-background-depth-1">
@@ -61,6 +62,11 @@ export default function McpServerList({
               <div className="ml-2 flex-shrink-0">
                 {checkingServers ? (
                   <McpStatusBadge status="checking" />
+                ) : retry ? (
+                  <McpStatusBadge
+                    status="reconnecting"
+                    detail={`Attempt ${retry.attempt}, next at ${new Date(retry.nextRetryAt).toLocaleTimeString()}`}
+                  />
                 ) : (
                   <McpStatusBadge status={isAvailable ? 'available' : 'unavailable'} />
                 )}
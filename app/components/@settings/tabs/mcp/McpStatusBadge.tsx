@@ -1,6 +1,11 @@
 import { useMemo } from 'react';
 
-export default function McpStatusBadge({ status }: { status: 'checking' | 'available' | 'unavailable' }) {
+type McpStatusBadgeProps = {
+  status: 'checking' | 'available' | 'unavailable' | 'reconnecting';
+  detail?: string;
+};
+
+export default function McpStatusBadge({ status, detail }: McpStatusBadgeProps) {
   const { styles, label, icon, ariaLabel } = useMemo(() => {
     const base = 'px-2 py-0.5 rounded-full text-xs font-medium flex items-center gap-1 transition-colors';
 
@@ -23,13 +28,19 @@ export default function McpStatusBadge({ status }: { status: 'checking' | 'avail
         ariaLabel: 'Server unavailable',
         icon: <span className="i-ph:warning-circle w-3 h-3 text-current" aria-hidden="true" />,
       },
+      reconnecting: {
+        styles: `${base} bg-yellow-100 text-yellow-800 dark:bg-yellow-900/80 dark:text-yellow-200`,
+        label: 'Reconnecting...',
+        ariaLabel: 'Server unavailable, reconnecting',
+        icon: <span className="i-ph:arrows-clockwise w-3 h-3 text-current" aria-hidden="true" />,
+      },
     };
 
     return config[status];
   }, [status]);
 
   return (
-    <span className={styles} role="status" aria-live="polite" aria-label={ariaLabel}>
+    <span className={styles} role="status" aria-live="polite" aria-label={ariaLabel} title={detail}>
       {icon}
       {label}
     </span>
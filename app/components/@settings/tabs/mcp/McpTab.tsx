@@ -4,6 +4,8 @@ import type { MCPConfig } from '~/lib/services/mcpService';
 import { toast } from 'react-toastify';
 import { useMCPStore } from '~/lib/stores/mcp';
 import McpServerList from '~/components/@settings/tabs/mcp/McpServerList';
+import McpToolPoliciesSection from '~/components/@settings/tabs/mcp/McpToolPoliciesSection';
+import WorkbenchServerSection from '~/components/@settings/tabs/mcp/WorkbenchServerSection';
 
 const EXAMPLE_MCP_CONFIG: MCPConfig = {
   mcpServers: {
@@ -154,6 +156,8 @@ export default function McpTab() {
         />
       </section>
 
+      <McpToolPoliciesSection />
+
       <section aria-labelledby="config-section-heading">
         <h2 className="text-base font-medium text-smack-elements-textPrimary mb-3">Configuration</h2>
 
@@ -234,6 +238,8 @@ export default function McpTab() {
           </button>
         </div>
       </div>
+
+      <WorkbenchServerSection />
     </div>
   );
 }
@@ -0,0 +1,174 @@
+import { useState } from 'react';
+import type { Tool } from 'ai';
+import { useMCPStore } from '~/lib/stores/mcp';
+import { MAX_TOOL_PATTERN_LENGTH, type MCPToolPolicy, type MCPToolPolicyMode } from '~/lib/services/mcpToolPolicies';
+import { classNames } from '~/utils/classNames';
+
+const MODE_LABELS: Record<MCPToolPolicyMode, string> = {
+  ask: 'Always ask',
+  allow: 'Always allow',
+  'allow-matching': 'Allow matching arguments',
+  deny: 'Deny',
+};
+
+const selectClassName = classNames(
+  'px-2 py-1 rounded-md text-xs',
+  'bg-smack-elements-background-depth-3 border border-smack-elements-borderColor',
+  'text-smack-elements-textPrimary',
+  'focus:outline-none focus:ring-1 focus:ring-smack-elements-focus',
+);
+
+function getParameterNames(tool: Tool) {
+  const schema = (tool.parameters as { jsonSchema?: { properties?: Record<string, unknown> } })?.jsonSchema;
+  return Object.keys(schema?.properties ?? {});
+}
+
+type PolicyEditorProps = {
+  policy?: MCPToolPolicy;
+  parameterNames?: string[];
+  inheritLabel?: string;
+  onChange: (policy: MCPToolPolicy | undefined) => void;
+};
+
+function PolicyEditor({ policy, parameterNames = [], inheritLabel, onChange }: PolicyEditorProps) {
+  const modes = Object.keys(MODE_LABELS) as MCPToolPolicyMode[];
+
+  const handleModeChange = (value: string) => {
+    if (value === 'inherit') {
+      onChange(undefined);
+    } else {
+      onChange({ ...policy, mode: value as MCPToolPolicyMode });
+    }
+  };
+
+  const handlePatternChange = (parameterName: string, pattern: string) => {
+    const argPatterns = { ...policy?.argPatterns, [parameterName]: pattern };
+
+    if (!pattern) {
+      delete argPatterns[parameterName];
+    }
+
+    onChange({ mode: 'allow-matching', argPatterns });
+  };
+
+  return (
+    <div className="flex flex-col gap-2">
+      <select
+        value={policy?.mode ?? (inheritLabel ? 'inherit' : 'ask')}
+        onChange={(e) => handleModeChange(e.target.value)}
+        className={selectClassName}
+      >
+        {inheritLabel && <option value="inherit">{inheritLabel}</option>}
+        {modes
+          .filter((mode) => mode !== 'allow-matching' || parameterNames.length > 0)
+          .map((mode) => (
+            <option key={mode} value={mode}>
+              {MODE_LABELS[mode]}
+            </option>
+          ))}
+      </select>
+      {policy?.mode === 'allow-matching' && (
+        <div className="space-y-1">
+          {parameterNames.map((parameterName) => (
+            <label key={parameterName} className="flex items-center gap-2 text-xs text-smack-elements-textSecondary">
+              <span className="w-24 truncate" title={parameterName}>
+                {parameterName}
+              </span>
+              <input
+                value={policy.argPatterns?.[parameterName] ?? ''}
+                onChange={(e) => handlePatternChange(parameterName, e.target.value)}
+                placeholder="Pattern, * matches anything, e.g. /tmp/*"
+                maxLength={MAX_TOOL_PATTERN_LENGTH}
+                className={classNames(selectClassName, 'flex-1 font-mono')}
+              />
+            </label>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
+export default function McpToolPoliciesSection() {
+  const serverTools = useMCPStore((state) => state.serverTools);
+  const toolPolicies = useMCPStore((state) => state.settings.toolPolicies) ?? {};
+  const updateToolPolicies = useMCPStore((state) => state.updateToolPolicies);
+  const [expandedServer, setExpandedServer] = useState<string | null>(null);
+
+  const serverEntries = Object.entries(serverTools);
+
+  const setServerDefault = (serverName: string, policy: MCPToolPolicy | undefined) => {
+    updateToolPolicies({ ...toolPolicies, [serverName]: { ...toolPolicies[serverName], default: policy } });
+  };
+
+  const setToolPolicy = (serverName: string, toolName: string, policy: MCPToolPolicy | undefined) => {
+    const tools = { ...toolPolicies[serverName]?.tools, [toolName]: policy };
+
+    if (!policy) {
+      delete tools[toolName];
+    }
+
+    updateToolPolicies({ ...toolPolicies, [serverName]: { ...toolPolicies[serverName], tools } });
+  };
+
+  return (
+    <section aria-labelledby="tool-policies-heading">
+      <h2 id="tool-policies-heading" className="text-base font-medium text-smack-elements-textPrimary mb-1">
+        Tool Approval
+      </h2>
+      <p className="text-sm text-smack-elements-textSecondary mb-3">
+        Decide which tool calls run without asking. Rules for a tool override the server default, every call is
+        recorded in the event logs.
+      </p>
+
+      {serverEntries.length === 0 ? (
+        <p className="text-sm text-smack-elements-textSecondary">No MCP servers configured</p>
+      ) : (
+        <div className="space-y-2">
+          {serverEntries.map(([serverName, server]) => {
+            const isExpanded = expandedServer === serverName;
+            const tools = server.status === 'available' ? Object.entries(server.tools) : [];
+            const serverDefault = toolPolicies[serverName]?.default;
+
+            return (
+              <div key={serverName} className="p-2 rounded-md bg-smack-elements-background-depth-1">
+                <div className="flex items-center justify-between gap-2">
+                  <button
+                    onClick={() => setExpandedServer(isExpanded ? null : serverName)}
+                    className="flex items-center gap-1.5 text-sm font-medium text-smack-elements-textPrimary bg-transparent"
+                    aria-expanded={isExpanded}
+                  >
+                    <div className={`i-ph:${isExpanded ? 'caret-down' : 'caret-right'} w-3 h-3`} />
+                    {serverName}
+                  </button>
+                  <PolicyEditor policy={serverDefault} onChange={(policy) => setServerDefault(serverName, policy)} />
+                </div>
+
+                {isExpanded && (
+                  <div className="mt-2 ml-4 space-y-2">
+                    {tools.length === 0 && (
+                      <p className="text-xs text-smack-elements-textSecondary">The server's tools are unavailable</p>
+                    )}
+                    {tools.map(([toolName, tool]) => (
+                      <div key={toolName} className="flex items-start justify-between gap-2 text-xs">
+                        <span className="pt-1 text-smack-elements-textPrimary truncate" title={tool.description}>
+                          {toolName}
+                        </span>
+                        <PolicyEditor
+                          policy={toolPolicies[serverName]?.tools?.[toolName]}
+                          parameterNames={getParameterNames(tool)}
+                          inheritLabel={`Server default (${MODE_LABELS[serverDefault?.mode ?? 'ask']})`}
+                          onChange={(policy) => setToolPolicy(serverName, toolName, policy)}
+                        />
+                      </div>
+                    ))}
+                  </div>
+                )}
+              </div>
+            );
+          })}
+        </div>
+      )}
+    </section>
+  );
+}
@@ -0,0 +1,101 @@
+import { useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+import { Switch } from '~/components/ui/Switch';
+import { useMCPStore } from '~/lib/stores/mcp';
+import { isWorkbenchServerSupported, type WorkbenchServerStatus } from '~/lib/services/workbenchMcpServer';
+import { classNames } from '~/utils/classNames';
+
+const STATUS_LABELS: Record<WorkbenchServerStatus, { label: string; className: string }> = {
+  stopped: { label: 'Stopped', className: 'bg-smack-elements-textTertiary' },
+  connecting: { label: 'Connecting...', className: 'bg-yellow-500' },
+  connected: { label: 'Accepting connections', className: 'bg-green-500' },
+  error: { label: 'Connection lost, retrying...', className: 'bg-red-500' },
+};
+
+function CopyField({ label, value, secret }: { label: string; value: string; secret?: boolean }) {
+  const [isRevealed, setIsRevealed] = useState(false);
+
+  const handleCopy = () => {
+    navigator.clipboard.writeText(value);
+    toast.success(`${label} copied to clipboard`);
+  };
+
+  return (
+    <div>
+      <span className="block text-sm text-smack-elements-textSecondary mb-1">{label}</span>
+      <div className="flex items-center gap-2">
+        <code className="flex-1 px-3 py-2 rounded-lg text-xs font-mono truncate bg-smack-elements-background-depth-3 text-smack-elements-textPrimary">
+          {secret && !isRevealed ? '•'.repeat(32) : value}
+        </code>
+        {secret && (
+          <button
+            onClick={() => setIsRevealed(!isRevealed)}
+            title={isRevealed ? 'Hide' : 'Reveal'}
+            className="p-2 rounded-lg text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3"
+          >
+            <div className={isRevealed ? 'i-ph:eye-slash w-4 h-4' : 'i-ph:eye w-4 h-4'} />
+          </button>
+        )}
+        <button
+          onClick={handleCopy}
+          title="Copy"
+          className="p-2 rounded-lg text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3"
+        >
+          <div className="i-ph:copy w-4 h-4" />
+        </button>
+      </div>
+    </div>
+  );
+}
+
+export default function WorkbenchServerSection() {
+  const workbenchServer = useMCPStore((state) => state.settings.workbenchServer);
+  const status = useMCPStore((state) => state.workbenchServerStatus);
+  const setWorkbenchServerEnabled = useMCPStore((state) => state.setWorkbenchServerEnabled);
+  const [endpoint, setEndpoint] = useState('');
+  const [isSupported, setIsSupported] = useState(true);
+
+  useEffect(() => {
+    setEndpoint(`${window.location.origin}/api/mcp-workbench`);
+    isWorkbenchServerSupported().then(setIsSupported);
+  }, []);
+
+  const handleToggle = (enabled: boolean) => {
+    setWorkbenchServerEnabled(enabled).catch((error) => {
+      toast.error(`Failed to ${enabled ? 'start' : 'stop'} the workbench MCP server: ${error}`);
+    });
+  };
+
+  // requests of a Cloudflare deployment may reach different isolates, the bridge can't relay calls there
+  if (!isSupported) {
+    return null;
+  }
+
+  const { label, className } = STATUS_LABELS[status];
+
+  return (
+    <section aria-labelledby="workbench-server-heading">
+      <div className="flex justify-between items-center mb-3">
+        <h2 id="workbench-server-heading" className="text-base font-medium text-smack-elements-textPrimary">
+          Share this workbench over MCP
+        </h2>
+        <Switch checked={!!workbenchServer?.enabled} onCheckedChange={handleToggle} />
+      </div>
+      <p className="text-sm text-smack-elements-textSecondary mb-3">
+        Lets MCP clients such as Claude Desktop, Cursor or other agents read and write files, run commands and actions,
+        and screenshot previews in the workspace open in this tab. Anyone with the token can drive the workspace.
+      </p>
+
+      {workbenchServer?.enabled && (
+        <div className="space-y-3">
+          <div className="flex items-center gap-2 text-sm text-smack-elements-textSecondary">
+            <span className={classNames('w-2 h-2 rounded-full', className)} />
+            {label}
+          </div>
+          <CopyField label="Endpoint (streamable HTTP)" value={endpoint} />
+          <CopyField label="Bearer token" value={workbenchServer.token} secret />
+        </div>
+      )}
+    </section>
+  );
+}
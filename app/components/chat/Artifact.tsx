@@ -3,7 +3,9 @@ import { AnimatePresence, motion } from 'framer-motion';
 import { computed } from 'nanostores';
 import { memo, useEffect, useRef, useState } from 'react';
 import type { BundledLanguage, BundledTheme, HighlighterGeneric } from 'shiki';
+import { toast } from 'react-toastify';
 import type { ActionState } from '~/lib/runtime/action-runner';
+import { RollbackConflictError } from '~/lib/runtime/artifact-transaction';
 import { workbenchStore } from '~/lib/stores/workbench';
 import { classNames } from '~/utils/classNames';
 import { cubicEasingFn } from '~/utils/easings';
@@ -54,6 +56,28 @@ export const Artifact = memo(({ artifactId }: ArtifactProps) => {
     setShowActions(!showActions);
   };
 
+  const canRollback = artifact.closed && artifact.type !== 'bundled' && !artifact.transaction.isEmpty;
+
+  const rollback = async (force = false) => {
+    try {
+      await workbenchStore.rollbackArtifact(artifactId, { force });
+      toast.success(`Rolled back "${artifact.title}"`);
+    } catch (error) {
+      if (error instanceof RollbackConflictError) {
+        const message = `These files changed after "${artifact.title}":\n\n${error.paths.join('\n')}`;
+
+        if (window.confirm(`${message}\n\nRoll back and drop those changes?`)) {
+          await rollback(true);
+        }
+
+        return;
+      }
+
+      console.error('Failed to roll back artifact:', error);
+      toast.error('Failed to roll back artifact');
+    }
+  };
+
   useEffect(() => {
     if (actions.length && !showActions && !userToggledActions.current) {
       setShowActions(true);
@@ -103,6 +127,24 @@ export const Artifact = memo(({ artifactId }: ArtifactProps) => {
               </div>
             </div>
           </button>
+          {(canRollback || artifact.rollbackStatus) && (
+            <>
+              <div className="bg-smack-elements-artifacts-borderColor w-[1px]" />
+              <button
+                className="flex items-center gap-1.5 px-4 text-xs text-smack-elements-textSecondary bg-smack-elements-artifacts-background hover:bg-smack-elements-artifacts-backgroundHover hover:text-smack-elements-textPrimary disabled:cursor-default disabled:hover:bg-smack-elements-artifacts-background whitespace-nowrap"
+                disabled={!!artifact.rollbackStatus}
+                title="Restore every file this artifact wrote, deleted or created"
+                onClick={() => rollback()}
+              >
+                {artifact.rollbackStatus === 'rolling-back' ? (
+                  <div className="i-svg-spinners:90-ring-with-bg"></div>
+                ) : (
+                  <div className="i-ph:arrow-counter-clockwise"></div>
+                )}
+                {artifact.rollbackStatus === 'rolled-back' ? 'Rolled back' : 'Roll back'}
+              </button>
+            </>
+          )}
           {artifact.type !== 'bundled' && <div className="bg-smack-elements-artifacts-borderColor w-[1px]" />}
           <AnimatePresence>
             {actions.length && artifact.type !== 'bundled' && (
@@ -259,6 +301,30 @@ const ActionList = memo(({ actions }: ActionListProps) => {
                       {action.filePath}
                     </code>
                   </div>
+                ) : type === 'patch' || type === 'delete' || type === 'mkdir' ? (
+                  <div>
+                    {type === 'patch' ? 'Patch' : type === 'delete' ? 'Delete' : 'Create folder'}{' '}
+                    <code
+                      className="bg-smack-elements-artifacts-inlineCode-background text-smack-elements-artifacts-inlineCode-text px-1.5 py-1 rounded-md text-smack-elements-item-contentAccent hover:underline cursor-pointer"
+                      onClick={() => type === 'patch' && openArtifactInWorkbench(action.filePath)}
+                    >
+                      {action.filePath}
+                    </code>
+                  </div>
+                ) : type === 'rename' ? (
+                  <div>
+                    Move{' '}
+                    <code className="bg-smack-elements-artifacts-inlineCode-background text-smack-elements-artifacts-inlineCode-text px-1.5 py-1 rounded-md">
+                      {action.filePath}
+                    </code>{' '}
+                    to{' '}
+                    <code
+                      className="bg-smack-elements-artifacts-inlineCode-background text-smack-elements-artifacts-inlineCode-text px-1.5 py-1 rounded-md text-smack-elements-item-contentAccent hover:underline cursor-pointer"
+                      onClick={() => openArtifactInWorkbench(action.newFilePath)}
+                    >
+                      {action.newFilePath}
+                    </code>
+                  </div>
                 ) : type === 'shell' ? (
                   <div className="flex items-center w-full min-h-[28px]">
                     <span className="flex-1">Run command</span>
@@ -273,8 +339,31 @@ const ActionList = memo(({ actions }: ActionListProps) => {
                   >
                     <span className="flex-1">Start Application</span>
                   </a>
+                ) : type === 'test' ? (
+                  <div className="flex items-center w-full min-h-[28px]">
+                    <span className="flex-1">Test {action.title ?? 'the preview'}</span>
+                  </div>
                 ) : null}
               </div>
+              {type === 'test' && action.testResults && (
+                <ul className={classNames('mt-1 ml-6 flex flex-col gap-0.5 text-xs', { 'mb-3.5': !isLast })}>
+                  {action.testResults.map((result) => (
+                    <li key={result.line} className="flex flex-col">
+                      <div className="flex items-center gap-1.5">
+                        <div
+                          className={classNames({
+                            'i-ph:check text-smack-elements-icon-success': result.status === 'passed',
+                            'i-ph:x text-smack-elements-icon-error': result.status === 'failed',
+                            'i-ph:minus text-smack-elements-textTertiary': result.status === 'skipped',
+                          })}
+                        />
+                        <code className="text-smack-elements-textSecondary">{result.step}</code>
+                      </div>
+                      {result.error && <span className="ml-5 text-smack-elements-icon-error">{result.error}</span>}
+                    </li>
+                  ))}
+                </ul>
+              )}
               {(type === 'shell' || type === 'start') && (
                 <ShellCodeBlock
                   classsName={classNames('mt-1', {
@@ -15,7 +15,8 @@ import type {
   StepStartUIPart,
 } from '@ai-sdk/ui-utils';
 import { ToolInvocations } from './ToolInvocations';
-import type { ToolCallAnnotation } from '~/types/context';
+import type { ModelAnswerAnnotation, ToolCallAnnotation, UsageAnnotation } from '~/types/context';
+import { formatCost } from '~/utils/formatCost';
 
 interface AssistantMessageProps {
   content: string;
@@ -90,11 +91,13 @@ export const AssistantMessage = memo(
       codeContext = filteredAnnotations.find((annotation) => annotation.type === 'codeContext')?.files;
     }
 
-    const usage: {
-      completionTokens: number;
-      promptTokens: number;
-      totalTokens: number;
-    } = filteredAnnotations.find((annotation) => annotation.type === 'usage')?.value;
+    const usage: UsageAnnotation['value'] | undefined = filteredAnnotations.find(
+      (annotation) => annotation.type === 'usage',
+    )?.value;
+
+    const modelAnswer = filteredAnnotations.find((annotation) => annotation.type === 'modelAnswer') as
+      | ModelAnswerAnnotation
+      | undefined;
 
     const toolInvocations = parts?.filter((part) => part.type === 'tool-invocation');
     const toolCallAnnotations = filteredAnnotations.filter(
@@ -145,11 +148,22 @@ export const AssistantMessage = memo(
               </Popover>
             )}
             <div className="flex w-full items-center justify-between">
-              {usage && (
-                <div>
-                  Tokens: {usage.totalTokens} (prompt: {usage.promptTokens}, completion: {usage.completionTokens})
-                </div>
-              )}
+              <div className="flex flex-col gap-0.5">
+                {usage && (
+                  <div>
+                    Tokens: {usage.totalTokens} (prompt: {usage.promptTokens}, completion: {usage.completionTokens}
+                    {usage.cachedTokens ? `, cached: ${usage.cachedTokens}` : ''})
+                    {usage.cost !== undefined && <> · {formatCost(usage.cost)}</>}
+                  </div>
+                )}
+                {modelAnswer?.fallbackFrom && (
+                  <div className="flex items-center gap-1">
+                    <div className="i-ph:arrows-split" />
+                    Answered by {modelAnswer.provider} {modelAnswer.model} (fallback from{' '}
+                    {modelAnswer.fallbackFrom.provider}: {modelAnswer.fallbackFrom.reason.replace('_', ' ')})
+                  </div>
+                )}
+              </div>
               {(onRewind || onFork) && messageId && (
                 <div className="flex gap-2 flex-col lg:flex-row ml-auto">
                   {onRewind && (
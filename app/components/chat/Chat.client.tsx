@@ -7,7 +7,7 @@ import { memo, useCallback, useEffect, useRef, useState } from 'react';
 import { cssTransition, toast, ToastContainer } from 'react-toastify';
 import { useMessageParser, usePromptEnhancer, useShortcuts } from '~/lib/hooks';
 import { description, useChatHistory } from '~/lib/persistence';
-import { chatStore } from '~/lib/stores/chat';
+import { chatModelStore, chatStore } from '~/lib/stores/chat';
 import { workbenchStore } from '~/lib/stores/workbench';
 import { DEFAULT_MODEL, DEFAULT_PROVIDER, PROMPT_COOKIE_KEY, PROVIDER_LIST } from '~/utils/constants';
 import { cubicEasingFn } from '~/utils/easings';
@@ -28,7 +28,9 @@ import { defaultDesignScheme, type DesignScheme } from '~/types/design-scheme';
 import type { ElementInfo } from '~/components/workbench/Inspector';
 import type { TextUIPart, FileUIPart, Attachment } from '@ai-sdk/ui-utils';
 import { useMCPStore } from '~/lib/stores/mcp';
+import { customPromptsStore, loadCustomPrompts } from '~/lib/stores/prompts';
 import type { LlmErrorAlertType } from '~/types/actions';
+import { formatPreviewErrors } from '~/utils/previewErrors';
 
 const toastAnimation = cssTransition({
   enter: 'animated fadeInRight',
@@ -134,7 +136,8 @@ export const ChatImpl = memo(
       (project) => project.id === supabaseConn.selectedProjectId,
     );
     const supabaseAlert = useStore(workbenchStore.supabaseAlert);
-    const { activeProviders, promptId, autoSelectTemplate, contextOptimizationEnabled } = useSettings();
+    const { activeProviders, promptId, autoSelectTemplate, contextOptimizationEnabled, lexicalContextEnabled } =
+      useSettings();
     const [llmErrorAlert, setLlmErrorAlert] = useState<LlmErrorAlertType | undefined>(undefined);
     const [model, setModel] = useState(DEFAULT_MODEL);
     const [provider, setProvider] = useState(DEFAULT_PROVIDER as ProviderInfo);
@@ -144,6 +147,7 @@ export const ChatImpl = memo(
     const [chatMode, setChatMode] = useState<'discuss' | 'build'>('build');
     const [selectedElement, setSelectedElement] = useState<ElementInfo | null>(null);
     const mcpSettings = useMCPStore((state) => state.settings);
+    const customPrompts = useStore(customPromptsStore);
     const [retryCount, setRetryCount] = useState(0);
     const MAX_RETRIES = 3;
     const STREAM_TIMEOUT = 30000; // 30 seconds
@@ -170,7 +174,9 @@ export const ChatImpl = memo(
         apiKeys,
         files,
         promptId,
+        promptTemplate: customPrompts[promptId]?.template,
         contextOptimization: contextOptimizationEnabled,
+        lexicalContext: lexicalContextEnabled,
         chatMode,
         designScheme,
         supabase: {
@@ -182,6 +188,7 @@ export const ChatImpl = memo(
           },
         },
         maxLLMSteps: mcpSettings.maxLLMSteps,
+        toolPolicies: mcpSettings.toolPolicies,
       },
       sendExtraMessageFields: true,
       onError: (e) => {
@@ -329,6 +336,14 @@ export const ChatImpl = memo(
       chatStore.setKey('started', initialMessages.length > 0);
     }, [initialMessages]);
 
+    useEffect(() => {
+      loadCustomPrompts();
+    }, []);
+
+    useEffect(() => {
+      chatModelStore.set({ model, provider });
+    }, [model, provider]);
+
     useEffect(() => {
       processSampledMessages({
         messages,
@@ -350,7 +365,13 @@ export const ChatImpl = memo(
     };
 
     const sendMessage = async (_event: React.UIEvent, messageInput?: string) => {
-      const messageContent = messageInput || input;
+      const previewErrors = workbenchStore.attachedPreviewErrors.get();
+      let messageContent = messageInput || input;
+
+      if (previewErrors.length > 0) {
+        messageContent = `${messageContent || ''}\n\n${formatPreviewErrors(previewErrors)}`.trim();
+      }
+
       if (!messageContent?.trim()) return;
       if (isLoading) {
         stop();
@@ -383,6 +404,7 @@ export const ChatImpl = memo(
 
         append(messageToSend);
 
+        workbenchStore.attachedPreviewErrors.set([]);
         setUploadedFiles([]);
         setImageDataList([]);
         setInput('');
@@ -451,6 +473,7 @@ export const ChatImpl = memo(
       append(assistantMsg);
       append(userMsg);
 
+      workbenchStore.attachedPreviewErrors.set([]);
       setUploadedFiles([]);
       setImageDataList([]);
       setFakeLoading(false);
@@ -480,7 +503,7 @@ export const ChatImpl = memo(
         setProvider={(newProvider) => {
           setProvider(newProvider);
         }}
-        providerList={[DEFAULT_PROVIDER as ProviderInfo]}
+        providerList={activeProviders}
         handleInputChange={handleInputChange}
         handleStop={stop}
         description={description}
@@ -1,4 +1,5 @@
 import { AnimatePresence, motion } from 'framer-motion';
+import { workbenchStore } from '~/lib/stores/workbench';
 import type { ActionAlert } from '~/types/actions';
 import { classNames } from '~/utils/classNames';
 
@@ -12,10 +13,38 @@ export default function ChatAlert({ alert, clearAlert, postMessage }: Props) {
   const { description, content, source } = alert;
 
   const isPreview = source === 'preview';
-  const title = isPreview ? 'Preview Error' : 'Terminal Error';
-  const message = isPreview
-    ? 'We encountered an error while running the preview. Would you like smack to analyze and help resolve this issue?'
-    : 'We encountered an error while running terminal commands. Would you like smack to analyze and help resolve this issue?';
+  const isPatch = source === 'patch';
+  const isTest = source === 'test';
+  const title = isPatch
+    ? 'Patch Rejected'
+    : isTest
+      ? 'Preview Test Failed'
+      : isPreview
+        ? 'Preview Error'
+        : 'Terminal Error';
+  const message = isPatch
+    ? 'Some changes could not be applied because the file no longer matches the patch. Would you like smack to retry against the current file?'
+    : isTest
+      ? 'A check that smack wrote did not pass against the preview. Would you like smack to fix the app or the test?'
+      : isPreview
+        ? 'We encountered errors while running the preview. Ask smack to fix them and they are attached to your next message.'
+        : 'We encountered an error while running terminal commands. Would you like smack to analyze and help resolve this issue?';
+
+  const fixMessage = isPatch
+    ? `*Your patch was rejected.* Re-read the current file and retry with context lines or SEARCH blocks that match it exactly, or send the full file instead.\n\`\`\`diff\n${content}\n\`\`\`\n`
+    : isTest
+      ? `*Fix this failing preview test.* Fix the app if it is wrong, or the test if it no longer matches the app.\n\`\`\`\n${content}\n\`\`\`\n`
+      : `*Fix this ${isPreview ? 'preview' : 'terminal'} error* \n\`\`\`${isPreview ? 'js' : 'sh'}\n${content}\n\`\`\`\n`;
+
+  const handleAsk = () => {
+    // preview errors go with the next message, so that users can say what they were doing when they happened
+    if (isPreview && workbenchStore.previewErrors.get().length > 0) {
+      workbenchStore.attachPreviewErrors();
+      return;
+    }
+
+    postMessage(fixMessage);
+  };
 
   return (
     <AnimatePresence>
@@ -69,11 +98,7 @@ export default function ChatAlert({ alert, clearAlert, postMessage }: Props) {
             >
               <div className={classNames(' flex gap-2')}>
                 <button
-                  onClick={() =>
-                    postMessage(
-                      `*Fix this ${isPreview ? 'preview' : 'terminal'} error* \n\`\`\`${isPreview ? 'js' : 'sh'}\n${content}\n\`\`\`\n`,
-                    )
-                  }
+                  onClick={handleAsk}
                   className={classNames(
                     `px-2 py-1.5 rounded-md text-sm font-medium`,
                     'bg-smack-elements-button-primary-background',
@@ -84,7 +109,7 @@ export default function ChatAlert({ alert, clearAlert, postMessage }: Props) {
                   )}
                 >
                   <div className="i-ph:chat-circle-duotone"></div>
-                  Ask smack
+                  {isPreview ? 'Ask smack to fix' : 'Ask smack'}
                 </button>
                 <button
                   onClick={clearAlert}
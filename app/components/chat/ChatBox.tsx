@@ -1,4 +1,5 @@
 import React, { useState, useEffect } from 'react';
+import { useStore } from '@nanostores/react';
 import { ClientOnly } from 'remix-utils/client-only';
 import { classNames } from '~/utils/classNames';
 import FilePreview from './FilePreview';
@@ -15,7 +16,9 @@ import { ColorSchemeDialog } from '~/components/ui/ColorSchemeDialog';
 import type { DesignScheme } from '~/types/design-scheme';
 import type { ElementInfo } from '~/components/workbench/Inspector';
 import { McpTools } from './MCPTools';
+import { PromptPicker } from './PromptPicker';
 import { isMobile } from '~/utils/mobile';
+import { workbenchStore } from '~/lib/stores/workbench';
 
 interface ChatBoxProps {
   isModelSettingsCollapsed: boolean;
@@ -62,6 +65,7 @@ interface ChatBoxProps {
 
 export const ChatBox: React.FC<ChatBoxProps> = (props) => {
   const [isMobileView, setIsMobileView] = useState(false);
+  const attachedPreviewErrors = useStore(workbenchStore.attachedPreviewErrors);
 
   useEffect(() => {
     setIsMobileView(isMobile());
@@ -137,6 +141,23 @@ export const ChatBox: React.FC<ChatBoxProps> = (props) => {
           </button>
         </div>
       )}
+      {attachedPreviewErrors.length > 0 && (
+        <div className="flex mx-1.5 gap-2 items-center justify-between rounded-lg rounded-b-none border border-b-none border-smack-elements-borderColor text-smack-elements-textPrimary flex py-1 px-2.5 font-medium text-xs">
+          <div
+            className="flex gap-2 items-center"
+            title={attachedPreviewErrors.map((error) => error.message).join('\n')}
+          >
+            <div className="i-ph:warning-duotone text-smack-elements-button-danger-text" />
+            {attachedPreviewErrors.length} preview error{attachedPreviewErrors.length === 1 ? '' : 's'} attached
+          </div>
+          <button
+            className="bg-transparent text-accent-500 pointer-auto"
+            onClick={() => workbenchStore.attachedPreviewErrors.set([])}
+          >
+            Clear
+          </button>
+        </div>
+      )}
       <div
         className={classNames('relative shadow-lg border border-smack-elements-borderColor backdrop-blur rounded-xl overflow-hidden transition-all duration-200 hover:border-accent/50 hover:shadow-accent/10', {
           'border-accent/50 shadow-accent/20': props.input.length > 0 || props.isStreaming || props.uploadedFiles.length > 0
@@ -216,7 +237,12 @@ export const ChatBox: React.FC<ChatBoxProps> = (props) => {
         <ClientOnly>
           {() => (
             <SendButton
-              show={props.input.length > 0 || props.isStreaming || props.uploadedFiles.length > 0}
+              show={
+                props.input.length > 0 ||
+                props.isStreaming ||
+                props.uploadedFiles.length > 0 ||
+                attachedPreviewErrors.length > 0
+              }
               isStreaming={props.isStreaming}
               disabled={!props.providerList || props.providerList.length === 0}
               onClick={(event) => {
@@ -235,7 +261,22 @@ export const ChatBox: React.FC<ChatBoxProps> = (props) => {
         <div className="flex justify-between items-center text-sm p-4 pt-3 border-t border-smack-elements-borderColor/50">
           <div className="flex gap-1 items-center">
             <ColorSchemeDialog designScheme={props.designScheme} setDesignScheme={props.setDesignScheme} />
-            <McpTools />
+            <McpTools
+              onAttachResource={(file, dataUrl) => {
+                props.setUploadedFiles?.([...props.uploadedFiles, file]);
+                props.setImageDataList?.([...props.imageDataList, dataUrl]);
+              }}
+            />
+            <ClientOnly>
+              {() => (
+                <PromptPicker
+                  onUsePrompt={(text) => {
+                    props.handleInputChange?.({ target: { value: text } } as React.ChangeEvent<HTMLTextAreaElement>);
+                    props.textareaRef?.current?.focus();
+                  }}
+                />
+              )}
+            </ClientOnly>
             <IconButton
               title="Upload file"
               className={classNames('transition-all duration-200 hover:bg-accent/10', isMobileView && 'p-2')}
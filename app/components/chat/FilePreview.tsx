@@ -15,7 +15,19 @@ const FilePreview: React.FC<FilePreviewProps> = ({ files, imageDataList, onRemov
     <div className="flex flex-row overflow-x-auto mx-2 -mt-1 p-2 bg-smack-elements-background-depth-3 border border-b-none border-smack-elements-borderColor rounded-lg rounded-b-none">
       {files.map((file, index) => (
         <div key={file.name + file.size} className="mr-2 relative">
-          {imageDataList[index] && (
+          {imageDataList[index] && !file.type.startsWith('image/') && (
+            <div className="relative flex items-center gap-1.5 h-10 px-3 pr-6 rounded-lg bg-smack-elements-background-depth-2 text-xs text-smack-elements-textSecondary">
+              <div className="i-ph:file-text w-4 h-4 shrink-0" />
+              <span className="truncate max-w-40">{file.name}</span>
+              <button
+                onClick={() => onRemove(index)}
+                className="absolute -top-1 -right-1 z-10 bg-black rounded-full w-5 h-5 shadow-md hover:bg-gray-900 transition-colors flex items-center justify-center"
+              >
+                <div className="i-ph:x w-3 h-3 text-gray-200" />
+              </button>
+            </div>
+          )}
+          {imageDataList[index] && file.type.startsWith('image/') && (
             <div className="relative">
               <img src={imageDataList[index]} alt={file.name} className="max-h-20 rounded-lg" />
               <button
@@ -4,8 +4,13 @@ import { Dialog, DialogRoot, DialogClose, DialogTitle, DialogButton } from '~/co
 import { IconButton } from '~/components/ui/IconButton';
 import { useMCPStore } from '~/lib/stores/mcp';
 import McpServerList from '~/components/@settings/tabs/mcp/McpServerList';
+import { McpResourceList } from './McpResourceList';
 
-export function McpTools() {
+interface McpToolsProps {
+  onAttachResource?: (file: File, dataUrl: string) => void;
+}
+
+export function McpTools({ onAttachResource }: McpToolsProps) {
   const isInitialized = useMCPStore((state) => state.isInitialized);
   const serverTools = useMCPStore((state) => state.serverTools);
   const initialize = useMCPStore((state) => state.initialize);
@@ -110,6 +115,15 @@ export function McpTools() {
                   )}
                 </div>
 
+                {onAttachResource && serverEntries.length > 0 && (
+                  <McpResourceList
+                    onAttach={(file, dataUrl) => {
+                      onAttachResource(file, dataUrl);
+                      setIsDialogOpen(false);
+                    }}
+                  />
+                )}
+
                 <div>{error && <p className="mt-2 text-sm text-smack-elements-icon-error">{error}</p>}</div>
               </div>
 
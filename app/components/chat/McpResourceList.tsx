@@ -0,0 +1,107 @@
+import { useEffect, useMemo, useState } from 'react';
+import { toast } from 'react-toastify';
+import { useMCPStore } from '~/lib/stores/mcp';
+import type { MCPResource } from '~/lib/services/mcpService';
+import { classNames } from '~/utils/classNames';
+
+function toDataUrl(text: string, mimeType: string) {
+  const bytes = new TextEncoder().encode(text);
+  let binary = '';
+
+  bytes.forEach((byte) => {
+    binary += String.fromCharCode(byte);
+  });
+
+  return `data:${mimeType};base64,${btoa(binary)}`;
+}
+
+interface McpResourceListProps {
+  onAttach: (file: File, dataUrl: string) => void;
+}
+
+export function McpResourceList({ onAttach }: McpResourceListProps) {
+  const serverContext = useMCPStore((state) => state.serverContext);
+  const loadServerContext = useMCPStore((state) => state.loadServerContext);
+  const readResource = useMCPStore((state) => state.readResource);
+  const [isLoading, setIsLoading] = useState(false);
+  const [attaching, setAttaching] = useState<string | null>(null);
+
+  useEffect(() => {
+    setIsLoading(true);
+    loadServerContext()
+      .catch((error) => toast.error(`Failed to load MCP resources: ${error.message}`))
+      .finally(() => setIsLoading(false));
+  }, []);
+
+  const servers = useMemo(
+    () => Object.entries(serverContext).filter(([, context]) => context.resources.length > 0 || context.error),
+    [serverContext],
+  );
+
+  // resources are attached like uploaded files, text attachments reach the model as part of the message
+  const handleAttach = async (serverName: string, resource: MCPResource) => {
+    setAttaching(resource.uri);
+
+    try {
+      const contents = await readResource(serverName, resource.uri);
+      const text = contents.map((content) => content.text).join('\n\n');
+      const mimeType = resource.mimeType?.startsWith('text/') ? resource.mimeType : 'text/plain';
+      const file = new File([text], resource.name, { type: mimeType });
+
+      onAttach(file, toDataUrl(text, mimeType));
+    } catch (error) {
+      toast.error(`Failed to read ${resource.name}: ${error instanceof Error ? error.message : String(error)}`);
+    } finally {
+      setAttaching(null);
+    }
+  };
+
+  return (
+    <div>
+      <h3 className="text-sm font-medium text-smack-elements-textPrimary mb-2">Resources</h3>
+      {isLoading && servers.length === 0 && (
+        <div className="i-svg-spinners:90-ring-with-bg w-4 h-4 text-smack-elements-loader-progress animate-spin" />
+      )}
+      {!isLoading && servers.length === 0 && (
+        <p className="text-xs text-smack-elements-textSecondary">None of the servers provide resources</p>
+      )}
+      <div className="space-y-2">
+        {servers.map(([serverName, context]) => (
+          <div key={serverName} className="p-2 rounded-md bg-smack-elements-background-depth-1">
+            <div className="text-sm font-medium text-smack-elements-textPrimary">{serverName}</div>
+            {context.error && <p className="text-xs text-smack-elements-icon-error">{context.error}</p>}
+            <ul className="mt-1 space-y-1">
+              {context.resources.map((resource) => (
+                <li key={resource.uri} className="flex items-center justify-between gap-2 text-xs">
+                  <div className="min-w-0">
+                    <div className="text-smack-elements-textPrimary truncate">{resource.name}</div>
+                    <div className="text-smack-elements-textTertiary truncate" title={resource.description}>
+                      {resource.uri}
+                    </div>
+                  </div>
+                  <button
+                    onClick={() => handleAttach(serverName, resource)}
+                    disabled={attaching !== null}
+                    className={classNames(
+                      'shrink-0 px-2 py-1 rounded-md flex items-center gap-1',
+                      'bg-smack-elements-background-depth-3 hover:bg-smack-elements-background-depth-4',
+                      'text-smack-elements-textPrimary',
+                      'disabled:opacity-50 disabled:cursor-not-allowed',
+                    )}
+                  >
+                    {attaching === resource.uri ? (
+                      <div className="i-svg-spinners:90-ring-with-bg w-3 h-3 animate-spin" />
+                    ) : (
+                      <div className="i-ph:paperclip w-3 h-3" />
+                    )}
+                    Attach
+                  </button>
+                </li>
+              ))}
+            </ul>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
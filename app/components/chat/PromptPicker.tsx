@@ -0,0 +1,175 @@
+import { useEffect, useState } from 'react';
+import { useStore } from '@nanostores/react';
+import * as DropdownMenu from '@radix-ui/react-dropdown-menu';
+import { toast } from 'react-toastify';
+import { Dialog, DialogButton, DialogRoot, DialogTitle } from '~/components/ui/Dialog';
+import { PromptLibrary } from '~/lib/common/prompt-library';
+import { useSettings } from '~/lib/hooks/useSettings';
+import type { MCPPrompt } from '~/lib/services/mcpService';
+import { useMCPStore } from '~/lib/stores/mcp';
+import { customPromptsStore, loadCustomPrompts } from '~/lib/stores/prompts';
+import { classNames } from '~/utils/classNames';
+
+const itemClassName = classNames(
+  'cursor-pointer flex items-start gap-2 w-full px-3 py-2 text-sm rounded-md outline-none',
+  'text-smack-elements-textPrimary hover:bg-smack-elements-item-backgroundActive',
+);
+
+interface PromptPickerProps {
+  onUsePrompt: (text: string) => void;
+}
+
+/**
+ * Picks the system prompt from the prompt library, or fills the chat input with a prompt of an MCP server.
+ */
+export function PromptPicker({ onUsePrompt }: PromptPickerProps) {
+  const { promptId, setPromptId } = useSettings();
+  const customPrompts = useStore(customPromptsStore);
+  const serverContext = useMCPStore((state) => state.serverContext);
+  const loadServerContext = useMCPStore((state) => state.loadServerContext);
+  const getPrompt = useMCPStore((state) => state.getPrompt);
+  const [pendingPrompt, setPendingPrompt] = useState<{ serverName: string; prompt: MCPPrompt } | null>(null);
+  const [promptArgs, setPromptArgs] = useState<Record<string, string>>({});
+  const [isLoading, setIsLoading] = useState(false);
+
+  const mcpPrompts = Object.entries(serverContext).flatMap(([serverName, context]) =>
+    context.prompts.map((prompt) => ({ serverName, prompt })),
+  );
+
+  useEffect(() => {
+    loadCustomPrompts();
+  }, []);
+
+  const handleOpenChange = (open: boolean) => {
+    if (open) {
+      loadServerContext().catch((error) => toast.error(`Failed to load MCP prompts: ${error.message}`));
+    }
+  };
+
+  const applyPrompt = async (serverName: string, prompt: MCPPrompt, args: Record<string, string> = {}) => {
+    setIsLoading(true);
+
+    try {
+      onUsePrompt(await getPrompt(serverName, prompt.name, args));
+      setPendingPrompt(null);
+    } catch (error) {
+      toast.error(`Failed to get prompt ${prompt.name}: ${error instanceof Error ? error.message : String(error)}`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const selectMcpPrompt = (serverName: string, prompt: MCPPrompt) => {
+    if (prompt.arguments?.length) {
+      setPromptArgs({});
+      setPendingPrompt({ serverName, prompt });
+    } else {
+      applyPrompt(serverName, prompt);
+    }
+  };
+
+  const missingArgs = pendingPrompt?.prompt.arguments?.some((arg) => arg.required && !promptArgs[arg.name]);
+
+  return (
+    <>
+      <DropdownMenu.Root onOpenChange={handleOpenChange}>
+        <DropdownMenu.Trigger
+          title="Prompts"
+          className="flex items-center p-1 rounded-md bg-transparent text-smack-elements-item-contentDefault hover:text-smack-elements-item-contentActive hover:bg-accent/10 transition-all duration-200"
+        >
+          <div className="i-ph:book-open-text text-xl" />
+        </DropdownMenu.Trigger>
+        <DropdownMenu.Content
+          className={classNames(
+            'z-[250] w-80 max-h-96 overflow-y-auto p-1',
+            'bg-smack-elements-background-depth-2',
+            'rounded-lg shadow-lg',
+            'border border-smack-elements-borderColor',
+          )}
+          sideOffset={5}
+          align="start"
+        >
+          <DropdownMenu.Label className="px-3 py-1 text-xs text-smack-elements-textTertiary">
+            System prompt
+          </DropdownMenu.Label>
+          {PromptLibrary.getList(Object.values(customPrompts)).map((prompt) => (
+            <DropdownMenu.Item key={prompt.id} className={itemClassName} onSelect={() => setPromptId(prompt.id)}>
+              <div
+                className={classNames('i-ph:check w-4 h-4 mt-0.5 shrink-0', { invisible: prompt.id !== promptId })}
+              />
+              <div className="min-w-0">
+                <div>{prompt.label}</div>
+                <div className="text-xs text-smack-elements-textSecondary">{prompt.description}</div>
+              </div>
+            </DropdownMenu.Item>
+          ))}
+
+          {mcpPrompts.length > 0 && (
+            <>
+              <DropdownMenu.Separator className="my-1 h-px bg-smack-elements-borderColor" />
+              <DropdownMenu.Label className="px-3 py-1 text-xs text-smack-elements-textTertiary">
+                MCP prompts
+              </DropdownMenu.Label>
+            </>
+          )}
+          {mcpPrompts.map(({ serverName, prompt }) => (
+            <DropdownMenu.Item
+              key={`${serverName}/${prompt.name}`}
+              className={itemClassName}
+              onSelect={() => selectMcpPrompt(serverName, prompt)}
+            >
+              <div className="i-smack:mcp w-4 h-4 mt-0.5 shrink-0" />
+              <div className="min-w-0">
+                <div className="truncate">
+                  {prompt.name} <span className="text-xs text-smack-elements-textTertiary">{serverName}</span>
+                </div>
+                {prompt.description && (
+                  <div className="text-xs text-smack-elements-textSecondary">{prompt.description}</div>
+                )}
+              </div>
+            </DropdownMenu.Item>
+          ))}
+        </DropdownMenu.Content>
+      </DropdownMenu.Root>
+
+      <DialogRoot open={pendingPrompt !== null} onOpenChange={(open) => !open && setPendingPrompt(null)}>
+        {pendingPrompt && (
+          <Dialog className="max-w-md w-full p-6">
+            <div className="space-y-4">
+              <DialogTitle>{pendingPrompt.prompt.name}</DialogTitle>
+              {pendingPrompt.prompt.arguments?.map((arg) => (
+                <label key={arg.name} className="block text-sm text-smack-elements-textSecondary">
+                  {arg.name}
+                  {arg.required && <span className="text-red-600 dark:text-red-400 ml-1">*</span>}
+                  <input
+                    value={promptArgs[arg.name] ?? ''}
+                    onChange={(e) => setPromptArgs({ ...promptArgs, [arg.name]: e.target.value })}
+                    placeholder={arg.description}
+                    className={classNames(
+                      'mt-1 w-full px-3 py-2 rounded-lg text-sm',
+                      'bg-smack-elements-background-depth-3 border border-smack-elements-borderColor',
+                      'text-smack-elements-textPrimary',
+                      'focus:outline-none focus:ring-1 focus:ring-smack-elements-focus',
+                    )}
+                  />
+                </label>
+              ))}
+              <div className="flex justify-end gap-2">
+                <DialogButton type="secondary" onClick={() => setPendingPrompt(null)}>
+                  Cancel
+                </DialogButton>
+                <DialogButton
+                  type="primary"
+                  disabled={isLoading || missingArgs}
+                  onClick={() => applyPrompt(pendingPrompt.serverName, pendingPrompt.prompt, promptArgs)}
+                >
+                  {isLoading ? 'Loading...' : 'Use prompt'}
+                </DialogButton>
+              </div>
+            </div>
+          </Dialog>
+        )}
+      </DialogRoot>
+    </>
+  );
+}
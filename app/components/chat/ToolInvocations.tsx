@@ -1,6 +1,6 @@
 import type { ToolInvocationUIPart } from '@ai-sdk/ui-utils';
 import { AnimatePresence, motion } from 'framer-motion';
-import { memo, useMemo, useState, useEffect } from 'react';
+import { memo, useMemo, useState, useEffect, useRef } from 'react';
 import type { BundledLanguage, BundledTheme, HighlighterGeneric } from 'shiki';
 import { classNames } from '~/utils/classNames';
 import {
@@ -14,6 +14,8 @@ import { logger } from '~/utils/logger';
 import { themeStore, type Theme } from '~/lib/stores/theme';
 import { useStore } from '@nanostores/react';
 import type { ToolCallAnnotation } from '~/types/context';
+import { useMCPStore } from '~/lib/stores/mcp';
+import { resolveToolPolicy } from '~/lib/services/mcpToolPolicies';
 
 const highlighterOptions = {
   langs: ['json'],
@@ -295,10 +297,34 @@ interface ToolCallsListProps {
 
 const ToolCallsList = memo(({ toolInvocations, toolCallAnnotations, addToolResult }: ToolCallsListProps) => {
   const [expanded, setExpanded] = useState<{ [id: string]: boolean }>({});
+  const toolPolicies = useMCPStore((state) => state.settings.toolPolicies);
+  const settledByPolicy = useRef(new Set<string>());
 
   // OS detection for shortcut display
   const isMac = typeof navigator !== 'undefined' && /Mac|iPod|iPhone|iPad/.test(navigator.platform);
 
+  // answer calls that a policy allows or denies, the server checks the policy again before running them
+  useEffect(() => {
+    toolInvocations.forEach(({ toolInvocation }) => {
+      const { toolCallId, toolName, args } = toolInvocation;
+      const annotation = toolCallAnnotations.find((annotation) => annotation.toolCallId === toolCallId);
+
+      if (toolInvocation.state !== 'call' || !annotation || settledByPolicy.current.has(toolCallId)) {
+        return;
+      }
+
+      const decision = resolveToolPolicy(toolPolicies, annotation.serverName, toolName, args);
+
+      if (decision !== 'ask') {
+        settledByPolicy.current.add(toolCallId);
+        addToolResult({
+          toolCallId,
+          result: decision === 'allow' ? TOOL_EXECUTION_APPROVAL.APPROVE : TOOL_EXECUTION_APPROVAL.REJECT,
+        });
+      }
+    });
+  }, [toolInvocations, toolCallAnnotations, toolPolicies, addToolResult]);
+
   useEffect(() => {
     const expandedState: { [id: string]: boolean } = {};
     toolInvocations.forEach((inv) => {
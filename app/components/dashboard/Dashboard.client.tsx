@@ -27,6 +27,7 @@ import {
   HiOutlineCog
 } from 'react-icons/hi';
 import { classNames } from '~/utils/classNames';
+import { UsageOverview } from './UsageOverview';
 
 // Register ChartJS components
 ChartJS.register(
@@ -401,6 +402,9 @@ export function Dashboard() {
         </motion.div>
       </div>
 
+      {/* Token Usage */}
+      <UsageOverview timeRange={timeRange} />
+
       {/* Quick Actions */}
       <motion.div
         initial={{ opacity: 0, y: 20 }}
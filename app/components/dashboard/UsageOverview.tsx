@@ -0,0 +1,143 @@
+import { useEffect, useMemo, useState } from 'react';
+import { motion } from 'framer-motion';
+import { HiOutlineCurrencyDollar } from 'react-icons/hi';
+import { db, getAll, getUsageRecords } from '~/lib/persistence';
+import type { UsageRecord } from '~/lib/persistence/types';
+import { aggregateUsage, summarizeUsage } from '~/lib/persistence/usage';
+import { classNames } from '~/utils/classNames';
+import { formatCost } from '~/utils/formatCost';
+
+type GroupBy = 'project' | 'provider' | 'model' | 'user';
+
+const GROUPS: { id: GroupBy; label: string }[] = [
+  { id: 'project', label: 'Project' },
+  { id: 'provider', label: 'Provider' },
+  { id: 'model', label: 'Model' },
+  { id: 'user', label: 'User' },
+];
+
+const RANGE_DAYS = { '7d': 7, '30d': 30, '90d': 90 };
+
+function formatTokens(tokens: number) {
+  return new Intl.NumberFormat(undefined, { notation: 'compact', maximumFractionDigits: 1 }).format(tokens);
+}
+
+/**
+ * Token and cost totals recorded by the chats in this browser, to see which projects burn the budget.
+ */
+export function UsageOverview({ timeRange }: { timeRange: keyof typeof RANGE_DAYS }) {
+  const [records, setRecords] = useState<UsageRecord[]>([]);
+  const [chatNames, setChatNames] = useState<Record<string, string>>({});
+  const [groupBy, setGroupBy] = useState<GroupBy>('project');
+
+  useEffect(() => {
+    if (!db) {
+      return;
+    }
+
+    const since = Date.now() - RANGE_DAYS[timeRange] * 24 * 60 * 60 * 1000;
+
+    Promise.all([getUsageRecords(db, since), getAll(db)])
+      .then(([usageRecords, chats]) => {
+        setRecords(usageRecords);
+        setChatNames(Object.fromEntries(chats.map((chat) => [chat.id, chat.description || `Chat ${chat.id}`])));
+      })
+      .catch((error) => console.error('Failed to load token usage:', error));
+  }, [timeRange]);
+
+  const totals = useMemo(() => summarizeUsage(records), [records]);
+
+  const groups = useMemo(
+    () =>
+      aggregateUsage(records, (record) => {
+        switch (groupBy) {
+          case 'project':
+            return chatNames[record.chatId] ?? 'Deleted chat';
+          case 'provider':
+            return record.provider;
+          case 'model':
+            return `${record.provider} / ${record.model}`;
+          case 'user':
+            return record.userId ?? 'Signed out';
+        }
+      }),
+    [records, chatNames, groupBy],
+  );
+
+  const tiles = [
+    { name: 'Cost', value: formatCost(totals.cost) },
+    { name: 'Prompt Tokens', value: formatTokens(totals.promptTokens) },
+    { name: 'Cached Tokens', value: formatTokens(totals.cachedTokens) },
+    { name: 'Completion Tokens', value: formatTokens(totals.completionTokens) },
+  ];
+
+  return (
+    <motion.div
+      initial={{ opacity: 0, y: 20 }}
+      animate={{ opacity: 1, y: 0 }}
+      transition={{ delay: 0.8 }}
+      className="mt-8 rounded-2xl bg-white dark:bg-gray-800 p-6 shadow-lg"
+    >
+      <div className="flex items-center justify-between mb-4">
+        <h3 className="text-lg font-semibold text-gray-900 dark:text-white">Token Usage & Cost</h3>
+        <HiOutlineCurrencyDollar className="w-5 h-5 text-gray-400" />
+      </div>
+
+      <div className="grid grid-cols-2 lg:grid-cols-4 gap-4 mb-6">
+        {tiles.map((tile) => (
+          <div key={tile.name} className="rounded-xl bg-gray-50 dark:bg-gray-900/40 p-4">
+            <p className="text-sm font-medium text-gray-600 dark:text-gray-400">{tile.name}</p>
+            <p className="mt-1 text-2xl font-bold text-gray-900 dark:text-white">{tile.value}</p>
+          </div>
+        ))}
+      </div>
+
+      <div className="flex gap-2 mb-4">
+        {GROUPS.map((group) => (
+          <button
+            key={group.id}
+            onClick={() => setGroupBy(group.id)}
+            className={classNames(
+              'rounded-lg px-3 py-1.5 text-sm font-medium transition-colors',
+              groupBy === group.id
+                ? 'bg-purple-600 text-white'
+                : 'bg-gray-100 dark:bg-gray-700 text-gray-700 dark:text-gray-300 hover:bg-gray-200 dark:hover:bg-gray-600',
+            )}
+          >
+            {group.label}
+          </button>
+        ))}
+      </div>
+
+      {groups.length === 0 ? (
+        <p className="text-sm text-gray-500 dark:text-gray-400">No usage recorded in this period.</p>
+      ) : (
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-gray-500 dark:text-gray-400">
+              <th className="pb-2 font-medium">{GROUPS.find((group) => group.id === groupBy)?.label}</th>
+              <th className="pb-2 font-medium text-right">Responses</th>
+              <th className="pb-2 font-medium text-right">Prompt</th>
+              <th className="pb-2 font-medium text-right">Completion</th>
+              <th className="pb-2 font-medium text-right">Cost</th>
+            </tr>
+          </thead>
+          <tbody>
+            {groups.map((group) => (
+              <tr
+                key={group.key}
+                className="border-t border-gray-100 dark:border-gray-700 text-gray-900 dark:text-white"
+              >
+                <td className="py-2 truncate max-w-xs">{group.key}</td>
+                <td className="py-2 text-right">{group.requests}</td>
+                <td className="py-2 text-right">{formatTokens(group.promptTokens)}</td>
+                <td className="py-2 text-right">{formatTokens(group.completionTokens)}</td>
+                <td className="py-2 text-right font-medium">{formatCost(group.cost)}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+    </motion.div>
+  );
+}
@@ -1,6 +1,8 @@
 import { memo, useMemo, useState, useEffect, useCallback } from 'react';
 import { useStore } from '@nanostores/react';
 import { workbenchStore } from '~/lib/stores/workbench';
+import { fileHistoryStore } from '~/lib/stores/fileHistory';
+import type { FileVersion } from '~/lib/persistence/types';
 import type { FileMap } from '~/lib/stores/files';
 import type { EditorDocument } from '~/components/editor/codemirror/CodeMirrorEditor';
 import { diffLines, type Change } from 'diff';
@@ -19,6 +21,13 @@ interface CodeComparisonProps {
   filename: string;
   lightTheme: string;
   darkTheme: string;
+  history?: HistoryToggleProps;
+}
+
+interface HistoryToggleProps {
+  versionCount: number;
+  isOpen: boolean;
+  onToggle: () => void;
 }
 
 interface DiffBlock {
@@ -484,6 +493,7 @@ const FileInfo = memo(
     isFullscreen,
     beforeCode,
     afterCode,
+    history,
   }: {
     filename: string;
     hasChanges: boolean;
@@ -491,6 +501,7 @@ const FileInfo = memo(
     isFullscreen: boolean;
     beforeCode: string;
     afterCode: string;
+    history?: HistoryToggleProps;
   }) => {
     // Calculate additions and deletions from the current document
     const { additions, deletions } = useMemo(() => {
@@ -541,6 +552,20 @@ const FileInfo = memo(
           ) : (
             <span className="text-green-700 dark:text-green-400">No Changes</span>
           )}
+          {history && (
+            <button
+              onClick={history.onToggle}
+              className={`ml-2 flex items-center gap-1 px-1.5 py-0.5 rounded text-xs transition-colors hover:bg-smack-elements-background-depth-3 ${
+                history.isOpen
+                  ? 'text-smack-elements-textPrimary bg-smack-elements-background-depth-3'
+                  : 'text-smack-elements-textTertiary hover:text-smack-elements-textPrimary'
+              }`}
+              title={history.isOpen ? 'Hide Version History' : 'Show Version History'}
+            >
+              <div className="i-ph:clock-counter-clockwise" />
+              {history.versionCount}
+            </button>
+          )}
           <FullscreenButton onClick={onToggleFullscreen} isFullscreen={isFullscreen} />
         </span>
       </div>
@@ -591,7 +616,7 @@ const getSharedHighlighter = async () => {
   return highlighterInstance;
 };
 
-const InlineDiffComparison = memo(({ beforeCode, afterCode, filename, language }: CodeComparisonProps) => {
+const InlineDiffComparison = memo(({ beforeCode, afterCode, filename, language, history }: CodeComparisonProps) => {
   const [isFullscreen, setIsFullscreen] = useState(false);
 
   // Use state to hold the shared highlighter instance
@@ -641,6 +666,7 @@ const InlineDiffComparison = memo(({ beforeCode, afterCode, filename, language }
           isFullscreen={isFullscreen}
           beforeCode={beforeCode}
           afterCode={afterCode}
+          history={history}
         />
         <div className={diffPanelStyles}>
           {hasChanges ? (
@@ -667,6 +693,87 @@ const InlineDiffComparison = memo(({ beforeCode, afterCode, filename, language }
   );
 });
 
+const versionSourceLabels: Record<FileVersion['source'], string> = {
+  original: 'Original',
+  llm: 'AI',
+  user: 'You',
+};
+
+interface VersionTimelineProps {
+  versions: FileVersion[];
+  baseIndex?: number;
+  targetIndex?: number;
+  onSelectBase: (index: number | undefined) => void;
+  onSelectTarget: (index: number | undefined) => void;
+  onRestore: (version: FileVersion) => void;
+}
+
+/**
+ * Lists the recorded versions of the selected file, newest first. `A` picks the left side of the diff,
+ * `B` the right side, the editor content being the implicit latest entry.
+ */
+const VersionTimeline = memo(
+  ({ versions, baseIndex, targetIndex, onSelectBase, onSelectTarget, onRestore }: VersionTimelineProps) => {
+    const selectorClass = (active: boolean) =>
+      `w-5 h-5 rounded text-[10px] font-semibold transition-colors ${
+        active
+          ? 'bg-smack-elements-item-backgroundAccent text-smack-elements-item-contentAccent'
+          : 'bg-smack-elements-background-depth-3 text-smack-elements-textTertiary hover:text-smack-elements-textPrimary'
+      }`;
+
+    return (
+      <div className="w-64 shrink-0 h-full flex flex-col border-l border-smack-elements-borderColor bg-smack-elements-background-depth-1">
+        <div className="px-3 py-2 text-xs font-medium text-smack-elements-textSecondary border-b border-smack-elements-borderColor">
+          Version History
+        </div>
+        <div className="flex-1 overflow-y-auto">
+          <div className="flex items-center gap-2 px-3 py-2 text-xs border-b border-smack-elements-borderColor">
+            <div className="flex-1 text-smack-elements-textPrimary">Editor (current)</div>
+            <button className={selectorClass(targetIndex === undefined)} onClick={() => onSelectTarget(undefined)}>
+              B
+            </button>
+          </div>
+          {versions.length === 0 && (
+            <div className="px-3 py-4 text-xs text-smack-elements-textTertiary">
+              No versions recorded yet. Versions are added whenever you or the AI save this file.
+            </div>
+          )}
+          {versions
+            .map((version, index) => ({ version, index }))
+            .reverse()
+            .map(({ version, index }) => (
+              <div
+                key={version.id ?? `${version.timestamp}-${index}`}
+                className="group flex items-center gap-2 px-3 py-2 text-xs border-b border-smack-elements-borderColor"
+              >
+                <div className="flex-1 min-w-0">
+                  <div className="text-smack-elements-textPrimary">{new Date(version.timestamp).toLocaleString()}</div>
+                  <div className="text-smack-elements-textTertiary">{versionSourceLabels[version.source]}</div>
+                </div>
+                <button
+                  className="opacity-0 group-hover:opacity-100 text-smack-elements-textTertiary hover:text-smack-elements-textPrimary transition-opacity"
+                  title="Restore this version into the editor"
+                  onClick={() => onRestore(version)}
+                >
+                  <div className="i-ph:arrow-counter-clockwise" />
+                </button>
+                <button
+                  className={selectorClass(baseIndex === index)}
+                  onClick={() => onSelectBase(baseIndex === index ? undefined : index)}
+                >
+                  A
+                </button>
+                <button className={selectorClass(targetIndex === index)} onClick={() => onSelectTarget(index)}>
+                  B
+                </button>
+              </div>
+            ))}
+        </div>
+      </div>
+    );
+  },
+);
+
 interface DiffViewProps {
   fileHistory: Record<string, FileHistory>;
   setFileHistory: React.Dispatch<React.SetStateAction<Record<string, FileHistory>>>;
@@ -677,6 +784,24 @@ export const DiffView = memo(({ fileHistory, setFileHistory }: DiffViewProps) =>
   const selectedFile = useStore(workbenchStore.selectedFile);
   const currentDocument = useStore(workbenchStore.currentDocument) as EditorDocument;
   const unsavedFiles = useStore(workbenchStore.unsavedFiles);
+  const versionsByFile = useStore(fileHistoryStore.versions);
+  const [isHistoryOpen, setIsHistoryOpen] = useState(false);
+  const [baseIndex, setBaseIndex] = useState<number | undefined>();
+  const [targetIndex, setTargetIndex] = useState<number | undefined>();
+
+  useEffect(() => {
+    setBaseIndex(undefined);
+    setTargetIndex(undefined);
+
+    if (selectedFile) {
+      fileHistoryStore.load(selectedFile);
+    }
+  }, [selectedFile]);
+
+  const restoreVersion = useCallback((version: FileVersion) => {
+    workbenchStore.setCurrentDocumentContent(version.content);
+    setTargetIndex(undefined);
+  }, []);
 
   useEffect(() => {
     if (selectedFile && currentDocument) {
@@ -776,17 +901,38 @@ export const DiffView = memo(({ fileHistory, setFileHistory }: DiffViewProps) =>
   const effectiveOriginalContent = history?.originalContent || originalContent;
   const language = getLanguageFromExtension(selectedFile.split('.').pop() || '');
 
+  const versions = versionsByFile[selectedFile] ?? [];
+  const beforeCode = baseIndex !== undefined ? (versions[baseIndex]?.content ?? '') : effectiveOriginalContent;
+  const afterCode = targetIndex !== undefined ? (versions[targetIndex]?.content ?? '') : currentContent;
+
   try {
     return (
-      <div className="h-full overflow-hidden">
-        <InlineDiffComparison
-          beforeCode={effectiveOriginalContent}
-          afterCode={currentContent}
-          language={language}
-          filename={selectedFile}
-          lightTheme="github-light"
-          darkTheme="github-dark"
-        />
+      <div className="h-full overflow-hidden flex">
+        <div className="flex-1 min-w-0 h-full">
+          <InlineDiffComparison
+            beforeCode={beforeCode}
+            afterCode={afterCode}
+            language={language}
+            filename={selectedFile}
+            lightTheme="github-light"
+            darkTheme="github-dark"
+            history={{
+              versionCount: versions.length,
+              isOpen: isHistoryOpen,
+              onToggle: () => setIsHistoryOpen((open) => !open),
+            }}
+          />
+        </div>
+        {isHistoryOpen && (
+          <VersionTimeline
+            versions={versions}
+            baseIndex={baseIndex}
+            targetIndex={targetIndex}
+            onSelectBase={setBaseIndex}
+            onSelectTarget={setTargetIndex}
+            onRestore={restoreVersion}
+          />
+        )}
       </div>
     );
   } catch (error) {
@@ -25,6 +25,7 @@ import { workbenchStore } from '~/lib/stores/workbench';
 import { Search } from './Search'; // <-- Ensure Search is imported
 import { classNames } from '~/utils/classNames'; // <-- Import classNames if not already present
 import { LockManager } from './LockManager'; // <-- Import LockManager
+import { GitPanel } from './GitPanel';
 
 interface EditorPanelProps {
   files?: FileMap;
@@ -106,6 +107,14 @@ export const EditorPanel = memo(
                         >
                           Search
                         </Tabs.Trigger>
+                        <Tabs.Trigger
+                          value="git"
+                          className={classNames(
+                            'h-full bg-transparent hover:bg-smack-elements-background-depth-3 py-0.5 px-2 rounded-lg text-sm font-medium text-smack-elements-textTertiary hover:text-smack-elements-textPrimary data-[state=active]:text-smack-elements-textPrimary',
+                          )}
+                        >
+                          Git
+                        </Tabs.Trigger>
                         <Tabs.Trigger
                           value="locks"
                           className={classNames(
@@ -135,6 +144,10 @@ export const EditorPanel = memo(
                     <Search />
                   </Tabs.Content>
 
+                  <Tabs.Content value="git" className="flex-grow overflow-auto focus-visible:outline-none">
+                    <GitPanel />
+                  </Tabs.Content>
+
                   <Tabs.Content value="locks" className="flex-grow overflow-auto focus-visible:outline-none">
                     <LockManager />
                   </Tabs.Content>
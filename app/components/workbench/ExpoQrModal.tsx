@@ -7,10 +7,15 @@ import { QRCode } from 'react-qrcode-logo';
 interface ExpoQrModalProps {
   open: boolean;
   onClose: () => void;
+
+  // shows this address instead of the Expo one, for previews shared on the local network
+  url?: string;
+  description?: string;
 }
 
-export const ExpoQrModal: React.FC<ExpoQrModalProps> = ({ open, onClose }) => {
+export const ExpoQrModal: React.FC<ExpoQrModalProps> = ({ open, onClose, url, description }) => {
   const expoUrl = useStore(expoUrlAtom);
+  const value = url ?? expoUrl;
 
   return (
     <DialogRoot open={open} onOpenChange={(v) => !v && onClose()}>
@@ -20,15 +25,15 @@ export const ExpoQrModal: React.FC<ExpoQrModalProps> = ({ open, onClose }) => {
         onClose={onClose}
       >
         <div className="border !border-smack-elements-borderColor flex flex-col gap-5 justify-center items-center p-6 bg-smack-elements-background-depth-2 rounded-md">
-          <div className="i-smack:expo-brand h-10 w-full invert dark:invert-none"></div>
+          {!url && <div className="i-smack:expo-brand h-10 w-full invert dark:invert-none"></div>}
           <DialogTitle className="text-smack-elements-textTertiary text-lg font-semibold leading-6">
             Preview on your own mobile device
           </DialogTitle>
           <DialogDescription className="bg-smack-elements-background-depth-3 max-w-sm rounded-md p-1 border border-smack-elements-borderColor">
-            Scan this QR code with the Expo Go app on your mobile device to open your project.
+            {description ?? 'Scan this QR code with the Expo Go app on your mobile device to open your project.'}
           </DialogDescription>
           <div className="my-6 flex flex-col items-center">
-            {expoUrl ? (
+            {value ? (
               <QRCode
                 logoImage="/favicon.svg"
                 removeQrCodeBehindLogo={true}
@@ -41,12 +46,13 @@ export const ExpoQrModal: React.FC<ExpoQrModalProps> = ({ open, onClose }) => {
                   padding: 2,
                   backgroundColor: '#8a5fff',
                 }}
-                value={expoUrl}
+                value={value}
                 size={200}
               />
             ) : (
               <div className="text-gray-500 text-center">No Expo URL detected.</div>
             )}
+            {url && <code className="mt-4 text-sm text-smack-elements-textSecondary select-all">{url}</code>}
           </div>
         </div>
       </Dialog>
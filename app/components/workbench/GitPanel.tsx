@@ -0,0 +1,475 @@
+import { useCallback, useEffect, useMemo, useState } from 'react';
+import { useStore } from '@nanostores/react';
+import { toast } from 'react-toastify';
+import type { ReadCommitResult } from 'isomorphic-git';
+import { useGit, type GitFileDiff, type GitFileStatus, type GitRepository } from '~/lib/hooks/useGit';
+import { chatModelStore } from '~/lib/stores/chat';
+import { workbenchStore } from '~/lib/stores/workbench';
+import { classNames } from '~/utils/classNames';
+import { generateCommitMessage } from '~/utils/commitMessage';
+import { debounce } from '~/utils/debounce';
+import type { GitHunk } from '~/utils/gitHunks';
+
+const sectionTitleClassName =
+  'flex items-center justify-between px-3 pt-3 pb-1 text-xs font-medium uppercase text-smack-elements-textTertiary';
+
+const iconButtonClassName =
+  'flex items-center justify-center w-6 h-6 shrink-0 rounded bg-transparent text-smack-elements-textTertiary hover:text-smack-elements-textPrimary hover:bg-smack-elements-background-depth-3 disabled:opacity-50';
+
+const inputClassName =
+  'w-full px-2 py-1 text-xs rounded-md bg-smack-elements-background-depth-3 text-smack-elements-textPrimary placeholder-smack-elements-textTertiary border border-smack-elements-borderColor focus:outline-none';
+
+const buttonClassName =
+  'flex items-center justify-center gap-1 px-2 py-1 text-xs rounded-md bg-smack-elements-background-depth-3 text-smack-elements-textPrimary hover:bg-smack-elements-background-depth-4 disabled:opacity-50';
+
+function assertNoUnsavedChanges() {
+  if (workbenchStore.unsavedFiles.get().size > 0) {
+    throw new Error('Save or discard the changes in the editor first');
+  }
+}
+
+function getStatusLabel(file: GitFileStatus) {
+  if (file.untracked) {
+    return { letter: 'U', className: 'text-green-500' };
+  }
+
+  if (file.deleted) {
+    return { letter: 'D', className: 'text-red-500' };
+  }
+
+  return { letter: 'M', className: 'text-yellow-500' };
+}
+
+function HunkView({ hunk, action, onAction }: { hunk: GitHunk; action: string; onAction: () => void }) {
+  return (
+    <div className="mx-2 mb-1 rounded border border-smack-elements-borderColor overflow-hidden">
+      <div className="flex items-center justify-between px-2 py-0.5 bg-smack-elements-background-depth-3">
+        <span className="font-mono text-xs text-smack-elements-textTertiary">{hunk.header}</span>
+        <button
+          onClick={onAction}
+          className="text-xs bg-transparent text-smack-elements-item-contentAccent hover:underline"
+        >
+          {action}
+        </button>
+      </div>
+      <pre className="font-mono text-xs overflow-x-auto">
+        {hunk.lines
+          .filter((line) => !line.startsWith('\\'))
+          .map((line, index) => (
+            <div
+              key={index}
+              className={classNames('px-2', {
+                'bg-green-500/10 text-green-500': line.startsWith('+'),
+                'bg-red-500/10 text-red-500': line.startsWith('-'),
+                'text-smack-elements-textTertiary': line.startsWith(' '),
+              })}
+            >
+              {line}
+            </div>
+          ))}
+      </pre>
+    </div>
+  );
+}
+
+interface FileRowProps {
+  repo: GitRepository;
+  file: GitFileStatus;
+  staged: boolean;
+
+  // changes after every refresh, so that an expanded diff is reloaded
+  version: number;
+  run: (label: string, operation: () => Promise<unknown>) => void;
+}
+
+function FileRow({ repo, file, staged, version, run }: FileRowProps) {
+  const [expanded, setExpanded] = useState(false);
+  const [diff, setDiff] = useState<GitFileDiff>();
+  const status = getStatusLabel(file);
+
+  useEffect(() => {
+    if (!expanded) {
+      return;
+    }
+
+    repo
+      .getFileDiff(file.filepath)
+      .then(setDiff)
+      .catch((error) => toast.error(`Failed to load the diff: ${error}`));
+  }, [expanded, version, repo, file.filepath]);
+
+  const hunks = staged ? diff?.stagedHunks : diff?.unstagedHunks;
+  const hasText = staged ? diff?.stage !== undefined || diff?.head !== undefined : diff?.workdir !== undefined;
+
+  return (
+    <div>
+      <div className="group flex items-center gap-1 pl-3 pr-2 py-0.5 hover:bg-smack-elements-background-depth-3">
+        <button
+          onClick={() => setExpanded(!expanded)}
+          className="flex-1 flex items-center gap-1 min-w-0 text-left bg-transparent text-sm text-smack-elements-textSecondary"
+          title={file.filepath}
+        >
+          <span
+            className="i-ph:caret-right w-3 h-3 shrink-0 transition-transform"
+            style={{ transform: expanded ? 'rotate(90deg)' : undefined }}
+          />
+          <span className="truncate">{file.filepath.split('/').pop()}</span>
+          <span className="truncate text-xs text-smack-elements-textTertiary">{file.filepath}</span>
+        </button>
+        <button
+          title={staged ? 'Unstage Changes' : 'Stage Changes'}
+          onClick={() =>
+            run(staged ? 'Unstage' : 'Stage', () =>
+              staged ? repo.unstageFile(file.filepath) : repo.stageFile(file.filepath),
+            )
+          }
+          className={classNames(iconButtonClassName, 'opacity-0 group-hover:opacity-100')}
+        >
+          <span className={staged ? 'i-ph:minus' : 'i-ph:plus'} />
+        </button>
+        <span className={classNames('w-3 text-xs font-mono', status.className)}>{status.letter}</span>
+      </div>
+      {expanded && diff && !hasText && (
+        <div className="px-6 py-1 text-xs text-smack-elements-textTertiary">
+          No text diff, stage the file as a whole
+        </div>
+      )}
+      {expanded &&
+        hasText &&
+        hunks?.map((hunk) => (
+          <HunkView
+            key={hunk.header}
+            hunk={hunk}
+            action={staged ? 'Unstage hunk' : 'Stage hunk'}
+            onAction={() =>
+              run(staged ? 'Unstage' : 'Stage', () =>
+                staged
+                  ? repo.unstageHunks(file.filepath, [hunk.header])
+                  : repo.stageHunks(file.filepath, [hunk.header]),
+              )
+            }
+          />
+        ))}
+    </div>
+  );
+}
+
+/**
+ * Git workflow for the project in the WebContainer: staging of files and hunks, commits, branches, stashes, the log
+ * and pushing to the `origin` remote.
+ */
+export function GitPanel() {
+  const { ready, repo } = useGit();
+  const files = useStore(workbenchStore.files);
+  const [isRepository, setIsRepository] = useState<boolean>();
+  const [status, setStatus] = useState<GitFileStatus[]>([]);
+  const [branches, setBranches] = useState<{ current?: string; branches: string[] }>({ branches: [] });
+  const [commits, setCommits] = useState<ReadCommitResult[]>([]);
+  const [stashes, setStashes] = useState<string[]>([]);
+  const [message, setMessage] = useState('');
+  const [newBranch, setNewBranch] = useState('');
+  const [busy, setBusy] = useState<string>();
+  const [version, setVersion] = useState(0);
+
+  const refresh = useCallback(async () => {
+    if (!repo) {
+      return;
+    }
+
+    const repository = await repo.isRepository();
+    setIsRepository(repository);
+
+    if (!repository) {
+      return;
+    }
+
+    const [nextStatus, nextBranches, nextCommits, nextStashes] = await Promise.all([
+      repo.status(),
+      repo.branches(),
+      repo.log(),
+      repo.stashList(),
+    ]);
+
+    setStatus(nextStatus);
+    setBranches(nextBranches);
+    setCommits(nextCommits);
+    setStashes(nextStashes);
+    setVersion((value) => value + 1);
+  }, [repo]);
+
+  // every file the editor or the model writes changes the status
+  const debouncedRefresh = useMemo(
+    () => debounce(() => refresh().catch((error) => console.error('Failed to read the git status:', error)), 500),
+    [refresh],
+  );
+
+  useEffect(() => {
+    debouncedRefresh();
+  }, [files, debouncedRefresh]);
+
+  const run = useCallback(
+    async (label: string, operation: () => Promise<unknown>, success?: string) => {
+      setBusy(label);
+
+      try {
+        await operation();
+
+        if (success) {
+          toast.success(success);
+        }
+      } catch (error) {
+        toast.error(`${label} failed: ${error instanceof Error ? error.message : String(error)}`);
+      } finally {
+        setBusy(undefined);
+        await refresh().catch(() => undefined);
+      }
+    },
+    [refresh],
+  );
+
+  const staged = status.filter((file) => file.staged);
+  const unstaged = status.filter((file) => file.unstaged);
+
+  if (!ready || !repo || isRepository === undefined) {
+    return (
+      <div className="flex items-center justify-center h-32 text-smack-elements-textTertiary">
+        <div className="i-ph:circle-notch animate-spin mr-2" /> Loading...
+      </div>
+    );
+  }
+
+  if (!isRepository) {
+    return (
+      <div className="flex flex-col items-center gap-3 p-4 text-sm text-smack-elements-textSecondary">
+        <p>The project is not a git repository yet.</p>
+        <button
+          className={buttonClassName}
+          disabled={!!busy}
+          onClick={() => run('Initialize', () => repo.init(), 'Repository initialized')}
+        >
+          <span className="i-ph:git-branch" />
+          Initialize Repository
+        </button>
+      </div>
+    );
+  }
+
+  const handleGenerateMessage = () =>
+    run('Generate message', async () => {
+      const changes = await Promise.all(
+        staged.map(async ({ filepath }) => {
+          const { head, stage } = await repo.getFileDiff(filepath);
+          return { filepath, before: head ?? '', after: stage ?? '' };
+        }),
+      );
+      const { model, provider } = chatModelStore.get();
+
+      setMessage(await generateCommitMessage({ changes, model, provider }));
+    });
+
+  const handleCommit = () =>
+    run(
+      'Commit',
+      async () => {
+        await repo.commit(message.trim());
+        setMessage('');
+      },
+      'Changes committed',
+    );
+
+  const handleCheckout = (name: string) =>
+    run(
+      'Checkout',
+      async () => {
+        assertNoUnsavedChanges();
+        await repo.checkout(name);
+      },
+      `Switched to ${name}`,
+    );
+
+  const handleCreateBranch = () =>
+    run(
+      'Create branch',
+      async () => {
+        await repo.createBranch(newBranch.trim());
+        setNewBranch('');
+      },
+      `Created and switched to ${newBranch.trim()}`,
+    );
+
+  return (
+    <div className="flex flex-col h-full overflow-auto bg-smack-elements-background-depth-2">
+      <div className="flex items-center gap-1 px-2 py-2 border-b border-smack-elements-borderColor">
+        <span className="i-ph:git-branch text-smack-elements-textTertiary" />
+        <select
+          value={branches.current ?? ''}
+          disabled={!!busy}
+          onChange={(e) => handleCheckout(e.target.value)}
+          className={classNames(inputClassName, 'flex-1 min-w-0')}
+        >
+          {!branches.current && <option value="">Detached HEAD</option>}
+          {branches.branches.map((branch) => (
+            <option key={branch} value={branch}>
+              {branch}
+            </option>
+          ))}
+        </select>
+        <button title="Refresh" onClick={() => run('Refresh', refresh)} className={iconButtonClassName}>
+          <span className={classNames('i-ph:arrows-clockwise', { 'animate-spin': busy === 'Refresh' })} />
+        </button>
+        <button
+          title="Push to origin"
+          disabled={!!busy}
+          onClick={() => run('Push', () => repo.push(), `Pushed ${branches.current}`)}
+          className={iconButtonClassName}
+        >
+          <span className={busy === 'Push' ? 'i-ph:circle-notch animate-spin' : 'i-ph:cloud-arrow-up'} />
+        </button>
+      </div>
+
+      <div className="flex gap-1 px-2 pt-2">
+        <input
+          value={newBranch}
+          onChange={(e) => setNewBranch(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && newBranch.trim() && handleCreateBranch()}
+          placeholder="New branch name"
+          className={inputClassName}
+        />
+        <button disabled={!!busy || !newBranch.trim()} onClick={handleCreateBranch} className={buttonClassName}>
+          <span className="i-ph:plus" />
+          Branch
+        </button>
+      </div>
+
+      <div className="flex flex-col gap-1 px-2 pt-2">
+        <textarea
+          value={message}
+          onChange={(e) => setMessage(e.target.value)}
+          placeholder={`Message (commit on "${branches.current ?? 'HEAD'}")`}
+          rows={3}
+          className={classNames(inputClassName, 'resize-none')}
+        />
+        <div className="flex gap-1">
+          <button
+            disabled={!!busy || !message.trim() || staged.length === 0}
+            onClick={handleCommit}
+            className={classNames(buttonClassName, 'flex-1 bg-accent-500 text-white hover:bg-accent-600')}
+          >
+            <span className="i-ph:check" />
+            Commit
+          </button>
+          <button
+            title="Generate a commit message from the staged changes"
+            disabled={!!busy || staged.length === 0}
+            onClick={handleGenerateMessage}
+            className={buttonClassName}
+          >
+            <span className={busy === 'Generate message' ? 'i-ph:circle-notch animate-spin' : 'i-ph:sparkle'} />
+          </button>
+        </div>
+      </div>
+
+      <div className={sectionTitleClassName}>
+        <span>Staged Changes ({staged.length})</span>
+        {staged.length > 0 && (
+          <button
+            title="Unstage All"
+            onClick={() => run('Unstage', () => Promise.all(staged.map((file) => repo.unstageFile(file.filepath))))}
+            className={iconButtonClassName}
+          >
+            <span className="i-ph:minus" />
+          </button>
+        )}
+      </div>
+      {staged.map((file) => (
+        <FileRow key={file.filepath} repo={repo} file={file} staged version={version} run={run} />
+      ))}
+
+      <div className={sectionTitleClassName}>
+        <span>Changes ({unstaged.length})</span>
+        {unstaged.length > 0 && (
+          <button
+            title="Stage All"
+            onClick={() =>
+              run('Stage', async () => {
+                for (const file of unstaged) {
+                  await repo.stageFile(file.filepath);
+                }
+              })
+            }
+            className={iconButtonClassName}
+          >
+            <span className="i-ph:plus" />
+          </button>
+        )}
+      </div>
+      {unstaged.map((file) => (
+        <FileRow key={file.filepath} repo={repo} file={file} staged={false} version={version} run={run} />
+      ))}
+
+      <div className={sectionTitleClassName}>
+        <span>Stashes ({stashes.length})</span>
+        <button
+          title="Stash Changes"
+          disabled={!!busy || status.length === 0}
+          onClick={() =>
+            run(
+              'Stash',
+              async () => {
+                assertNoUnsavedChanges();
+                await repo.stash(message.trim() || undefined);
+              },
+              'Changes stashed',
+            )
+          }
+          className={iconButtonClassName}
+        >
+          <span className="i-ph:archive-box" />
+        </button>
+      </div>
+      {stashes.map((stash, index) => (
+        <div key={stash} className="group flex items-center gap-1 pl-3 pr-2 py-0.5 text-xs">
+          <span className="flex-1 truncate text-smack-elements-textSecondary" title={stash}>
+            {stash}
+          </span>
+          <button
+            title="Pop Stash"
+            onClick={() =>
+              run('Pop stash', async () => {
+                assertNoUnsavedChanges();
+                await repo.stashPop(index);
+              })
+            }
+            className={iconButtonClassName}
+          >
+            <span className="i-ph:arrow-square-out" />
+          </button>
+          <button
+            title="Drop Stash"
+            onClick={() => run('Drop stash', () => repo.stashDrop(index))}
+            className={iconButtonClassName}
+          >
+            <span className="i-ph:trash" />
+          </button>
+        </div>
+      ))}
+
+      <div className={sectionTitleClassName}>
+        <span>History</span>
+      </div>
+      {commits.length === 0 && (
+        <div className="px-3 py-1 text-xs text-smack-elements-textTertiary">No commits yet</div>
+      )}
+      {commits.map(({ oid, commit }) => (
+        <div key={oid} className="px-3 py-1 text-xs" title={commit.message}>
+          <div className="truncate text-smack-elements-textPrimary">{commit.message.split('\n')[0]}</div>
+          <div className="flex gap-2 text-smack-elements-textTertiary">
+            <span className="font-mono">{oid.slice(0, 7)}</span>
+            <span className="truncate">{commit.author.name}</span>
+            <span className="ml-auto shrink-0">{new Date(commit.author.timestamp * 1000).toLocaleDateString()}</span>
+          </div>
+        </div>
+      ))}
+    </div>
+  );
+}
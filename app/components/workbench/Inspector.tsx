@@ -21,6 +21,19 @@ export interface ElementInfo {
     top: number;
     left: number;
   };
+
+  // JSX of the closest element stamped by the source plugin of the visual editor
+  source?: {
+    file: string;
+    line: number;
+    column: number;
+
+    // whether the element itself is stamped rather than one of its ancestors
+    exact: boolean;
+
+    // content of elements that contain only text
+    text?: string;
+  };
 }
 
 export const Inspector = ({ isActive, iframeRef, onElementSelect }: InspectorProps) => {
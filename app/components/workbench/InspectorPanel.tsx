@@ -1,19 +1,18 @@
-import { useState } from 'react';
-
-interface ElementInfo {
-  tagName: string;
-  className: string;
-  id: string;
-  textContent: string;
-  styles: Record<string, string>; // Changed from CSSStyleDeclaration
-  rect: {
-    x: number;
-    y: number;
-    width: number;
-    height: number;
-    top: number;
-    left: number;
-  };
+import { useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+import { workbenchStore } from '~/lib/stores/workbench';
+import { WORK_DIR } from '~/utils/constants';
+import { setJsxStyles, setJsxText } from '~/utils/jsxSource';
+import type { ElementInfo } from './Inspector';
+
+// styles the visual editor writes inline into the JSX
+const EDITABLE_STYLES = ['color', 'background-color', 'font-size', 'font-weight', 'padding', 'margin', 'border-radius'];
+
+type ElementSource = NonNullable<ElementInfo['source']>;
+
+export function openElementSource(source: ElementSource) {
+  workbenchStore.setSelectedFile(`${WORK_DIR}/${source.file}`);
+  workbenchStore.setCurrentDocumentScrollPosition({ line: source.line - 1, column: source.column });
 }
 
 interface InspectorPanelProps {
@@ -23,12 +22,67 @@ interface InspectorPanelProps {
 }
 
 export const InspectorPanel = ({ selectedElement, isVisible, onClose }: InspectorPanelProps) => {
-  const [activeTab, setActiveTab] = useState<'styles' | 'computed' | 'box'>('styles');
+  const [activeTab, setActiveTab] = useState<'edit' | 'styles' | 'computed' | 'box'>('styles');
+  const [text, setText] = useState<string>();
+  const [styles, setStyles] = useState<Record<string, string>>({});
+  const [isApplying, setIsApplying] = useState(false);
+
+  const source = selectedElement?.source;
+
+  useEffect(() => {
+    const elementSource = selectedElement?.source;
+
+    setText(elementSource?.exact ? elementSource.text : undefined);
+    setStyles({});
+    setActiveTab(elementSource ? 'edit' : 'styles');
+  }, [selectedElement]);
 
   if (!isVisible || !selectedElement) {
     return null;
   }
 
+  const tabs = source ? (['edit', 'styles', 'box'] as const) : (['styles', 'computed', 'box'] as const);
+
+  const applyEdits = async () => {
+    if (!source) {
+      return;
+    }
+
+    const filePath = `${WORK_DIR}/${source.file}`;
+    const file = workbenchStore.files.get()[filePath];
+
+    if (file?.type !== 'file') {
+      toast.error(`${source.file} could not be found`);
+      return;
+    }
+
+    setIsApplying(true);
+
+    try {
+      let content = file.content;
+      const changedStyles = Object.fromEntries(Object.entries(styles).filter(([, value]) => value.trim()));
+
+      // the styles go into the opening tag, on the same line, so the position still points at the element after
+      if (Object.keys(changedStyles).length > 0) {
+        content = setJsxStyles(content, source, changedStyles);
+      }
+
+      if (text !== undefined && source.text !== undefined && text !== source.text) {
+        content = setJsxText(content, source, source.text, text);
+      }
+
+      if (content !== file.content) {
+        const tagName = selectedElement.tagName.toLowerCase();
+        await workbenchStore.replaceInFiles({ [filePath]: content }, `Visual edit of <${tagName}> in ${source.file}`);
+        setStyles({});
+      }
+    } catch (error) {
+      toast.error(error instanceof Error ? error.message : 'Failed to apply the edit');
+    } finally {
+      setIsApplying(false);
+    }
+  };
+
   const getRelevantStyles = (styles: Record<string, string>) => {
     const relevantProps = [
       'display',
@@ -92,7 +146,7 @@ export const InspectorPanel = ({ selectedElement, isVisible, onClose }: Inspecto
 
       {/* Tabs */}
       <div className="flex border-b border-smack-elements-borderColor">
-        {(['styles', 'computed', 'box'] as const).map((tab) => (
+        {tabs.map((tab) => (
           <button
             key={tab}
             onClick={() => setActiveTab(tab)}
@@ -109,6 +163,59 @@ export const InspectorPanel = ({ selectedElement, isVisible, onClose }: Inspecto
 
       {/* Content */}
       <div className="p-3 overflow-y-auto max-h-96">
+        {activeTab === 'edit' && source && (
+          <div className="space-y-3 text-sm">
+            <button
+              onClick={() => {
+                workbenchStore.currentView.set('code');
+                openElementSource(source);
+              }}
+              className="flex items-center gap-1 font-mono text-xs text-blue-500 hover:underline truncate"
+              title="Open in editor"
+            >
+              <div className="i-ph:code shrink-0" />
+              {source.file}:{source.line}
+            </button>
+            {!source.exact && (
+              <div className="text-xs text-smack-elements-textSecondary">
+                This element comes from a component or a library, only the JSX that renders it can be opened.
+              </div>
+            )}
+            {source.exact && text !== undefined && (
+              <label className="block">
+                <span className="text-smack-elements-textSecondary">Text</span>
+                <textarea
+                  value={text}
+                  onChange={(event) => setText(event.target.value)}
+                  rows={2}
+                  className="mt-1 w-full px-2 py-1 rounded bg-smack-elements-background-depth-2 border border-smack-elements-borderColor text-smack-elements-textPrimary resize-none"
+                />
+              </label>
+            )}
+            {source.exact &&
+              EDITABLE_STYLES.map((property) => (
+                <label key={property} className="flex items-center justify-between gap-2">
+                  <span className="text-smack-elements-textSecondary">{property}</span>
+                  <input
+                    value={styles[property] ?? ''}
+                    placeholder={selectedElement.styles[property]}
+                    onChange={(event) => setStyles({ ...styles, [property]: event.target.value })}
+                    className="w-36 px-2 py-1 rounded font-mono text-xs bg-smack-elements-background-depth-2 border border-smack-elements-borderColor text-smack-elements-textPrimary"
+                  />
+                </label>
+              ))}
+            {source.exact && (
+              <button
+                onClick={applyEdits}
+                disabled={isApplying}
+                className="w-full px-3 py-1.5 rounded bg-accent-500 text-white hover:bg-accent-600 disabled:opacity-50"
+              >
+                {isApplying ? 'Applying...' : 'Apply to source'}
+              </button>
+            )}
+          </div>
+        )}
+
         {activeTab === 'styles' && (
           <div className="space-y-2">
             {Object.entries(getRelevantStyles(selectedElement.styles)).map(([prop, value]) => (
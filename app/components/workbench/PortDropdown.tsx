@@ -1,4 +1,6 @@
-import { memo, useEffect, useRef } from 'react';
+import { useStore } from '@nanostores/react';
+import { memo, useEffect, useRef, useState } from 'react';
+import { getPortLabel, portLabelsStore, setPortLabel } from '~/lib/stores/previewSessions';
 import type { PreviewInfo } from '~/lib/stores/previews';
 
 interface PortDropdownProps {
@@ -20,6 +22,10 @@ export const PortDropdown = memo(
     previews,
   }: PortDropdownProps) => {
     const dropdownRef = useRef<HTMLDivElement>(null);
+    const labels = useStore(portLabelsStore);
+    const [editingPort, setEditingPort] = useState<number>();
+    const activePort = previews[activePreviewIndex]?.port;
+    const activeLabel = activePort !== undefined ? getPortLabel(activePort, labels) : undefined;
 
     // sort previews, preserving original index
     const sortedPreviews = previews
@@ -54,7 +60,9 @@ export const PortDropdown = memo(
         >
           <span className="i-ph:plug text-base"></span>
           {previews.length > 0 && activePreviewIndex >= 0 && activePreviewIndex < previews.length ? (
-            <span className="text-xs font-medium">{previews[activePreviewIndex].port}</span>
+            <span className="text-xs font-medium">
+              {activeLabel ? `${activeLabel} · ${activePort}` : activePort}
+            </span>
           ) : null}
         </button>
         {isDropdownOpen && (
@@ -65,22 +73,55 @@ export const PortDropdown = memo(
             {sortedPreviews.map((preview) => (
               <div
                 key={preview.port}
-                className="flex items-center px-4 py-2 cursor-pointer hover:bg-smack-elements-item-backgroundActive"
+                className="group flex items-center gap-2 px-4 py-2 cursor-pointer hover:bg-smack-elements-item-backgroundActive"
                 onClick={() => {
+                  if (editingPort === preview.port) {
+                    return;
+                  }
+
                   setActivePreviewIndex(preview.index);
                   setIsDropdownOpen(false);
                   setHasSelectedPreview(true);
                 }}
               >
-                <span
-                  className={
-                    activePreviewIndex === preview.index
-                      ? 'text-smack-elements-item-contentAccent'
-                      : 'text-smack-elements-item-contentDefault group-hover:text-smack-elements-item-contentActive'
-                  }
-                >
-                  {preview.port}
-                </span>
+                {editingPort === preview.port ? (
+                  <input
+                    autoFocus
+                    defaultValue={getPortLabel(preview.port, labels) ?? ''}
+                    placeholder="frontend, api..."
+                    className="w-28 px-1 text-sm rounded bg-smack-elements-background-depth-1 border border-smack-elements-borderColor text-smack-elements-textPrimary outline-none"
+                    onBlur={(event) => {
+                      setPortLabel(preview.port, event.target.value);
+                      setEditingPort(undefined);
+                    }}
+                    onKeyDown={(event) => {
+                      if (event.key === 'Enter') {
+                        event.currentTarget.blur();
+                      } else if (event.key === 'Escape') {
+                        setEditingPort(undefined);
+                      }
+                    }}
+                  />
+                ) : (
+                  <span
+                    className={
+                      activePreviewIndex === preview.index
+                        ? 'flex-1 text-smack-elements-item-contentAccent'
+                        : 'flex-1 text-smack-elements-item-contentDefault group-hover:text-smack-elements-item-contentActive'
+                    }
+                  >
+                    {getPortLabel(preview.port, labels) ?? 'port'}{' '}
+                    <span className="text-smack-elements-textTertiary">{preview.port}</span>
+                  </span>
+                )}
+                <button
+                  title="Rename"
+                  className="i-ph:pencil-simple text-smack-elements-textTertiary opacity-0 group-hover:opacity-100 hover:text-smack-elements-textPrimary"
+                  onClick={(event) => {
+                    event.stopPropagation();
+                    setEditingPort(preview.port);
+                  }}
+                />
               </div>
             ))}
           </div>
@@ -6,7 +6,12 @@ import { PortDropdown } from './PortDropdown';
 import { ScreenshotSelector } from './ScreenshotSelector';
 import { expoUrlAtom } from '~/lib/stores/qrCodeStore';
 import { ExpoQrModal } from '~/components/workbench/ExpoQrModal';
+import { VisualSnapshotsModal } from './VisualSnapshotsModal';
+import { PreviewSessionsMenu } from './PreviewSessionsMenu';
+import { portPresetsStore, setPortPreset, type DevicePreset, type PreviewSession } from '~/lib/stores/previewSessions';
 import type { ElementInfo } from './Inspector';
+import { InspectorPanel, openElementSource } from './InspectorPanel';
+import { toast } from 'react-toastify';
 
 type ResizeSide = 'left' | 'right' | null;
 
@@ -66,6 +71,8 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
   const [iframeUrl, setIframeUrl] = useState<string | undefined>();
   const [isSelectionMode, setIsSelectionMode] = useState(false);
   const [isInspectorMode, setIsInspectorMode] = useState(false);
+  const [isVisualEditMode, setIsVisualEditMode] = useState(false);
+  const [visualEditElement, setVisualEditElement] = useState<ElementInfo | null>(null);
   const [isDeviceModeOn, setIsDeviceModeOn] = useState(false);
   const [widthPercent, setWidthPercent] = useState<number>(37.5);
   const [currentWidth, setCurrentWidth] = useState<number>(0);
@@ -89,6 +96,18 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
   const [showDeviceFrameInPreview, setShowDeviceFrameInPreview] = useState(false);
   const expoUrl = useStore(expoUrlAtom);
   const [isExpoQrModalOpen, setIsExpoQrModalOpen] = useState(false);
+  const [isVisualSnapshotsOpen, setIsVisualSnapshotsOpen] = useState(false);
+
+  // path of the session that switched to another port, opened once that port is shown
+  const pendingSessionPath = useRef<string>();
+
+  const devicePreset: DevicePreset = {
+    device: selectedWindowSize.name,
+    width: selectedWindowSize.width,
+    height: selectedWindowSize.height,
+    landscape: isLandscape,
+    enabled: isDeviceModeOn,
+  };
 
   useEffect(() => {
     if (!activePreview) {
@@ -99,10 +118,58 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
     }
 
     const { baseUrl } = activePreview;
-    setIframeUrl(baseUrl);
-    setDisplayPath('/');
+    const path = pendingSessionPath.current ?? '/';
+    pendingSessionPath.current = undefined;
+    setIframeUrl(path === '/' ? baseUrl : baseUrl + path);
+    setDisplayPath(path);
   }, [activePreview]);
 
+  // every port keeps the device it was last looked at with
+  const activePort = activePreview?.port;
+
+  useEffect(() => {
+    const preset = activePort !== undefined ? portPresetsStore.get()[activePort] : undefined;
+
+    if (preset) {
+      setSelectedWindowSize(WINDOW_SIZES.find((size) => size.name === preset.device) ?? WINDOW_SIZES[0]);
+      setIsLandscape(preset.landscape);
+      setIsDeviceModeOn(preset.enabled);
+    }
+  }, [activePort]);
+
+  const applyDevicePreset = (preset: DevicePreset, port = activePort) => {
+    setSelectedWindowSize(WINDOW_SIZES.find((size) => size.name === preset.device) ?? selectedWindowSize);
+    setIsLandscape(preset.landscape);
+    setIsDeviceModeOn(preset.enabled);
+
+    if (port !== undefined) {
+      setPortPreset(port, preset);
+    }
+  };
+
+  const openSession = (session: PreviewSession) => {
+    const index = previews.findIndex((preview) => preview.port === session.port);
+
+    if (index === -1) {
+      toast.error(`Nothing is running on port ${session.port}`);
+      return;
+    }
+
+    hasSelectedPreview.current = true;
+
+    if (session.preset) {
+      applyDevicePreset(session.preset, session.port);
+    }
+
+    if (index === activePreviewIndex) {
+      setIframeUrl(previews[index].baseUrl + session.path);
+      setDisplayPath(session.path);
+    } else {
+      pendingSessionPath.current = session.path;
+      setActivePreviewIndex(index);
+    }
+  };
+
   const findMinPortIndex = useCallback(
     (minIndex: number, preview: { port: number }, index: number, array: { port: number }[]) => {
       return preview.port < array[minIndex].port ? index : minIndex;
@@ -144,7 +211,7 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
   }, []);
 
   const toggleDeviceMode = () => {
-    setIsDeviceModeOn((prev) => !prev);
+    applyDevicePreset({ ...devicePreset, enabled: !isDeviceModeOn });
   };
 
   const startResizing = (e: React.PointerEvent, side: ResizeSide) => {
@@ -632,7 +699,17 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
           );
         }
       } else if (event.data.type === 'INSPECTOR_CLICK') {
-        const element = event.data.elementInfo;
+        const element: ElementInfo = event.data.elementInfo;
+
+        if (isVisualEditMode) {
+          setVisualEditElement(element);
+
+          if (element.source) {
+            openElementSource(element.source);
+          }
+
+          return;
+        }
 
         navigator.clipboard.writeText(element.displayText).then(() => {
           setSelectedElement?.(element);
@@ -643,10 +720,9 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
     window.addEventListener('message', handleMessage);
 
     return () => window.removeEventListener('message', handleMessage);
-  }, [isInspectorMode]);
+  }, [isInspectorMode, isVisualEditMode]);
 
-  const toggleInspectorMode = () => {
-    const newInspectorMode = !isInspectorMode;
+  const setInspectorMode = (newInspectorMode: boolean) => {
     setIsInspectorMode(newInspectorMode);
 
     if (iframeRef.current?.contentWindow) {
@@ -660,6 +736,31 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
     }
   };
 
+  const toggleInspectorMode = () => setInspectorMode(!isInspectorMode);
+
+  const toggleVisualEditMode = async () => {
+    if (isVisualEditMode) {
+      setIsVisualEditMode(false);
+      setVisualEditElement(null);
+      setInspectorMode(false);
+
+      return;
+    }
+
+    try {
+      if (!(await workbenchStore.enableSourceMapping())) {
+        toast.warning('Visual editing needs a Vite project with a plugins array in its config');
+        return;
+      }
+    } catch (error) {
+      toast.error(error instanceof Error ? error.message : 'Failed to enable visual editing');
+      return;
+    }
+
+    setIsVisualEditMode(true);
+    setInspectorMode(true);
+  };
+
   return (
     <div ref={containerRef} className={`w-full h-full flex flex-col relative`}>
       {isPortDropdownOpen && (
@@ -725,11 +826,22 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
 
           <ExpoQrModal open={isExpoQrModalOpen} onClose={() => setIsExpoQrModalOpen(false)} />
 
+          <PreviewSessionsMenu
+            activePreview={activePreview}
+            previews={previews}
+            path={displayPath}
+            preset={devicePreset}
+            onOpenSession={openSession}
+          />
+
+          <IconButton icon="i-ph:images" onClick={() => setIsVisualSnapshotsOpen(true)} title="Visual Snapshots" />
+          <VisualSnapshotsModal open={isVisualSnapshotsOpen} onClose={() => setIsVisualSnapshotsOpen(false)} />
+
           {isDeviceModeOn && (
             <>
               <IconButton
                 icon="i-ph:device-rotate"
-                onClick={() => setIsLandscape(!isLandscape)}
+                onClick={() => applyDevicePreset({ ...devicePreset, landscape: !isLandscape })}
                 title={isLandscape ? 'Switch to Portrait' : 'Switch to Landscape'}
               />
               <IconButton
@@ -747,6 +859,14 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
             }
             title={isInspectorMode ? 'Disable Element Inspector' : 'Enable Element Inspector'}
           />
+          <IconButton
+            icon="i-ph:pencil-simple-line"
+            onClick={toggleVisualEditMode}
+            className={
+              isVisualEditMode ? 'bg-smack-elements-background-depth-3 !text-smack-elements-item-contentAccent' : ''
+            }
+            title={isVisualEditMode ? 'Disable Visual Editing' : 'Edit Elements Visually'}
+          />
           <IconButton
             icon={isFullscreen ? 'i-ph:arrows-in' : 'i-ph:arrows-out'}
             onClick={toggleFullscreen}
@@ -835,7 +955,7 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
                           } relative`}
                           onClick={(e) => {
                             e.stopPropagation();
-                            setIsLandscape(!isLandscape);
+                            applyDevicePreset({ ...devicePreset, landscape: !isLandscape });
                           }}
                         >
                           <span
@@ -852,7 +972,12 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
                       key={size.name}
                       className="w-full px-4 py-3.5 text-left text-[#111827] dark:text-gray-300 text-sm whitespace-nowrap flex items-center gap-3 group hover:bg-[#F5EEFF] dark:hover:bg-gray-900 bg-white dark:bg-black"
                       onClick={() => {
-                        setSelectedWindowSize(size);
+                        applyDevicePreset({
+                          ...devicePreset,
+                          device: size.name,
+                          width: size.width,
+                          height: size.height,
+                        });
                         setIsWindowSizeDropdownOpen(false);
                         openInNewWindow(size);
                       }}
@@ -1044,6 +1169,11 @@ export const Preview = memo(({ setSelectedElement }: PreviewProps) => {
           )}
         </div>
       </div>
+      <InspectorPanel
+        selectedElement={visualEditElement}
+        isVisible={isVisualEditMode}
+        onClose={() => setVisualEditElement(null)}
+      />
     </div>
   );
 });
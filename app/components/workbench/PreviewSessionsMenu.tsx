@@ -0,0 +1,236 @@
+import { useStore } from '@nanostores/react';
+import { memo, useState } from 'react';
+import { toast } from 'react-toastify';
+import { IconButton } from '~/components/ui/IconButton';
+import {
+  getPortLabel,
+  getSessionUrl,
+  portLabelsStore,
+  previewSessionsStore,
+  removePreviewSession,
+  savePreviewSession,
+  type DevicePreset,
+  type PreviewSession,
+} from '~/lib/stores/previewSessions';
+import type { PreviewInfo } from '~/lib/stores/previews';
+import { previewTunnelAtom } from '~/lib/stores/qrCodeStore';
+import {
+  getPreviewTunnelLink,
+  isPreviewTunnelAvailable,
+  startPreviewTunnel,
+  stopPreviewTunnel,
+} from '~/lib/webcontainer/preview-tunnel';
+import { ExpoQrModal } from './ExpoQrModal';
+
+interface PreviewSessionsMenuProps {
+  activePreview?: PreviewInfo;
+  previews: PreviewInfo[];
+  path: string;
+  preset: DevicePreset;
+  onOpenSession: (session: PreviewSession) => void;
+}
+
+export const PreviewSessionsMenu = memo(
+  ({ activePreview, previews, path, preset, onOpenSession }: PreviewSessionsMenuProps) => {
+    const sessions = useStore(previewSessionsStore);
+    const labels = useStore(portLabelsStore);
+    const tunnel = useStore(previewTunnelAtom);
+    const [isOpen, setIsOpen] = useState(false);
+    const [isQrOpen, setIsQrOpen] = useState(false);
+    const [isSharing, setIsSharing] = useState(false);
+    const [name, setName] = useState('');
+
+    const findPreview = (session: PreviewSession) => previews.find((preview) => preview.port === session.port);
+
+    const saveSession = () => {
+      if (!activePreview || !name.trim()) {
+        return;
+      }
+
+      savePreviewSession({ name: name.trim(), port: activePreview.port, path, preset });
+      setName('');
+    };
+
+    const openInNewTab = (session: PreviewSession) => {
+      const preview = findPreview(session);
+      const url = preview && getSessionUrl(preview.baseUrl, session);
+
+      if (!url) {
+        toast.error(`Nothing is running on port ${session.port}`);
+        return;
+      }
+
+      window.open(url, `preview-session-${session.id}`);
+    };
+
+    const copyLink = async (session: PreviewSession) => {
+      const preview = findPreview(session);
+      const url = preview && getSessionUrl(preview.baseUrl, session);
+
+      if (!url) {
+        toast.error(`Nothing is running on port ${session.port}`);
+        return;
+      }
+
+      await navigator.clipboard.writeText(url);
+      toast.success('Copied the link, it opens the session in any tab of this browser');
+    };
+
+    const shareOnNetwork = async () => {
+      if (!activePreview) {
+        return;
+      }
+
+      setIsSharing(true);
+
+      try {
+        const { url, token } = await startPreviewTunnel(activePreview.baseUrl);
+
+        previewTunnelAtom.set({ port: activePreview.port, url, token });
+        setIsQrOpen(true);
+      } catch (error) {
+        toast.error(error instanceof Error ? error.message : 'Failed to share the preview');
+      } finally {
+        setIsSharing(false);
+      }
+    };
+
+    const stopSharing = async () => {
+      await stopPreviewTunnel();
+      previewTunnelAtom.set(null);
+    };
+
+    const removeSession = (session: PreviewSession) => removePreviewSession(session.id);
+
+    const formatPort = (port: number) => {
+      const label = getPortLabel(port, labels);
+      return label ? `${label} · ${port}` : `${port}`;
+    };
+
+    return (
+      <div className="flex items-center relative">
+        <IconButton icon="i-ph:stack" onClick={() => setIsOpen(!isOpen)} title="Preview Sessions" />
+
+        {tunnel && (
+          <ExpoQrModal
+            open={isQrOpen}
+            onClose={() => setIsQrOpen(false)}
+            url={getPreviewTunnelLink(tunnel, tunnel.port === activePreview?.port ? path : '/')}
+            description={`Scan this QR code with a phone on the same network to open ${formatPort(tunnel.port)}. Cookies set by the preview do not reach the phone, so sign-in flows only work on the desktop.`}
+          />
+        )}
+
+        {isOpen && (
+          <>
+            <div className="fixed inset-0 z-50" onClick={() => setIsOpen(false)} />
+            <div className="absolute right-0 top-full mt-2 z-50 w-[300px] max-h-[420px] overflow-y-auto rounded-lg border border-smack-elements-borderColor bg-smack-elements-background-depth-2 shadow-lg text-sm">
+              <div className="px-3 py-2 border-b border-smack-elements-borderColor font-semibold text-smack-elements-textPrimary">
+                Sessions
+              </div>
+              {sessions.length === 0 && (
+                <div className="px-3 py-2 text-xs text-smack-elements-textTertiary">
+                  Save a port, path and device to come back to them or open them in other tabs.
+                </div>
+              )}
+              {sessions.map((session) => (
+                <div
+                  key={session.id}
+                  className="group flex items-center gap-2 px-3 py-2 cursor-pointer hover:bg-smack-elements-item-backgroundActive"
+                  onClick={() => {
+                    onOpenSession(session);
+                    setIsOpen(false);
+                  }}
+                >
+                  <div className="flex-1 min-w-0">
+                    <div className="truncate text-smack-elements-textPrimary">{session.name}</div>
+                    <div className="truncate text-xs text-smack-elements-textTertiary">
+                      {formatPort(session.port)} {session.path}
+                      {session.preset?.enabled ? ` · ${session.preset.device}` : ''}
+                      {findPreview(session) ? '' : ' · not running'}
+                    </div>
+                  </div>
+                  {[
+                    { icon: 'i-ph:arrow-square-out', title: 'Open in new tab', action: openInNewTab },
+                    { icon: 'i-ph:link', title: 'Copy link', action: copyLink },
+                    { icon: 'i-ph:trash', title: 'Remove', action: removeSession },
+                  ].map(({ icon, title, action }) => (
+                    <button
+                      key={title}
+                      title={title}
+                      className={`${icon} shrink-0 text-smack-elements-textTertiary opacity-0 group-hover:opacity-100 hover:text-smack-elements-textPrimary`}
+                      onClick={(event) => {
+                        event.stopPropagation();
+                        action(session);
+                      }}
+                    />
+                  ))}
+                </div>
+              ))}
+              <div className="flex items-center gap-2 px-3 py-2 border-t border-smack-elements-borderColor">
+                <input
+                  value={name}
+                  placeholder={activePreview ? 'Session name' : 'Start a preview first'}
+                  disabled={!activePreview}
+                  onChange={(event) => setName(event.target.value)}
+                  onKeyDown={(event) => event.key === 'Enter' && saveSession()}
+                  className="flex-1 min-w-0 px-2 py-1 rounded bg-smack-elements-background-depth-1 border border-smack-elements-borderColor text-smack-elements-textPrimary outline-none"
+                />
+                <button
+                  onClick={saveSession}
+                  disabled={!activePreview || !name.trim()}
+                  className="px-2 py-1 rounded bg-accent-500 text-white disabled:opacity-50"
+                >
+                  Save
+                </button>
+              </div>
+              {isPreviewTunnelAvailable() && (
+                <div className="flex flex-col gap-2 px-3 py-2 border-t border-smack-elements-borderColor">
+                  <div className="font-semibold text-smack-elements-textPrimary">Local network</div>
+                  {tunnel ? (
+                    <>
+                      <div className="text-xs text-smack-elements-textTertiary">
+                        Sharing {formatPort(tunnel.port)} at {tunnel.url}, reload the phone to see changes.
+                      </div>
+                      <div className="flex gap-2">
+                        <button
+                          onClick={() => setIsQrOpen(true)}
+                          className="flex items-center gap-1 px-2 py-1 rounded bg-accent-500 text-white"
+                        >
+                          <div className="i-ph:qr-code" />
+                          Show QR
+                        </button>
+                        {activePreview && tunnel.port !== activePreview.port && (
+                          <button
+                            onClick={shareOnNetwork}
+                            className="px-2 py-1 rounded text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3"
+                          >
+                            Share {formatPort(activePreview.port)} instead
+                          </button>
+                        )}
+                        <button
+                          onClick={stopSharing}
+                          className="px-2 py-1 rounded text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3"
+                        >
+                          Stop
+                        </button>
+                      </div>
+                    </>
+                  ) : (
+                    <button
+                      onClick={shareOnNetwork}
+                      disabled={!activePreview || isSharing}
+                      className="flex items-center gap-1 self-start px-2 py-1 rounded bg-accent-500 text-white disabled:opacity-50"
+                    >
+                      <div className={isSharing ? 'i-svg-spinners:90-ring-with-bg' : 'i-ph:device-mobile'} />
+                      Open on a phone
+                    </button>
+                  )}
+                </div>
+              )}
+            </div>
+          </>
+        )}
+      </div>
+    );
+  },
+);
@@ -0,0 +1,229 @@
+import { useEffect, useMemo, useState } from 'react';
+import { useStore } from '@nanostores/react';
+import { toast } from 'react-toastify';
+import { RollbackConflictError } from '~/lib/runtime/artifact-transaction';
+import { workbenchStore } from '~/lib/stores/workbench';
+import { classNames } from '~/utils/classNames';
+import { extractRelativePath } from '~/utils/diff';
+import { applyReplacements, findReplaceMatches, type ReplaceMatch, type ReplaceQuery } from '~/utils/searchReplace';
+
+const CONTEXT_CHARS = 20;
+
+function MatchDiff({ match }: { match: ReplaceMatch }) {
+  const isStart = match.column <= CONTEXT_CHARS;
+  const before = match.lineText.slice(isStart ? 0 : match.column - CONTEXT_CHARS, match.column);
+  const after = match.lineText.slice(match.column + match.matchText.length);
+
+  return (
+    <pre className="font-mono text-xs text-smack-elements-textTertiary truncate">
+      {!isStart && <span>...</span>}
+      {before}
+      <del className="bg-red-500/20 text-red-500 no-underline line-through">{match.matchText}</del>
+      <ins className="bg-green-500/20 text-green-500 no-underline">{match.replacement}</ins>
+      {after}
+    </pre>
+  );
+}
+
+/**
+ * Previews a project-wide replace as a diff of every match, lets the user pick the matches to replace and applies
+ * them as one change set that can be undone.
+ */
+export function ReplacePreview({ query }: { query: ReplaceQuery }) {
+  const files = useStore(workbenchStore.files);
+  const changeSets = useStore(workbenchStore.replaceChangeSets);
+  const [excluded, setExcluded] = useState<Set<string>>(new Set());
+  const [collapsed, setCollapsed] = useState<Set<string>>(new Set());
+  const [isReplacing, setIsReplacing] = useState(false);
+
+  const { matches, error } = useMemo(() => {
+    try {
+      return { matches: findReplaceMatches(files, query), error: undefined };
+    } catch (e) {
+      return { matches: [], error: e instanceof Error ? e.message : String(e) };
+    }
+  }, [files, query]);
+
+  useEffect(() => {
+    setExcluded(new Set());
+  }, [query]);
+
+  const matchesByFile = useMemo(() => {
+    const grouped: Record<string, ReplaceMatch[]> = {};
+
+    for (const match of matches) {
+      (grouped[match.path] ??= []).push(match);
+    }
+
+    return grouped;
+  }, [matches]);
+
+  const isLocked = (filePath: string) => {
+    const dirent = files[filePath];
+    return dirent?.type === 'file' && !!dirent.isLocked;
+  };
+
+  const included = matches.filter((match) => !excluded.has(match.id) && !isLocked(match.path));
+  const includedFiles = new Set(included.map((match) => match.path));
+
+  const toggle = (ids: string[], include: boolean) => {
+    const next = new Set(excluded);
+
+    for (const id of ids) {
+      if (include) {
+        next.delete(id);
+      } else {
+        next.add(id);
+      }
+    }
+
+    setExcluded(next);
+  };
+
+  const toggleCollapsed = (filePath: string) => {
+    const next = new Set(collapsed);
+
+    if (!next.delete(filePath)) {
+      next.add(filePath);
+    }
+
+    setCollapsed(next);
+  };
+
+  const handleReplace = async () => {
+    const contents: Record<string, string> = {};
+
+    for (const filePath of includedFiles) {
+      const dirent = files[filePath];
+
+      if (dirent?.type === 'file') {
+        contents[filePath] = applyReplacements(
+          dirent.content,
+          included.filter((match) => match.path === filePath),
+        );
+      }
+    }
+
+    setIsReplacing(true);
+
+    try {
+      await workbenchStore.replaceInFiles(
+        contents,
+        `Replaced ${included.length} × "${query.search}" with "${query.replace}" in ${includedFiles.size} files`,
+      );
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsReplacing(false);
+    }
+  };
+
+  const handleUndo = async (changeSetId: string, force = false) => {
+    try {
+      await workbenchStore.undoReplace(changeSetId, { force });
+    } catch (e) {
+      if (e instanceof RollbackConflictError) {
+        const message = `These files changed after the replace:\n\n${e.paths.join('\n')}`;
+
+        if (window.confirm(`${message}\n\nUndo the replace and drop those changes?`)) {
+          await handleUndo(changeSetId, true);
+        }
+
+        return;
+      }
+
+      toast.error(`Failed to undo the replace: ${e}`);
+    }
+  };
+
+  const lastChangeSet = changeSets[changeSets.length - 1];
+
+  return (
+    <div className="flex flex-col">
+      {lastChangeSet && (
+        <div className="flex items-center gap-2 mx-3 mb-2 px-2 py-1.5 rounded-md text-xs bg-smack-elements-background-depth-3 text-smack-elements-textSecondary">
+          <span className="flex-1 truncate" title={lastChangeSet.description}>
+            {lastChangeSet.description}
+          </span>
+          <button
+            onClick={() => handleUndo(lastChangeSet.id)}
+            className="flex items-center gap-1 bg-transparent text-smack-elements-item-contentAccent hover:underline"
+          >
+            <div className="i-ph:arrow-counter-clockwise" />
+            Undo
+          </button>
+        </div>
+      )}
+
+      {error && <div className="px-3 py-2 text-xs text-red-500">{error}</div>}
+
+      {!error && query.search && (
+        <div className="flex items-center justify-between px-3 pb-2 text-xs text-smack-elements-textSecondary">
+          <span>
+            {included.length} of {matches.length} matches in {includedFiles.size} files
+          </span>
+          <button
+            disabled={isReplacing || included.length === 0}
+            onClick={handleReplace}
+            className="px-2 py-1 rounded-md bg-accent-500 text-white hover:bg-accent-600 disabled:opacity-50"
+          >
+            {isReplacing ? 'Replacing...' : 'Replace'}
+          </button>
+        </div>
+      )}
+
+      {Object.entries(matchesByFile).map(([filePath, fileMatches]) => {
+        const locked = isLocked(filePath);
+        const ids = fileMatches.map((match) => match.id);
+        const allIncluded = !locked && ids.every((id) => !excluded.has(id));
+
+        return (
+          <div key={filePath} className="mb-2">
+            <div className="flex gap-2 items-center py-1 px-2 text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3">
+              <input
+                type="checkbox"
+                checked={allIncluded}
+                disabled={locked}
+                onChange={(e) => toggle(ids, e.target.checked)}
+                title={locked ? 'The file is locked' : 'Replace in this file'}
+              />
+              <button
+                className="flex-1 flex items-center gap-2 min-w-0 text-left bg-transparent"
+                onClick={() => toggleCollapsed(filePath)}
+              >
+                <span className="font-normal text-sm truncate" title={extractRelativePath(filePath)}>
+                  {filePath.split('/').pop()}
+                </span>
+                {locked && <span className="i-ph:lock-simple text-red-500 shrink-0" title="The file is locked" />}
+                <span className="h-5.5 w-5.5 flex items-center justify-center text-xs ml-auto bg-smack-elements-item-backgroundAccent text-smack-elements-item-contentAccent rounded-full">
+                  {fileMatches.length}
+                </span>
+              </button>
+            </div>
+            {!collapsed.has(filePath) &&
+              fileMatches.map((match) => (
+                <label
+                  key={match.id}
+                  className={classNames(
+                    'flex items-center gap-2 pl-6 pr-2 py-1 hover:bg-smack-elements-background-depth-3',
+                    { 'opacity-50': locked || excluded.has(match.id) },
+                  )}
+                >
+                  <input
+                    type="checkbox"
+                    checked={!locked && !excluded.has(match.id)}
+                    disabled={locked}
+                    onChange={(e) => toggle([match.id], e.target.checked)}
+                  />
+                  <span className="text-xs text-smack-elements-textTertiary w-8 shrink-0">{match.lineNumber}</span>
+                  <div className="min-w-0 flex-1">
+                    <MatchDiff match={match} />
+                  </div>
+                </label>
+              ))}
+          </div>
+        );
+      })}
+    </div>
+  );
+}
@@ -2,8 +2,19 @@ import { useState, useMemo, useCallback, useEffect } from 'react';
 import type { TextSearchOptions, TextSearchOnProgressCallback, WebContainer } from '@webcontainer/api';
 import { workbenchStore } from '~/lib/stores/workbench';
 import { webcontainer } from '~/lib/webcontainer';
+import { classNames } from '~/utils/classNames';
 import { WORK_DIR } from '~/utils/constants';
 import { debounce } from '~/utils/debounce';
+import type { ReplaceQuery } from '~/utils/searchReplace';
+import { ReplacePreview } from './ReplacePreview';
+
+type SearchFlags = Pick<ReplaceQuery, 'isRegex' | 'caseSensitive' | 'isWordMatch'>;
+
+const SEARCH_FLAGS: { key: keyof SearchFlags; icon: string; title: string }[] = [
+  { key: 'caseSensitive', icon: 'i-ph:text-aa', title: 'Match Case' },
+  { key: 'isWordMatch', icon: 'i-ph:text-underline', title: 'Match Whole Word' },
+  { key: 'isRegex', icon: 'i-ph:asterisk', title: 'Use Regular Expression' },
+];
 
 interface DisplayMatch {
   path: string;
@@ -94,6 +105,10 @@ export function Search() {
   const [isSearching, setIsSearching] = useState(false);
   const [expandedFiles, setExpandedFiles] = useState<Record<string, boolean>>({});
   const [hasSearched, setHasSearched] = useState(false);
+  const [flags, setFlags] = useState<SearchFlags>({ isRegex: false, caseSensitive: false, isWordMatch: false });
+  const [showReplace, setShowReplace] = useState(false);
+  const [replaceText, setReplaceText] = useState('');
+  const [replaceQuery, setReplaceQuery] = useState<ReplaceQuery | null>(null);
 
   const groupedResults = useMemo(() => groupResultsByFile(searchResults), [searchResults]);
 
@@ -107,7 +122,7 @@ export function Search() {
     }
   }, [groupedResults, searchResults]);
 
-  const handleSearch = useCallback(async (query: string) => {
+  const handleSearch = useCallback(async (query: string, searchFlags: SearchFlags) => {
     if (!query.trim()) {
       setSearchResults([]);
       setIsSearching(false);
@@ -136,9 +151,7 @@ export function Search() {
         globalIgnoreFiles: true,
         ignoreSymlinks: false,
         resultLimit: 500,
-        isRegex: false,
-        caseSensitive: false,
-        isWordMatch: false,
+        ...searchFlags,
       };
 
       const progressHandler = (batchResults: DisplayMatch[]) => {
@@ -161,9 +174,16 @@ export function Search() {
 
   const debouncedSearch = useCallback(debounce(handleSearch, 300), [handleSearch]);
 
+  // the replace preview matches against the files store, debounced like the search so it doesn't run on every key
+  const debouncedReplaceQuery = useCallback(debounce(setReplaceQuery, 300), []);
+
   useEffect(() => {
-    debouncedSearch(searchQuery);
-  }, [searchQuery, debouncedSearch]);
+    if (showReplace) {
+      debouncedReplaceQuery({ search: searchQuery, replace: replaceText, ...flags });
+    } else {
+      debouncedSearch(searchQuery, flags);
+    }
+  }, [searchQuery, replaceText, flags, showReplace, debouncedSearch, debouncedReplaceQuery]);
 
   const handleResultClick = (filePath: string, line?: number) => {
     workbenchStore.setSelectedFile(filePath);
@@ -180,29 +200,67 @@ export function Search() {
   return (
     <div className="flex flex-col h-full bg-smack-elements-background-depth-2">
       {/* Search Bar */}
-      <div className="flex items-center py-3 px-3">
-        <div className="relative flex-1">
-          <input
-            type="text"
-            value={searchQuery}
-            onChange={(e) => setSearchQuery(e.target.value)}
-            placeholder="Search"
-            className="w-full px-2 py-1 rounded-md bg-smack-elements-background-depth-3 text-smack-elements-textPrimary placeholder-smack-elements-textTertiary focus:outline-none transition-all"
+      <div className="flex items-start gap-1 py-3 pl-1 pr-3">
+        <button
+          title={showReplace ? 'Hide Replace' : 'Toggle Replace'}
+          onClick={() => setShowReplace(!showReplace)}
+          className="flex items-center justify-center w-5 h-7 bg-transparent text-smack-elements-textSecondary hover:text-smack-elements-textPrimary"
+        >
+          <span
+            className="i-ph:caret-right w-3 h-3 transition-transform"
+            style={{ transform: showReplace ? 'rotate(90deg)' : undefined }}
           />
+        </button>
+        <div className="flex-1 flex flex-col gap-1">
+          <div className="relative flex items-center">
+            <input
+              type="text"
+              value={searchQuery}
+              onChange={(e) => setSearchQuery(e.target.value)}
+              placeholder="Search"
+              className="w-full pl-2 pr-18 py-1 rounded-md bg-smack-elements-background-depth-3 text-smack-elements-textPrimary placeholder-smack-elements-textTertiary focus:outline-none transition-all"
+            />
+            <div className="absolute right-1 flex gap-0.5">
+              {SEARCH_FLAGS.map(({ key, icon, title }) => (
+                <button
+                  key={key}
+                  title={title}
+                  onClick={() => setFlags((prev) => ({ ...prev, [key]: !prev[key] }))}
+                  className={classNames('flex items-center justify-center w-5 h-5 rounded bg-transparent', {
+                    'text-smack-elements-item-contentAccent bg-smack-elements-item-backgroundAccent': flags[key],
+                    'text-smack-elements-textTertiary hover:text-smack-elements-textPrimary': !flags[key],
+                  })}
+                >
+                  <span className={classNames(icon, 'w-3.5 h-3.5')} />
+                </button>
+              ))}
+            </div>
+          </div>
+          {showReplace && (
+            <input
+              type="text"
+              value={replaceText}
+              onChange={(e) => setReplaceText(e.target.value)}
+              placeholder="Replace"
+              className="w-full px-2 py-1 rounded-md bg-smack-elements-background-depth-3 text-smack-elements-textPrimary placeholder-smack-elements-textTertiary focus:outline-none transition-all"
+            />
+          )}
         </div>
       </div>
 
       {/* Results */}
       <div className="flex-1 overflow-auto py-2">
-        {isSearching && (
+        {showReplace && replaceQuery && <ReplacePreview query={replaceQuery} />}
+        {!showReplace && isSearching && (
           <div className="flex items-center justify-center h-32 text-smack-elements-textTertiary">
             <div className="i-ph:circle-notch animate-spin mr-2" /> Searching...
           </div>
         )}
-        {!isSearching && hasSearched && searchResults.length === 0 && searchQuery.trim() !== '' && (
+        {!showReplace && !isSearching && hasSearched && searchResults.length === 0 && searchQuery.trim() !== '' && (
           <div className="flex items-center justify-center h-32 text-gray-500">No results found.</div>
         )}
-        {!isSearching &&
+        {!showReplace &&
+          !isSearching &&
           Object.keys(groupedResults).map((file) => (
             <div key={file} className="mb-2">
               <button
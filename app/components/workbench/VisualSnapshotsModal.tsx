@@ -0,0 +1,253 @@
+import { useStore } from '@nanostores/react';
+import { useEffect, useState } from 'react';
+import { toast } from 'react-toastify';
+import { Dialog, DialogDescription, DialogRoot, DialogTitle } from '~/components/ui/Dialog';
+import type { VisualCapture } from '~/lib/persistence/types';
+import { updateVisualSnapshotViewports, visualSnapshotViewportsStore } from '~/lib/stores/settings';
+import { visualSnapshotsStore } from '~/lib/stores/visualSnapshots';
+import { workbenchStore } from '~/lib/stores/workbench';
+import { classNames } from '~/utils/classNames';
+import { diffPixels } from '~/utils/pixelDiff';
+
+interface VisualSnapshotsModalProps {
+  open: boolean;
+  onClose: () => void;
+}
+
+type CompareMode = 'diff' | 'side-by-side';
+
+const NEW_VIEWPORT = { name: 'Tablet', width: 768, height: 1024 };
+
+function loadImageData(dataUrl: string) {
+  return new Promise<ImageData>((resolve, reject) => {
+    const image = new Image();
+
+    image.onload = () => {
+      const canvas = document.createElement('canvas');
+      canvas.width = image.naturalWidth;
+      canvas.height = image.naturalHeight;
+
+      const context = canvas.getContext('2d')!;
+      context.drawImage(image, 0, 0);
+      resolve(context.getImageData(0, 0, canvas.width, canvas.height));
+    };
+    image.onerror = () => reject(new Error('Failed to load the capture'));
+    image.src = dataUrl;
+  });
+}
+
+async function createDiff(before: VisualCapture, after: VisualCapture) {
+  const [beforeData, afterData] = await Promise.all([loadImageData(before.dataUrl), loadImageData(after.dataUrl)]);
+  const diff = diffPixels(beforeData, afterData);
+  const canvas = document.createElement('canvas');
+
+  canvas.width = diff.width;
+  canvas.height = diff.height;
+  canvas.getContext('2d')!.putImageData(new ImageData(diff.data, diff.width, diff.height), 0, 0);
+
+  return { dataUrl: canvas.toDataURL('image/png'), changedRatio: diff.changedRatio };
+}
+
+export function VisualSnapshotsModal({ open, onClose }: VisualSnapshotsModalProps) {
+  const snapshots = useStore(visualSnapshotsStore.snapshots);
+  const capturing = useStore(visualSnapshotsStore.capturing);
+  const viewports = useStore(visualSnapshotViewportsStore);
+  const [selectedIndex, setSelectedIndex] = useState<number>();
+  const [viewportName, setViewportName] = useState<string>();
+  const [mode, setMode] = useState<CompareMode>('diff');
+  const [diff, setDiff] = useState<{ dataUrl: string; changedRatio: number }>();
+  const [showViewports, setShowViewports] = useState(false);
+
+  const index = selectedIndex !== undefined && selectedIndex < snapshots.length ? selectedIndex : snapshots.length - 1;
+  const after = snapshots[index];
+  const before = index > 0 ? snapshots[index - 1] : undefined;
+  const viewport = viewportName ?? after?.captures[0]?.viewport;
+  const afterCapture = after?.captures.find((capture) => capture.viewport === viewport);
+  const beforeCapture = before?.captures.find((capture) => capture.viewport === viewport);
+
+  useEffect(() => {
+    setDiff(undefined);
+
+    if (!open || !beforeCapture || !afterCapture) {
+      return undefined;
+    }
+
+    let cancelled = false;
+
+    createDiff(beforeCapture, afterCapture)
+      .then((result) => !cancelled && setDiff(result))
+      .catch((error) => console.error('Failed to diff visual snapshots:', error));
+
+    return () => {
+      cancelled = true;
+    };
+  }, [open, beforeCapture, afterCapture]);
+
+  const captureNow = async () => {
+    const artifactId = workbenchStore.artifactIdList[workbenchStore.artifactIdList.length - 1];
+    const snapshot = artifactId ? await workbenchStore.captureVisualSnapshot(artifactId) : undefined;
+
+    if (!snapshot) {
+      toast.error('Nothing to capture, start the preview after the first artifact');
+      return;
+    }
+
+    setSelectedIndex(undefined);
+  };
+
+  const updateViewport = (viewportIndex: number, changes: Partial<(typeof viewports)[number]>) => {
+    updateVisualSnapshotViewports(viewports.map((item, i) => (i === viewportIndex ? { ...item, ...changes } : item)));
+  };
+
+  return (
+    <DialogRoot open={open} onOpenChange={(value) => !value && onClose()}>
+      <Dialog className="!w-[1100px] !max-w-[95vw]" onClose={onClose}>
+        <div className="flex flex-col gap-4 p-6 max-h-[90vh]">
+          <div>
+            <DialogTitle>Visual Snapshots</DialogTitle>
+            <DialogDescription>
+              The preview is captured after every artifact, each capture is compared with the one before it.
+            </DialogDescription>
+          </div>
+
+          <div className="flex items-center gap-2 flex-wrap text-sm">
+            {(after?.captures ?? []).map((capture) => (
+              <button
+                key={capture.viewport}
+                onClick={() => setViewportName(capture.viewport)}
+                className={classNames(
+                  'px-3 py-1 rounded-md border border-smack-elements-borderColor',
+                  capture.viewport === viewport
+                    ? 'bg-smack-elements-item-backgroundAccent text-smack-elements-item-contentAccent'
+                    : 'text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3',
+                )}
+              >
+                {capture.viewport} · {capture.width}×{capture.height}
+              </button>
+            ))}
+            <div className="flex-1" />
+            {(['diff', 'side-by-side'] as const).map((item) => (
+              <button
+                key={item}
+                onClick={() => setMode(item)}
+                className={classNames(
+                  'px-3 py-1 rounded-md capitalize',
+                  mode === item
+                    ? 'bg-smack-elements-item-backgroundAccent text-smack-elements-item-contentAccent'
+                    : 'text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3',
+                )}
+              >
+                {item.replaceAll('-', ' ')}
+              </button>
+            ))}
+            <button
+              onClick={() => setShowViewports(!showViewports)}
+              className="px-3 py-1 rounded-md text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3"
+            >
+              Viewports
+            </button>
+            <button
+              onClick={captureNow}
+              disabled={capturing}
+              className="flex items-center gap-1 px-3 py-1 rounded-md bg-accent-500 text-white disabled:opacity-50"
+            >
+              <div className={capturing ? 'i-svg-spinners:90-ring-with-bg' : 'i-ph:camera'} />
+              {capturing ? 'Capturing...' : 'Capture now'}
+            </button>
+          </div>
+
+          {showViewports && (
+            <div className="flex flex-col gap-2 p-3 rounded-md bg-smack-elements-background-depth-3 text-sm">
+              {viewports.map((item, viewportIndex) => (
+                <div key={viewportIndex} className="flex items-center gap-2">
+                  <input
+                    value={item.name}
+                    onChange={(event) => updateViewport(viewportIndex, { name: event.target.value })}
+                    className="flex-1 px-2 py-1 rounded bg-smack-elements-background-depth-1 border border-smack-elements-borderColor text-smack-elements-textPrimary"
+                  />
+                  {(['width', 'height'] as const).map((dimension) => (
+                    <input
+                      key={dimension}
+                      type="number"
+                      min={100}
+                      value={item[dimension]}
+                      title={dimension}
+                      onChange={(event) => {
+                        const changes = { ...item, [dimension]: Number(event.target.value) };
+                        updateViewport(viewportIndex, changes);
+                      }}
+                      className="w-24 px-2 py-1 rounded bg-smack-elements-background-depth-1 border border-smack-elements-borderColor text-smack-elements-textPrimary"
+                    />
+                  ))}
+                  <button
+                    onClick={() => updateVisualSnapshotViewports(viewports.filter((_, i) => i !== viewportIndex))}
+                    disabled={viewports.length === 1}
+                    title="Remove viewport"
+                    className="i-ph:trash text-smack-elements-textSecondary hover:text-red-500 disabled:opacity-30"
+                  />
+                </div>
+              ))}
+              <button
+                onClick={() => updateVisualSnapshotViewports([...viewports, NEW_VIEWPORT])}
+                className="self-start text-smack-elements-item-contentAccent hover:underline"
+              >
+                Add viewport
+              </button>
+            </div>
+          )}
+
+          <div className="flex gap-4 min-h-0 flex-1">
+            <div className="w-56 shrink-0 overflow-y-auto flex flex-col gap-1">
+              {snapshots.length === 0 && (
+                <div className="text-sm text-smack-elements-textTertiary">
+                  No snapshots yet, turn on Visual Snapshots in the settings or capture the preview now.
+                </div>
+              )}
+              {snapshots
+                .map((snapshot, snapshotIndex) => (
+                  <button
+                    key={snapshot.artifactId}
+                    onClick={() => setSelectedIndex(snapshotIndex)}
+                    className={classNames(
+                      'text-left px-2 py-1.5 rounded-md text-sm',
+                      snapshotIndex === index
+                        ? 'bg-smack-elements-item-backgroundAccent text-smack-elements-item-contentAccent'
+                        : 'text-smack-elements-textSecondary hover:bg-smack-elements-background-depth-3',
+                    )}
+                  >
+                    <div className="truncate">{snapshot.title}</div>
+                    <div className="text-xs opacity-70">{new Date(snapshot.timestamp).toLocaleString()}</div>
+                  </button>
+                ))
+                .reverse()}
+            </div>
+
+            <div className="flex-1 min-w-0 overflow-auto">
+              {afterCapture && !beforeCapture && (
+                <div className="text-sm text-smack-elements-textTertiary mb-2">
+                  The first capture of this viewport, there is nothing to compare it with.
+                </div>
+              )}
+              {beforeCapture && afterCapture && (
+                <div className="text-sm text-smack-elements-textSecondary mb-2">
+                  {diff
+                    ? `${(diff.changedRatio * 100).toFixed(2)}% of the pixels changed since "${before?.title}"`
+                    : 'Comparing...'}
+                </div>
+              )}
+              {afterCapture && (mode === 'side-by-side' || !beforeCapture) && (
+                <div className="grid grid-cols-2 gap-2">
+                  {beforeCapture && <img src={beforeCapture.dataUrl} alt="Before" className="w-full border" />}
+                  <img src={afterCapture.dataUrl} alt="After" className="w-full border" />
+                </div>
+              )}
+              {mode === 'diff' && diff && beforeCapture && (
+                <img src={diff.dataUrl} alt="Changed pixels" className="w-full border" />
+              )}
+            </div>
+          </div>
+        </div>
+      </Dialog>
+    </DialogRoot>
+  );
+}
@@ -11,10 +11,11 @@ export const PROVIDER_COMPLETION_LIMITS: Record<string, number> = {
   Anthropic: 64000,
   Google: 8192,
   Cohere: 4000,
-  DeepSeek: 8192,
+  Deepseek: 8192,
   Groq: 8192,
   HuggingFace: 4096,
   Mistral: 8192,
+  OpenAI: 16384,
   Ollama: 8192,
   OpenRouter: 8192,
   Perplexity: 8192,
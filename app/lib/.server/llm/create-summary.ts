@@ -1,12 +1,25 @@
 import { generateText, type Tool, type GenerateTextResult, type UIMessage as Message } from 'ai';
 import type { IProviderSetting } from '~/types/model';
-import { DEFAULT_MODEL, DEFAULT_PROVIDER, PROVIDER_LIST } from '~/utils/constants';
-import { extractCurrentContext, extractPropertiesFromMessage, simplifysmackActions } from './utils';
+import { DEFAULT_MODEL, DEFAULT_PROVIDER } from '~/utils/constants';
+import { extractPropertiesFromMessage, simplifysmackActions } from './utils';
+import { extractSummaryTree, updateSummaryTree } from './summary-tree';
 import { createScopedLogger } from '~/utils/logger';
 import { LLMManager } from '~/lib/modules/llm/manager';
 
 const logger = createScopedLogger('create-summary');
 
+const WINDOW_SUMMARY_PROMPT = `
+You are a software engineer summarizing a part of a chat about a project you are working on.
+Write at most 200 words covering the requests, decisions, changes made to the code and open problems.
+Do not write anything other than the summary.
+`;
+
+const MERGE_SUMMARY_PROMPT = `
+You are a software engineer. You are given the summaries of consecutive parts of a chat about a project, oldest first.
+Combine them into one summary of at most 300 words, keep decisions and constraints that still apply and drop details
+that later parts made obsolete. Do not write anything other than the summary.
+`;
+
 export async function createSummary(props: {
   messages: Message[];
   env?: Env;
@@ -39,14 +52,15 @@ export async function createSummary(props: {
     return message;
   });
 
-  const provider = PROVIDER_LIST.find((p) => p.name === currentProvider) || DEFAULT_PROVIDER;
-  const staticModels = LLMManager.getInstance().getStaticModelListFromProvider(provider);
+  const llmManager = LLMManager.getInstance();
+  const provider = llmManager.getProvider(currentProvider) || llmManager.getDefaultProvider();
+  const staticModels = llmManager.getStaticModelListFromProvider(provider);
   let modelDetails = staticModels.find((m) => m.name === currentModel);
 
   if (!modelDetails) {
     const modelsList = [
       ...(provider.staticModels || []),
-      ...(await LLMManager.getInstance().getModelListFromProvider(provider, {
+      ...(await llmManager.getModelListFromProvider(provider, {
         apiKeys,
         providerSettings,
         serverEnv: serverEnv as any,
@@ -68,33 +82,48 @@ export async function createSummary(props: {
     }
   }
 
-  let slicedMessages = processedMessages;
-  const { summary } = extractCurrentContext(processedMessages);
-  let summaryText: string | undefined = undefined;
-  let chatId: string | undefined = undefined;
-
-  if (summary && summary.type === 'chatSummary') {
-    chatId = summary.chatId;
-    summaryText = `Below is the Chat Summary till now, this is chat summary before the conversation provided by the user 
-you should also use this as historical message while providing the response to the user.        
-${summary.summary}`;
-
-    if (chatId) {
-      let index = 0;
-
-      for (let i = 0; i < processedMessages.length; i++) {
-        if (processedMessages[i].id === chatId) {
-          index = i;
-          break;
-        }
-      }
-      slicedMessages = processedMessages.slice(index + 1);
-    }
-  }
-
-  logger.debug('Sliced Messages:', slicedMessages.length);
+  const model = provider.getModelInstance({
+    model: currentModel,
+    serverEnv,
+    apiKeys,
+    providerSettings,
+  });
 
   const extractTextContent = (message: Message) => message.content;
+  const formatMessages = (items: Message[]) =>
+    items.map((x) => `---\n[${x.role}] ${extractTextContent(x)}\n---`).join('\n');
+
+  // earlier windows keep their summaries, only windows completed since the last turn are summarized
+  const { nodes, pending } = await updateSummaryTree(extractSummaryTree(processedMessages), processedMessages, {
+    async window(windowMessages) {
+      const resp = await generateText({
+        system: WINDOW_SUMMARY_PROMPT,
+        prompt: `<chats>\n${formatMessages(windowMessages)}\n</chats>`,
+        model,
+      });
+      onFinish?.(resp);
+
+      return resp.text;
+    },
+    async merge(siblings) {
+      const resp = await generateText({
+        system: MERGE_SUMMARY_PROMPT,
+        prompt: siblings.map((node) => `<summary>\n${node.summary}\n</summary>`).join('\n'),
+        model,
+      });
+      onFinish?.(resp);
+
+      return resp.text;
+    },
+  });
+
+  logger.debug(`Summary tree: ${nodes.length} nodes, ${pending.length} pending messages`);
+
+  const summaryText = nodes.length
+    ? `Below is the Chat Summary till now, oldest first, you should also use this as historical message while providing
+the response to the user.
+${nodes.map((node) => `## ${node.messageCount} messages\n${node.summary}`).join('\n\n')}`
+    : undefined;
 
   // select files from the list of code file from the project that might be useful for the current request from the user
   const resp = await generateText({
@@ -166,22 +195,13 @@ ${summaryText}
 Below is the chat after that:
 ---
 <new_chats>
-${slicedMessages
-  .map((x) => {
-    return `---\n[${x.role}] ${extractTextContent(x)}\n---`;
-  })
-  .join('\n')}
+${formatMessages(pending)}
 </new_chats>
 ---
 
 Please provide a summary of the chat till now including the hitorical summary of the chat.
 `,
-    model: provider.getModelInstance({
-      model: currentModel,
-      serverEnv,
-      apiKeys,
-      providerSettings,
-    }),
+    model,
   });
 
   const response = resp.text;
@@ -190,5 +210,5 @@ Please provide a summary of the chat till now including the hitorical summary of
     onFinish(resp);
   }
 
-  return response;
+  return { summary: response, summaryTree: nodes };
 }
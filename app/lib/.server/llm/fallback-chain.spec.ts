@@ -0,0 +1,61 @@
+import { describe, expect, it } from 'vitest';
+import { classifyLlmError, parseFallbackChain, shouldFallback } from './fallback-chain';
+
+describe('parseFallbackChain', () => {
+  it('splits entries into providers and models', () => {
+    expect(parseFallbackChain('Anthropic:claude-3-5-sonnet-latest, OpenAI:gpt-4o')).toEqual([
+      { provider: 'Anthropic', model: 'claude-3-5-sonnet-latest' },
+      { provider: 'OpenAI', model: 'gpt-4o' },
+    ]);
+  });
+
+  it('keeps colons in model names', () => {
+    expect(parseFallbackChain('Ollama:llama3.1:8b')).toEqual([{ provider: 'Ollama', model: 'llama3.1:8b' }]);
+  });
+
+  it('skips empty and malformed entries', () => {
+    expect(parseFallbackChain(' , OpenAI, :gpt-4o, Google:, Groq : llama-3.3-70b ,')).toEqual([
+      { provider: 'Groq', model: 'llama-3.3-70b' },
+    ]);
+    expect(parseFallbackChain(undefined)).toEqual([]);
+    expect(parseFallbackChain('')).toEqual([]);
+  });
+});
+
+describe('classifyLlmError', () => {
+  it('detects authentication errors', () => {
+    expect(classifyLlmError({ message: 'Forbidden', statusCode: 403 })).toBe('authentication');
+    expect(classifyLlmError(new Error('Invalid API key provided'))).toBe('authentication');
+  });
+
+  it('detects exhausted quotas before rate limits', () => {
+    expect(classifyLlmError({ message: 'You exceeded your current quota', statusCode: 429 })).toBe('quota');
+    expect(classifyLlmError(new Error('insufficient_quota'))).toBe('quota');
+  });
+
+  it('detects rate limits', () => {
+    expect(classifyLlmError({ message: 'Too Many Requests', status: 429 })).toBe('rate_limit');
+    expect(classifyLlmError(new Error('Rate limit reached for gpt-4o'))).toBe('rate_limit');
+  });
+
+  it('detects network and server errors', () => {
+    expect(classifyLlmError({ message: 'Internal Server Error', statusCode: 500 })).toBe('network');
+    expect(classifyLlmError(new Error('Overloaded'))).toBe('network');
+    expect(classifyLlmError(new Error('fetch failed'))).toBe('network');
+  });
+
+  it('falls back to unknown', () => {
+    expect(classifyLlmError(new Error('Context length exceeded'))).toBe('unknown');
+    expect(classifyLlmError(undefined)).toBe('unknown');
+  });
+});
+
+describe('shouldFallback', () => {
+  it('only falls back on rate limit and quota errors', () => {
+    expect(shouldFallback('rate_limit')).toBe(true);
+    expect(shouldFallback('quota')).toBe(true);
+    expect(shouldFallback('authentication')).toBe(false);
+    expect(shouldFallback('network')).toBe(false);
+    expect(shouldFallback('unknown')).toBe(false);
+  });
+});
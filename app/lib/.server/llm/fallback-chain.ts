@@ -0,0 +1,85 @@
+import type { LlmErrorAlertType } from '~/types/actions';
+
+export interface ModelTarget {
+  provider: string;
+  model: string;
+}
+
+export type LlmErrorType = NonNullable<LlmErrorAlertType['errorType']>;
+
+/**
+ * Error types worth retrying on another provider, everything else would fail the same way there or is a
+ * problem with the request itself.
+ */
+const FALLBACK_ERROR_TYPES: LlmErrorType[] = ['rate_limit', 'quota'];
+
+/**
+ * Parses a fallback chain such as `Anthropic:claude-3-5-sonnet-latest, OpenAI:gpt-4o, Ollama:llama3.1`.
+ * Model names may contain colons, only the first one separates the provider.
+ */
+export function parseFallbackChain(value?: string): ModelTarget[] {
+  if (!value) {
+    return [];
+  }
+
+  return value
+    .split(',')
+    .map((entry) => entry.trim())
+    .filter(Boolean)
+    .flatMap((entry) => {
+      const separator = entry.indexOf(':');
+
+      if (separator <= 0 || separator === entry.length - 1) {
+        return [];
+      }
+
+      return [{ provider: entry.slice(0, separator).trim(), model: entry.slice(separator + 1).trim() }];
+    });
+}
+
+/**
+ * Models to try after the one the user picked, configured through `LLM_FALLBACK_CHAIN`.
+ */
+export function getFallbackChain(serverEnv: Env | undefined, primary: ModelTarget): ModelTarget[] {
+  const value = serverEnv?.LLM_FALLBACK_CHAIN || process?.env?.LLM_FALLBACK_CHAIN;
+
+  return parseFallbackChain(value).filter(
+    (target) => target.provider !== primary.provider || target.model !== primary.model,
+  );
+}
+
+/**
+ * Server side counterpart of the classification the chat applies to failed requests.
+ */
+export function classifyLlmError(error: unknown): LlmErrorType {
+  const err = error as { message?: string; statusCode?: number; status?: number } | undefined;
+  const message = String(err?.message ?? error ?? '').toLowerCase();
+  const statusCode = err?.statusCode ?? err?.status;
+
+  if (statusCode === 401 || statusCode === 403 || message.includes('api key') || message.includes('unauthorized')) {
+    return 'authentication';
+  }
+
+  if (message.includes('quota') || message.includes('insufficient_quota') || message.includes('billing')) {
+    return 'quota';
+  }
+
+  if (statusCode === 429 || /rate.?limit|\b429\b/.test(message)) {
+    return 'rate_limit';
+  }
+
+  if (
+    (statusCode !== undefined && statusCode >= 500) ||
+    message.includes('overloaded') ||
+    message.includes('network') ||
+    message.includes('fetch failed')
+  ) {
+    return 'network';
+  }
+
+  return 'unknown';
+}
+
+export function shouldFallback(errorType: LlmErrorType) {
+  return FALLBACK_ERROR_TYPES.includes(errorType);
+}
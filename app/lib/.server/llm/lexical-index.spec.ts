@@ -0,0 +1,73 @@
+import { describe, expect, it } from 'vitest';
+import { chunkBySymbol, LexicalIndex } from './lexical-index';
+import type { FileMap } from './constants';
+
+const file = (content: string) => ({ type: 'file' as const, content, isBinary: false });
+
+const cartSource = [
+  "import { formatPrice } from './format';",
+  '',
+  'export function addToCart(cart: Cart, product: Product) {',
+  '  cart.items.push(product);',
+  '}',
+  '',
+  'export function cartTotal(cart: Cart) {',
+  '  return cart.items.reduce((total, item) => total + item.price, 0);',
+  '}',
+].join('\n');
+
+const files: FileMap = {
+  '/home/project/src/cart.ts': file(cartSource),
+  '/home/project/src/auth.ts': file(
+    ['export async function login(email: string, password: string) {', '  return session(email, password);', '}'].join(
+      '\n',
+    ),
+  ),
+  '/home/project/src/theme.css': file('body {\n  background: white;\n  color: black;\n}'),
+  '/home/project/README.md': file('# Shop\n\nA small demo shop.'),
+};
+
+describe('chunkBySymbol', () => {
+  it('cuts files at top level declarations', () => {
+    const chunks = chunkBySymbol(cartSource);
+
+    expect(chunks.map((chunk) => chunk.symbol)).toEqual([undefined, 'addToCart', 'cartTotal']);
+    expect(chunks[1]).toMatchObject({ startLine: 2, endLine: 6 });
+  });
+});
+
+describe('LexicalIndex', () => {
+  it('ranks the file with the most similar chunk first', () => {
+    const index = new LexicalIndex();
+    index.sync(files);
+
+    const { matches, confident } = index.search('The cart total ignores the item price');
+
+    expect(matches[0].path).toBe('/home/project/src/cart.ts');
+    expect(matches[0].chunk.symbol).toBe('cartTotal');
+    expect(confident).toBe(true);
+  });
+
+  it('is not confident about messages unrelated to the code', () => {
+    const index = new LexicalIndex();
+    index.sync(files);
+
+    expect(index.search('hello there').confident).toBe(false);
+  });
+
+  it('only re-indexes files whose content changed', () => {
+    const index = new LexicalIndex();
+    index.sync(files);
+
+    const cartChunks = index.getChunks('/home/project/src/cart.ts');
+    const authChunks = index.getChunks('/home/project/src/auth.ts');
+
+    const changedFiles: FileMap = { ...files, '/home/project/src/auth.ts': file('export function logout() {}') };
+    delete changedFiles['/home/project/README.md'];
+    index.sync(changedFiles);
+
+    expect(index.getChunks('/home/project/src/cart.ts')).toBe(cartChunks);
+    expect(index.getChunks('/home/project/src/auth.ts')).not.toBe(authChunks);
+    expect(index.size).toBe(3);
+  });
+});
@@ -0,0 +1,246 @@
+import type { FileMap } from './constants';
+import { WORK_DIR } from '~/utils/constants';
+
+const DIMENSIONS = 512;
+
+// chunks longer than this are split, files without recognisable symbols are cut into windows of this size
+const MAX_CHUNK_LINES = 80;
+
+// files larger than this are most likely generated or minified and are not worth indexing
+const MAX_FILE_SIZE = 200_000;
+
+// a match is trusted when its best chunk scores at least this and clearly stands out from the other files
+const MIN_CONFIDENT_SCORE = 0.3;
+const MIN_CONFIDENT_MARGIN = 0.1;
+
+const SYMBOL_PATTERN =
+  /^(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(?:pub(?:\([\w\s]+\))?\s+)?(?:function\*?|class|interface|type|enum|const|let|var|def|fn|struct|trait|impl)\s+([A-Za-z_$][\w$]*)/;
+
+// keywords that appear in almost every chunk and only blur the vectors
+const STOP_WORDS = new Set(
+  [
+    'the a an and or of to in is it for on with as be this that are by at from if else return',
+    'import export default const let var function class new type interface extends implements',
+    'async await true false null undefined void string number boolean any public private protected',
+    'static readonly get set def self fn pub use mut impl struct',
+  ]
+    .join(' ')
+    .split(' '),
+);
+
+export interface LexicalChunk {
+  path: string;
+  symbol?: string;
+  startLine: number;
+  endLine: number;
+  vector: Float32Array;
+}
+
+export interface LexicalMatch {
+  path: string;
+  score: number;
+  chunk: Omit<LexicalChunk, 'vector'>;
+}
+
+export interface LexicalSearchResult {
+  matches: LexicalMatch[];
+
+  /**
+   * Whether the ranking can be used as is, low confidence means the caller should fall back to asking the LLM.
+   */
+  confident: boolean;
+}
+
+/**
+ * Splits identifiers such as `useChatHistory` or `MAX_TOKENS` into lower case words, keeping the whole identifier too.
+ */
+export function tokenize(text: string): string[] {
+  const tokens: string[] = [];
+
+  for (const word of text.match(/[A-Za-z_$][\w$]*|\d+/g) ?? []) {
+    const parts = word
+      .replace(/([a-z\d])([A-Z])/g, '$1 $2')
+      .replace(/([A-Z]+)([A-Z][a-z])/g, '$1 $2')
+      .split(/[\s_$]+/)
+      .map((part) => part.toLowerCase())
+      .filter((part) => part.length > 1 && !STOP_WORDS.has(part));
+
+    tokens.push(...parts);
+
+    if (parts.length > 1) {
+      tokens.push(word.toLowerCase());
+    }
+  }
+
+  return tokens;
+}
+
+function hashToken(token: string) {
+  // FNV-1a
+  let hash = 0x811c9dc5;
+
+  for (let i = 0; i < token.length; i++) {
+    hash ^= token.charCodeAt(i);
+    hash = Math.imul(hash, 0x01000193);
+  }
+
+  return hash >>> 0;
+}
+
+/**
+ * Turns text into a hashed bag of words: a fixed size vector of its token counts, weighted by sublinear term frequency
+ * and normalised so that the dot product of two vectors is their cosine similarity. This is not an embedding, texts
+ * only score when they share words.
+ */
+export function vectorize(text: string): Float32Array {
+  const counts = new Map<string, number>();
+
+  for (const token of tokenize(text)) {
+    counts.set(token, (counts.get(token) ?? 0) + 1);
+  }
+
+  const vector = new Float32Array(DIMENSIONS);
+
+  for (const [token, count] of counts) {
+    const hash = hashToken(token);
+    const sign = hash & 0x80000000 ? -1 : 1;
+    vector[hash % DIMENSIONS] += sign * (1 + Math.log(count));
+  }
+
+  let norm = 0;
+
+  for (const value of vector) {
+    norm += value * value;
+  }
+
+  norm = Math.sqrt(norm);
+
+  if (norm > 0) {
+    for (let i = 0; i < vector.length; i++) {
+      vector[i] /= norm;
+    }
+  }
+
+  return vector;
+}
+
+function similarity(a: Float32Array, b: Float32Array) {
+  let sum = 0;
+
+  for (let i = 0; i < a.length; i++) {
+    sum += a[i] * b[i];
+  }
+
+  return sum;
+}
+
+/**
+ * Cuts a file at its top level declarations, the lines before the first one (usually imports) form their own chunk.
+ */
+export function chunkBySymbol(content: string): Array<{ symbol?: string; startLine: number; endLine: number }> {
+  const lines = content.split('\n');
+  const boundaries: Array<{ symbol?: string; startLine: number }> = [{ startLine: 0 }];
+
+  lines.forEach((line, index) => {
+    const match = line.match(SYMBOL_PATTERN);
+
+    if (match && index > 0) {
+      boundaries.push({ symbol: match[1], startLine: index });
+    } else if (match) {
+      boundaries[0].symbol = match[1];
+    }
+  });
+
+  const chunks: Array<{ symbol?: string; startLine: number; endLine: number }> = [];
+
+  boundaries.forEach((boundary, index) => {
+    const end = boundaries[index + 1]?.startLine ?? lines.length;
+
+    for (let start = boundary.startLine; start < end; start += MAX_CHUNK_LINES) {
+      chunks.push({ symbol: boundary.symbol, startLine: start, endLine: Math.min(start + MAX_CHUNK_LINES, end) });
+    }
+  });
+
+  return chunks.filter((chunk) => lines.slice(chunk.startLine, chunk.endLine).some((line) => line.trim()));
+}
+
+/**
+ * Lexical index over the files of a project, ranking chunks by the words and identifiers they share with the query.
+ * Synonyms are not recognised: "login page" finds `login.tsx` but not `AuthForm.tsx`. Files are only chunked and
+ * indexed again when their content changed since the last sync, so keeping it up to date on every chat turn is cheap.
+ */
+export class LexicalIndex {
+  #entries = new Map<string, { content: string; chunks: LexicalChunk[] }>();
+
+  get size() {
+    return this.#entries.size;
+  }
+
+  sync(files: FileMap, include: (path: string) => boolean = () => true) {
+    const seen = new Set<string>();
+
+    for (const [path, dirent] of Object.entries(files)) {
+      if (dirent?.type !== 'file' || dirent.isBinary || dirent.content.length > MAX_FILE_SIZE || !include(path)) {
+        continue;
+      }
+
+      seen.add(path);
+
+      if (this.#entries.get(path)?.content !== dirent.content) {
+        this.#entries.set(path, { content: dirent.content, chunks: this.#chunkFile(path, dirent.content) });
+      }
+    }
+
+    for (const path of this.#entries.keys()) {
+      if (!seen.has(path)) {
+        this.#entries.delete(path);
+      }
+    }
+  }
+
+  getChunks(path: string) {
+    return this.#entries.get(path)?.chunks ?? [];
+  }
+
+  /**
+   * Ranks files by their most similar chunk.
+   */
+  search(query: string, limit = 5): LexicalSearchResult {
+    const queryVector = vectorize(query);
+    const bestPerFile = new Map<string, LexicalMatch>();
+
+    for (const { chunks } of this.#entries.values()) {
+      for (const { vector, ...chunk } of chunks) {
+        const score = similarity(queryVector, vector);
+        const best = bestPerFile.get(chunk.path);
+
+        if (!best || score > best.score) {
+          bestPerFile.set(chunk.path, { path: chunk.path, score, chunk });
+        }
+      }
+    }
+
+    const ranked = [...bestPerFile.values()].sort((a, b) => b.score - a.score);
+    const top = ranked[0]?.score ?? 0;
+    const baseline = ranked[limit]?.score ?? 0;
+    const matches = ranked.slice(0, limit).filter((match) => match.score > 0 && match.score >= top / 2);
+
+    return {
+      matches,
+      confident: top >= MIN_CONFIDENT_SCORE && top - baseline >= MIN_CONFIDENT_MARGIN,
+    };
+  }
+
+  #chunkFile(path: string, content: string): LexicalChunk[] {
+    const lines = content.split('\n');
+
+    // the path is part of every chunk, a question about "the navbar" should find `components/Navbar.tsx`
+    const relativePath = path.replace(`${WORK_DIR}/`, '');
+
+    return chunkBySymbol(content).map((chunk) => ({
+      path,
+      ...chunk,
+      vector: vectorize(`${relativePath}\n${lines.slice(chunk.startLine, chunk.endLine).join('\n')}`),
+    }));
+  }
+}
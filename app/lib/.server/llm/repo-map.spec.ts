@@ -0,0 +1,73 @@
+import ts from 'typescript';
+import { describe, expect, it } from 'vitest';
+import { createRepoMap, outlineScript, outlineScriptLoosely } from './repo-map';
+
+const source = `import { useState } from 'react';
+import type { Product } from './types';
+
+const TAX_RATE = 0.2;
+
+export const CURRENCY: string = 'EUR';
+
+export async function fetchProducts(category: string, limit = 10): Promise<Product[]> {
+  return [];
+}
+
+export class Cart extends Store {
+  items: Product[] = [];
+  private total = 0;
+
+  add(product: Product) {}
+}
+
+export const useCart = (initial: Product[]) => useState(initial);
+
+export default Cart;
+`;
+
+describe('outlineScript', () => {
+  it('lists imports and exported signatures', () => {
+    expect(outlineScript(ts, 'src/cart.ts', source)).toEqual([
+      'imports: react, ./types',
+      'export const CURRENCY: string',
+      'export async function fetchProducts(category: string, limit = 10): Promise<Product[]>',
+      'export class Cart extends Store { items, add }',
+      'export const useCart = (initial: Product[]) =>',
+      'export default Cart',
+    ]);
+  });
+});
+
+describe('outlineScriptLoosely', () => {
+  it('lists imports and export lines without the compiler', () => {
+    expect(outlineScriptLoosely(source)).toEqual([
+      'imports: react, ./types',
+      'export const CURRENCY: string',
+      'export async function fetchProducts(category: string, limit = 10): Promise<Product[]>',
+      'export class Cart extends Store',
+      'export const useCart = (initial: Product[]) =>',
+      'export default Cart',
+    ]);
+  });
+
+  it('reads imports that span several lines', () => {
+    expect(outlineScriptLoosely(`import {\n  a,\n  b,\n} from './ab';\nimport './styles.css';\n`)).toEqual([
+      'imports: ./ab, ./styles.css',
+    ]);
+  });
+});
+
+describe('createRepoMap', () => {
+  it('maps every file relative to the project and skips ignored ones', async () => {
+    const repoMap = await createRepoMap({
+      '/home/project/src/cart.ts': { type: 'file', content: source, isBinary: false },
+      '/home/project/README.md': { type: 'file', content: '# Shop', isBinary: false },
+      '/home/project/node_modules/react/index.js': { type: 'file', content: 'export {}', isBinary: false },
+      '/home/project/src': { type: 'folder' },
+    });
+
+    expect(repoMap).toContain('README.md');
+    expect(repoMap).toContain('src/cart.ts\n  imports: react, ./types');
+    expect(repoMap).not.toContain('node_modules');
+  });
+});
@@ -0,0 +1,265 @@
+import type * as TypeScript from 'typescript';
+import ignore from 'ignore';
+import { IGNORE_PATTERNS, type FileMap } from './constants';
+import { WORK_DIR } from '~/utils/constants';
+import { createScopedLogger } from '~/utils/logger';
+
+const logger = createScopedLogger('repo-map');
+
+// keeps the map to a few thousand tokens, files beyond it are only counted
+const MAX_REPO_MAP_LENGTH = 24_000;
+
+// longer signatures are cut, a huge inline object type tells the model little about the project's shape
+const MAX_SIGNATURE_LENGTH = 160;
+
+const SCRIPT_EXTENSIONS = /\.(?:[cm]?[jt]sx?)$/;
+
+const ig = ignore().add(IGNORE_PATTERNS);
+
+// outlines are cached by path and only parsed again when the file's content changed
+const outlineCache = new Map<string, { content: string; outline: string[] }>();
+
+let typescript: Promise<typeof TypeScript | undefined> | undefined;
+let isTypeScriptMissingLogged = false;
+
+function loadTypeScript() {
+  /*
+   * `typescript` is only a dev dependency and is not bundled into the worker, so this usually fails in production;
+   * the compiler is large, only load it once the first map is requested
+   */
+  typescript ??= import('typescript')
+    .then((module) => (module as { default?: typeof TypeScript }).default ?? module)
+    .catch((error) => {
+      // the next map tries again
+      typescript = undefined;
+
+      if (!isTypeScriptMissingLogged) {
+        isTypeScriptMissingLogged = true;
+        logger.info('TypeScript is not installed, outlining scripts from their export lines', error);
+      }
+
+      return undefined;
+    });
+
+  return typescript;
+}
+
+function clip(text: string) {
+  const singleLine = text.replace(/\s+/g, ' ').trim();
+
+  return singleLine.length > MAX_SIGNATURE_LENGTH ? `${singleLine.slice(0, MAX_SIGNATURE_LENGTH)}...` : singleLine;
+}
+
+function getScriptKind(ts: typeof TypeScript, path: string) {
+  if (path.endsWith('.tsx')) {
+    return ts.ScriptKind.TSX;
+  }
+
+  if (path.endsWith('.jsx')) {
+    return ts.ScriptKind.JSX;
+  }
+
+  return /\.[cm]?js$/.test(path) ? ts.ScriptKind.JS : ts.ScriptKind.TS;
+}
+
+function hasModifier(ts: typeof TypeScript, node: TypeScript.Node, ...kinds: TypeScript.SyntaxKind[]) {
+  return ts.canHaveModifiers(node) && !!ts.getModifiers(node)?.some((modifier) => kinds.includes(modifier.kind));
+}
+
+function getSignature(sourceFile: TypeScript.SourceFile, node: TypeScript.SignatureDeclarationBase) {
+  const typeParameters = node.typeParameters
+    ? `<${node.typeParameters.map((parameter) => parameter.getText(sourceFile)).join(', ')}>`
+    : '';
+  const parameters = node.parameters.map((parameter) => parameter.getText(sourceFile)).join(', ');
+  const returnType = node.type ? `: ${node.type.getText(sourceFile)}` : '';
+
+  return `${typeParameters}(${parameters})${returnType}`;
+}
+
+function getHeritage(
+  sourceFile: TypeScript.SourceFile,
+  node: TypeScript.ClassLikeDeclaration | TypeScript.InterfaceDeclaration,
+) {
+  const clauses = node.heritageClauses?.map((clause) => clause.getText(sourceFile)) ?? [];
+
+  return clauses.length ? ` ${clauses.join(' ')}` : '';
+}
+
+function getClassMembers(
+  ts: typeof TypeScript,
+  sourceFile: TypeScript.SourceFile,
+  node: TypeScript.ClassLikeDeclaration,
+) {
+  const members = node.members
+    .filter((member) => {
+      const isPrivate = hasModifier(ts, member, ts.SyntaxKind.PrivateKeyword, ts.SyntaxKind.ProtectedKeyword);
+
+      return member.name && !isPrivate && !ts.isPrivateIdentifier(member.name);
+    })
+    .map((member) => member.name!.getText(sourceFile));
+
+  return members.length ? ` { ${members.join(', ')} }` : '';
+}
+
+/**
+ * Lists the imports and the exported symbols of a script with their signatures.
+ */
+export function outlineScript(ts: typeof TypeScript, path: string, content: string): string[] {
+  const sourceFile = ts.createSourceFile(path, content, ts.ScriptTarget.Latest, false, getScriptKind(ts, path));
+  const imports: string[] = [];
+  const symbols: string[] = [];
+
+  for (const statement of sourceFile.statements) {
+    if (ts.isImportDeclaration(statement) && ts.isStringLiteral(statement.moduleSpecifier)) {
+      imports.push(statement.moduleSpecifier.text);
+      continue;
+    }
+
+    if (ts.isExportDeclaration(statement)) {
+      symbols.push(clip(statement.getText(sourceFile).replace(/;$/, '')));
+      continue;
+    }
+
+    if (ts.isExportAssignment(statement)) {
+      symbols.push(`export default ${clip(statement.expression.getText(sourceFile))}`);
+      continue;
+    }
+
+    if (!hasModifier(ts, statement, ts.SyntaxKind.ExportKeyword)) {
+      continue;
+    }
+
+    const prefix = hasModifier(ts, statement, ts.SyntaxKind.DefaultKeyword) ? 'export default' : 'export';
+
+    if (ts.isFunctionDeclaration(statement)) {
+      const name = statement.name?.text ?? '';
+      const keyword = hasModifier(ts, statement, ts.SyntaxKind.AsyncKeyword) ? 'async function' : 'function';
+      symbols.push(clip(`${prefix} ${keyword} ${name}${getSignature(sourceFile, statement)}`));
+    } else if (ts.isClassDeclaration(statement)) {
+      const name = statement.name?.text ?? '';
+      const members = getClassMembers(ts, sourceFile, statement);
+      symbols.push(clip(`${prefix} class ${name}${getHeritage(sourceFile, statement)}${members}`));
+    } else if (ts.isInterfaceDeclaration(statement)) {
+      symbols.push(clip(`${prefix} interface ${statement.name.text}${getHeritage(sourceFile, statement)}`));
+    } else if (ts.isTypeAliasDeclaration(statement)) {
+      symbols.push(clip(`${prefix} type ${statement.name.text} = ${statement.type.getText(sourceFile)}`));
+    } else if (ts.isEnumDeclaration(statement)) {
+      const members = statement.members.map((member) => member.name.getText(sourceFile)).join(', ');
+      symbols.push(clip(`${prefix} enum ${statement.name.text} { ${members} }`));
+    } else if (ts.isVariableStatement(statement)) {
+      const kind = statement.declarationList.flags & ts.NodeFlags.Const ? 'const' : 'let';
+
+      for (const declaration of statement.declarationList.declarations) {
+        const name = declaration.name.getText(sourceFile);
+        const initializer = declaration.initializer;
+
+        if (initializer && (ts.isArrowFunction(initializer) || ts.isFunctionExpression(initializer))) {
+          symbols.push(clip(`${prefix} ${kind} ${name} = ${getSignature(sourceFile, initializer)} =>`));
+        } else {
+          const type = declaration.type ? `: ${declaration.type.getText(sourceFile)}` : '';
+          symbols.push(clip(`${prefix} ${kind} ${name}${type}`));
+        }
+      }
+    }
+  }
+
+  return [...(imports.length ? [`imports: ${imports.join(', ')}`] : []), ...symbols];
+}
+
+/**
+ * Outline of a script without the compiler: its imports and the top-level lines that export something, with
+ * initializers and bodies left out. This is how production servers outline scripts. It is coarser than
+ * {@link outlineScript}: class members and multi-line signatures are not listed.
+ */
+export function outlineScriptLoosely(content: string): string[] {
+  const imports = [...content.matchAll(/^import\s+(?:[^'"]*?\sfrom\s+)?['"]([^'"]+)['"]/gm)].map((match) => match[1]);
+  const symbols = content
+    .split('\n')
+    .filter((line) => /^export\s/.test(line))
+    .map((line) => {
+      const declaration = line.trim().replace(/\s*[{;]?$/, '');
+
+      if (!/^export\s+(?:default\s+)?(?:const|let|var)\s/.test(declaration)) {
+        return clip(declaration);
+      }
+
+      const arrow = declaration.indexOf('=>');
+
+      return clip(arrow === -1 ? declaration.split(' = ')[0] : declaration.slice(0, arrow + 2));
+    });
+
+  return [...(imports.length ? [`imports: ${imports.join(', ')}`] : []), ...symbols];
+}
+
+async function getOutline(path: string, content: string) {
+  const cached = outlineCache.get(path);
+
+  if (cached?.content === content) {
+    return cached.outline;
+  }
+
+  if (!SCRIPT_EXTENSIONS.test(path)) {
+    return [];
+  }
+
+  const ts = await loadTypeScript();
+
+  if (!ts) {
+    return outlineScriptLoosely(content);
+  }
+
+  let outline: string[] = [];
+
+  try {
+    outline = outlineScript(ts, path, content);
+  } catch (error) {
+    logger.warn(`Failed to outline ${path}`, error);
+  }
+
+  outlineCache.set(path, { content, outline });
+
+  return outline;
+}
+
+/**
+ * Creates a compact map of the project: every file with its imports and exported signatures, so that the model knows
+ * the shape of the project without reading every file.
+ *
+ * Scripts are outlined from their export lines with {@link outlineScriptLoosely}. Only where the `typescript`
+ * dev dependency is installed, such as the dev server, the compiler refines them with {@link outlineScript}.
+ */
+export async function createRepoMap(files: FileMap) {
+  const paths = Object.keys(files)
+    .filter((path) => files[path]?.type === 'file' && !ig.ignores(path.replace(`${WORK_DIR}/`, '')))
+    .sort();
+
+  const sections: string[] = [];
+  let length = 0;
+
+  for (const [index, path] of paths.entries()) {
+    const dirent = files[path];
+
+    if (dirent?.type !== 'file') {
+      continue;
+    }
+
+    const outline = dirent.isBinary ? [] : await getOutline(path, dirent.content);
+    const section = [path.replace(`${WORK_DIR}/`, ''), ...outline.map((line) => `  ${line}`)].join('\n');
+
+    if (length + section.length > MAX_REPO_MAP_LENGTH) {
+      sections.push(`... and ${paths.length - index} more files`);
+      break;
+    }
+
+    sections.push(section);
+    length += section.length + 1;
+  }
+
+  for (const path of outlineCache.keys()) {
+    if (!files[path]) {
+      outlineCache.delete(path);
+    }
+  }
+
+  return sections.join('\n');
+}
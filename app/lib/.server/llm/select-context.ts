@@ -2,28 +2,61 @@ import { generateText, type Tool, type GenerateTextResult, type UIMessage as Mes
 import ignore from 'ignore';
 import type { IProviderSetting } from '~/types/model';
 import { IGNORE_PATTERNS, type FileMap } from './constants';
-import { DEFAULT_MODEL, DEFAULT_PROVIDER, PROVIDER_LIST } from '~/utils/constants';
+import { DEFAULT_MODEL, DEFAULT_PROVIDER } from '~/utils/constants';
 import { createFilesContext, extractCurrentContext, extractPropertiesFromMessage, simplifysmackActions } from './utils';
 import { createScopedLogger } from '~/utils/logger';
 import { LLMManager } from '~/lib/modules/llm/manager';
+import { LexicalIndex } from './lexical-index';
 
 // Common patterns to ignore, similar to .gitignore
 
 const ig = ignore().add(IGNORE_PATTERNS);
 const logger = createScopedLogger('select-context');
 
+/*
+ * indexes of the chats used last, kept across requests so that only files changed since the previous turn are
+ * indexed again
+ */
+const MAX_LEXICAL_INDEXES = 20;
+const lexicalIndexes = new Map<string, LexicalIndex>();
+
+/**
+ * The index of a chat, the least recently used one is dropped when too many chats are open. Requests without a chat
+ * id get an index of their own, every chat has a different project.
+ */
+function getLexicalIndex(chatId?: string) {
+  if (!chatId) {
+    return new LexicalIndex();
+  }
+
+  const lexicalIndex = lexicalIndexes.get(chatId) ?? new LexicalIndex();
+
+  // re-inserting keeps the map ordered from least to most recently used
+  lexicalIndexes.delete(chatId);
+  lexicalIndexes.set(chatId, lexicalIndex);
+
+  if (lexicalIndexes.size > MAX_LEXICAL_INDEXES) {
+    lexicalIndexes.delete(lexicalIndexes.keys().next().value!);
+  }
+
+  return lexicalIndex;
+}
+
 export async function selectContext(props: {
   messages: Message[];
+  chatId?: string;
   env?: Env;
   apiKeys?: Record<string, string>;
   files: FileMap;
   providerSettings?: Record<string, IProviderSetting>;
   promptId?: string;
   contextOptimization?: boolean;
+  lexicalContext?: boolean;
   summary: string;
   onFinish?: (resp: GenerateTextResult<Record<string, Tool<any, any>>, never>) => void;
 }) {
-  const { messages, env: serverEnv, apiKeys, files, providerSettings, summary, onFinish } = props;
+  const { messages, chatId, env: serverEnv, apiKeys, files, providerSettings, lexicalContext, summary, onFinish } =
+    props;
   let currentModel = DEFAULT_MODEL;
   let currentProvider = DEFAULT_PROVIDER.name;
   const processedMessages = messages.map((message) => {
@@ -47,14 +80,15 @@ export async function selectContext(props: {
     return message;
   });
 
-  const provider = PROVIDER_LIST.find((p) => p.name === currentProvider) || DEFAULT_PROVIDER;
-  const staticModels = LLMManager.getInstance().getStaticModelListFromProvider(provider);
+  const llmManager = LLMManager.getInstance();
+  const provider = llmManager.getProvider(currentProvider) || llmManager.getDefaultProvider();
+  const staticModels = llmManager.getStaticModelListFromProvider(provider);
   let modelDetails = staticModels.find((m) => m.name === currentModel);
 
   if (!modelDetails) {
     const modelsList = [
       ...(provider.staticModels || []),
-      ...(await LLMManager.getInstance().getModelListFromProvider(provider, {
+      ...(await llmManager.getModelListFromProvider(provider, {
         apiKeys,
         providerSettings,
         serverEnv: serverEnv as any,
@@ -118,6 +152,15 @@ export async function selectContext(props: {
     throw new Error('No user message found');
   }
 
+  if (lexicalContext) {
+    const query = extractTextContent(lastUserMessage);
+    const lexicalFiles = selectLexicalContext(getLexicalIndex(chatId), files || {}, query);
+
+    if (lexicalFiles) {
+      return lexicalFiles;
+    }
+  }
+
   // select files from the list of code file from the project that might be useful for the current request from the user
   const resp = await generateText({
     system: `
@@ -233,6 +276,32 @@ export async function selectContext(props: {
   // generateText({
 }
 
+/**
+ * Picks the files whose chunks are most similar to the user's message, returns nothing when the ranking is not
+ * confident enough so that the LLM selects the files instead.
+ */
+function selectLexicalContext(lexicalIndex: LexicalIndex, files: FileMap, query: string): FileMap | undefined {
+  lexicalIndex.sync(files, (path) => !ig.ignores(path.replace('/home/project/', '')));
+
+  const { matches, confident } = lexicalIndex.search(query);
+
+  if (!confident || matches.length === 0) {
+    logger.info(`Lexical context is not confident (${matches[0]?.score.toFixed(2) ?? 0}), asking the LLM`);
+    return undefined;
+  }
+
+  const selectedFiles: FileMap = {};
+
+  for (const { path, score, chunk } of matches) {
+    logger.debug(`Lexical match ${path}:${chunk.startLine + 1} ${chunk.symbol ?? ''} (${score.toFixed(2)})`);
+    selectedFiles[path.replace('/home/project/', '')] = files[path];
+  }
+
+  logger.info(`Total files: ${matches.length} (lexical)`);
+
+  return selectedFiles;
+}
+
 export function getFilePaths(files: FileMap) {
   let filePaths = Object.keys(files);
   filePaths = filePaths.filter((x) => {
@@ -2,6 +2,11 @@ import { createScopedLogger } from '~/utils/logger';
 
 const logger = createScopedLogger('stream-recovery');
 
+const ARTIFACT_TAG_OPEN = '<smackArtifact';
+const ARTIFACT_TAG_CLOSE = '</smackArtifact>';
+const ACTION_TAG_OPEN = '<smackAction';
+const ACTION_TAG_CLOSE = '</smackAction>';
+
 export interface StreamRecoveryOptions {
   maxRetries?: number;
   timeout?: number;
@@ -9,11 +14,96 @@ export interface StreamRecoveryOptions {
   onRecovery?: () => void;
 }
 
+export interface ResumePoint {
+  insideArtifact: boolean;
+
+  /**
+   * The response up to and including the last complete action of the open artifact.
+   */
+  completedText: string;
+
+  /**
+   * Unfinished output after `completedText`, the client has to drop it before the continuation.
+   */
+  discardedText: string;
+
+  /**
+   * Opening tag of the last complete action.
+   */
+  lastAction?: string;
+
+  /**
+   * Opening tag of the action that was cut off, possibly incomplete itself.
+   */
+  interruptedAction?: string;
+}
+
+/**
+ * Finds where an interrupted response can be picked up again: right after the last complete action of
+ * the artifact that was still open when the stream died.
+ */
+export function findResumePoint(text: string): ResumePoint {
+  const artifactOpenIndex = text.lastIndexOf(ARTIFACT_TAG_OPEN);
+  const artifactBodyIndex = artifactOpenIndex === -1 ? -1 : text.indexOf('>', artifactOpenIndex) + 1;
+
+  if (artifactBodyIndex <= 0 || text.indexOf(ARTIFACT_TAG_CLOSE, artifactBodyIndex) !== -1) {
+    return { insideArtifact: false, completedText: text, discardedText: '' };
+  }
+
+  const lastCloseIndex = text.lastIndexOf(ACTION_TAG_CLOSE);
+  const cutIndex = lastCloseIndex >= artifactBodyIndex ? lastCloseIndex + ACTION_TAG_CLOSE.length : artifactBodyIndex;
+  const discardedText = text.slice(cutIndex);
+
+  let lastAction: string | undefined;
+
+  if (cutIndex > artifactBodyIndex) {
+    const lastOpenIndex = text.lastIndexOf(ACTION_TAG_OPEN, lastCloseIndex);
+    lastAction = text.slice(lastOpenIndex, text.indexOf('>', lastOpenIndex) + 1);
+  }
+
+  const interruptedIndex = discardedText.indexOf(ACTION_TAG_OPEN);
+  let interruptedAction: string | undefined;
+
+  if (interruptedIndex !== -1) {
+    const tagEndIndex = discardedText.indexOf('>', interruptedIndex);
+    interruptedAction = discardedText.slice(interruptedIndex, tagEndIndex === -1 ? undefined : tagEndIndex + 1);
+  }
+
+  return {
+    insideArtifact: true,
+    completedText: text.slice(0, cutIndex),
+    discardedText,
+    lastAction,
+    interruptedAction,
+  };
+}
+
+/**
+ * Removes the output a resumed response discarded, everything between the last complete action (or the
+ * artifact opening tag) and each resume marker, so the model never sees half written actions.
+ */
+export function stripDiscardedOutput(text: string, marker: string): string {
+  let markerIndex = text.indexOf(marker);
+
+  while (markerIndex !== -1) {
+    const { completedText } = findResumePoint(text.slice(0, markerIndex));
+    text = completedText + text.slice(markerIndex + marker.length);
+    markerIndex = text.indexOf(marker, completedText.length);
+  }
+
+  return text;
+}
+
+/**
+ * Watches a response stream for stalls and keeps track of the text written so far, so a response that
+ * dies in the middle of an artifact can be resumed from its last complete action.
+ */
 export class StreamRecoveryManager {
   private _retryCount = 0;
   private _timeoutHandle: NodeJS.Timeout | null = null;
   private _lastActivity: number = Date.now();
   private _isActive = true;
+  private _text = '';
 
   constructor(private _options: StreamRecoveryOptions = {}) {
     this._options = {
@@ -24,6 +114,7 @@ export class StreamRecoveryManager {
   }
 
   startMonitoring() {
+    this._isActive = true;
     this._resetTimeout();
   }
 
@@ -32,6 +123,49 @@ export class StreamRecoveryManager {
     this._resetTimeout();
   }
 
+  appendText(text: string) {
+    this._text += text;
+    this.updateActivity();
+  }
+
+  get text() {
+    return this._text;
+  }
+
+  /**
+   * Whether an interrupted response can be resumed, which needs an open artifact and retries left.
+   */
+  canResume() {
+    if (this._retryCount >= (this._options.maxRetries || 3)) {
+      logger.error('Max retries reached for stream recovery');
+      return false;
+    }
+
+    return findResumePoint(this._text).insideArtifact;
+  }
+
+  /**
+   * Counts a resume attempt and cuts the tracked text back to the last complete action, the continuation
+   * is appended from there.
+   */
+  beginResume(): ResumePoint {
+    const resumePoint = findResumePoint(this._text);
+
+    this._retryCount++;
+    this._text = resumePoint.completedText;
+
+    logger.info(`Attempting stream recovery (attempt ${this._retryCount})`, {
+      lastAction: resumePoint.lastAction,
+      interruptedAction: resumePoint.interruptedAction,
+    });
+
+    if (this._options.onRecovery) {
+      this._options.onRecovery();
+    }
+
+    return resumePoint;
+  }
+
   private _resetTimeout() {
     if (this._timeoutHandle) {
       clearTimeout(this._timeoutHandle);
@@ -50,26 +184,12 @@ export class StreamRecoveryManager {
   }
 
   private _handleTimeout() {
-    if (this._retryCount >= (this._options.maxRetries || 3)) {
-      logger.error('Max retries reached for stream recovery');
-      this.stop();
-
-      return;
-    }
-
-    this._retryCount++;
-    logger.info(`Attempting stream recovery (attempt ${this._retryCount})`);
+    // monitoring resumes with the next attempt
+    this._isActive = false;
 
     if (this._options.onTimeout) {
       this._options.onTimeout();
     }
-
-    // Reset monitoring after recovery attempt
-    this._resetTimeout();
-
-    if (this._options.onRecovery) {
-      this._options.onRecovery();
-    }
   }
 
   stop() {
@@ -1,14 +1,22 @@
 import { convertToCoreMessages, streamText as _streamText, type UIMessage as Message } from 'ai';
 import { MAX_TOKENS, PROVIDER_COMPLETION_LIMITS, isReasoningModel, type FileMap } from './constants';
 import { getSystemPrompt } from '~/lib/common/prompts/prompts';
-import { DEFAULT_MODEL, DEFAULT_PROVIDER, MODIFICATIONS_TAG_NAME, PROVIDER_LIST, WORK_DIR } from '~/utils/constants';
+import {
+  ACTION_RESUME_MARKER,
+  DEFAULT_MODEL,
+  DEFAULT_PROVIDER,
+  MODIFICATIONS_TAG_NAME,
+  WORK_DIR,
+} from '~/utils/constants';
 import type { IProviderSetting } from '~/types/model';
 import { PromptLibrary } from '~/lib/common/prompt-library';
 import { allowedHTMLElements } from '~/utils/markdown';
 import { LLMManager } from '~/lib/modules/llm/manager';
 import { createScopedLogger } from '~/utils/logger';
 import { createFilesContext, extractPropertiesFromMessage } from './utils';
+import { createRepoMap } from './repo-map';
 import { discussPrompt } from '~/lib/common/prompts/discuss-prompt';
+import { stripDiscardedOutput } from './stream-recovery';
 import type { DesignScheme } from '~/types/design-scheme';
 
 export type Messages = Message[];
@@ -44,7 +52,8 @@ function getCompletionTokenLimit(modelDetails: any): number {
 }
 
 function sanitizeText(text: string): string {
-  let sanitized = text.replace(/<div class=\\"__smackThought__\\">[\s\S]*?<\/div>/gs, '');
+  let sanitized = stripDiscardedOutput(text, ACTION_RESUME_MARKER);
+  sanitized = sanitized.replace(/<div class=\\"__smackThought__\\">[\s\S]*?<\/div>/gs, '');
   sanitized = sanitized.replace(/<think>[\s\S]*?<\/think>/gs, '');
   sanitized = sanitized.replace(/<smackAction type="file" filePath="package-lock\.json">[\s\S]*?<\/smackAction>/g, '');
 
@@ -59,12 +68,18 @@ export async function streamText(props: {
   files?: FileMap;
   providerSettings?: Record<string, IProviderSetting>;
   promptId?: string;
+
+  // text of a custom prompt of the user, rendered instead of the library prompt `promptId`
+  promptTemplate?: string;
   contextOptimization?: boolean;
   contextFiles?: FileMap;
   summary?: string;
   messageSliceId?: number;
   chatMode?: 'discuss' | 'build';
   designScheme?: DesignScheme;
+
+  // answers with this model instead of the one picked in the messages, used by the fallback chain
+  modelOverride?: { provider: string; model: string };
 }) {
   const {
     messages,
@@ -74,11 +89,13 @@ export async function streamText(props: {
     files,
     providerSettings,
     promptId,
+    promptTemplate,
     contextOptimization,
     contextFiles,
     summary,
     chatMode,
     designScheme,
+    modelOverride,
   } = props;
   let currentModel = DEFAULT_MODEL;
   let currentProvider = DEFAULT_PROVIDER.name;
@@ -104,14 +121,20 @@ export async function streamText(props: {
     return newMessage;
   });
 
-  const provider = PROVIDER_LIST.find((p) => p.name === currentProvider) || DEFAULT_PROVIDER;
-  const staticModels = LLMManager.getInstance().getStaticModelListFromProvider(provider);
+  if (modelOverride) {
+    currentModel = modelOverride.model;
+    currentProvider = modelOverride.provider;
+  }
+
+  const llmManager = LLMManager.getInstance();
+  const provider = llmManager.getProvider(currentProvider) || llmManager.getDefaultProvider();
+  const staticModels = llmManager.getStaticModelListFromProvider(provider);
   let modelDetails = staticModels.find((m) => m.name === currentModel);
 
   if (!modelDetails) {
     const modelsList = [
       ...(provider.staticModels || []),
-      ...(await LLMManager.getInstance().getModelListFromProvider(provider, {
+      ...(await llmManager.getModelListFromProvider(provider, {
         apiKeys,
         providerSettings,
         serverEnv: serverEnv as any,
@@ -150,17 +173,35 @@ export async function streamText(props: {
   );
 
   let systemPrompt =
-    PromptLibrary.getPropmtFromLibrary(promptId || 'default', {
-      cwd: WORK_DIR,
-      allowedHtmlElements: allowedHTMLElements,
-      modificationTagName: MODIFICATIONS_TAG_NAME,
-      designScheme,
-      supabase: {
-        isConnected: options?.supabaseConnection?.isConnected || false,
-        hasSelectedProject: options?.supabaseConnection?.hasSelectedProject || false,
-        credentials: options?.supabaseConnection?.credentials || undefined,
+    PromptLibrary.getPropmtFromLibrary(
+      promptId || 'default',
+      {
+        cwd: WORK_DIR,
+        allowedHtmlElements: allowedHTMLElements,
+        modificationTagName: MODIFICATIONS_TAG_NAME,
+        designScheme,
+        supabase: {
+          isConnected: options?.supabaseConnection?.isConnected || false,
+          hasSelectedProject: options?.supabaseConnection?.hasSelectedProject || false,
+          credentials: options?.supabaseConnection?.credentials || undefined,
+        },
       },
-    }) ?? getSystemPrompt();
+      promptTemplate,
+    ) ?? getSystemPrompt();
+
+  if (chatMode === 'build' && files && contextOptimization) {
+    const repoMap = await createRepoMap(files);
+
+    systemPrompt = `${systemPrompt}
+
+    Below is a map of the project with the imports and exported symbols of every file. Use it to find where things are
+    defined, the files themselves are not loaded unless they are in the context buffer.
+    REPOSITORY MAP:
+    ---
+    ${repoMap}
+    ---
+    `;
+  }
 
   if (chatMode === 'build' && contextFiles && contextOptimization) {
     const codeContext = createFilesContext(contextFiles, true);
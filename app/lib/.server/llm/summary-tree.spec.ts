@@ -0,0 +1,48 @@
+import type { UIMessage as Message } from 'ai';
+import { describe, expect, it, vi } from 'vitest';
+import { extractSummaryTree, SUMMARY_FANOUT, SUMMARY_WINDOW_SIZE, updateSummaryTree } from './summary-tree';
+
+const createMessages = (count: number) =>
+  Array.from({ length: count }, (_, i) => ({
+    id: `m${i}`,
+    role: i % 2 ? 'assistant' : 'user',
+    content: `message ${i}`,
+  })) as Message[];
+
+const createSummarizer = () => ({
+  window: vi.fn(async (messages: Message[]) => `${messages[0].id}..${messages[messages.length - 1].id}`),
+  merge: vi.fn(async (nodes: { summary: string }[]) => `(${nodes.map((node) => node.summary).join(' ')})`),
+});
+
+describe('updateSummaryTree', () => {
+  it('summarizes complete windows and merges full levels', async () => {
+    const messages = createMessages(SUMMARY_WINDOW_SIZE * SUMMARY_FANOUT + 3);
+    const { nodes, pending } = await updateSummaryTree([], messages, createSummarizer());
+
+    expect(nodes).toHaveLength(1);
+    expect(nodes[0]).toMatchObject({ level: 1, from: 'm0', to: 'm31', messageCount: 32 });
+    expect(pending.map((message) => message.id)).toEqual(['m32', 'm33', 'm34']);
+  });
+
+  it('never summarizes a window twice', async () => {
+    const messages = createMessages(SUMMARY_WINDOW_SIZE * 2 + 1);
+    const first = await updateSummaryTree([], messages.slice(0, SUMMARY_WINDOW_SIZE + 1), createSummarizer());
+
+    const summarizer = createSummarizer();
+    const second = await updateSummaryTree(first.nodes, messages, summarizer);
+
+    expect(summarizer.window).toHaveBeenCalledTimes(1);
+    expect(second.nodes.map((node) => node.summary)).toEqual(['m0..m7', 'm8..m15']);
+  });
+});
+
+describe('extractSummaryTree', () => {
+  it('drops nodes of messages that are no longer in the chat', async () => {
+    const messages = createMessages(SUMMARY_WINDOW_SIZE * 2);
+    const { nodes } = await updateSummaryTree([], messages, createSummarizer());
+    const rewound = [...messages.slice(0, SUMMARY_WINDOW_SIZE + 2)];
+    rewound[rewound.length - 1] = { ...rewound[rewound.length - 1], annotations: [{ type: 'summaryTree', nodes }] };
+
+    expect(extractSummaryTree(rewound).map((node) => node.to)).toEqual(['m7']);
+  });
+});
@@ -0,0 +1,89 @@
+import type { UIMessage as Message } from 'ai';
+import type { SummaryNode } from '~/types/context';
+
+// number of messages summarized together into a leaf
+export const SUMMARY_WINDOW_SIZE = 8;
+
+// number of nodes of one level merged into a node of the level above
+export const SUMMARY_FANOUT = 4;
+
+export interface Summarizer {
+  window: (messages: Message[]) => Promise<string>;
+  merge: (nodes: SummaryNode[]) => Promise<string>;
+}
+
+/**
+ * Restores the summary tree written with the latest answer. Nodes covering messages that are no longer part of the
+ * chat, e.g. after a rewind, are dropped together with every node after them.
+ */
+export function extractSummaryTree(messages: Message[]): SummaryNode[] {
+  let nodes: SummaryNode[] = [];
+
+  for (let i = messages.length - 1; i >= 0; i--) {
+    const annotation = messages[i].annotations?.find(
+      (item) => item && typeof item === 'object' && (item as { type?: string }).type === 'summaryTree',
+    ) as { nodes?: SummaryNode[] } | undefined;
+
+    if (annotation) {
+      nodes = annotation.nodes ?? [];
+      break;
+    }
+  }
+
+  const ids = messages.map((message) => message.id);
+  const validNodes: SummaryNode[] = [];
+  let nextIndex = 0;
+
+  for (const node of nodes) {
+    if (ids.indexOf(node.from) !== nextIndex || ids.indexOf(node.to) !== nextIndex + node.messageCount - 1) {
+      break;
+    }
+
+    validNodes.push(node);
+    nextIndex += node.messageCount;
+  }
+
+  return validNodes;
+}
+
+/**
+ * Summarizes every complete window of messages not covered by the tree yet and merges full levels, so each message is
+ * summarized once and the tree holds at most `SUMMARY_FANOUT - 1` nodes per level. Messages that do not fill a window
+ * yet are returned as pending.
+ */
+export async function updateSummaryTree(nodes: SummaryNode[], messages: Message[], summarizer: Summarizer) {
+  const tree = [...nodes];
+  const coveredCount = tree.reduce((count, node) => count + node.messageCount, 0);
+  let pending = messages.slice(coveredCount);
+
+  while (pending.length >= SUMMARY_WINDOW_SIZE) {
+    const window = pending.slice(0, SUMMARY_WINDOW_SIZE);
+    pending = pending.slice(SUMMARY_WINDOW_SIZE);
+
+    tree.push({
+      level: 0,
+      from: window[0].id,
+      to: window[window.length - 1].id,
+      messageCount: window.length,
+      summary: await summarizer.window(window),
+    });
+
+    while (tree.length >= SUMMARY_FANOUT) {
+      const siblings = tree.slice(-SUMMARY_FANOUT);
+
+      if (!siblings.every((node) => node.level === siblings[0].level)) {
+        break;
+      }
+
+      tree.splice(-SUMMARY_FANOUT, SUMMARY_FANOUT, {
+        level: siblings[0].level + 1,
+        from: siblings[0].from,
+        to: siblings[siblings.length - 1].to,
+        messageCount: siblings.reduce((count, node) => count + node.messageCount, 0),
+        summary: await summarizer.merge(siblings),
+      });
+    }
+  }
+
+  return { nodes: tree, pending };
+}
@@ -0,0 +1,92 @@
+import { LLMManager } from '~/lib/modules/llm/manager';
+import type { ModelPricing } from '~/lib/modules/llm/types';
+import type { ModelUsage, TokenUsage, UsageAnnotation } from '~/types/context';
+import type { ModelTarget } from './fallback-chain';
+
+/**
+ * Reads the usage reported by the `ai` sdk, which names the fields differently across versions.
+ */
+export function normalizeUsage(usage: any): TokenUsage {
+  const promptTokens = usage?.promptTokens ?? usage?.inputTokens ?? 0;
+  const completionTokens = usage?.completionTokens ?? usage?.outputTokens ?? 0;
+
+  return {
+    promptTokens,
+    completionTokens,
+    totalTokens: usage?.totalTokens ?? promptTokens + completionTokens,
+    cachedTokens: usage?.cachedInputTokens ?? usage?.cachedPromptTokens ?? 0,
+  };
+}
+
+export function calculateCost(usage: TokenUsage, pricing: ModelPricing) {
+  const cachedTokens = Math.min(usage.cachedTokens, usage.promptTokens);
+  const uncachedTokens = usage.promptTokens - cachedTokens;
+
+  return (
+    (uncachedTokens * pricing.input +
+      cachedTokens * (pricing.cachedInput ?? pricing.input) +
+      usage.completionTokens * pricing.output) /
+    1_000_000
+  );
+}
+
+/**
+ * Adds up the usage of every model call that goes into a single chat response.
+ */
+export class UsageTracker {
+  private _models = new Map<string, ModelUsage>();
+
+  add(target: ModelTarget, usage: any) {
+    if (!usage) {
+      return;
+    }
+
+    const tokens = normalizeUsage(usage);
+    const key = `${target.provider}/${target.model}`;
+    const entry = this._models.get(key) ?? {
+      provider: target.provider,
+      model: target.model,
+      promptTokens: 0,
+      completionTokens: 0,
+      totalTokens: 0,
+      cachedTokens: 0,
+    };
+
+    entry.promptTokens += tokens.promptTokens;
+    entry.completionTokens += tokens.completionTokens;
+    entry.totalTokens += tokens.totalTokens;
+    entry.cachedTokens += tokens.cachedTokens;
+
+    const pricing = LLMManager.getInstance().getModelInfo(target.provider, target.model)?.pricing;
+    entry.cost = pricing ? calculateCost(entry, pricing) : undefined;
+
+    this._models.set(key, entry);
+  }
+
+  get models() {
+    return [...this._models.values()];
+  }
+
+  toAnnotation(userId?: string): UsageAnnotation {
+    const models = this.models;
+    const priced = models.filter((entry) => entry.cost !== undefined);
+
+    return {
+      type: 'usage',
+      value: {
+        promptTokens: sum(models, 'promptTokens'),
+        completionTokens: sum(models, 'completionTokens'),
+        totalTokens: sum(models, 'totalTokens'),
+        cachedTokens: sum(models, 'cachedTokens'),
+        cost: priced.length > 0 ? priced.reduce((total, entry) => total + entry.cost!, 0) : undefined,
+      },
+      models,
+      userId,
+      timestamp: Date.now(),
+    };
+  }
+}
+
+function sum(models: ModelUsage[], key: keyof TokenUsage) {
+  return models.reduce((total, entry) => total + entry[key], 0);
+}
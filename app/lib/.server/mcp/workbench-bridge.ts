@@ -0,0 +1,149 @@
+import type { WorkbenchBridgeCall, WorkbenchBridgeResponse } from '~/lib/services/workbenchMcpTools';
+import { createScopedLogger } from '~/utils/logger';
+
+const logger = createScopedLogger('workbench-bridge');
+
+// a workspace that hasn't polled for this long is considered closed
+const WORKSPACE_TTL = 60_000;
+const POLL_TIMEOUT = 25_000;
+const CALL_TIMEOUT = 120_000;
+
+interface PendingCall {
+  call: WorkbenchBridgeCall;
+  resolve: (result: unknown) => void;
+  reject: (error: Error) => void;
+  timeout: ReturnType<typeof setTimeout>;
+}
+
+interface Workspace {
+  lastSeen: number;
+  queue: WorkbenchBridgeCall[];
+  pending: Map<string, PendingCall>;
+  waiter?: () => void;
+}
+
+/**
+ * The bridge only works where every request is served by the same process, which isn't the case on Cloudflare.
+ */
+export function isWorkbenchBridgeSupported() {
+  return typeof navigator === 'undefined' || navigator.userAgent !== 'Cloudflare-Workers';
+}
+
+export const WORKBENCH_BRIDGE_UNSUPPORTED_MESSAGE =
+  'Sharing the workbench over MCP needs a single server process, it is not available on Cloudflare deployments';
+
+/**
+ * Relays MCP requests to the browser tab that owns a workspace. The workspace lives in a WebContainer in
+ * the browser, so the tab long-polls for calls and posts the results back. Workspaces are identified by a
+ * token the tab generates, anyone holding it can drive the workspace.
+ *
+ * State is kept in memory, the bridge needs the MCP endpoint and the tab to reach the same server process.
+ * Cloudflare Workers spread requests over isolates that don't share memory, see {@link isWorkbenchBridgeSupported}.
+ */
+class WorkbenchBridge {
+  private _workspaces = new Map<string, Workspace>();
+  private _callCounter = 0;
+
+  isConnected(token: string) {
+    const workspace = this._workspaces.get(token);
+
+    return !!workspace && Date.now() - workspace.lastSeen < WORKSPACE_TTL;
+  }
+
+  /**
+   * Waits for calls for the workspace, resolves with an empty list when nothing came in before the timeout.
+   */
+  async poll(token: string, signal?: AbortSignal): Promise<WorkbenchBridgeCall[]> {
+    const workspace = this._getWorkspace(token);
+    workspace.lastSeen = Date.now();
+
+    if (workspace.queue.length === 0) {
+      await new Promise<void>((resolve) => {
+        const done = () => {
+          clearTimeout(timeout);
+          signal?.removeEventListener('abort', done);
+
+          if (workspace.waiter === done) {
+            workspace.waiter = undefined;
+          }
+
+          resolve();
+        };
+        const timeout = setTimeout(done, POLL_TIMEOUT);
+
+        // only the latest poll of a tab receives calls
+        workspace.waiter?.();
+        workspace.waiter = done;
+        signal?.addEventListener('abort', done);
+      });
+    }
+
+    workspace.lastSeen = Date.now();
+
+    if (signal?.aborted) {
+      return [];
+    }
+
+    return workspace.queue.splice(0);
+  }
+
+  respond(token: string, response: WorkbenchBridgeResponse) {
+    const pending = this._workspaces.get(token)?.pending.get(response.id);
+
+    if (!pending) {
+      logger.warn(`Dropping response to unknown call ${response.id}`);
+      return;
+    }
+
+    clearTimeout(pending.timeout);
+    this._workspaces.get(token)?.pending.delete(response.id);
+
+    if ('error' in response) {
+      pending.reject(new Error(response.error));
+    } else {
+      pending.resolve(response.result);
+    }
+  }
+
+  call(token: string, method: WorkbenchBridgeCall['method'], params: unknown): Promise<unknown> {
+    if (!this.isConnected(token)) {
+      return Promise.reject(new Error('The workspace is not connected, open it in smack and enable the MCP server'));
+    }
+
+    const workspace = this._getWorkspace(token);
+    const call: WorkbenchBridgeCall = { id: `call_${++this._callCounter}`, method, params };
+
+    return new Promise((resolve, reject) => {
+      const timeout = setTimeout(() => {
+        workspace.pending.delete(call.id);
+        reject(new Error(`The workspace did not answer ${method} in time`));
+      }, CALL_TIMEOUT);
+
+      workspace.pending.set(call.id, { call, resolve, reject, timeout });
+      workspace.queue.push(call);
+      workspace.waiter?.();
+    });
+  }
+
+  private _getWorkspace(token: string) {
+    let workspace = this._workspaces.get(token);
+
+    if (!workspace) {
+      workspace = { lastSeen: Date.now(), queue: [], pending: new Map() };
+      this._workspaces.set(token, workspace);
+      this._pruneWorkspaces();
+    }
+
+    return workspace;
+  }
+
+  private _pruneWorkspaces() {
+    for (const [token, workspace] of this._workspaces) {
+      if (Date.now() - workspace.lastSeen > WORKSPACE_TTL && workspace.pending.size === 0) {
+        this._workspaces.delete(token);
+      }
+    }
+  }
+}
+
+export const workbenchBridge = new WorkbenchBridge();
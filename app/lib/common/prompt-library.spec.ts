@@ -0,0 +1,27 @@
+import { describe, expect, it } from 'vitest';
+import { PromptLibrary, renderPromptTemplate, type PromptOptions } from './prompt-library';
+
+const options: PromptOptions = {
+  cwd: '/home/project',
+  allowedHtmlElements: ['b', 'code'],
+  modificationTagName: 'smack_file_modifications',
+  supabase: { isConnected: true, hasSelectedProject: false, credentials: { supabaseUrl: 'https://db.example.com' } },
+};
+
+describe('renderPromptTemplate', () => {
+  it('expands the prompt options', () => {
+    expect(renderPromptTemplate('Work in {{cwd}}, use {{ allowedHtmlElements }}', options)).toBe(
+      'Work in /home/project, use <b>, <code>',
+    );
+    expect(renderPromptTemplate('{{supabase.isConnected}} {{supabase.url}}', options)).toBe(
+      'true https://db.example.com',
+    );
+  });
+
+  it('includes prompts of the library and keeps unknown placeholders', () => {
+    const rendered = renderPromptTemplate('{{prompt:original}}\nAlso {{unknown}}', options);
+
+    expect(rendered.startsWith(PromptLibrary.getPropmtFromLibrary('original', options))).toBe(true);
+    expect(rendered.endsWith('Also {{unknown}}')).toBe(true);
+  });
+});
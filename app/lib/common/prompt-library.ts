@@ -18,6 +18,73 @@ export interface PromptOptions {
   };
 }
 
+export interface CustomPromptVersion {
+  version: number;
+  template: string;
+  updatedAt: string;
+}
+
+/**
+ * System prompt written by a user, `template` may reference the prompt options as `{{variable}}`.
+ */
+export interface CustomPrompt {
+  id: string;
+  label: string;
+  description: string;
+  template: string;
+  version: number;
+
+  // earlier versions, oldest first
+  history: CustomPromptVersion[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export const PROMPT_TEMPLATE_VARIABLES: Array<{ name: string; description: string }> = [
+  { name: 'cwd', description: 'Working directory of the project' },
+  { name: 'allowedHtmlElements', description: 'HTML elements allowed in answers' },
+  { name: 'modificationTagName', description: 'Tag wrapping the user\'s file modifications' },
+  { name: 'designScheme', description: 'Palette, features and fonts picked by the user, as JSON' },
+  { name: 'supabase.isConnected', description: 'Whether Supabase is connected' },
+  { name: 'supabase.hasSelectedProject', description: 'Whether a Supabase project is selected' },
+  { name: 'supabase.url', description: 'URL of the selected Supabase project' },
+  { name: 'prompt:default', description: 'A prompt of the library, e.g. to extend it' },
+];
+
+function getTemplateVariable(name: string, options: PromptOptions): string | undefined {
+  switch (name) {
+    case 'cwd':
+      return options.cwd;
+    case 'allowedHtmlElements':
+      return options.allowedHtmlElements.map((tagName) => `<${tagName}>`).join(', ');
+    case 'modificationTagName':
+      return options.modificationTagName;
+    case 'designScheme':
+      return options.designScheme ? JSON.stringify(options.designScheme, null, 2) : '';
+    case 'supabase.isConnected':
+      return String(!!options.supabase?.isConnected);
+    case 'supabase.hasSelectedProject':
+      return String(!!options.supabase?.hasSelectedProject);
+    case 'supabase.url':
+      return options.supabase?.credentials?.supabaseUrl ?? '';
+    default:
+      return undefined;
+  }
+}
+
+/**
+ * Expands the `{{variable}}` placeholders of a custom prompt, unknown placeholders are left as they are.
+ */
+export function renderPromptTemplate(template: string, options: PromptOptions): string {
+  return template.replace(/\{\{\s*([\w.:-]+)\s*\}\}/g, (placeholder, name: string) => {
+    if (name.startsWith('prompt:')) {
+      return PromptLibrary.library[name.slice('prompt:'.length)]?.get(options) ?? placeholder;
+    }
+
+    return getTemplateVariable(name, options) ?? placeholder;
+  });
+}
+
 export class PromptLibrary {
   static library: Record<
     string,
@@ -43,17 +110,30 @@ export class PromptLibrary {
       get: (options) => optimized(options),
     },
   };
-  static getList() {
-    return Object.entries(this.library).map(([key, value]) => {
+  static getList(customPrompts: CustomPrompt[] = []) {
+    const builtIn = Object.entries(this.library).map(([key, value]) => {
       const { label, description } = value;
       return {
         id: key,
         label,
         description,
+        custom: false,
       };
     });
+
+    const custom = customPrompts.map(({ id, label, description }) => ({ id, label, description, custom: true }));
+
+    return [...builtIn, ...custom];
   }
-  static getPropmtFromLibrary(promptId: string, options: PromptOptions) {
+
+  /**
+   * `template` is the text of a custom prompt, custom prompts live in the user's browser and are sent along.
+   */
+  static getPropmtFromLibrary(promptId: string, options: PromptOptions, template?: string) {
+    if (template) {
+      return renderPromptTemplate(template, options);
+    }
+
     const prompt = this.library[promptId];
 
     if (!prompt) {
@@ -535,6 +535,12 @@ If you cannot make something work, you MUST:
         - Only use this action when you need to run a dev server or start the application
         - ULTRA IMPORTANT: do NOT re-run a dev server if files are updated. The existing dev server can automatically detect changes and executes the file changes
 
+      - test: For checking the running application in the preview. Add a \`title\` attribute, the content is a test script with one step per line.
+
+        - Steps: \`goto /path\`, \`click <selector>\`, \`fill <selector> <value>\`, \`press <key>\`, \`wait <ms>\`, \`expect text <text>\`, \`expect no-text <text>\`, \`expect visible <selector>\`, \`expect hidden <selector>\` and \`expect url <path>\`.
+        - Selectors are CSS selectors or \`text=...\` for the element whose text contains it. Quote arguments that contain spaces, except the last one.
+        - Add test actions AFTER the start action for the features you build or change. Failing steps are reported back to you, fix the app or the test when they do.
+
 
     9. The order of the actions is VERY IMPORTANT. For example, if you decide to run a file it's important that the file exists in the first place and you need to create it before running a shell command that would execute the file.
 
@@ -893,3 +899,9 @@ export const CONTINUE_PROMPT = stripIndents`
   Continue your prior response. IMPORTANT: Immediately begin from where you left off without any interruptions.
   Do not repeat any content, including artifact and action tags. Also on top of everything I always need you to know that if the rrquest is very big you have to build all of it at once with no rush no matter how big it is
 `;
+
+export const getResumePrompt = (lastAction?: string, interruptedAction?: string) => stripIndents`
+  Your prior response was cut off in the middle of the artifact. It was closed after ${lastAction ? `the last complete action ${lastAction}` : 'the artifact opening tag'}, everything after that was discarded.
+  Continue the artifact from exactly that point: ${interruptedAction ? `first write the interrupted action ${interruptedAction} again in full, then ` : ''}write the remaining actions and close the artifact with </smackArtifact>.
+  IMPORTANT: Do not repeat the artifact opening tag or any completed action, and do not write anything before the next action tag.
+`;
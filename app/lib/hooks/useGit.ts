@@ -1,10 +1,12 @@
 import type { WebContainer } from '@webcontainer/api';
-import { useCallback, useEffect, useRef, useState, type MutableRefObject } from 'react';
+import { useCallback, useEffect, useMemo, useRef, useState, type MutableRefObject } from 'react';
 import { webcontainer as webcontainerPromise } from '~/lib/webcontainer';
-import git, { type GitAuth, type PromiseFsClient } from 'isomorphic-git';
+import git, { type GitAuth, type PromiseFsClient, type ReadCommitResult } from 'isomorphic-git';
 import http from 'isomorphic-git/http/web';
 import Cookies from 'js-cookie';
 import { toast } from 'react-toastify';
+import { githubConnectionAtom } from '~/lib/stores/githubConnection';
+import { applyHunks, diffHunks, type GitHunk } from '~/utils/gitHunks';
 
 const lookupSavedPassword = (url: string) => {
   const domain = url.split('/')[2];
@@ -28,6 +30,300 @@ const saveGitAuth = (url: string, auth: GitAuth) => {
   Cookies.set(`git:${domain}`, JSON.stringify(auth));
 };
 
+// the GitHub connection token is only ever sent to github.com itself
+const isGitHubUrl = (url: string) => {
+  try {
+    const { protocol, hostname } = new URL(url);
+    return protocol === 'https:' && hostname === 'github.com';
+  } catch {
+    return false;
+  }
+};
+
+const requestGitAuth = (url: string): GitAuth => {
+  let auth = lookupSavedPassword(url);
+
+  if (auth) {
+    console.log('Using saved authentication for', url);
+    return auth;
+  }
+
+  console.log('Repository requires authentication:', url);
+
+  if (confirm('This repository requires authentication. Would you like to enter your GitHub credentials?')) {
+    auth = {
+      username: prompt('Enter username') || '',
+      password: prompt('Enter password or personal access token') || '',
+    };
+    return auth;
+  } else {
+    return { cancel: true };
+  }
+};
+
+export interface GitFileStatus {
+  // path relative to the repository root
+  filepath: string;
+
+  // the index differs from HEAD
+  staged: boolean;
+
+  // the working directory differs from the index
+  unstaged: boolean;
+  deleted: boolean;
+  untracked: boolean;
+}
+
+export interface GitFileDiff {
+  head?: string;
+  stage?: string;
+  workdir?: string;
+
+  // HEAD → index
+  stagedHunks: GitHunk[];
+
+  // index → working directory
+  unstagedHunks: GitHunk[];
+}
+
+const textDecoder = new TextDecoder();
+
+const readText = (data: Uint8Array) => {
+  const text = textDecoder.decode(data);
+
+  // binary files have no hunks to show
+  return text.includes('\u0000') ? undefined : text;
+};
+
+/**
+ * Git operations on the project in the WebContainer. They all go through the same fs adapter as cloning and push
+ * through the git proxy.
+ */
+const createRepository = (webcontainer: WebContainer) => {
+  const fs = getFs(webcontainer);
+  const dir = webcontainer.workdir;
+
+  const readHead = async (filepath: string) => {
+    try {
+      const oid = await git.resolveRef({ fs, dir, ref: 'HEAD' });
+      const { blob } = await git.readBlob({ fs, dir, oid, filepath });
+
+      return readText(blob);
+    } catch {
+      // no commit yet, or the file is not in HEAD
+      return undefined;
+    }
+  };
+
+  const readStage = async (filepath: string) => {
+    const [oid] = await git.walk({
+      fs,
+      dir,
+      trees: [git.STAGE()],
+      map: async (path, [entry]) => (path === filepath && entry ? entry.oid() : undefined),
+    });
+
+    if (!oid) {
+      return undefined;
+    }
+
+    const { blob } = await git.readBlob({ fs, dir, oid });
+
+    return readText(blob);
+  };
+
+  const readWorkdir = async (filepath: string) => {
+    try {
+      return readText(await webcontainer.fs.readFile(filepath));
+    } catch {
+      return undefined;
+    }
+  };
+
+  const writeStage = async (filepath: string, content: string) => {
+    const oid = await git.writeBlob({ fs, dir, blob: new TextEncoder().encode(content) });
+    await git.updateIndex({ fs, dir, filepath, oid, add: true });
+  };
+
+  // commits and stashes need an author, taken from the GitHub connection when the repository has none
+  const ensureAuthor = async () => {
+    if (await git.getConfig({ fs, dir, path: 'user.name' })) {
+      return;
+    }
+
+    const user = githubConnectionAtom.get().user;
+    const login = user?.login ?? 'smack';
+
+    await git.setConfig({ fs, dir, path: 'user.name', value: user?.name || login });
+    await git.setConfig({ fs, dir, path: 'user.email', value: `${login}@users.noreply.github.com` });
+  };
+
+  const getFileDiff = async (filepath: string): Promise<GitFileDiff> => {
+    const [head, stage, workdir] = await Promise.all([readHead(filepath), readStage(filepath), readWorkdir(filepath)]);
+
+    return {
+      head,
+      stage,
+      workdir,
+      stagedHunks: diffHunks(head ?? '', stage ?? ''),
+      unstagedHunks: diffHunks(stage ?? '', workdir ?? ''),
+    };
+  };
+
+  return {
+    async isRepository() {
+      try {
+        await webcontainer.fs.readdir('.git');
+        return true;
+      } catch {
+        return false;
+      }
+    },
+
+    init() {
+      return git.init({ fs, dir, defaultBranch: 'main' });
+    },
+
+    async status(): Promise<GitFileStatus[]> {
+      const matrix = await git.statusMatrix({ fs, dir });
+
+      /*
+       * HEAD: 0 absent, 1 present
+       * WORKDIR: 0 absent, 1 same as HEAD, 2 differs from HEAD
+       * STAGE: 0 absent, 1 same as HEAD, 2 same as WORKDIR, 3 differs from both
+       */
+      return matrix
+        .map(([filepath, head, workdir, stage]) => ({
+          filepath,
+          staged: head === 1 ? stage !== 1 && !(stage === 2 && workdir === 1) : stage !== 0,
+          unstaged: stage === 3 || (stage === 1 && workdir !== 1) || (stage === 0 && workdir !== 0),
+          deleted: workdir === 0,
+          untracked: head === 0 && stage === 0,
+        }))
+        .filter((file) => file.staged || file.unstaged);
+    },
+
+    getFileDiff,
+
+    async stageFile(filepath: string) {
+      const exists = await webcontainer.fs.readFile(filepath).then(
+        () => true,
+        () => false,
+      );
+
+      if (exists) {
+        await git.add({ fs, dir, filepath });
+      } else {
+        await git.remove({ fs, dir, filepath });
+      }
+    },
+
+    unstageFile(filepath: string) {
+      return git.resetIndex({ fs, dir, filepath });
+    },
+
+    // hunks are identified by their header, so hunks of an outdated diff are never applied
+    async stageHunks(filepath: string, headers: string[]) {
+      const { stage, unstagedHunks } = await getFileDiff(filepath);
+      const hunks = unstagedHunks.filter((hunk) => headers.includes(hunk.header));
+
+      await writeStage(filepath, applyHunks(stage ?? '', hunks));
+    },
+
+    async unstageHunks(filepath: string, headers: string[]) {
+      const { head, stagedHunks } = await getFileDiff(filepath);
+      const hunks = stagedHunks.filter((hunk) => !headers.includes(hunk.header));
+
+      await writeStage(filepath, applyHunks(head ?? '', hunks));
+    },
+
+    async commit(message: string) {
+      await ensureAuthor();
+      return git.commit({ fs, dir, message });
+    },
+
+    async branches() {
+      const [current, branches] = await Promise.all([
+        git.currentBranch({ fs, dir }),
+        git.listBranches({ fs, dir }).catch(() => [] as string[]),
+      ]);
+
+      return { current: current ?? undefined, branches };
+    },
+
+    createBranch(name: string) {
+      return git.branch({ fs, dir, ref: name, checkout: true });
+    },
+
+    checkout(name: string) {
+      return git.checkout({ fs, dir, ref: name });
+    },
+
+    async log(depth = 50): Promise<ReadCommitResult[]> {
+      try {
+        return await git.log({ fs, dir, depth });
+      } catch {
+        // no commit yet
+        return [];
+      }
+    },
+
+    async stashList() {
+      // a repository that never stashed has no stash ref
+      const entries: unknown = await git.stash({ fs, dir, op: 'list' }).catch(() => []);
+      return Array.isArray(entries) ? entries.map(String) : [];
+    },
+
+    async stash(message?: string) {
+      await ensureAuthor();
+      await git.stash({ fs, dir, op: 'push', message });
+    },
+
+    async stashPop(refIdx = 0) {
+      await git.stash({ fs, dir, op: 'pop', refIdx });
+    },
+
+    async stashDrop(refIdx: number) {
+      await git.stash({ fs, dir, op: 'drop', refIdx });
+    },
+
+    async push() {
+      const ref = await git.currentBranch({ fs, dir });
+      const url = await git.getConfig({ fs, dir, path: 'remote.origin.url' });
+
+      if (!ref || !url) {
+        throw new Error('Add an `origin` remote and check out a branch before pushing');
+      }
+
+      const { token } = githubConnectionAtom.get();
+      let usesConnectionToken = false;
+
+      return git.push({
+        fs,
+        http,
+        dir,
+        remote: 'origin',
+        ref,
+        corsProxy: '/api/git-proxy',
+        headers: { 'User-Agent': 'smack.sh' },
+        onAuth: (remoteUrl) => {
+          usesConnectionToken = !!token && isGitHubUrl(remoteUrl) && !lookupSavedPassword(remoteUrl);
+
+          return usesConnectionToken ? { username: token, password: 'x-oauth-basic' } : requestGitAuth(remoteUrl);
+        },
+        onAuthSuccess: (remoteUrl, auth) => {
+          // the token stays with the GitHub connection, only credentials the user typed in are saved
+          if (!usesConnectionToken) {
+            saveGitAuth(remoteUrl, auth);
+          }
+        },
+      });
+    },
+  };
+};
+
+export type GitRepository = ReturnType<typeof createRepository>;
+
 export function useGit() {
   const [ready, setReady] = useState(false);
   const [webcontainer, setWebcontainer] = useState<WebContainer>();
@@ -42,6 +338,8 @@ export function useGit() {
     });
   }, []);
 
+  const repo = useMemo(() => (webcontainer ? createRepository(webcontainer) : undefined), [webcontainer]);
+
   const gitClone = useCallback(
     async (url: string, retryCount = 0) => {
       if (!webcontainer || !fs || !ready) {
@@ -94,26 +392,7 @@ export function useGit() {
           onProgress: (event) => {
             console.log('Git clone progress:', event);
           },
-          onAuth: (baseUrl) => {
-            let auth = lookupSavedPassword(baseUrl);
-
-            if (auth) {
-              console.log('Using saved authentication for', baseUrl);
-              return auth;
-            }
-
-            console.log('Repository requires authentication:', baseUrl);
-
-            if (confirm('This repository requires authentication. Would you like to enter your GitHub credentials?')) {
-              auth = {
-                username: prompt('Enter username') || '',
-                password: prompt('Enter password or personal access token') || '',
-              };
-              return auth;
-            } else {
-              return { cancel: true };
-            }
-          },
+          onAuth: requestGitAuth,
           onAuthFailure: (baseUrl, _auth) => {
             console.error(`Authentication failed for ${baseUrl}`);
             toast.error(
@@ -178,12 +457,12 @@ export function useGit() {
     [webcontainer, fs, ready],
   );
 
-  return { ready, gitClone };
+  return { ready, gitClone, repo };
 }
 
 const getFs = (
   webcontainer: WebContainer,
-  record: MutableRefObject<Record<string, { data: any; encoding?: string }>>,
+  record?: MutableRefObject<Record<string, { data: any; encoding?: string }>>,
 ) => ({
   promises: {
     readFile: async (path: string, options: any) => {
@@ -201,7 +480,7 @@ const getFs = (
     writeFile: async (path: string, data: any, options: any = {}) => {
       const relativePath = pathUtils.relative(webcontainer.workdir, path);
 
-      if (record.current) {
+      if (record?.current) {
         record.current[relativePath] = { data, encoding: options?.encoding };
       }
 
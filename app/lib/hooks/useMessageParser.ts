@@ -47,6 +47,10 @@ const messageParser = new EnhancedStreamingMessageParser({
       logger.trace('onActionStream', data.action);
       workbenchStore.runAction(data, true);
     },
+    onActionAbort: (data) => {
+      logger.trace('onActionAbort', data.action);
+      workbenchStore.abortAction(data);
+    },
   },
 });
 const extractTextContent = (message: Message) =>
@@ -7,12 +7,16 @@ import {
   latestBranchStore,
   autoSelectStarterTemplate,
   enableContextOptimizationStore,
+  enableLexicalContextStore,
+  enableVisualSnapshotsStore,
   tabConfigurationStore,
   resetTabConfiguration as resetTabConfig,
   updateProviderSettings as updateProviderSettingsStore,
   updateLatestBranch,
   updateAutoSelectTemplate,
   updateContextOptimization,
+  updateLexicalContext,
+  updateVisualSnapshots,
   updateEventLogs,
   updatePromptId,
 } from '~/lib/stores/settings';
@@ -58,6 +62,10 @@ export interface UseSettingsReturn {
   setAutoSelectTemplate: (enabled: boolean) => void;
   contextOptimizationEnabled: boolean;
   enableContextOptimization: (enabled: boolean) => void;
+  lexicalContextEnabled: boolean;
+  enableLexicalContext: (enabled: boolean) => void;
+  visualSnapshotsEnabled: boolean;
+  enableVisualSnapshots: (enabled: boolean) => void;
 
   // Tab configuration
   tabConfiguration: TabWindowConfig;
@@ -78,6 +86,8 @@ export function useSettings(): UseSettingsReturn {
   const autoSelectTemplate = useStore(autoSelectStarterTemplate);
   const [activeProviders, setActiveProviders] = useState<ProviderInfo[]>([]);
   const contextOptimizationEnabled = useStore(enableContextOptimizationStore);
+  const lexicalContextEnabled = useStore(enableLexicalContextStore);
+  const visualSnapshotsEnabled = useStore(enableVisualSnapshotsStore);
   const tabConfiguration = useStore(tabConfigurationStore);
   const [settings, setSettings] = useState<Settings>(() => {
     const storedSettings = getLocalStorage('settings');
@@ -143,6 +153,16 @@ export function useSettings(): UseSettingsReturn {
     logStore.logSystem(`Context optimization ${enabled ? 'enabled' : 'disabled'}`);
   }, []);
 
+  const enableLexicalContext = useCallback((enabled: boolean) => {
+    updateLexicalContext(enabled);
+    logStore.logSystem(`Lexical context selection ${enabled ? 'enabled' : 'disabled'}`);
+  }, []);
+
+  const enableVisualSnapshots = useCallback((enabled: boolean) => {
+    updateVisualSnapshots(enabled);
+    logStore.logSystem(`Visual snapshots ${enabled ? 'enabled' : 'disabled'}`);
+  }, []);
+
   const setTheme = useCallback(
     (theme: Settings['theme']) => {
       saveSettings({ theme });
@@ -197,6 +217,10 @@ export function useSettings(): UseSettingsReturn {
     setAutoSelectTemplate,
     contextOptimizationEnabled,
     enableContextOptimization,
+    lexicalContextEnabled,
+    enableLexicalContext,
+    visualSnapshotsEnabled,
+    enableVisualSnapshots,
     setTheme,
     setLanguage,
     setNotifications,
@@ -33,8 +33,12 @@ export abstract class BaseProvider implements ProviderInfo {
     }
 
     const baseUrlKey = this.config.baseUrlKey || defaultBaseUrlKey;
-    let baseUrl = settingsBaseUrl || serverEnv?.[baseUrlKey] || process?.env?.[baseUrlKey] || 'http://localhost:3000';
-    manager.env?.[baseUrlKey] || this.config.baseUrl;
+    let baseUrl =
+      settingsBaseUrl ||
+      serverEnv?.[baseUrlKey] ||
+      process?.env?.[baseUrlKey] ||
+      manager.env?.[baseUrlKey] ||
+      this.config.baseUrl;
 
     if (baseUrl && baseUrl.endsWith('/')) {
       baseUrl = baseUrl.slice(0, -1);
@@ -49,6 +53,21 @@ export abstract class BaseProvider implements ProviderInfo {
       apiKey,
     };
   }
+
+  /*
+   * Inside Docker the host machine is not reachable through localhost,
+   * so rewrite loopback addresses to the Docker host alias
+   */
+  protected resolveDockerHost(baseUrl: string, serverEnv?: Record<string, string>): string {
+    const isDocker = process?.env?.RUNNING_IN_DOCKER === 'true' || serverEnv?.RUNNING_IN_DOCKER === 'true';
+
+    if (!isDocker) {
+      return baseUrl;
+    }
+
+    return baseUrl.replace('localhost', 'host.docker.internal').replace('127.0.0.1', 'host.docker.internal');
+  }
+
   getModelsFromCache(options: {
     apiKeys?: Record<string, string>;
     providerSettings?: Record<string, IProviderSetting>;
@@ -115,6 +134,15 @@ export abstract class BaseProvider implements ProviderInfo {
 
 type OptionalApiKey = string | undefined;
 
+export function getOpenAILikeModel(baseURL: string, apiKey: OptionalApiKey, model: string) {
+  const openai = createOpenAI({
+    baseURL,
+    apiKey,
+  });
+
+  return openai(model);
+}
+
 export function getgeminiLikeModel(baseURL: string, apiKey: OptionalApiKey, model: string) {
   const gemini = createOpenAI({
     baseURL,
@@ -3,23 +3,10 @@ import { BaseProvider } from './base-provider';
 import type { ModelInfo, ProviderInfo } from './types';
 import * as providers from './registry';
 import { createScopedLogger } from '~/utils/logger';
+import { serverManager } from '~/lib/modules/smack/server-manager.server';
 
 const logger = createScopedLogger('LLMManager');
 
-// Lazy load server manager only on server-side
-function getServerManager(): any {
-  if (typeof window === 'undefined' && typeof process !== 'undefined') {
-    try {
-      // Use dynamic import to avoid bundling server code in client
-      const serverManagerModule = eval('require')('~/lib/modules/smack/server-manager.server');
-      return serverManagerModule?.serverManager || null;
-    } catch {
-      return null;
-    }
-  }
-
-  return null;
-}
 export class LLMManager {
   private static _instance: LLMManager;
   private _providers: Map<string, BaseProvider> = new Map();
@@ -35,67 +22,22 @@ export class LLMManager {
     this._initializeSmackServer();
   }
 
-  private async _initializeSmackServer() {
-    try {
-      const serverManager = getServerManager();
-
-      if (!serverManager) {
-        // Server manager not available (client-side), skip initialization
-        return;
-      }
-
-      logger.info('Initializing Smack-7B server...');
-
-      // Start the server in the background
-      serverManager.startServer().catch((error) => {
-        logger.warn('Failed to start Smack-7B server:', error);
-      });
-
-      // Set up cleanup handlers
-      this._setupCleanupHandlers();
-    } catch (error) {
-      logger.error('Error initializing Smack-7B server:', error);
+  private _initializeSmackServer() {
+    if (typeof window !== 'undefined') {
+      return;
     }
-  }
-
-  private _setupCleanupHandlers() {
-    const cleanup = async () => {
-      if (this._isShuttingDown) {
-        return;
-      }
 
-      this._isShuttingDown = true;
-      logger.info('LLMManager shutting down...');
+    serverManager.configure(this._env);
 
-      try {
-        const serverManager = getServerManager();
+    if (!serverManager.isEnabled()) {
+      return;
+    }
 
-        if (serverManager) {
-          await serverManager.shutdown();
-        }
+    logger.info(`Initializing Smack-7B server (${serverManager.getConfig().mode} mode)...`);
 
-        logger.info('LLMManager shutdown complete');
-      } catch (error) {
-        logger.error('Error during LLMManager shutdown:', error);
-      }
-    };
-
-    // Handle various exit scenarios
-    process.on('SIGINT', cleanup);
-    process.on('SIGTERM', cleanup);
-    process.on('exit', cleanup);
-
-    // Handle uncaught exceptions
-    process.on('uncaughtException', async (error) => {
-      logger.error('Uncaught exception:', error);
-      await cleanup();
-      process.exit(1);
-    });
-
-    process.on('unhandledRejection', async (reason, promise) => {
-      logger.error('Unhandled rejection at:', promise, 'reason:', reason);
-      await cleanup();
-      process.exit(1);
+    // Start the server in the background, the server manager registers its own cleanup handlers
+    serverManager.ensureStarted().catch((error) => {
+      logger.warn('Failed to start Smack-7B server:', error);
     });
   }
 
@@ -111,11 +53,7 @@ export class LLMManager {
     logger.info('Manual LLMManager shutdown initiated...');
 
     try {
-      const serverManager = getServerManager();
-
-      if (serverManager) {
-        await serverManager.shutdown();
-      }
+      await serverManager.shutdown();
 
       logger.info('LLMManager shutdown complete');
     } catch (error) {
@@ -181,6 +119,23 @@ export class LLMManager {
     return this._modelList;
   }
 
+  /**
+   * Looks a model up in the last fetched model list, falling back to the models the provider has cached.
+   */
+  getModelInfo(providerName: string, modelName: string): ModelInfo | undefined {
+    const model = this._modelList.find((m) => m.provider === providerName && m.name === modelName);
+
+    if (model) {
+      return model;
+    }
+
+    const provider = this._providers.get(providerName);
+
+    return [...(provider?.cachedDynamicModels?.models || []), ...(provider?.staticModels || [])].find(
+      (m) => m.name === modelName,
+    );
+  }
+
   async updateModelList(options: {
     apiKeys?: Record<string, string>;
     providerSettings?: Record<string, IProviderSetting>;
@@ -0,0 +1,86 @@
+import { BaseProvider, getOpenAILikeModel } from '~/lib/modules/llm/base-provider';
+import type { ModelInfo } from '~/lib/modules/llm/types';
+import type { IProviderSetting } from '~/types/model';
+import type { LanguageModelV1 } from 'ai';
+
+interface AWSBedRockConfig {
+  region: string;
+  apiKey: string;
+}
+
+export default class AmazonBedrockProvider extends BaseProvider {
+  name = 'AmazonBedrock';
+  getApiKeyLink = 'https://console.aws.amazon.com/bedrock/home#/api-keys';
+  labelForGetApiKey = 'Get Bedrock API Key';
+  icon = '/icons/AmazonBedrock.svg';
+
+  config = {
+    apiTokenKey: 'AWS_BEDROCK_CONFIG',
+  };
+
+  /*
+   * Only models served by the Bedrock OpenAI-compatible chat completions
+   * endpoint can be used, see getModelInstance
+   */
+  staticModels: ModelInfo[] = [
+    {
+      name: 'openai.gpt-oss-120b-1:0',
+      label: 'GPT OSS 120B (Bedrock)',
+      provider: 'AmazonBedrock',
+      maxTokenAllowed: 128000,
+      maxCompletionTokens: 8192,
+    },
+    {
+      name: 'openai.gpt-oss-20b-1:0',
+      label: 'GPT OSS 20B (Bedrock)',
+      provider: 'AmazonBedrock',
+      maxTokenAllowed: 128000,
+      maxCompletionTokens: 8192,
+    },
+  ];
+
+  private _parseAndValidateConfig(apiKey: string): AWSBedRockConfig {
+    let parsedConfig: AWSBedRockConfig;
+
+    try {
+      parsedConfig = JSON.parse(apiKey);
+    } catch {
+      throw new Error(
+        'Invalid AWS Bedrock configuration format. Please provide a valid JSON string containing region and apiKey.',
+      );
+    }
+
+    const { region, apiKey: bedrockApiKey } = parsedConfig;
+
+    if (!region || !bedrockApiKey) {
+      throw new Error('Missing required AWS Bedrock configuration. Configuration must include region and apiKey.');
+    }
+
+    return parsedConfig;
+  }
+
+  getModelInstance(options: {
+    model: string;
+    serverEnv: Env;
+    apiKeys?: Record<string, string>;
+    providerSettings?: Record<string, IProviderSetting>;
+  }): LanguageModelV1 {
+    const { model, serverEnv, apiKeys, providerSettings } = options;
+
+    const { apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: providerSettings?.[this.name],
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'AWS_BEDROCK_CONFIG',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing API key for ${this.name} provider`);
+    }
+
+    const config = this._parseAndValidateConfig(apiKey);
+
+    return getOpenAILikeModel(`https://bedrock-runtime.${config.region}.amazonaws.com/openai/v1`, config.apiKey, model);
+  }
+}
@@ -0,0 +1,104 @@
+import { BaseProvider, getOpenAILikeModel } from '~/lib/modules/llm/base-provider';
+import type { ModelInfo } from '~/lib/modules/llm/types';
+import type { IProviderSetting } from '~/types/model';
+import type { LanguageModelV1 } from 'ai';
+
+export default class CohereProvider extends BaseProvider {
+  name = 'Cohere';
+  getApiKeyLink = 'https://dashboard.cohere.com/api-keys';
+  icon = '/icons/Cohere.svg';
+
+  config = {
+    baseUrl: 'https://api.cohere.ai/compatibility/v1',
+    apiTokenKey: 'COHERE_API_KEY',
+  };
+
+  staticModels: ModelInfo[] = [
+    {
+      name: 'command-a-03-2025',
+      label: 'Command A',
+      provider: 'Cohere',
+      maxTokenAllowed: 256000,
+      maxCompletionTokens: 8000,
+    },
+    {
+      name: 'command-r-plus',
+      label: 'Command R plus',
+      provider: 'Cohere',
+      maxTokenAllowed: 128000,
+      maxCompletionTokens: 4000,
+    },
+    {
+      name: 'command-r',
+      label: 'Command R',
+      provider: 'Cohere',
+      maxTokenAllowed: 128000,
+      maxCompletionTokens: 4000,
+    },
+  ];
+
+  async getDynamicModels(
+    apiKeys?: Record<string, string>,
+    settings?: IProviderSetting,
+    serverEnv?: Record<string, string>,
+  ): Promise<ModelInfo[]> {
+    const { apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: settings,
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'COHERE_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing Api Key configuration for ${this.name} provider`);
+    }
+
+    // The compatibility API has no model listing, so use the native endpoint
+    const response = await fetch('https://api.cohere.com/v1/models?endpoint=chat', {
+      headers: {
+        Authorization: `Bearer ${apiKey}`,
+      },
+    });
+
+    if (!response.ok) {
+      throw new Error(`Failed to fetch ${this.name} models: ${response.status} ${response.statusText}`);
+    }
+
+    const res = (await response.json()) as any;
+    const staticModelIds = this.staticModels.map((m) => m.name);
+
+    const data = (res.models || []).filter((model: any) => !staticModelIds.includes(model.name));
+
+    return data.map((m: any) => ({
+      name: m.name,
+      label: m.name,
+      provider: this.name,
+      maxTokenAllowed: m.context_length || 128000,
+      maxCompletionTokens: 4000,
+    }));
+  }
+
+  getModelInstance(options: {
+    model: string;
+    serverEnv: Env;
+    apiKeys?: Record<string, string>;
+    providerSettings?: Record<string, IProviderSetting>;
+  }): LanguageModelV1 {
+    const { model, serverEnv, apiKeys, providerSettings } = options;
+
+    const { baseUrl, apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: providerSettings?.[this.name],
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'COHERE_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing API key for ${this.name} provider`);
+    }
+
+    return getOpenAILikeModel(baseUrl!, apiKey, model);
+  }
+}
@@ -3,9 +3,7 @@ import type { ModelInfo } from '~/lib/modules/llm/types';
 import type { IProviderSetting } from '~/types/model';
 import type { LanguageModelV1 } from 'ai';
 import { createGoogleGenerativeAI } from '@ai-sdk/google';
-import { createOpenAI } from '@ai-sdk/openai';
-import { serverManager } from '~/lib/modules/smack/server-manager.server';
-import { requestManager } from '~/lib/modules/smack/request-manager';
+import { createSmackModel, serverManager } from '~/lib/modules/smack';
 
 export default class GoogleProvider extends BaseProvider {
   name = 'Google';
@@ -15,8 +13,6 @@ export default class GoogleProvider extends BaseProvider {
     apiTokenKey: 'GOOGLE_GENERATIVE_AI_API_KEY',
   };
 
-  private smackServerUrl = 'http://127.0.0.1:8001';
-
   async checkSmackServerHealth(): Promise<boolean> {
     try {
       const status = await serverManager.getStatus();
@@ -28,17 +24,6 @@ export default class GoogleProvider extends BaseProvider {
   }
 
   staticModels: ModelInfo[] = [
-    /*
-     * Smack-7B Local Model - Code-specialized model running locally
-     */
-    {
-      name: 'smack-7b',
-      label: 'Smack-7B (Local Code Model)',
-      provider: 'Google',
-      maxTokenAllowed: 8192,
-      maxCompletionTokens: 4096,
-    },
-
     /*
      * Essential fallback models - only the most reliable/stable ones
      * Gemini 1.5 Pro: 2M context, 8K output limit (verified from API docs)
@@ -49,6 +34,7 @@ export default class GoogleProvider extends BaseProvider {
       provider: 'Google',
       maxTokenAllowed: 2000000,
       maxCompletionTokens: 8192,
+      pricing: { input: 1.25, output: 5, cachedInput: 0.3125 },
     },
 
     // Gemini 1.5 Flash: 1M context, 8K output limit, fast and cost-effective
@@ -58,6 +44,7 @@ export default class GoogleProvider extends BaseProvider {
       provider: 'Google',
       maxTokenAllowed: 1000000,
       maxCompletionTokens: 8192,
+      pricing: { input: 0.075, output: 0.3, cachedInput: 0.01875 },
     },
   ];
 
@@ -68,11 +55,18 @@ export default class GoogleProvider extends BaseProvider {
   ): Promise<ModelInfo[]> {
     const dynamicModels: ModelInfo[] = [];
 
-    // Check Smack-7B server status and add dynamic model info
+    serverManager.configure(serverEnv);
+
+    /*
+     * Smack-7B Local Model - Code-specialized model served by a local llama.cpp server,
+     * only listed when SMACK_SERVER_MODE enables it
+     */
     try {
-      const status = await serverManager.getStatus();
+      const status = serverManager.isEnabled() ? await serverManager.getStatus() : undefined;
 
-      if (status.running) {
+      if (!status) {
+        // disabled, nothing to list
+      } else if (status.running) {
         let label = 'Smack-7B';
 
         if (status.healthy && status.modelLoaded) {
@@ -92,7 +86,7 @@ export default class GoogleProvider extends BaseProvider {
         });
       } else {
         // Server not running, try to start it
-        serverManager.startServer().catch((error) => {
+        serverManager.ensureStarted().catch((error) => {
           console.warn('Failed to start Smack-7B server:', error);
         });
 
@@ -197,17 +191,6 @@ export default class GoogleProvider extends BaseProvider {
   }): LanguageModelV1 {
     const { model, serverEnv, apiKeys, providerSettings } = options;
 
-    // Handle Smack-7B local model
-    if (model === 'smack-7b') {
-      const smackServer = createOpenAI({
-        baseURL: 'http://127.0.0.1:8001/v1',
-        apiKey: 'local-key', // Local server doesn't need real API key
-      });
-
-      return smackServer(model);
-    }
-
-    // Handle regular Gemini models
     const { apiKey } = this.getProviderBaseUrlAndKey({
       apiKeys,
       providerSettings: providerSettings?.[this.name],
@@ -216,6 +199,24 @@ export default class GoogleProvider extends BaseProvider {
       defaultApiTokenKey: 'GOOGLE_GENERATIVE_AI_API_KEY',
     });
 
+    // Handle Smack-7B local model, falling back to Gemini while the local server is unavailable
+    if (model === 'smack-7b') {
+      serverManager.configure(serverEnv);
+
+      const fallbackModel = serverEnv?.SMACK_FALLBACK_MODEL || process?.env?.SMACK_FALLBACK_MODEL || 'gemini-1.5-flash';
+      const fallback = apiKey ? createGoogleGenerativeAI({ apiKey })(fallbackModel) : undefined;
+
+      if (serverManager.isEnabled()) {
+        return createSmackModel(fallback);
+      }
+
+      if (!fallback) {
+        throw new Error(`Smack-7B server is disabled and no API key is configured for the ${this.name} fallback`);
+      }
+
+      return fallback;
+    }
+
     if (!apiKey) {
       throw new Error(`Missing API key for ${this.name} provider`);
     }
@@ -0,0 +1,102 @@
+import { BaseProvider, getOpenAILikeModel } from '~/lib/modules/llm/base-provider';
+import type { ModelInfo } from '~/lib/modules/llm/types';
+import type { IProviderSetting } from '~/types/model';
+import type { LanguageModelV1 } from 'ai';
+
+export default class GroqProvider extends BaseProvider {
+  name = 'Groq';
+  getApiKeyLink = 'https://console.groq.com/keys';
+  icon = '/icons/Groq.svg';
+
+  config = {
+    baseUrl: 'https://api.groq.com/openai/v1',
+    apiTokenKey: 'GROQ_API_KEY',
+  };
+
+  staticModels: ModelInfo[] = [
+    {
+      name: 'llama-3.3-70b-versatile',
+      label: 'Llama 3.3 70B',
+      provider: 'Groq',
+      maxTokenAllowed: 131072,
+      maxCompletionTokens: 32768,
+    },
+    {
+      name: 'llama-3.1-8b-instant',
+      label: 'Llama 3.1 8B',
+      provider: 'Groq',
+      maxTokenAllowed: 131072,
+      maxCompletionTokens: 8192,
+    },
+  ];
+
+  async getDynamicModels(
+    apiKeys?: Record<string, string>,
+    settings?: IProviderSetting,
+    serverEnv?: Record<string, string>,
+  ): Promise<ModelInfo[]> {
+    const { baseUrl, apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: settings,
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'GROQ_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing Api Key configuration for ${this.name} provider`);
+    }
+
+    const response = await fetch(`${baseUrl}/models`, {
+      headers: {
+        Authorization: `Bearer ${apiKey}`,
+      },
+    });
+
+    if (!response.ok) {
+      throw new Error(`Failed to fetch ${this.name} models: ${response.status} ${response.statusText}`);
+    }
+
+    const res = (await response.json()) as any;
+    const staticModelIds = this.staticModels.map((m) => m.name);
+
+    const data = (res.data || []).filter(
+      (model: any) =>
+        model.object === 'model' &&
+        model.active &&
+        !model.id.includes('whisper') &&
+        !staticModelIds.includes(model.id),
+    );
+
+    return data.map((m: any) => ({
+      name: m.id,
+      label: m.display_name || m.id,
+      provider: this.name,
+      maxTokenAllowed: m.context_window || 8000,
+      maxCompletionTokens: m.max_completion_tokens || 8192,
+    }));
+  }
+
+  getModelInstance(options: {
+    model: string;
+    serverEnv: Env;
+    apiKeys?: Record<string, string>;
+    providerSettings?: Record<string, IProviderSetting>;
+  }): LanguageModelV1 {
+    const { model, serverEnv, apiKeys, providerSettings } = options;
+
+    const { baseUrl, apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: providerSettings?.[this.name],
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'GROQ_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing API key for ${this.name} provider`);
+    }
+
+    return getOpenAILikeModel(baseUrl!, apiKey, model);
+  }
+}
@@ -0,0 +1,102 @@
+import { BaseProvider, getOpenAILikeModel } from '~/lib/modules/llm/base-provider';
+import type { ModelInfo } from '~/lib/modules/llm/types';
+import type { IProviderSetting } from '~/types/model';
+import type { LanguageModelV1 } from 'ai';
+
+export default class HuggingFaceProvider extends BaseProvider {
+  name = 'HuggingFace';
+  getApiKeyLink = 'https://huggingface.co/settings/tokens';
+  icon = '/icons/HuggingFace.svg';
+
+  config = {
+    baseUrl: 'https://router.huggingface.co/v1',
+    apiTokenKey: 'HUGGINGFACE_API_KEY',
+  };
+
+  staticModels: ModelInfo[] = [
+    {
+      name: 'Qwen/Qwen2.5-Coder-32B-Instruct',
+      label: 'Qwen2.5-Coder-32B-Instruct',
+      provider: 'HuggingFace',
+      maxTokenAllowed: 32768,
+      maxCompletionTokens: 8192,
+    },
+    {
+      name: 'meta-llama/Llama-3.3-70B-Instruct',
+      label: 'Llama-3.3-70B-Instruct',
+      provider: 'HuggingFace',
+      maxTokenAllowed: 131072,
+      maxCompletionTokens: 8192,
+    },
+  ];
+
+  async getDynamicModels(
+    apiKeys?: Record<string, string>,
+    settings?: IProviderSetting,
+    serverEnv?: Record<string, string>,
+  ): Promise<ModelInfo[]> {
+    const { baseUrl, apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: settings,
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'HUGGINGFACE_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing Api Key configuration for ${this.name} provider`);
+    }
+
+    const response = await fetch(`${baseUrl}/models`, {
+      headers: {
+        Authorization: `Bearer ${apiKey}`,
+      },
+    });
+
+    if (!response.ok) {
+      throw new Error(`Failed to fetch ${this.name} models: ${response.status} ${response.statusText}`);
+    }
+
+    const res = (await response.json()) as any;
+    const staticModelIds = this.staticModels.map((m) => m.name);
+
+    const data = (res.data || []).filter((model: any) => !staticModelIds.includes(model.id));
+
+    return data.map((m: any) => {
+      // The router reports context length per inference provider, take the largest one
+      const contextLengths: number[] = (m.providers || []).map((p: any) => p.context_length || 0);
+      const contextWindow = Math.max(8000, ...contextLengths);
+
+      return {
+        name: m.id,
+        label: m.id.split('/').pop(),
+        provider: this.name,
+        maxTokenAllowed: contextWindow,
+        maxCompletionTokens: 8192,
+      };
+    });
+  }
+
+  getModelInstance(options: {
+    model: string;
+    serverEnv: Env;
+    apiKeys?: Record<string, string>;
+    providerSettings?: Record<string, IProviderSetting>;
+  }): LanguageModelV1 {
+    const { model, serverEnv, apiKeys, providerSettings } = options;
+
+    const { baseUrl, apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: providerSettings?.[this.name],
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'HUGGINGFACE_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing API key for ${this.name} provider`);
+    }
+
+    return getOpenAILikeModel(baseUrl!, apiKey, model);
+  }
+}
@@ -0,0 +1,97 @@
+import { BaseProvider, getOpenAILikeModel } from '~/lib/modules/llm/base-provider';
+import type { ModelInfo } from '~/lib/modules/llm/types';
+import type { IProviderSetting } from '~/types/model';
+import type { LanguageModelV1 } from 'ai';
+
+export default class HyperbolicProvider extends BaseProvider {
+  name = 'Hyperbolic';
+  getApiKeyLink = 'https://app.hyperbolic.xyz/settings';
+  icon = '/icons/Hyperbolic.svg';
+
+  config = {
+    baseUrl: 'https://api.hyperbolic.xyz/v1',
+    baseUrlKey: 'HYPERBOLIC_API_BASE_URL',
+    apiTokenKey: 'HYPERBOLIC_API_KEY',
+  };
+
+  staticModels: ModelInfo[] = [
+    {
+      name: 'Qwen/Qwen2.5-Coder-32B-Instruct',
+      label: 'Qwen 2.5 Coder 32B Instruct',
+      provider: 'Hyperbolic',
+      maxTokenAllowed: 32768,
+      maxCompletionTokens: 8192,
+    },
+    {
+      name: 'deepseek-ai/DeepSeek-V3',
+      label: 'DeepSeek V3',
+      provider: 'Hyperbolic',
+      maxTokenAllowed: 131072,
+      maxCompletionTokens: 8192,
+    },
+  ];
+
+  async getDynamicModels(
+    apiKeys?: Record<string, string>,
+    settings?: IProviderSetting,
+    serverEnv?: Record<string, string>,
+  ): Promise<ModelInfo[]> {
+    const { baseUrl, apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: settings,
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: 'HYPERBOLIC_API_BASE_URL',
+      defaultApiTokenKey: 'HYPERBOLIC_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing Api Key configuration for ${this.name} provider`);
+    }
+
+    const response = await fetch(`${baseUrl}/models`, {
+      headers: {
+        Authorization: `Bearer ${apiKey}`,
+      },
+    });
+
+    if (!response.ok) {
+      throw new Error(`Failed to fetch ${this.name} models: ${response.status} ${response.statusText}`);
+    }
+
+    const res = (await response.json()) as any;
+    const staticModelIds = this.staticModels.map((m) => m.name);
+
+    const data = (res.data || []).filter((model: any) => model.supports_chat && !staticModelIds.includes(model.id));
+
+    return data.map((m: any) => ({
+      name: m.id,
+      label: m.display_name || m.id,
+      provider: this.name,
+      maxTokenAllowed: m.context_length || 8000,
+      maxCompletionTokens: 8192,
+    }));
+  }
+
+  getModelInstance(options: {
+    model: string;
+    serverEnv: Env;
+    apiKeys?: Record<string, string>;
+    providerSettings?: Record<string, IProviderSetting>;
+  }): LanguageModelV1 {
+    const { model, serverEnv, apiKeys, providerSettings } = options;
+
+    const { baseUrl, apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: providerSettings?.[this.name],
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: 'HYPERBOLIC_API_BASE_URL',
+      defaultApiTokenKey: 'HYPERBOLIC_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing API key for ${this.name} provider`);
+    }
+
+    return getOpenAILikeModel(baseUrl!, apiKey, model);
+  }
+}
@@ -0,0 +1,85 @@
+import { BaseProvider, getOpenAILikeModel } from '~/lib/modules/llm/base-provider';
+import type { ModelInfo } from '~/lib/modules/llm/types';
+import type { IProviderSetting } from '~/types/model';
+import type { LanguageModelV1 } from 'ai';
+import { createScopedLogger } from '~/utils/logger';
+
+const logger = createScopedLogger('LMStudioProvider');
+
+export default class LMStudioProvider extends BaseProvider {
+  name = 'LMStudio';
+  getApiKeyLink = 'https://lmstudio.ai/';
+  labelForGetApiKey = 'Get LMStudio';
+  icon = '/icons/LMStudio.svg';
+
+  config = {
+    baseUrlKey: 'LMSTUDIO_API_BASE_URL',
+    baseUrl: 'http://127.0.0.1:1234',
+  };
+
+  staticModels: ModelInfo[] = [];
+
+  async getDynamicModels(
+    apiKeys?: Record<string, string>,
+    settings?: IProviderSetting,
+    serverEnv: Record<string, string> = {},
+  ): Promise<ModelInfo[]> {
+    let { baseUrl } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: settings,
+      serverEnv,
+      defaultBaseUrlKey: 'LMSTUDIO_API_BASE_URL',
+      defaultApiTokenKey: '',
+    });
+
+    if (!baseUrl) {
+      throw new Error('No baseUrl found for LMStudio provider');
+    }
+
+    baseUrl = this.resolveDockerHost(baseUrl, serverEnv);
+
+    const response = await fetch(`${baseUrl}/v1/models`);
+
+    if (!response.ok) {
+      throw new Error(`Failed to fetch ${this.name} models: ${response.status} ${response.statusText}`);
+    }
+
+    const data = (await response.json()) as { data: Array<{ id: string }> };
+
+    return (data.data || []).map((model) => ({
+      name: model.id,
+      label: model.id,
+      provider: this.name,
+      maxTokenAllowed: 8000,
+      maxCompletionTokens: 8192,
+    }));
+  }
+
+  getModelInstance(options: {
+    model: string;
+    serverEnv?: Env;
+    apiKeys?: Record<string, string>;
+    providerSettings?: Record<string, IProviderSetting>;
+  }): LanguageModelV1 {
+    const { apiKeys, providerSettings, serverEnv, model } = options;
+
+    let { baseUrl } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: providerSettings?.[this.name],
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: 'LMSTUDIO_API_BASE_URL',
+      defaultApiTokenKey: '',
+    });
+
+    if (!baseUrl) {
+      throw new Error('No baseUrl found for LMStudio provider');
+    }
+
+    baseUrl = this.resolveDockerHost(baseUrl, serverEnv as any);
+
+    logger.debug('LMStudio Base Url used: ', baseUrl);
+
+    // LM Studio ignores the API key but the OpenAI client requires one
+    return getOpenAILikeModel(`${baseUrl}/v1`, 'lm-studio', model);
+  }
+}
@@ -0,0 +1,133 @@
+import { BaseProvider } from '~/lib/modules/llm/base-provider';
+import type { ModelInfo, ModelPricing } from '~/lib/modules/llm/types';
+import type { IProviderSetting } from '~/types/model';
+import type { LanguageModelV1 } from 'ai';
+import { createOpenRouter } from '@openrouter/ai-sdk-provider';
+
+interface OpenRouterModel {
+  id: string;
+  name: string;
+  context_length: number;
+  top_provider?: {
+    max_completion_tokens?: number | null;
+  };
+
+  // USD per token, as decimal strings
+  pricing?: {
+    prompt?: string;
+    completion?: string;
+    input_cache_read?: string;
+  };
+}
+
+function toPricePerMillion(price?: string) {
+  const value = Number(price);
+
+  return Number.isFinite(value) && value >= 0 ? value * 1_000_000 : undefined;
+}
+
+function getPricing(model: OpenRouterModel): ModelPricing | undefined {
+  const input = toPricePerMillion(model.pricing?.prompt);
+  const output = toPricePerMillion(model.pricing?.completion);
+
+  if (input === undefined || output === undefined) {
+    return undefined;
+  }
+
+  return { input, output, cachedInput: toPricePerMillion(model.pricing?.input_cache_read) };
+}
+
+export default class OpenRouterProvider extends BaseProvider {
+  name = 'OpenRouter';
+  getApiKeyLink = 'https://openrouter.ai/settings/keys';
+  icon = '/icons/OpenRouter.svg';
+
+  config = {
+    apiTokenKey: 'OPEN_ROUTER_API_KEY',
+  };
+
+  staticModels: ModelInfo[] = [
+    {
+      name: 'anthropic/claude-sonnet-4',
+      label: 'Anthropic: Claude Sonnet 4',
+      provider: 'OpenRouter',
+      maxTokenAllowed: 200000,
+      maxCompletionTokens: 64000,
+      pricing: { input: 3, output: 15, cachedInput: 0.3 },
+    },
+    {
+      name: 'openai/gpt-4o',
+      label: 'OpenAI: GPT-4o',
+      provider: 'OpenRouter',
+      maxTokenAllowed: 128000,
+      maxCompletionTokens: 16384,
+      pricing: { input: 2.5, output: 10, cachedInput: 1.25 },
+    },
+    {
+      name: 'qwen/qwen-2.5-coder-32b-instruct',
+      label: 'Qwen 2.5 Coder 32B Instruct',
+      provider: 'OpenRouter',
+      maxTokenAllowed: 32768,
+      maxCompletionTokens: 8192,
+    },
+  ];
+
+  async getDynamicModels(
+    _apiKeys?: Record<string, string>,
+    _settings?: IProviderSetting,
+    _serverEnv?: Record<string, string>,
+  ): Promise<ModelInfo[]> {
+    // The OpenRouter model catalogue is public and does not need an API key
+    const response = await fetch('https://openrouter.ai/api/v1/models', {
+      headers: {
+        'Content-Type': 'application/json',
+      },
+    });
+
+    if (!response.ok) {
+      throw new Error(`Failed to fetch ${this.name} models: ${response.status} ${response.statusText}`);
+    }
+
+    const res = (await response.json()) as { data?: OpenRouterModel[] };
+    const staticModelIds = this.staticModels.map((m) => m.name);
+
+    return (res.data || [])
+      .filter((m) => !staticModelIds.includes(m.id))
+      .sort((a, b) => a.name.localeCompare(b.name))
+      .map((m) => ({
+        name: m.id,
+        label: m.name,
+        provider: this.name,
+        maxTokenAllowed: m.context_length || 8000,
+        maxCompletionTokens: m.top_provider?.max_completion_tokens || 8192,
+        pricing: getPricing(m),
+      }));
+  }
+
+  getModelInstance(options: {
+    model: string;
+    serverEnv: Env;
+    apiKeys?: Record<string, string>;
+    providerSettings?: Record<string, IProviderSetting>;
+  }): LanguageModelV1 {
+    const { model, serverEnv, apiKeys, providerSettings } = options;
+
+    const { apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: providerSettings?.[this.name],
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'OPEN_ROUTER_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing API key for ${this.name} provider`);
+    }
+
+    const openRouter = createOpenRouter({
+      apiKey,
+    });
+
+    return openRouter.chat(model) as LanguageModelV1;
+  }
+}
@@ -0,0 +1,66 @@
+import { BaseProvider, getOpenAILikeModel } from '~/lib/modules/llm/base-provider';
+import type { ModelInfo } from '~/lib/modules/llm/types';
+import type { IProviderSetting } from '~/types/model';
+import type { LanguageModelV1 } from 'ai';
+
+export default class PerplexityProvider extends BaseProvider {
+  name = 'Perplexity';
+  getApiKeyLink = 'https://www.perplexity.ai/settings/api';
+  icon = '/icons/Perplexity.svg';
+
+  config = {
+    baseUrl: 'https://api.perplexity.ai',
+    apiTokenKey: 'PERPLEXITY_API_KEY',
+  };
+
+  /*
+   * Perplexity does not expose a model listing endpoint, so the
+   * supported models are maintained here
+   */
+  staticModels: ModelInfo[] = [
+    {
+      name: 'sonar',
+      label: 'Sonar',
+      provider: 'Perplexity',
+      maxTokenAllowed: 127072,
+      maxCompletionTokens: 8192,
+    },
+    {
+      name: 'sonar-pro',
+      label: 'Sonar Pro',
+      provider: 'Perplexity',
+      maxTokenAllowed: 200000,
+      maxCompletionTokens: 8192,
+    },
+    {
+      name: 'sonar-reasoning-pro',
+      label: 'Sonar Reasoning Pro',
+      provider: 'Perplexity',
+      maxTokenAllowed: 127072,
+      maxCompletionTokens: 8192,
+    },
+  ];
+
+  getModelInstance(options: {
+    model: string;
+    serverEnv: Env;
+    apiKeys?: Record<string, string>;
+    providerSettings?: Record<string, IProviderSetting>;
+  }): LanguageModelV1 {
+    const { model, serverEnv, apiKeys, providerSettings } = options;
+
+    const { baseUrl, apiKey } = this.getProviderBaseUrlAndKey({
+      apiKeys,
+      providerSettings: providerSettings?.[this.name],
+      serverEnv: serverEnv as any,
+      defaultBaseUrlKey: '',
+      defaultApiTokenKey: 'PERPLEXITY_API_KEY',
+    });
+
+    if (!apiKey) {
+      throw new Error(`Missing API key for ${this.name} provider`);
+    }
+
+    return getOpenAILikeModel(baseUrl!, apiKey, model);
+  }
+}
@@ -1,6 +1,15 @@
 import type { LanguageModelV1 } from 'ai';
 import type { IProviderSetting } from '~/types/model';
 
+/** Prices in USD per million tokens */
+export interface ModelPricing {
+  input: number;
+  output: number;
+
+  /** Price of prompt tokens served from the provider's prompt cache, defaults to the input price */
+  cachedInput?: number;
+}
+
 export interface ModelInfo {
   name: string;
   label: string;
@@ -11,6 +20,9 @@ export interface ModelInfo {
 
   /** Maximum completion/output tokens - how many tokens the model can generate. If not specified, falls back to provider defaults */
   maxCompletionTokens?: number;
+
+  /** Used to compute the cost of a response, models without pricing are only counted in tokens */
+  pricing?: ModelPricing;
 }
 
 export interface ProviderInfo {
@@ -1,11 +1,7 @@
 /*
- * This is a stub implementation that disables the Smack-7B server
- * To re-enable, remove this file and restore the original implementation
+ * The Smack-7B server is configured through SMACK_SERVER_MODE, see server-manager.server.ts.
+ * With the default `disabled` mode nothing is started and Smack-7B requests use remote providers.
  */
 
-export * from './server-manager.fixed';
-
-/*
- * Uncomment the line below to use the real implementation
- * export * from './server-manager';
- */
+export * from './server-manager.server';
+export { createSmackModel } from './smack-model-wrapper';
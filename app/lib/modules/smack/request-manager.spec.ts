@@ -0,0 +1,154 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { RequestCancelledError, requestManager, type QueueUpdate } from './request-manager';
+
+vi.mock('./resource-monitor', () => ({
+  resourceMonitor: {
+    getPerformanceSummary: () => ({ averageMemoryUsage: 0, averageCpuUsage: 0 }),
+    isSystemUnderStress: () => false,
+  },
+}));
+
+function deferred() {
+  let resolve!: () => void;
+  const promise = new Promise<void>((done) => (resolve = done));
+
+  return { promise, resolve };
+}
+
+// occupies the only slot until the returned function is called
+function block() {
+  const gate = deferred();
+  const done = requestManager.queueRequest(() => gate.promise, { userId: 'blocker' });
+
+  return async () => {
+    gate.resolve();
+    await done;
+  };
+}
+
+describe('RequestManager', () => {
+  beforeEach(() => {
+    requestManager.updateConfig({ maxConcurrentRequests: 1, agingInterval: 15000, userWeights: {} });
+  });
+
+  afterEach(() => {
+    requestManager.clearQueue();
+    vi.useRealTimers();
+  });
+
+  it('takes turns between users', async () => {
+    const order: string[] = [];
+    const run = (userId: string, name: string) =>
+      requestManager.queueRequest(async () => order.push(name), { userId });
+
+    const unblock = block();
+    const requests = [run('a', 'a1'), run('a', 'a2'), run('a', 'a3'), run('b', 'b1'), run('b', 'b2')];
+
+    await unblock();
+    await Promise.all(requests);
+
+    expect(order).toEqual(['a1', 'b1', 'a2', 'b2', 'a3']);
+  });
+
+  it('gives users as many requests per turn as their weight', async () => {
+    requestManager.updateConfig({ userWeights: { a: 2 } });
+
+    const order: string[] = [];
+    const run = (userId: string, name: string) =>
+      requestManager.queueRequest(async () => order.push(name), { userId });
+
+    const unblock = block();
+    const requests = [run('a', 'a1'), run('a', 'a2'), run('a', 'a3'), run('b', 'b1'), run('b', 'b2')];
+
+    await unblock();
+    await Promise.all(requests);
+
+    expect(order).toEqual(['a1', 'a2', 'b1', 'a3', 'b2']);
+  });
+
+  it('raises the priority of waiting requests over time', async () => {
+    vi.useFakeTimers({ toFake: ['Date'] });
+    requestManager.updateConfig({ agingInterval: 1000 });
+
+    const order: string[] = [];
+    const run = (name: string, priority: number) =>
+      requestManager.queueRequest(async () => order.push(name), { userId: 'a', priority });
+
+    const unblock = block();
+    const requests = [run('old', 1)];
+
+    vi.setSystemTime(Date.now() + 2000);
+    requests.push(run('urgent', 2));
+
+    // without aging the urgent request would go first
+    await unblock();
+    await Promise.all(requests);
+
+    expect(order).toEqual(['old', 'urgent']);
+  });
+
+  it('reports queue positions when they change', async () => {
+    const updates: Record<string, string[]> = {};
+    const run = (userId: string, name: string) =>
+      requestManager.queueRequest(async () => undefined, {
+        userId,
+        onQueueUpdate: ({ status, position }: QueueUpdate) => {
+          (updates[name] ??= []).push(`${status} ${position}`);
+        },
+      });
+
+    const unblock = block();
+    const requests = [run('a', 'a1'), run('a', 'a2'), run('b', 'b1')];
+
+    await unblock();
+    await Promise.all(requests);
+
+    expect(updates.a1).toEqual(['queued 1', 'started 0']);
+    expect(updates.b1).toEqual(['queued 2', 'queued 1', 'started 0']);
+
+    // b1 takes the turn before the second request of a
+    expect(updates.a2).toEqual(['queued 2', 'queued 3', 'queued 2', 'queued 1', 'started 0']);
+  });
+
+  it('cancels queued requests when their signal aborts', async () => {
+    const controller = new AbortController();
+    const execute = vi.fn(async () => undefined);
+    const updates: QueueUpdate[] = [];
+
+    const unblock = block();
+    const request = requestManager.queueRequest(execute, {
+      userId: 'a',
+      signal: controller.signal,
+      onQueueUpdate: (update) => updates.push(update),
+    });
+
+    controller.abort();
+
+    await expect(request).rejects.toBeInstanceOf(RequestCancelledError);
+    expect(updates.at(-1)?.status).toBe('cancelled');
+    expect(requestManager.getQueueStatus().queuedByUser).toEqual({});
+
+    await unblock();
+
+    expect(execute).not.toHaveBeenCalled();
+  });
+
+  it('keeps held slots until they are released', async () => {
+    let release!: () => void;
+    const started = vi.fn(async () => undefined);
+
+    const stream = await requestManager.queueRequest(async (slot) => {
+      release = slot.hold();
+      return 'stream';
+    });
+    const next = requestManager.queueRequest(started, { userId: 'a' });
+
+    expect(stream).toBe('stream');
+    expect(started).not.toHaveBeenCalled();
+
+    release();
+    await next;
+
+    expect(started).toHaveBeenCalled();
+  });
+});
@@ -10,25 +10,84 @@ const logger = createScopedLogger('RequestManager');
 export interface RequestConfig {
   maxConcurrentRequests: number;
   maxQueueSize: number;
+  maxQueuedPerUser: number;
   requestTimeout: number;
   throttleThreshold: number;
   priorityLevels: number;
+
+  /**
+   * Time a request has to wait to gain one priority level, so low priority requests can't starve.
+   */
+  agingInterval: number;
+
+  /**
+   * Requests taken from a user per round robin turn, users that aren't listed get a weight of 1.
+   */
+  userWeights: Record<string, number>;
+}
+
+export interface QueueUpdate {
+  requestId: string;
+  status: 'queued' | 'started' | 'cancelled';
+
+  /**
+   * 1-based position in the dispatch order, 0 once the request started.
+   */
+  position: number;
+  queueLength: number;
+  estimatedWaitTime: number;
+}
+
+export interface QueueRequestOptions {
+  userId?: string;
+  priority?: number;
+  timeout?: number;
+  signal?: AbortSignal;
+  onQueueUpdate?: (update: QueueUpdate) => void;
+}
+
+/**
+ * Scheduling details of a chat request, registered by the route handling it and looked up by the model
+ * through the {@link SCHEDULING_CLIENT_HEADER} call header.
+ */
+export interface SchedulingClient {
+  id: string;
+  userId: string;
+  signal?: AbortSignal;
+  onQueueUpdate?: (update: QueueUpdate) => void;
+}
+
+export const SCHEDULING_CLIENT_HEADER = 'x-smack-scheduling-client';
+
+export const DEFAULT_USER_ID = 'anonymous';
+
+/**
+ * Passed to queued functions whose result outlives the call, like streams. `hold` keeps the concurrency slot
+ * after the function returned until the returned release callback is called.
+ */
+export interface RequestSlot {
+  hold: () => () => void;
 }
 
 export interface QueuedRequest {
   id: string;
+  userId: string;
   priority: number;
   timestamp: number;
   timeout: number;
   resolve: (value: any) => void;
   reject: (error: Error) => void;
-  execute: () => Promise<any>;
+  execute: (slot: RequestSlot) => Promise<any>;
+  onQueueUpdate?: (update: QueueUpdate) => void;
+  lastReportedPosition?: number;
+  detachSignal?: () => void;
 }
 
 export interface RequestStats {
   totalRequests: number;
   completedRequests: number;
   failedRequests: number;
+  cancelledRequests: number;
   queuedRequests: number;
   activeRequests: number;
   averageWaitTime: number;
@@ -36,9 +95,29 @@ export interface RequestStats {
   throughputPerMinute: number;
 }
 
+export class RequestCancelledError extends Error {
+  name = 'AbortError';
+
+  constructor(message = 'Request cancelled by the client') {
+    super(message);
+  }
+}
+
+/**
+ * Weighted round robin state: users with queued requests in arrival order, the user whose turn it is
+ * and how many more requests that user may dispatch during the turn.
+ */
+interface SchedulerState {
+  queues: Map<string, QueuedRequest[]>;
+  rotation: string[];
+  index: number;
+  credits: number;
+}
+
 export class RequestManager {
   private static _instance: RequestManager;
-  private _requestQueue: QueuedRequest[] = [];
+  private _scheduler: SchedulerState = { queues: new Map(), rotation: [], index: -1, credits: 0 };
+  private _clients = new Map<string, SchedulingClient>();
   private _activeRequests = new Map<string, QueuedRequest>();
   private _requestStats: RequestStats;
   private _config: RequestConfig;
@@ -51,15 +130,19 @@ export class RequestManager {
     this._config = {
       maxConcurrentRequests: 4,
       maxQueueSize: 20,
+      maxQueuedPerUser: 5,
       requestTimeout: 60000, // 60 seconds
       throttleThreshold: 0.8, // Start throttling at 80% resource usage
       priorityLevels: 3,
+      agingInterval: 15000, // one priority level per 15 seconds of waiting
+      userWeights: {},
     };
 
     this._requestStats = {
       totalRequests: 0,
       completedRequests: 0,
       failedRequests: 0,
+      cancelledRequests: 0,
       queuedRequests: 0,
       activeRequests: 0,
       averageWaitTime: 0,
@@ -79,38 +162,148 @@ export class RequestManager {
   }
 
   /**
-   * Queue a request for execution
+   * Queue a request for execution, requests of different users are dispatched in weighted round robin
    */
-  async queueRequest<T>(executeFunction: () => Promise<T>, priority = 1, timeout?: number): Promise<T> {
+  async queueRequest<T>(
+    executeFunction: (slot: RequestSlot) => Promise<T>,
+    options: QueueRequestOptions = {},
+  ): Promise<T> {
     return new Promise<T>((resolve, reject) => {
-      const requestId = this.generateRequestId();
-      const requestTimeout = timeout || this._config.requestTimeout;
+      const userId = options.userId || DEFAULT_USER_ID;
+
+      if (options.signal?.aborted) {
+        reject(new RequestCancelledError());
+        return;
+      }
 
       // Check if queue is full
-      if (this._requestQueue.length >= this._config.maxQueueSize) {
+      if (this.queueLength >= this._config.maxQueueSize) {
         reject(new Error('Request queue is full. Please try again later.'));
         return;
       }
 
+      if ((this._scheduler.queues.get(userId)?.length ?? 0) >= this._config.maxQueuedPerUser) {
+        reject(new Error('Too many queued requests. Please wait for your previous requests to finish.'));
+        return;
+      }
+
+      const request: QueuedRequest = {
+        id: this.generateRequestId(),
+        userId,
+        priority: Math.max(1, Math.min(options.priority ?? 1, this._config.priorityLevels)),
+        timestamp: Date.now(),
+        timeout: options.timeout || this._config.requestTimeout,
+        resolve,
+        reject,
+        execute: executeFunction,
+        onQueueUpdate: options.onQueueUpdate,
+      };
+
+      if (options.signal) {
+        const signal = options.signal;
+        const onAbort = () => this.cancelRequest(request.id);
+
+        signal.addEventListener('abort', onAbort, { once: true });
+        request.detachSignal = () => signal.removeEventListener('abort', onAbort);
+      }
+
+      this._requestStats.totalRequests++;
+
       // Check if system is under stress and apply throttling
       if (this.shouldThrottle()) {
         const delay = this.calculateThrottleDelay();
         setTimeout(() => {
-          this.addRequestToQueue(requestId, priority, requestTimeout, resolve, reject, executeFunction);
+          if (!options.signal?.aborted) {
+            this.addRequestToQueue(request);
+            return;
+          }
+
+          // aborted during the throttle delay, before it could be cancelled from the queue
+          request.detachSignal?.();
+          request.reject(new RequestCancelledError());
+          this._requestStats.cancelledRequests++;
         }, delay);
       } else {
-        this.addRequestToQueue(requestId, priority, requestTimeout, resolve, reject, executeFunction);
+        this.addRequestToQueue(request);
       }
     });
   }
 
+  /**
+   * Registers the scheduling details of a chat request, the returned headers have to be passed along with
+   * the model call. The client is dropped when its signal aborts or `dispose` is called.
+   */
+  registerClient(options: Omit<SchedulingClient, 'id'>): {
+    client: SchedulingClient;
+    headers: Record<string, string>;
+    dispose: () => void;
+  } {
+    const client: SchedulingClient = { id: `client_${Date.now()}_${++this._requestCounter}`, ...options };
+    const dispose = () => {
+      this._clients.delete(client.id);
+      options.signal?.removeEventListener('abort', dispose);
+    };
+
+    this._clients.set(client.id, client);
+    options.signal?.addEventListener('abort', dispose, { once: true });
+
+    return { client, headers: { [SCHEDULING_CLIENT_HEADER]: client.id }, dispose };
+  }
+
+  /**
+   * Looks up the client registered for a model call from its headers.
+   */
+  getClient(headers?: Record<string, string | undefined>): SchedulingClient | undefined {
+    const clientId = headers?.[SCHEDULING_CLIENT_HEADER];
+
+    return clientId ? this._clients.get(clientId) : undefined;
+  }
+
+  /**
+   * Removes a queued request, rejecting it with a {@link RequestCancelledError}. Requests that already
+   * started are cancelled through the abort signal passed to the model instead.
+   */
+  cancelRequest(requestId: string): boolean {
+    for (const [userId, queue] of this._scheduler.queues) {
+      const index = queue.findIndex((request) => request.id === requestId);
+
+      if (index === -1) {
+        continue;
+      }
+
+      const [request] = queue.splice(index, 1);
+
+      if (queue.length === 0) {
+        this.removeUser(this._scheduler, userId);
+      }
+
+      request.detachSignal?.();
+      request.onQueueUpdate?.({
+        requestId,
+        status: 'cancelled',
+        position: 0,
+        queueLength: this.queueLength,
+        estimatedWaitTime: 0,
+      });
+      request.reject(new RequestCancelledError());
+      this._requestStats.cancelledRequests++;
+
+      logger.debug(`Request ${requestId} of ${userId} cancelled while queued`);
+      this.notifyQueuePositions();
+
+      return true;
+    }
+
+    return false;
+  }
+
   /**
    * Get current request statistics
    */
   getStats(): RequestStats {
     return {
       ...this._requestStats,
-      queuedRequests: this._requestQueue.length,
+      queuedRequests: this.queueLength,
       activeRequests: this._activeRequests.size,
     };
   }
@@ -137,11 +330,14 @@ export class RequestManager {
     logger.warn('Clearing request queue');
 
     // Reject all queued requests
-    for (const request of this._requestQueue) {
-      request.reject(new Error('Request queue cleared'));
+    for (const queue of this._scheduler.queues.values()) {
+      for (const request of queue) {
+        request.detachSignal?.();
+        request.reject(new Error('Request queue cleared'));
+      }
     }
 
-    this._requestQueue = [];
+    this._scheduler = { queues: new Map(), rotation: [], index: -1, credits: 0 };
     this.updateStats();
   }
 
@@ -153,46 +349,43 @@ export class RequestManager {
     activeRequests: number;
     isThrottling: boolean;
     estimatedWaitTime: number;
+    queuedByUser: Record<string, number>;
   } {
     return {
-      queueLength: this._requestQueue.length,
+      queueLength: this.queueLength,
       activeRequests: this._activeRequests.size,
       isThrottling: this._isThrottling,
       estimatedWaitTime: this.estimateWaitTime(),
+      queuedByUser: Object.fromEntries(
+        [...this._scheduler.queues].map(([userId, queue]) => [userId, queue.length] as const),
+      ),
     };
   }
 
+  private get queueLength(): number {
+    let length = 0;
+
+    for (const queue of this._scheduler.queues.values()) {
+      length += queue.length;
+    }
+
+    return length;
+  }
+
   /**
-   * Add request to queue
+   * Add request to its user's queue
    */
-  private addRequestToQueue<T>(
-    requestId: string,
-    priority: number,
-    timeout: number,
-    resolve: (value: T) => void,
-    reject: (error: Error) => void,
-    executeFunction: () => Promise<T>,
-  ): void {
-    const request: QueuedRequest = {
-      id: requestId,
-      priority: Math.max(1, Math.min(priority, this._config.priorityLevels)),
-      timestamp: Date.now(),
-      timeout,
-      resolve,
-      reject,
-      execute: executeFunction,
-    };
-
-    // Insert request based on priority (higher priority first)
-    const insertIndex = this._requestQueue.findIndex((r) => r.priority < request.priority);
+  private addRequestToQueue(request: QueuedRequest): void {
+    const queue = this._scheduler.queues.get(request.userId);
 
-    if (insertIndex === -1) {
-      this._requestQueue.push(request);
+    if (queue) {
+      queue.push(request);
     } else {
-      this._requestQueue.splice(insertIndex, 0, request);
+      // new users join the end of the rotation
+      this._scheduler.queues.set(request.userId, [request]);
+      this._scheduler.rotation.push(request.userId);
     }
 
-    this._requestStats.totalRequests++;
     this.processQueue();
   }
 
@@ -201,13 +394,15 @@ export class RequestManager {
    */
   private async processQueue(): Promise<void> {
     // Process requests while we have capacity and queued requests
-    while (this._activeRequests.size < this._config.maxConcurrentRequests && this._requestQueue.length > 0) {
-      const request = this._requestQueue.shift();
+    while (this._activeRequests.size < this._config.maxConcurrentRequests) {
+      const request = this.nextRequest(this._scheduler, Date.now());
 
       if (!request) {
         break;
       }
 
+      request.detachSignal?.();
+
       // Check if request has timed out while waiting
       if (Date.now() - request.timestamp > request.timeout) {
         request.reject(new Error('Request timed out while waiting in queue'));
@@ -215,14 +410,123 @@ export class RequestManager {
         continue;
       }
 
+      request.onQueueUpdate?.({
+        requestId: request.id,
+        status: 'started',
+        position: 0,
+        queueLength: this.queueLength,
+        estimatedWaitTime: 0,
+      });
+
       // Start processing the request
       this._activeRequests.set(request.id, request);
       this.executeRequest(request);
     }
 
+    this.notifyQueuePositions();
     this.updateStats();
   }
 
+  /**
+   * Takes the next request from the scheduler: users take turns in arrival order, each dispatching up to
+   * its weight in requests per turn, and within a user the request with the highest aged priority wins.
+   */
+  private nextRequest(state: SchedulerState, now: number): QueuedRequest | undefined {
+    if (state.rotation.length === 0) {
+      return undefined;
+    }
+
+    if (state.credits <= 0) {
+      state.index = (state.index + 1) % state.rotation.length;
+      state.credits = this.getUserWeight(state.rotation[state.index]);
+    }
+
+    const userId = state.rotation[state.index];
+    const queue = state.queues.get(userId) ?? [];
+
+    let bestIndex = 0;
+
+    for (let i = 1; i < queue.length; i++) {
+      const candidate = this.effectivePriority(queue[i], now);
+      const best = this.effectivePriority(queue[bestIndex], now);
+
+      if (candidate > best || (candidate === best && queue[i].timestamp < queue[bestIndex].timestamp)) {
+        bestIndex = i;
+      }
+    }
+
+    const [request] = queue.splice(bestIndex, 1);
+    state.credits--;
+
+    if (queue.length === 0) {
+      this.removeUser(state, userId);
+    }
+
+    return request;
+  }
+
+  private removeUser(state: SchedulerState, userId: string): void {
+    const position = state.rotation.indexOf(userId);
+
+    state.queues.delete(userId);
+
+    if (position === -1) {
+      return;
+    }
+
+    state.rotation.splice(position, 1);
+
+    if (position < state.index) {
+      state.index--;
+    } else if (position === state.index) {
+      // the turn ends, the next user moved into this slot
+      state.index--;
+      state.credits = 0;
+    }
+  }
+
+  private effectivePriority(request: QueuedRequest, now: number): number {
+    return request.priority + Math.floor((now - request.timestamp) / this._config.agingInterval);
+  }
+
+  private getUserWeight(userId: string): number {
+    return Math.max(1, Math.floor(this._config.userWeights[userId] ?? 1));
+  }
+
+  /**
+   * Replays the scheduler on a copy of its state to report each waiting request's position in the
+   * dispatch order, only requests whose position changed are notified.
+   */
+  private notifyQueuePositions(): void {
+    const state: SchedulerState = {
+      ...this._scheduler,
+      queues: new Map([...this._scheduler.queues].map(([userId, queue]) => [userId, [...queue]])),
+      rotation: [...this._scheduler.rotation],
+    };
+    const queueLength = this.queueLength;
+    const now = Date.now();
+
+    let position = 0;
+    let request: QueuedRequest | undefined;
+
+    while ((request = this.nextRequest(state, now))) {
+      position++;
+
+      if (!request.onQueueUpdate || request.lastReportedPosition === position) {
+        continue;
+      }
+
+      request.lastReportedPosition = position;
+      request.onQueueUpdate({
+        requestId: request.id,
+        status: 'queued',
+        position,
+        queueLength,
+        estimatedWaitTime: this.estimateWaitTime(position),
+      });
+    }
+  }
+
   /**
    * Execute a request
    */
@@ -230,16 +534,34 @@ export class RequestManager {
     const startTime = Date.now();
     const waitTime = startTime - request.timestamp;
 
+    let timeoutId: ReturnType<typeof setTimeout> | undefined;
+    let released: Promise<void> | undefined;
+
+    const slot: RequestSlot = {
+      hold: () => {
+        let release!: () => void;
+        released = new Promise<void>((resolve) => (release = resolve));
+
+        return release;
+      },
+    };
+
     try {
       // Set up timeout for the execution
       const timeoutPromise = new Promise<never>((_, reject) => {
-        setTimeout(() => {
+        timeoutId = setTimeout(() => {
           reject(new Error('Request execution timed out'));
         }, request.timeout);
       });
 
       // Race between execution and timeout
-      const result = await Promise.race([request.execute(), timeoutPromise]);
+      const result = await Promise.race([request.execute(slot), timeoutPromise]);
+
+      clearTimeout(timeoutId);
+      request.resolve(result);
+
+      // results like streams keep the slot until they are consumed
+      await released;
 
       const processingTime = Date.now() - startTime;
 
@@ -256,13 +578,14 @@ export class RequestManager {
         this._completionTimes = this._completionTimes.slice(-100);
       }
 
-      request.resolve(result);
       this._requestStats.completedRequests++;
     } catch (error) {
       logger.error(`Request ${request.id} failed:`, error);
       request.reject(error instanceof Error ? error : new Error(String(error)));
       this._requestStats.failedRequests++;
     } finally {
+      clearTimeout(timeoutId);
+
       // Remove from active requests
       this._activeRequests.delete(request.id);
 
@@ -281,7 +604,7 @@ export class RequestManager {
       // Check if system is under stress
       const memoryStress = resourceStats.averageMemoryUsage > this._config.throttleThreshold * 100;
       const cpuStress = resourceStats.averageCpuUsage > this._config.throttleThreshold * 100;
-      const queueStress = this._requestQueue.length > this._config.maxQueueSize * 0.7;
+      const queueStress = this.queueLength > this._config.maxQueueSize * 0.7;
 
       this._isThrottling = memoryStress || cpuStress || queueStress;
 
@@ -297,17 +620,17 @@ export class RequestManager {
    */
   private calculateThrottleDelay(): number {
     const baseDelay = 1000; // 1 second base delay
-    const queueFactor = Math.min(this._requestQueue.length / this._config.maxQueueSize, 1);
+    const queueFactor = Math.min(this.queueLength / this._config.maxQueueSize, 1);
     const resourceFactor = resourceMonitor.isSystemUnderStress() ? 2 : 1;
 
     return baseDelay * (1 + queueFactor) * resourceFactor;
   }
 
   /**
-   * Estimate wait time for new requests
+   * Estimate wait time for a request at the given queue position, new requests by default
    */
-  private estimateWaitTime(): number {
-    if (this._requestQueue.length === 0) {
+  private estimateWaitTime(queuePosition = this.queueLength): number {
+    if (queuePosition === 0) {
       return 0;
     }
 
@@ -316,7 +639,6 @@ export class RequestManager {
         ? this._completionTimes.reduce((sum, time) => sum + time, 0) / this._completionTimes.length
         : 5000; // Default 5 seconds
 
-    const queuePosition = this._requestQueue.length;
     const concurrentSlots = this._config.maxConcurrentRequests;
 
     return Math.ceil(queuePosition / concurrentSlots) * avgProcessingTime;
@@ -326,7 +648,7 @@ export class RequestManager {
    * Generate unique request ID
    */
   private generateRequestId(): string {
-    return `req_${Date.now()}_${++this.requestCounter}`;
+    return `req_${Date.now()}_${++this._requestCounter}`;
   }
 
   /**
@@ -360,6 +682,9 @@ export class RequestManager {
     setInterval(() => {
       this.updateStats();
 
+      // aging may have reordered waiting requests
+      this.notifyQueuePositions();
+
       // Log stats periodically if there's activity
       if (this._requestStats.totalRequests > 0) {
         logger.debug('Request stats:', this.getStats());
@@ -1,8 +1,31 @@
 /**
- * ServerManager - Manages the lifecycle of the Smack-7B Python model server
+ * ServerManager - Manages the lifecycle of the local Smack-7B inference server
+ *
+ * The server is any llama.cpp-compatible HTTP server (`llama-server`) exposing `/health`, `/metrics`
+ * and an OpenAI-compatible API under `/v1`. What happens is controlled by `SMACK_SERVER_MODE`:
+ *
+ * - `disabled` (default): nothing is started and Smack-7B requests fall back to remote providers
+ * - `local`: spawns `SMACK_SERVER_COMMAND` with `SMACK_MODEL_PATH` and supervises the process
+ * - `external`: connects to an already running server at `SMACK_SERVER_URL`
+ *
+ * Like the provider settings, the variables are read from the Cloudflare env first and `process.env` second.
  */
 
-import { logger } from '~/utils/logger';
+import { createScopedLogger } from '~/utils/logger';
+
+const logger = createScopedLogger('SmackServerManager');
+
+export type SmackServerMode = 'disabled' | 'local' | 'external';
+
+export interface SmackServerConfig {
+  mode: SmackServerMode;
+  url: string;
+  command: string;
+  args: string[];
+  modelPath?: string;
+  contextSize: number;
+  startupTimeoutMs: number;
+}
 
 export interface ServerStatus {
   running: boolean;
@@ -35,49 +58,141 @@ export interface ServerMetrics {
   };
 }
 
+const DEFAULT_SERVER_URL = 'http://127.0.0.1:8001';
+
+export function getSmackServerConfig(serverEnv?: Record<string, string>): SmackServerConfig {
+  const readEnv = (key: string): string | undefined =>
+    serverEnv?.[key] || (typeof process !== 'undefined' ? process.env?.[key] : undefined);
+  const mode = readEnv('SMACK_SERVER_MODE');
+
+  return {
+    mode: mode === 'local' || mode === 'external' ? mode : 'disabled',
+    url: (readEnv('SMACK_SERVER_URL') || DEFAULT_SERVER_URL).replace(/\/+$/, ''),
+    command: readEnv('SMACK_SERVER_COMMAND') || 'llama-server',
+    args: (readEnv('SMACK_SERVER_ARGS') || '').split(/\s+/).filter(Boolean),
+    modelPath: readEnv('SMACK_MODEL_PATH'),
+    contextSize: parseInt(readEnv('SMACK_CONTEXT_SIZE') || '8192', 10),
+    startupTimeoutMs: parseInt(readEnv('SMACK_STARTUP_TIMEOUT_MS') || '120000', 10),
+  };
+}
+
+/**
+ * Parses the Prometheus text exposition served by llama.cpp's `/metrics` endpoint.
+ */
+function parsePrometheusMetrics(text: string): Record<string, number> {
+  const values: Record<string, number> = {};
+
+  for (const line of text.split('\n')) {
+    if (!line || line.startsWith('#')) {
+      continue;
+    }
+
+    const [name, value] = line.trim().split(/\s+/);
+    const parsed = Number(value);
+
+    if (name && !Number.isNaN(parsed)) {
+      values[name.replace(/\{.*\}$/, '')] = parsed;
+    }
+  }
+
+  return values;
+}
+
 export class ServerManager {
   private static instance: ServerManager;
+  private config: SmackServerConfig = getSmackServerConfig();
   private serverProcess: any = null;
-  private serverUrl = 'http://127.0.0.1:8001';
-  private serverPath!: string;
   private startTime: number | null = null;
+  private startPromise: Promise<boolean> | null = null;
   private healthCheckInterval: NodeJS.Timeout | null = null;
   private restartAttempts = 0;
   private maxRestartAttempts = 3;
   private isShuttingDown = false;
+  private intentionalStop = false; // set by stopServer, the exit of a stopped process is not a crash
+  private cleanupHandlersRegistered = false;
+  private lastStatus: ServerStatus = { running: false, healthy: false, modelLoaded: false };
+  private requestStats = { total: 0, successful: 0, failed: 0, totalDurationMs: 0 };
 
-  private constructor() {
-    // Set up cleanup handlers
-    process.on('SIGINT', () => this.shutdown());
-    process.on('SIGTERM', () => this.shutdown());
-    process.on('exit', () => this.shutdown());
-  }
+  private constructor() {}
 
-  private async init() {
-    const { join } = await import('path');
-    this.serverPath = join(process.cwd(), 'smack-server');
-  }
-
-  static async getInstance(): Promise<ServerManager> {
+  static getInstance(): ServerManager {
     if (!ServerManager.instance) {
       ServerManager.instance = new ServerManager();
-      await ServerManager.instance.init();
     }
 
     return ServerManager.instance;
   }
 
+  get serverUrl() {
+    return this.config.url;
+  }
+
+  getConfig(): SmackServerConfig {
+    return { ...this.config };
+  }
+
   /**
-   * Start the Python model server
+   * Applies the configuration from the server env of a request, ignored while a local server process runs.
    */
-  async startServer(): Promise<boolean> {
-    const { existsSync } = await import('fs');
-    const { join } = await import('path');
-    const { spawn } = await import('child_process');
+  configure(serverEnv?: Record<string, string>): void {
+    if (!serverEnv) {
+      return;
+    }
 
-    if (this.serverProcess && !this.serverProcess.killed) {
-      logger.info('Server is already running');
-      return true;
+    const config = getSmackServerConfig(serverEnv);
+
+    if (JSON.stringify(config) === JSON.stringify(this.config)) {
+      return;
+    }
+
+    if (this.serverProcess || this.startPromise) {
+      logger.warn('Ignoring Smack-7B server configuration changes while the server is running');
+      return;
+    }
+
+    this.config = config;
+  }
+
+  isEnabled(): boolean {
+    return this.config.mode !== 'disabled';
+  }
+
+  /**
+   * Last known health, updated by start-up and the periodic health checks. Cheap enough to call while
+   * resolving a model instance.
+   */
+  isAvailable(): boolean {
+    return this.isEnabled() && this.lastStatus.healthy && this.lastStatus.modelLoaded;
+  }
+
+  /**
+   * Starts the server once, concurrent and repeated callers share the same attempt.
+   */
+  ensureStarted(): Promise<boolean> {
+    if (!this.isEnabled()) {
+      return Promise.resolve(false);
+    }
+
+    if (this.isAvailable()) {
+      return Promise.resolve(true);
+    }
+
+    if (!this.startPromise) {
+      this.startPromise = this.startServer().finally(() => {
+        this.startPromise = null;
+      });
+    }
+
+    return this.startPromise;
+  }
+
+  /**
+   * Start the inference server (or attach to the external one)
+   */
+  async startServer(): Promise<boolean> {
+    if (!this.isEnabled()) {
+      logger.debug('Smack-7B server is disabled (SMACK_SERVER_MODE is not set)');
+      return false;
     }
 
     if (this.isShuttingDown) {
@@ -85,33 +200,57 @@ export class ServerManager {
       return false;
     }
 
-    try {
-      // Check if server directory exists
-      if (!existsSync(this.serverPath)) {
-        logger.error(`Server directory not found: ${this.serverPath}`);
-        return false;
-      }
+    if (this.config.mode === 'external') {
+      logger.info(`Connecting to external Smack-7B server at ${this.config.url}`);
 
-      // Check if main.py exists
-      const mainPyPath = join(this.serverPath, 'main.py');
+      const isReady = await this.waitForServerReady(this.config.startupTimeoutMs);
 
-      if (!existsSync(mainPyPath)) {
-        logger.error(`Server main.py not found: ${mainPyPath}`);
+      if (!isReady) {
+        logger.warn(`External Smack-7B server at ${this.config.url} is not reachable, using remote providers`);
         return false;
       }
 
-      logger.info('Starting Smack-7B model server...');
+      this.startTime = Date.now();
+      this.startHealthMonitoring();
+      this.startResourceMonitoring();
 
-      // Spawn the Python server process
-      this.serverProcess = spawn('python3', ['main.py'], {
-        cwd: this.serverPath,
-        stdio: ['pipe', 'pipe', 'pipe'],
-        env: {
-          ...process.env,
-          PYTHONUNBUFFERED: '1', // Ensure real-time output
-        },
-      });
+      return true;
+    }
 
+    if (this.serverProcess && !this.serverProcess.killed) {
+      logger.info('Server is already running');
+      return this.waitForServerReady(this.config.startupTimeoutMs);
+    }
+
+    try {
+      const { existsSync } = await import('fs');
+      const { spawn } = await import('child_process');
+
+      if (!this.config.modelPath || !existsSync(this.config.modelPath)) {
+        this.setStatusError(`Model file not found: ${this.config.modelPath || '(SMACK_MODEL_PATH is not set)'}`);
+        return false;
+      }
+
+      const { hostname, port } = new URL(this.config.url);
+      const args = [
+        '--model',
+        this.config.modelPath,
+        '--host',
+        hostname,
+        '--port',
+        port || '80',
+        '--ctx-size',
+        String(this.config.contextSize),
+        '--metrics',
+        ...this.config.args,
+      ];
+
+      logger.info(`Starting Smack-7B server: ${this.config.command} ${args.join(' ')}`);
+
+      this.registerCleanupHandlers();
+
+      this.intentionalStop = false;
+      this.serverProcess = spawn(this.config.command, args, { stdio: ['ignore', 'pipe', 'pipe'] });
       this.startTime = Date.now();
 
       // Handle server output
@@ -119,15 +258,16 @@ export class ServerManager {
         const output = data.toString().trim();
 
         if (output) {
-          logger.info(`[Smack Server] ${output}`);
+          logger.debug(`[Smack Server] ${output}`);
         }
       });
 
       this.serverProcess.stderr?.on('data', (data: Buffer) => {
-        const error = data.toString().trim();
+        // llama.cpp logs its regular progress to stderr
+        const output = data.toString().trim();
 
-        if (error) {
-          logger.error(`[Smack Server Error] ${error}`);
+        if (output) {
+          logger.debug(`[Smack Server] ${output}`);
         }
       });
 
@@ -136,27 +276,32 @@ export class ServerManager {
         logger.info(`Server process exited with code ${code}, signal ${signal}`);
         this.serverProcess = null;
         this.startTime = null;
+        this.lastStatus = { running: false, healthy: false, modelLoaded: false, error: 'Server process exited' };
+
+        // Attempt restart if the process crashed and within retry limits
+        if (this.isShuttingDown || this.intentionalStop) {
+          return;
+        }
 
-        // Attempt restart if not shutting down and within retry limits
-        if (!this.isShuttingDown && this.restartAttempts < this.maxRestartAttempts) {
+        if (this.restartAttempts < this.maxRestartAttempts) {
           this.restartAttempts++;
           logger.info(`Attempting server restart (${this.restartAttempts}/${this.maxRestartAttempts})`);
-          setTimeout(async () => {
-            await this.startServer();
-          }, 5000); // Wait 5 seconds before restart
-        } else if (this.restartAttempts >= this.maxRestartAttempts) {
-          logger.error('Max restart attempts reached. Server will not be restarted automatically.');
+          setTimeout(() => this.ensureStarted(), 5000); // Wait 5 seconds before restart
+        } else {
+          logger.error('Max restart attempts reached. Smack-7B requests will use remote providers.');
         }
       });
 
       this.serverProcess.on('error', (error: any) => {
-        logger.error(`Server process error: ${error.message}`);
+        // e.g. ENOENT when the llama.cpp binary isn't installed
+        this.setStatusError(`Server process error: ${error.message}`);
         this.serverProcess = null;
         this.startTime = null;
+        this.restartAttempts = this.maxRestartAttempts;
       });
 
       // Wait for server to be ready
-      const isReady = await this.waitForServerReady();
+      const isReady = await this.waitForServerReady(this.config.startupTimeoutMs);
 
       if (isReady) {
         logger.info('Smack-7B server started successfully');
@@ -165,48 +310,53 @@ export class ServerManager {
         this.startResourceMonitoring();
 
         return true;
-      } else {
-        logger.error('Server failed to become ready within timeout');
-        this.stopServer();
-
-        return false;
       }
+
+      logger.error('Server failed to become ready within timeout, using remote providers');
+      await this.stopServer();
+
+      return false;
     } catch (error) {
-      logger.error(`Failed to start server: ${error}`);
+      this.setStatusError(`Failed to start server: ${error}`);
       return false;
     }
   }
 
   /**
-   * Stop the Python model server
+   * Stop the inference server
    */
   async stopServer(): Promise<void> {
+    this.stopHealthMonitoring();
+    this.stopResourceMonitoring();
+    this.lastStatus = { running: false, healthy: false, modelLoaded: false };
+
     if (!this.serverProcess) {
       return;
     }
 
     logger.info('Stopping Smack-7B server...');
 
-    this.stopHealthMonitoring();
-    this.stopResourceMonitoring();
+    const serverProcess = this.serverProcess;
+
+    this.intentionalStop = true;
 
     try {
       // Try graceful shutdown first
-      this.serverProcess.kill('SIGTERM');
+      serverProcess.kill('SIGTERM');
 
       // Wait for graceful shutdown
       await new Promise<void>((resolve) => {
         const timeout = setTimeout(() => {
           // Force kill if graceful shutdown takes too long
-          if (this.serverProcess && !this.serverProcess.killed) {
+          if (!serverProcess.killed) {
             logger.warn('Forcing server shutdown...');
-            this.serverProcess.kill('SIGKILL');
+            serverProcess.kill('SIGKILL');
           }
 
           resolve();
         }, 10000); // 10 second timeout
 
-        this.serverProcess?.on('exit', () => {
+        serverProcess.on('exit', () => {
           clearTimeout(timeout);
           resolve();
         });
@@ -226,66 +376,60 @@ export class ServerManager {
   async restartServer(): Promise<boolean> {
     logger.info('Restarting Smack-7B server...');
     await this.stopServer();
-    await new Promise((resolve) => setTimeout(resolve, 2000));
 
     // Wait 2 seconds
-    return await this.startServer();
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    return await this.ensureStarted();
   }
 
   /**
    * Get current server status
    */
   async getStatus(): Promise<ServerStatus> {
-    const isRunning = this.serverProcess !== null && !this.serverProcess.killed;
+    if (!this.isEnabled()) {
+      return {
+        running: false,
+        healthy: false,
+        modelLoaded: false,
+        error: 'Smack-7B server is disabled, set SMACK_SERVER_MODE to enable it',
+      };
+    }
+
+    const isRunning = this.config.mode === 'external' || (this.serverProcess !== null && !this.serverProcess.killed);
 
     if (!isRunning) {
-      return {
+      this.lastStatus = {
         running: false,
         healthy: false,
         modelLoaded: false,
-        error: 'Server is not running',
+        error: this.lastStatus.error || 'Server is not running',
       };
+
+      return this.lastStatus;
     }
 
     try {
-      // Check health endpoint
-      const healthResponse = await fetch(`${this.serverUrl}/health`, {
+      // llama.cpp answers 503 while the model is still loading
+      const healthResponse = await fetch(`${this.config.url}/health`, {
         method: 'GET',
         signal: AbortSignal.timeout(5000),
       });
 
-      if (!healthResponse.ok) {
-        return {
-          running: true,
-          healthy: false,
-          modelLoaded: false,
-          error: `Health check failed: ${healthResponse.status}`,
-          uptime: this.getUptime(),
-          pid: this.serverProcess?.pid,
-        };
-      }
-
-      const healthData: unknown = await healthResponse.json();
-      let modelLoaded = false;
-
-      if (healthData && typeof healthData === 'object' && 'model_loaded' in healthData) {
-        try {
-          modelLoaded = Boolean((healthData as any).model_loaded);
-        } catch {
-          modelLoaded = false;
-        }
-      }
+      const healthData = (await healthResponse.json().catch(() => ({}))) as { status?: string; model_loaded?: boolean };
+      const modelLoaded = healthResponse.ok && (healthData.status === 'ok' || Boolean(healthData.model_loaded));
 
-      return {
+      this.lastStatus = {
         running: true,
-        healthy: true,
+        healthy: healthResponse.ok,
         modelLoaded,
+        error: healthResponse.ok ? undefined : `Health check failed: ${healthResponse.status}`,
         uptime: this.getUptime(),
         pid: this.serverProcess?.pid,
       };
     } catch (error) {
-      return {
-        running: true,
+      this.lastStatus = {
+        running: this.config.mode !== 'external',
         healthy: false,
         modelLoaded: false,
         error: `Health check error: ${error}`,
@@ -293,29 +437,83 @@ export class ServerManager {
         pid: this.serverProcess?.pid,
       };
     }
+
+    return this.lastStatus;
   }
 
   /**
-   * Get server metrics
+   * Get server metrics, combining llama.cpp's `/metrics` with host and request statistics
    */
   async getMetrics(): Promise<ServerMetrics | null> {
+    if (!this.isEnabled()) {
+      return null;
+    }
+
     try {
-      const response = await fetch(`${this.serverUrl}/metrics`, {
-        method: 'GET',
-        signal: AbortSignal.timeout(5000),
-      });
+      const os = await import('os');
 
-      if (!response.ok) {
-        throw new Error(`Metrics request failed: ${response.status}`);
+      let queueLength = 0;
+
+      try {
+        const response = await fetch(`${this.config.url}/metrics`, {
+          method: 'GET',
+          signal: AbortSignal.timeout(5000),
+        });
+
+        if (response.ok) {
+          const values = parsePrometheusMetrics(await response.text());
+          queueLength = (values['llamacpp:requests_processing'] ?? 0) + (values['llamacpp:requests_deferred'] ?? 0);
+        }
+      } catch {
+        // the server may run without --metrics, the rest is still useful
       }
 
-      return await response.json();
+      const total = os.totalmem();
+      const used = total - os.freemem();
+      const cores = os.cpus().length;
+      const { total: requestCount, successful, failed, totalDurationMs } = this.requestStats;
+
+      return {
+        memory: {
+          used,
+          total,
+          percentage: (used / total) * 100,
+        },
+        cpu: {
+          usage: Math.min((os.loadavg()[0] / cores) * 100, 100),
+          cores,
+        },
+        model: {
+          status: this.lastStatus.modelLoaded ? 'ready' : this.lastStatus.healthy ? 'loading' : 'unavailable',
+          queue_length: queueLength,
+          average_response_time: requestCount > 0 ? totalDurationMs / requestCount / 1000 : 0,
+        },
+        requests: {
+          total: requestCount,
+          successful,
+          failed,
+        },
+      };
     } catch (error) {
       logger.error(`Failed to get server metrics: ${error}`);
       return null;
     }
   }
 
+  /**
+   * Record the outcome of a request served by the local model
+   */
+  recordRequest(success: boolean, durationMs: number): void {
+    this.requestStats.total++;
+    this.requestStats.totalDurationMs += durationMs;
+
+    if (success) {
+      this.requestStats.successful++;
+    } else {
+      this.requestStats.failed++;
+    }
+  }
+
   /**
    * Check if server is healthy
    */
@@ -335,32 +533,28 @@ export class ServerManager {
     this.isShuttingDown = true;
     logger.info('Shutting down ServerManager...');
 
-    this.stopHealthMonitoring();
-    this.stopResourceMonitoring();
     await this.stopServer();
 
     logger.info('ServerManager shutdown complete');
   }
 
   /**
-   * Wait for server to be ready
+   * Wait for server to be ready, i.e. healthy with the model loaded
    */
   private async waitForServerReady(timeoutMs = 60000): Promise<boolean> {
     const startTime = Date.now();
     const checkInterval = 2000; // Check every 2 seconds
 
     while (Date.now() - startTime < timeoutMs) {
-      try {
-        const response = await fetch(`${this.serverUrl}/health`, {
-          method: 'GET',
-          signal: AbortSignal.timeout(5000),
-        });
+      if (this.config.mode === 'local' && !this.serverProcess) {
+        // the process died while loading
+        return false;
+      }
 
-        if (response.ok) {
-          return true;
-        }
-      } catch (error) {
-        // Server not ready yet, continue waiting
+      const status = await this.getStatus();
+
+      if (status.healthy && status.modelLoaded) {
+        return true;
       }
 
       await new Promise((resolve) => setTimeout(resolve, checkInterval));
@@ -369,6 +563,32 @@ export class ServerManager {
     return false;
   }
 
+  private setStatusError(error: string) {
+    logger.error(error);
+    this.lastStatus = { running: false, healthy: false, modelLoaded: false, error };
+  }
+
+  private registerCleanupHandlers() {
+    if (this.cleanupHandlersRegistered || typeof process === 'undefined') {
+      return;
+    }
+
+    this.cleanupHandlersRegistered = true;
+
+    /*
+     * A signal listener replaces Node's default of exiting, so stop the server
+     * and then raise the signal again with the listener gone
+     */
+    for (const signal of ['SIGINT', 'SIGTERM'] as const) {
+      process.once(signal, async () => {
+        await this.shutdown();
+        process.kill(process.pid, signal);
+      });
+    }
+
+    process.on('exit', () => this.serverProcess?.kill('SIGTERM'));
+  }
+
   /**
    * Start health monitoring
    */
@@ -380,7 +600,7 @@ export class ServerManager {
     this.healthCheckInterval = setInterval(async () => {
       const status = await this.getStatus();
 
-      if (!status.healthy && status.running) {
+      if (!status.healthy && status.running && this.config.mode === 'local') {
         logger.warn('Server health check failed, attempting restart...');
         this.restartServer();
       }
@@ -401,37 +621,28 @@ export class ServerManager {
    * Start resource monitoring
    */
   private startResourceMonitoring(): void {
-    try {
-      // Import and start resource monitor
-      import('./resource-monitor')
-        .then(({ resourceMonitor }) => {
-          resourceMonitor.startMonitoring(15000); // Monitor every 15 seconds
-          logger.info('Resource monitoring started');
-        })
-        .catch((error) => {
-          logger.error('Failed to start resource monitoring:', error);
-        });
-    } catch (error) {
-      logger.error('Error starting resource monitoring:', error);
-    }
+    // resource-monitor imports this module, load it lazily to keep the import graph acyclic at startup
+    import('./resource-monitor')
+      .then(({ resourceMonitor }) => {
+        resourceMonitor.startMonitoring(15000); // Monitor every 15 seconds
+        logger.info('Resource monitoring started');
+      })
+      .catch((error) => {
+        logger.error('Failed to start resource monitoring:', error);
+      });
   }
 
   /**
    * Stop resource monitoring
    */
   private stopResourceMonitoring(): void {
-    try {
-      import('./resource-monitor')
-        .then(({ resourceMonitor }) => {
-          resourceMonitor.stopMonitoring();
-          logger.info('Resource monitoring stopped');
-        })
-        .catch((error) => {
-          logger.error('Failed to stop resource monitoring:', error);
-        });
-    } catch (error) {
-      logger.error('Error stopping resource monitoring:', error);
-    }
+    import('./resource-monitor')
+      .then(({ resourceMonitor }) => {
+        resourceMonitor.stopMonitoring();
+      })
+      .catch((error) => {
+        logger.error('Failed to stop resource monitoring:', error);
+      });
   }
 
   /**
@@ -447,4 +658,4 @@ export class ServerManager {
 }
 
 // Export singleton instance
-export const serverManager = await ServerManager.getInstance();
\ No newline at end of file
+export const serverManager = ServerManager.getInstance();
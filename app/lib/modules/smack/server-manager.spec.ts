@@ -0,0 +1,156 @@
+import { EventEmitter } from 'node:events';
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+
+const { spawn } = vi.hoisted(() => ({ spawn: vi.fn() }));
+
+vi.mock('child_process', () => ({ spawn }));
+vi.mock('fs', () => ({ existsSync: () => true }));
+vi.mock('./resource-monitor', () => ({
+  resourceMonitor: { startMonitoring: vi.fn(), stopMonitoring: vi.fn() },
+}));
+
+const LOCAL_ENV = { SMACK_SERVER_MODE: 'local', SMACK_MODEL_PATH: '/models/smack-7b.gguf' };
+
+// a llama-server process that exits when it's killed
+function createProcess() {
+  const serverProcess = Object.assign(new EventEmitter(), {
+    pid: 1234,
+    killed: false,
+    stdout: new EventEmitter(),
+    stderr: new EventEmitter(),
+    kill: vi.fn(() => {
+      serverProcess.killed = true;
+      queueMicrotask(() => serverProcess.emit('exit', null, 'SIGTERM'));
+
+      return true;
+    }),
+  });
+
+  return serverProcess;
+}
+
+async function createServerManager(env: Record<string, string>) {
+  vi.resetModules();
+
+  const { serverManager } = await import('./server-manager.server');
+  serverManager.configure(env);
+
+  return serverManager;
+}
+
+describe('getSmackServerConfig', () => {
+  afterEach(() => {
+    vi.unstubAllEnvs();
+  });
+
+  it('is disabled unless the mode is local or external', async () => {
+    const { getSmackServerConfig } = await import('./server-manager.server');
+
+    expect(getSmackServerConfig({}).mode).toBe('disabled');
+    expect(getSmackServerConfig({ SMACK_SERVER_MODE: 'remote' }).mode).toBe('disabled');
+    expect(getSmackServerConfig({ SMACK_SERVER_MODE: 'local' }).mode).toBe('local');
+    expect(getSmackServerConfig({ SMACK_SERVER_MODE: 'external' }).mode).toBe('external');
+  });
+
+  it('prefers the server env over process.env', async () => {
+    vi.stubEnv('SMACK_SERVER_MODE', 'local');
+    vi.stubEnv('SMACK_SERVER_URL', 'http://127.0.0.1:9000');
+
+    const { getSmackServerConfig } = await import('./server-manager.server');
+    const config = getSmackServerConfig({ SMACK_SERVER_MODE: 'external', SMACK_SERVER_ARGS: ' --threads 4 ' });
+
+    expect(config.mode).toBe('external');
+    expect(config.url).toBe('http://127.0.0.1:9000');
+    expect(config.args).toEqual(['--threads', '4']);
+  });
+
+  it('strips trailing slashes from the url', async () => {
+    const { getSmackServerConfig } = await import('./server-manager.server');
+
+    expect(getSmackServerConfig({ SMACK_SERVER_URL: 'http://smack.local:8001//' }).url).toBe('http://smack.local:8001');
+  });
+});
+
+describe('ServerManager restart policy', () => {
+  const processes: ReturnType<typeof createProcess>[] = [];
+
+  beforeEach(() => {
+    vi.useFakeTimers();
+    processes.length = 0;
+    spawn.mockReset().mockImplementation(() => {
+      const serverProcess = createProcess();
+      processes.push(serverProcess);
+
+      return serverProcess;
+    });
+    vi.stubGlobal('fetch', vi.fn(async () => new Response(JSON.stringify({ status: 'ok' }), { status: 200 })));
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+    vi.unstubAllGlobals();
+  });
+
+  it('restarts the server when the process crashes', async () => {
+    const serverManager = await createServerManager(LOCAL_ENV);
+
+    expect(await serverManager.ensureStarted()).toBe(true);
+
+    processes[0].emit('exit', 1, null);
+    await vi.advanceTimersByTimeAsync(5000);
+
+    await vi.waitFor(() => expect(serverManager.isAvailable()).toBe(true));
+    expect(spawn).toHaveBeenCalledTimes(2);
+
+    await serverManager.shutdown();
+  });
+
+  it('does not restart a server it stopped', async () => {
+    const serverManager = await createServerManager(LOCAL_ENV);
+
+    await serverManager.ensureStarted();
+    await serverManager.stopServer();
+    await vi.advanceTimersByTimeAsync(5000);
+
+    expect(processes[0].kill).toHaveBeenCalledWith('SIGTERM');
+    expect(spawn).toHaveBeenCalledTimes(1);
+
+    await serverManager.shutdown();
+  });
+
+  it('starts exactly one new process on restart', async () => {
+    const serverManager = await createServerManager(LOCAL_ENV);
+
+    await serverManager.ensureStarted();
+
+    const restarted = serverManager.restartServer();
+    await vi.advanceTimersByTimeAsync(5000);
+
+    expect(await restarted).toBe(true);
+    expect(spawn).toHaveBeenCalledTimes(2);
+
+    await serverManager.shutdown();
+  });
+
+  it('gives up after the maximum number of restarts', async () => {
+    const serverManager = await createServerManager(LOCAL_ENV);
+
+    await serverManager.ensureStarted();
+
+    // the server stops answering and the process crashes before it ever becomes ready again
+    vi.mocked(fetch).mockRejectedValue(new Error('fetch failed'));
+    spawn.mockImplementation(() => {
+      const serverProcess = createProcess();
+      processes.push(serverProcess);
+      setTimeout(() => serverProcess.emit('exit', 1, null), 1000);
+
+      return serverProcess;
+    });
+    processes[0].emit('exit', 1, null);
+
+    await vi.advanceTimersByTimeAsync(60000);
+
+    expect(spawn).toHaveBeenCalledTimes(4);
+    expect(serverManager.isAvailable()).toBe(false);
+  });
+});
@@ -9,7 +9,7 @@ import type {
   LanguageModelV1StreamPart,
 } from 'ai';
 import { createOpenAI } from '@ai-sdk/openai';
-import { requestManager } from './request-manager';
+import { requestManager, SCHEDULING_CLIENT_HEADER, type QueueRequestOptions } from './request-manager';
 import { serverManager } from './server-manager.server';
 import { createScopedLogger } from '~/utils/logger';
 
@@ -22,16 +22,82 @@ export class SmackModelWrapper implements LanguageModelV1 {
   readonly maxTokens = 8192;
 
   private baseModel: LanguageModelV1;
-  private serverUrl = 'http://127.0.0.1:8001/v1';
+  private fallbackModel?: LanguageModelV1;
 
-  constructor() {
+  constructor(fallbackModel?: LanguageModelV1) {
     // Create the base OpenAI-compatible model
     const smackClient = createOpenAI({
-      baseURL: this.serverUrl,
+      baseURL: `${serverManager.serverUrl}/v1`,
       apiKey: 'local-key', // Local server doesn't need real API key
     });
 
     this.baseModel = smackClient('smack-7b');
+    this.fallbackModel = fallbackModel;
+  }
+
+  /**
+   * Returns the remote model to use instead when the local server isn't usable, kicking off a start
+   * attempt in the background so later requests can use it again.
+   */
+  private async resolveFallback(): Promise<LanguageModelV1 | undefined> {
+    if (serverManager.isAvailable()) {
+      return undefined;
+    }
+
+    const status = await serverManager.getStatus();
+
+    if (status.healthy && status.modelLoaded) {
+      return undefined;
+    }
+
+    serverManager.ensureStarted().catch((error) => logger.warn('Failed to start Smack-7B server:', error));
+
+    if (!this.fallbackModel) {
+      throw new Error('Smack-7B server is not available. Please check server status.');
+    }
+
+    const { provider, modelId } = this.fallbackModel;
+    logger.warn(`Smack-7B server unavailable, falling back to ${provider}/${modelId}`);
+
+    return this.fallbackModel;
+  }
+
+  /**
+   * Splits the scheduling details registered by the chat route off the call options, the header only
+   * identifies the client and isn't forwarded to the model server.
+   */
+  private prepareCall(
+    options: LanguageModelV1CallOptions,
+    priority: number,
+    timeout: number,
+  ): { callOptions: LanguageModelV1CallOptions; queueOptions: QueueRequestOptions } {
+    const client = requestManager.getClient(options.headers);
+    const { [SCHEDULING_CLIENT_HEADER]: _clientId, ...headers } = options.headers ?? {};
+
+    return {
+      callOptions: { ...options, headers },
+      queueOptions: {
+        userId: client?.userId,
+        priority,
+        timeout,
+        signal: options.abortSignal ?? client?.signal,
+        onQueueUpdate: client?.onQueueUpdate,
+      },
+    };
+  }
+
+  private async track<T>(execute: () => Promise<T>): Promise<T> {
+    const startTime = Date.now();
+
+    try {
+      const result = await execute();
+      serverManager.recordRequest(true, Date.now() - startTime);
+
+      return result;
+    } catch (error) {
+      serverManager.recordRequest(false, Date.now() - startTime);
+      throw error;
+    }
   }
 
   async doGenerate(options: LanguageModelV1CallOptions): Promise<{
@@ -56,40 +122,37 @@ export class SmackModelWrapper implements LanguageModelV1 {
       setting: string;
     }>;
   }> {
+    // Determine request priority based on prompt length and complexity
+    const priority = this.calculateRequestPriority(options);
+    const { callOptions, queueOptions } = this.prepareCall(options, priority, 60000); // 60 second timeout
+
     // Check server health before processing
-    const status = await serverManager.getStatus();
+    const fallback = await this.resolveFallback();
 
-    if (!status.healthy) {
-      throw new Error('Smack-7B server is not available. Please check server status.');
+    if (fallback) {
+      return await fallback.doGenerate(callOptions);
     }
 
-    // Determine request priority based on prompt length and complexity
-    const priority = this.calculateRequestPriority(options);
-
     // Queue the request through the request manager
-    return await requestManager.queueRequest(
-      async () => {
-        try {
-          return await this.baseModel.doGenerate(options);
-        } catch (error) {
-          logger.error('Smack-7B generation failed:', error);
-
-          // Check if it's a server connectivity issue
-          if (
-            error instanceof Error &&
-            (error.message.includes('ECONNREFUSED') ||
-              error.message.includes('fetch failed') ||
-              error.message.includes('network'))
-          ) {
-            throw new Error('Smack-7B server is not responding. The server may be starting up or experiencing issues.');
-          }
-
-          throw error;
+    return await requestManager.queueRequest(async () => {
+      try {
+        return await this.track(() => this.baseModel.doGenerate(callOptions));
+      } catch (error) {
+        logger.error('Smack-7B generation failed:', error);
+
+        // Check if it's a server connectivity issue
+        if (
+          error instanceof Error &&
+          (error.message.includes('ECONNREFUSED') ||
+            error.message.includes('fetch failed') ||
+            error.message.includes('network'))
+        ) {
+          throw new Error('Smack-7B server is not responding. The server may be starting up or experiencing issues.');
         }
-      },
-      priority,
-      60000, // 60 second timeout
-    );
+
+        throw error;
+      }
+    }, queueOptions);
   }
 
   async doStream(options: LanguageModelV1CallOptions): Promise<{
@@ -103,40 +166,64 @@ export class SmackModelWrapper implements LanguageModelV1 {
       setting: string;
     }>;
   }> {
+    // Determine request priority
+    const priority = this.calculateRequestPriority(options);
+    const { callOptions, queueOptions } = this.prepareCall(options, priority, 120000); // 2 minute timeout for streaming
+
     // Check server health before processing
-    const status = await serverManager.getStatus();
+    const fallback = await this.resolveFallback();
 
-    if (!status.healthy) {
-      throw new Error('Smack-7B server is not available. Please check server status.');
+    if (fallback) {
+      return await fallback.doStream(callOptions);
     }
 
-    // Determine request priority
-    const priority = this.calculateRequestPriority(options);
-
     // Queue the streaming request
-    return await requestManager.queueRequest(
-      async () => {
-        try {
-          return await this.baseModel.doStream(options);
-        } catch (error) {
-          logger.error('Smack-7B streaming failed:', error);
-
-          // Check if it's a server connectivity issue
-          if (
-            error instanceof Error &&
-            (error.message.includes('ECONNREFUSED') ||
-              error.message.includes('fetch failed') ||
-              error.message.includes('network'))
-          ) {
-            throw new Error('Smack-7B server is not responding. The server may be starting up or experiencing issues.');
-          }
-
-          throw error;
+    return await requestManager.queueRequest(async (slot) => {
+      try {
+        const result = await this.track(() => this.baseModel.doStream(callOptions));
+        const release = slot.hold();
+
+        // the server keeps generating until the stream ends, so the slot is only freed then
+        const reader = result.stream.getReader();
+        const stream = new ReadableStream<LanguageModelV1StreamPart>({
+          async pull(controller) {
+            try {
+              const { done, value } = await reader.read();
+
+              if (done) {
+                release();
+                controller.close();
+              } else {
+                controller.enqueue(value);
+              }
+            } catch (error) {
+              release();
+              controller.error(error);
+            }
+          },
+          cancel(reason) {
+            release();
+            return reader.cancel(reason);
+          },
+        });
+
+        return { ...result, stream };
+      } catch (error) {
+        logger.error('Smack-7B streaming failed:', error);
+
+        // Check if it's a server connectivity issue
+        if (
+          error instanceof Error &&
+          (error.message.includes('ECONNREFUSED') ||
+            error.message.includes('fetch failed') ||
+            error.message.includes('network'))
+        ) {
+          throw new Error('Smack-7B server is not responding. The server may be starting up or experiencing issues.');
         }
-      },
-      priority,
-      120000, // 2 minute timeout for streaming
-    );
+
+        throw error;
+      }
+    }, queueOptions);
   }
 
   /**
@@ -249,8 +336,9 @@ export class SmackModelWrapper implements LanguageModelV1 {
 }
 
 /**
- * Create a new Smack-7B model instance with request management
+ * Create a new Smack-7B model instance with request management, optionally falling back to a remote
+ * model while the local server is unavailable
  */
-export function createSmackModel(): LanguageModelV1 {
-  return new SmackModelWrapper();
+export function createSmackModel(fallbackModel?: LanguageModelV1): LanguageModelV1 {
+  return new SmackModelWrapper(fallbackModel);
 }
@@ -0,0 +1,56 @@
+import 'fake-indexeddb/auto';
+import { beforeAll, describe, expect, it } from 'vitest';
+import { addFileVersion, deleteFileVersions, getFileVersions, openDatabase } from './db';
+import type { FileVersion } from './types';
+
+const FILE_PATH = '/home/project/src/index.ts';
+
+let db: IDBDatabase;
+
+function version(overrides: Partial<FileVersion>): FileVersion {
+  return { chatId: 'chat-1', filePath: FILE_PATH, content: '', timestamp: 1, source: 'user', ...overrides };
+}
+
+async function contentsOf(chatId: string, filePath = FILE_PATH) {
+  return (await getFileVersions(db, chatId, filePath)).map((entry) => entry.content);
+}
+
+describe('file versions', () => {
+  beforeAll(async () => {
+    db = (await openDatabase())!;
+  });
+
+  it('returns the versions of one file in a chat, oldest first', async () => {
+    await addFileVersion(db, version({ content: 'second', timestamp: 2 }));
+    await addFileVersion(db, version({ content: 'first', timestamp: 1 }));
+    await addFileVersion(db, version({ content: 'other file', filePath: '/home/project/src/other.ts' }));
+    await addFileVersion(db, version({ content: 'other chat', chatId: 'chat-2' }));
+
+    expect(await contentsOf('chat-1')).toEqual(['first', 'second']);
+  });
+
+  it('keeps the save order of versions with the same timestamp', async () => {
+    await addFileVersion(db, version({ chatId: 'chat-3', content: 'a', timestamp: 5 }));
+    await addFileVersion(db, version({ chatId: 'chat-3', content: 'b', timestamp: 5 }));
+
+    expect(await contentsOf('chat-3')).toEqual(['a', 'b']);
+  });
+
+  it('stores versions under a generated id', async () => {
+    const id = await addFileVersion(db, version({ chatId: 'chat-4', id: 9999 }));
+    const [stored] = await getFileVersions(db, 'chat-4', FILE_PATH);
+
+    expect(id).not.toBe(9999);
+    expect(stored.id).toBe(id);
+  });
+
+  it('deletes versions by id', async () => {
+    const first = await addFileVersion(db, version({ chatId: 'chat-5', content: 'a', timestamp: 1 }));
+    await addFileVersion(db, version({ chatId: 'chat-5', content: 'b', timestamp: 2 }));
+
+    await deleteFileVersions(db, [first]);
+    await deleteFileVersions(db, []);
+
+    expect(await contentsOf('chat-5')).toEqual(['b']);
+  });
+});
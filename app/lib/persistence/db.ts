@@ -1,13 +1,24 @@
 import type { UIMessage as Message } from 'ai';
 import { createScopedLogger } from '~/utils/logger';
 import type { ChatHistoryItem } from './useChatHistory';
-import type { Snapshot } from './types';
+import type {
+  ArtifactRollback,
+  ChatUsageSummary,
+  FileVersion,
+  McpAuditRecord,
+  Snapshot,
+  UsageRecord,
+  VisualSnapshot,
+} from './types';
+import type { FileMap } from '~/lib/stores/files';
+import type { CustomPrompt } from '~/lib/common/prompt-library';
 import { z } from 'zod';
 
 export interface IChatMetadata {
-  gitUrl: string;
+  gitUrl?: string;
   gitBranch?: string;
   netlifySiteId?: string;
+  usage?: ChatUsageSummary;
 }
 
 const logger = createScopedLogger('ChatHistory');
@@ -30,10 +41,33 @@ const ChatHistoryItemSchema = z.object({
   metadata: z.any().optional(),
 });
 
+const ArtifactRollbackSchema = z.object({
+  artifactId: z.string(),
+  title: z.string(),
+  files: z.array(z.string()),
+  timestamp: z.string(),
+});
+
+const VisualSnapshotSchema = z.object({
+  artifactId: z.string(),
+  title: z.string(),
+  timestamp: z.string(),
+  captures: z.array(
+    z.object({
+      viewport: z.string(),
+      width: z.number(),
+      height: z.number(),
+      dataUrl: z.string(),
+    }),
+  ),
+});
+
 const SnapshotSchema = z.object({
   chatIndex: z.string(),
   files: z.record(z.any()),
   summary: z.string().optional(),
+  rollbacks: z.array(ArtifactRollbackSchema).optional(),
+  visualSnapshots: z.array(VisualSnapshotSchema).optional(),
 });
 
 export async function openDatabase(): Promise<IDBDatabase | undefined> {
@@ -43,7 +77,7 @@ export async function openDatabase(): Promise<IDBDatabase | undefined> {
   }
 
   return new Promise((resolve) => {
-    const request = indexedDB.open('smackHistory', 3);
+    const request = indexedDB.open('smackHistory', 7);
 
     request.onupgradeneeded = (event: IDBVersionChangeEvent) => {
       const db = (event.target as IDBOpenDBRequest).result;
@@ -68,6 +102,35 @@ export async function openDatabase(): Promise<IDBDatabase | undefined> {
           db.createObjectStore('corrupted_chats', { keyPath: 'id' });
         }
       }
+
+      if (oldVersion < 4) {
+        if (!db.objectStoreNames.contains('fileVersions')) {
+          const store = db.createObjectStore('fileVersions', { keyPath: 'id', autoIncrement: true });
+          store.createIndex('chatId', 'chatId', { unique: false });
+          store.createIndex('chatFile', ['chatId', 'filePath'], { unique: false });
+        }
+      }
+
+      if (oldVersion < 5) {
+        if (!db.objectStoreNames.contains('usage')) {
+          const store = db.createObjectStore('usage', { keyPath: 'id' });
+          store.createIndex('chatId', 'chatId', { unique: false });
+          store.createIndex('timestamp', 'timestamp', { unique: false });
+        }
+      }
+
+      if (oldVersion < 6) {
+        if (!db.objectStoreNames.contains('mcpAudit')) {
+          const store = db.createObjectStore('mcpAudit', { keyPath: 'id' });
+          store.createIndex('timestamp', 'timestamp', { unique: false });
+        }
+      }
+
+      if (oldVersion < 7) {
+        if (!db.objectStoreNames.contains('prompts')) {
+          db.createObjectStore('prompts', { keyPath: 'id' });
+        }
+      }
     };
 
     request.onsuccess = (event: Event) => {
@@ -202,22 +265,41 @@ export async function getMessagesById(db: IDBDatabase, id: string): Promise<Chat
 
 export async function deleteById(db: IDBDatabase, id: string): Promise<void> {
   return new Promise((resolve, reject) => {
-    const transaction = db.transaction(['chats', 'snapshots'], 'readwrite'); // Add snapshots store to transaction
+    // Add snapshots and file versions stores to transaction
+    const transaction = db.transaction(['chats', 'snapshots', 'fileVersions'], 'readwrite');
     const chatStore = transaction.objectStore('chats');
     const snapshotStore = transaction.objectStore('snapshots');
+    const versionsIndex = transaction.objectStore('fileVersions').index('chatId');
 
     const deleteChatRequest = chatStore.delete(id);
     const deleteSnapshotRequest = snapshotStore.delete(id); // Also delete snapshot
+    const versionsCursorRequest = versionsIndex.openCursor(IDBKeyRange.only(id)); // And the file version history
 
     let chatDeleted = false;
     let snapshotDeleted = false;
+    let versionsDeleted = false;
 
     const checkCompletion = () => {
-      if (chatDeleted && snapshotDeleted) {
+      if (chatDeleted && snapshotDeleted && versionsDeleted) {
         resolve(undefined);
       }
     };
 
+    versionsCursorRequest.onsuccess = () => {
+      const cursor = versionsCursorRequest.result;
+
+      if (cursor) {
+        cursor.delete();
+        cursor.continue();
+
+        return;
+      }
+
+      versionsDeleted = true;
+      checkCompletion();
+    };
+    versionsCursorRequest.onerror = () => reject(versionsCursorRequest.error);
+
     deleteChatRequest.onsuccess = () => {
       chatDeleted = true;
       checkCompletion();
@@ -372,6 +454,44 @@ export async function setSnapshot(db: IDBDatabase, chatId: string, snapshot: Sna
   });
 }
 
+export async function recordArtifactRollback(
+  db: IDBDatabase,
+  chatId: string,
+  rollback: ArtifactRollback,
+  files: FileMap,
+): Promise<void> {
+  const snapshot = await getSnapshot(db, chatId);
+
+  await setSnapshot(db, chatId, {
+    chatIndex: snapshot?.chatIndex ?? '',
+    files,
+    summary: snapshot?.summary,
+    rollbacks: [...(snapshot?.rollbacks ?? []), rollback],
+    visualSnapshots: snapshot?.visualSnapshots,
+  });
+}
+
+/**
+ * Replaces the visual snapshots of a chat, keeping the rest of its snapshot. `files` is only used when the chat has
+ * no snapshot yet.
+ */
+export async function setVisualSnapshots(
+  db: IDBDatabase,
+  chatId: string,
+  visualSnapshots: VisualSnapshot[],
+  files: FileMap,
+): Promise<void> {
+  const snapshot = await getSnapshot(db, chatId);
+
+  await setSnapshot(db, chatId, {
+    chatIndex: snapshot?.chatIndex ?? '',
+    files: snapshot?.files ?? files,
+    summary: snapshot?.summary,
+    rollbacks: snapshot?.rollbacks,
+    visualSnapshots,
+  });
+}
+
 export async function deleteSnapshot(db: IDBDatabase, chatId: string): Promise<void> {
   return new Promise((resolve, reject) => {
     const transaction = db.transaction('snapshots', 'readwrite');
@@ -388,3 +508,158 @@ export async function deleteSnapshot(db: IDBDatabase, chatId: string): Promise<v
     };
   });
 }
+
+export async function addFileVersion(db: IDBDatabase, version: FileVersion): Promise<number> {
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('fileVersions', 'readwrite');
+    const store = transaction.objectStore('fileVersions');
+    const { id: _id, ...record } = version;
+    const request = store.add(record);
+
+    request.onsuccess = () => resolve(request.result as number);
+    request.onerror = () => reject(request.error);
+  });
+}
+
+/**
+ * Returns every recorded version of a file in a chat, oldest first.
+ */
+export async function getFileVersions(db: IDBDatabase, chatId: string, filePath: string): Promise<FileVersion[]> {
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('fileVersions', 'readonly');
+    const index = transaction.objectStore('fileVersions').index('chatFile');
+    const request = index.getAll(IDBKeyRange.only([chatId, filePath]));
+
+    request.onsuccess = () => {
+      const versions = (request.result as FileVersion[]).sort((a, b) => a.timestamp - b.timestamp || a.id! - b.id!);
+      resolve(versions);
+    };
+    request.onerror = () => reject(request.error);
+  });
+}
+
+export async function deleteFileVersions(db: IDBDatabase, ids: number[]): Promise<void> {
+  if (ids.length === 0) {
+    return;
+  }
+
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('fileVersions', 'readwrite');
+    const store = transaction.objectStore('fileVersions');
+
+    for (const id of ids) {
+      store.delete(id);
+    }
+
+    transaction.oncomplete = () => resolve();
+    transaction.onerror = () => reject(transaction.error);
+  });
+}
+
+export async function putUsageRecords(db: IDBDatabase, records: UsageRecord[]): Promise<void> {
+  if (records.length === 0) {
+    return;
+  }
+
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('usage', 'readwrite');
+    const store = transaction.objectStore('usage');
+
+    for (const record of records) {
+      store.put(record);
+    }
+
+    transaction.oncomplete = () => resolve();
+    transaction.onerror = () => reject(transaction.error);
+  });
+}
+
+/**
+ * Returns the usage recorded since `since` (ms since epoch) across all chats. Records outlive their chat,
+ * the tokens were spent either way.
+ */
+export async function getUsageRecords(db: IDBDatabase, since = 0): Promise<UsageRecord[]> {
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('usage', 'readonly');
+    const index = transaction.objectStore('usage').index('timestamp');
+    const request = index.getAll(IDBKeyRange.lowerBound(since));
+
+    request.onsuccess = () => resolve(request.result as UsageRecord[]);
+    request.onerror = () => reject(request.error);
+  });
+}
+
+/**
+ * Appends tool calls to the MCP audit log. Calls that are already logged are left as they are, so the
+ * log can be fed the whole chat every time it is saved.
+ */
+export async function appendMcpAuditRecords(db: IDBDatabase, records: McpAuditRecord[]): Promise<void> {
+  if (records.length === 0) {
+    return;
+  }
+
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('mcpAudit', 'readwrite');
+    const store = transaction.objectStore('mcpAudit');
+
+    for (const record of records) {
+      const request = store.add(record);
+
+      request.onerror = (event) => {
+        if (request.error?.name === 'ConstraintError') {
+          // keep the transaction alive, the call is already logged
+          event.preventDefault();
+          event.stopPropagation();
+        }
+      };
+    }
+
+    transaction.oncomplete = () => resolve();
+    transaction.onerror = () => reject(transaction.error);
+  });
+}
+
+export async function getMcpAuditRecords(db: IDBDatabase, since = 0): Promise<McpAuditRecord[]> {
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('mcpAudit', 'readonly');
+    const index = transaction.objectStore('mcpAudit').index('timestamp');
+    const request = index.getAll(IDBKeyRange.lowerBound(since));
+
+    request.onsuccess = () => resolve(request.result as McpAuditRecord[]);
+    request.onerror = () => reject(request.error);
+  });
+}
+
+export async function getCustomPrompts(db: IDBDatabase): Promise<CustomPrompt[]> {
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('prompts', 'readonly');
+    const request = transaction.objectStore('prompts').getAll();
+
+    request.onsuccess = () => resolve(request.result as CustomPrompt[]);
+    request.onerror = () => reject(request.error);
+  });
+}
+
+export async function putCustomPrompts(db: IDBDatabase, prompts: CustomPrompt[]): Promise<void> {
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('prompts', 'readwrite');
+    const store = transaction.objectStore('prompts');
+
+    for (const prompt of prompts) {
+      store.put(prompt);
+    }
+
+    transaction.oncomplete = () => resolve();
+    transaction.onerror = () => reject(transaction.error);
+  });
+}
+
+export async function deleteCustomPrompt(db: IDBDatabase, id: string): Promise<void> {
+  return new Promise((resolve, reject) => {
+    const transaction = db.transaction('prompts', 'readwrite');
+    transaction.objectStore('prompts').delete(id);
+
+    transaction.oncomplete = () => resolve();
+    transaction.onerror = () => reject(transaction.error);
+  });
+}
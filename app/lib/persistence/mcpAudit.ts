@@ -0,0 +1,21 @@
+import type { JSONValue, UIMessage as Message } from 'ai';
+import type { ToolAuditAnnotation } from '~/types/context';
+import type { McpAuditRecord } from './types';
+
+/**
+ * Collects the MCP tool calls the server reported in a chat's assistant messages.
+ */
+export function extractMcpAuditRecords(chatId: string, messages: Message[]): McpAuditRecord[] {
+  return messages.flatMap((message) => {
+    if (message.role !== 'assistant') {
+      return [];
+    }
+
+    return ((message.annotations as JSONValue[] | undefined) ?? [])
+      .filter(
+        (annotation): annotation is ToolAuditAnnotation =>
+          !!annotation && typeof annotation === 'object' && 'type' in annotation && annotation.type === 'toolAudit',
+      )
+      .map(({ type: _type, ...audit }) => ({ ...audit, id: audit.toolCallId, chatId, messageId: message.id }));
+  });
+}
@@ -1,7 +1,84 @@
 import type { FileMap } from '~/lib/stores/files';
+import type { ModelUsage, ToolAuditAnnotation } from '~/types/context';
+
+export interface ArtifactRollback {
+  artifactId: string;
+  title: string;
+  files: string[];
+  timestamp: string;
+}
+
+/**
+ * The preview captured at one viewport, `dataUrl` is a `data:image/png` URL.
+ */
+export interface VisualCapture {
+  viewport: string;
+  width: number;
+  height: number;
+  dataUrl: string;
+}
+
+/**
+ * Preview captures taken after an artifact completed, compared with the previous artifact to spot regressions.
+ */
+export interface VisualSnapshot {
+  artifactId: string;
+  title: string;
+  timestamp: string;
+  captures: VisualCapture[];
+}
 
 export interface Snapshot {
   chatIndex: string;
   files: FileMap;
   summary?: string;
+
+  /**
+   * Artifacts whose file changes were rolled back, replaying the chat skips their actions.
+   */
+  rollbacks?: ArtifactRollback[];
+  visualSnapshots?: VisualSnapshot[];
+}
+
+/**
+ * `original` marks the content a file had before its first tracked write.
+ */
+export type FileVersionSource = 'original' | 'llm' | 'user';
+
+export interface FileVersion {
+  id?: number;
+  chatId: string;
+  filePath: string;
+  content: string;
+  timestamp: number;
+  source: FileVersionSource;
+}
+
+/**
+ * Token usage of one model in one assistant message, `id` is `<messageId>:<provider>/<model>`.
+ */
+export interface UsageRecord extends ModelUsage {
+  id: string;
+  chatId: string;
+  messageId: string;
+  userId?: string;
+  timestamp: number;
+}
+
+export interface ChatUsageSummary {
+  promptTokens: number;
+  completionTokens: number;
+  cachedTokens: number;
+
+  // USD, only counts models with pricing
+  cost: number;
+}
+
+/**
+ * One MCP tool call, keyed by its tool call id. Records are only ever added, never updated or deleted.
+ */
+export interface McpAuditRecord extends Omit<ToolAuditAnnotation, 'type'> {
+  id: string;
+  chatId: string;
+  messageId: string;
 }
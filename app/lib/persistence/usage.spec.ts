@@ -0,0 +1,64 @@
+import { describe, expect, it } from 'vitest';
+import type { UIMessage as Message } from 'ai';
+import { aggregateUsage, extractUsageRecords, summarizeUsage } from './usage';
+
+const tokens = { promptTokens: 1000, completionTokens: 200, totalTokens: 1200, cachedTokens: 400 };
+
+const messages = [
+  { id: 'u1', role: 'user', content: 'build a todo app', parts: [] },
+  {
+    id: 'a1',
+    role: 'assistant',
+    content: 'done',
+    parts: [],
+    annotations: [
+      {
+        type: 'usage',
+        value: { ...tokens, cost: 0.02 },
+        models: [
+          { provider: 'OpenAI', model: 'gpt-4o', ...tokens, cost: 0.015 },
+          { provider: 'Anthropic', model: 'claude-3-5-haiku-20241022', ...tokens, cost: 0.005 },
+        ],
+        userId: 'user_1',
+        timestamp: 1000,
+      },
+    ],
+  },
+  {
+    id: 'a2',
+    role: 'assistant',
+    content: 'done again',
+    parts: [],
+    annotations: [{ type: 'usage', value: tokens, models: [{ provider: 'Ollama', model: 'llama3.1', ...tokens }] }],
+  },
+] as unknown as Message[];
+
+describe('extractUsageRecords', () => {
+  it('should create one record per message and model', () => {
+    const records = extractUsageRecords('1', messages);
+
+    expect(records.map((record) => record.id)).toEqual([
+      'a1:OpenAI/gpt-4o',
+      'a1:Anthropic/claude-3-5-haiku-20241022',
+      'a2:Ollama/llama3.1',
+    ]);
+    expect(records[0]).toMatchObject({ chatId: '1', messageId: 'a1', userId: 'user_1', timestamp: 1000 });
+  });
+});
+
+describe('summarizeUsage', () => {
+  it('should add up tokens and only count priced models in the cost', () => {
+    const summary = summarizeUsage(extractUsageRecords('1', messages));
+
+    expect(summary).toEqual({ promptTokens: 3000, completionTokens: 600, cachedTokens: 1200, cost: 0.02 });
+  });
+});
+
+describe('aggregateUsage', () => {
+  it('should group records and sort the most expensive first', () => {
+    const groups = aggregateUsage(extractUsageRecords('1', messages), (record) => record.provider);
+
+    expect(groups.map((group) => group.key)).toEqual(['OpenAI', 'Anthropic', 'Ollama']);
+    expect(groups[0]).toMatchObject({ requests: 1, promptTokens: 1000, cost: 0.015 });
+  });
+});
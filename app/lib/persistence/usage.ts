@@ -0,0 +1,70 @@
+import type { JSONValue, UIMessage as Message } from 'ai';
+import type { UsageAnnotation } from '~/types/context';
+import type { ChatUsageSummary, UsageRecord } from './types';
+
+function getUsageAnnotation(message: Message): UsageAnnotation | undefined {
+  return (message.annotations as JSONValue[] | undefined)?.find(
+    (annotation) => annotation && typeof annotation === 'object' && 'type' in annotation && annotation.type === 'usage',
+  ) as UsageAnnotation | undefined;
+}
+
+/**
+ * Turns the usage annotations of a chat's assistant messages into one record per message and model.
+ */
+export function extractUsageRecords(chatId: string, messages: Message[]): UsageRecord[] {
+  return messages.flatMap((message) => {
+    const annotation = message.role === 'assistant' ? getUsageAnnotation(message) : undefined;
+
+    if (!annotation) {
+      return [];
+    }
+
+    const timestamp = annotation.timestamp ?? new Date(message.createdAt ?? 0).getTime();
+
+    // responses from before the per model breakdown only carry totals
+    const models = annotation.models ?? [{ provider: 'unknown', model: 'unknown', ...annotation.value }];
+
+    return models.map((usage) => ({
+      ...usage,
+      id: `${message.id}:${usage.provider}/${usage.model}`,
+      chatId,
+      messageId: message.id,
+      userId: annotation.userId,
+      timestamp,
+    }));
+  });
+}
+
+export function summarizeUsage(records: UsageRecord[]): ChatUsageSummary {
+  return records.reduce<ChatUsageSummary>(
+    (summary, record) => ({
+      promptTokens: summary.promptTokens + record.promptTokens,
+      completionTokens: summary.completionTokens + record.completionTokens,
+      cachedTokens: summary.cachedTokens + (record.cachedTokens ?? 0),
+      cost: summary.cost + (record.cost ?? 0),
+    }),
+    { promptTokens: 0, completionTokens: 0, cachedTokens: 0, cost: 0 },
+  );
+}
+
+export type UsageGroup = ChatUsageSummary & { key: string; requests: number };
+
+/**
+ * Groups usage records by chat, provider, model or user, most expensive first.
+ */
+export function aggregateUsage(records: UsageRecord[], groupBy: (record: UsageRecord) => string): UsageGroup[] {
+  const groups = new Map<string, UsageRecord[]>();
+
+  for (const record of records) {
+    const key = groupBy(record);
+    groups.set(key, [...(groups.get(key) ?? []), record]);
+  }
+
+  return [...groups.entries()]
+    .map(([key, group]) => ({
+      key,
+      requests: new Set(group.map((record) => record.messageId)).size,
+      ...summarizeUsage(group),
+    }))
+    .sort((a, b) => b.cost - a.cost || b.promptTokens + b.completionTokens - (a.promptTokens + a.completionTokens));
+}
@@ -53,6 +53,7 @@ vi.mock('~/lib/stores/workbench', () => ({
       get: vi.fn(() => ({})),
     },
     setReloadedMessages: vi.fn(),
+    setRolledBackArtifacts: vi.fn(),
   },
 }));
 
@@ -15,10 +15,14 @@ import {
   createChatFromMessages,
   getSnapshot,
   setSnapshot,
+  putUsageRecords,
+  appendMcpAuditRecords,
   type IChatMetadata,
 } from './db';
 import type { FileMap } from '~/lib/stores/files';
 import type { Snapshot } from './types';
+import { extractUsageRecords, summarizeUsage } from './usage';
+import { extractMcpAuditRecords } from './mcpAudit';
 import { webcontainer } from '~/lib/webcontainer';
 import { detectProjectCommands, createCommandActionsString } from '~/utils/projectCommands';
 import type { ContextAnnotation } from '~/types/context';
@@ -137,8 +141,16 @@ export function useChatHistory() {
       const id = chatId.get();
       if (!id || !db) return;
 
-      const snapshot: Snapshot = { chatIndex: chatIdx, files, summary: chatSummary };
       try {
+        // keep the rollback history and the visual snapshots, they're only ever written by the workbench
+        const previous = await getSnapshot(db, id);
+        const snapshot: Snapshot = {
+          chatIndex: chatIdx,
+          files,
+          summary: chatSummary,
+          rollbacks: previous?.rollbacks,
+          visualSnapshots: previous?.visualSnapshots,
+        };
         await setSnapshot(db, id, snapshot);
       } catch (error) {
         console.error('Failed to save snapshot:', error);
@@ -169,6 +181,11 @@ export function useChatHistory() {
           if (storedMessages && storedMessages.messages.length > 0) {
             const validSnapshot = snapshot || { chatIndex: '', files: {} };
             const summary = validSnapshot.summary;
+
+            if (validSnapshot.rollbacks?.length) {
+              workbenchStore.setRolledBackArtifacts(validSnapshot.rollbacks.map((rollback) => rollback.artifactId));
+            }
+
             const rewindId = searchParams.get('rewindTo');
             let startingIdx = -1;
             const endingIdx = rewindId
@@ -267,7 +284,7 @@ export function useChatHistory() {
         let localUrlId = urlId;
         let localDescription = description.get();
         const localChatId = chatId.get();
-        const localMetadata = chatMetadata.get();
+        let localMetadata = chatMetadata.get();
 
         if (!localUrlId && firstArtifact?.id) {
           const newUrlId = await getUrlId(db, firstArtifact.id);
@@ -301,6 +318,24 @@ export function useChatHistory() {
 
         await takeSnapshot(lastMessage.id, workbenchStore.files.get(), localUrlId, chatSummary);
 
+        const usageRecords = extractUsageRecords(finalChatId, [...archivedMessages, ...messagesToStore]);
+
+        if (usageRecords.length > 0) {
+          try {
+            await putUsageRecords(db, usageRecords);
+            localMetadata = { ...localMetadata, usage: summarizeUsage(usageRecords) };
+          } catch (error) {
+            // the chat itself still gets saved
+            console.error('Failed to save token usage:', error);
+          }
+        }
+
+        try {
+          await appendMcpAuditRecords(db, extractMcpAuditRecords(finalChatId, messagesToStore));
+        } catch (error) {
+          console.error('Failed to save MCP audit log:', error);
+        }
+
         try {
           await setMessages(db, finalChatId, [...archivedMessages, ...messagesToStore], localUrlId, localDescription, undefined, localMetadata);
           // Commit atom updates only on success
@@ -8,11 +8,15 @@ import type {
   FileHistory,
   SupabaseAction,
   SupabaseAlert,
+  TestStepResult,
 } from '~/types/actions';
 import { createScopedLogger } from '~/utils/logger';
 import { unreachable } from '~/utils/unreachable';
 import type { ActionCallbackData } from './message-parser';
 import type { smackShell } from '~/utils/shell';
+import { applyFilePatch, formatRejectedHunks } from './patch-applier';
+import { formatTestResults, parseTestScript } from '~/utils/previewTest';
+import { runPreviewTest } from '~/lib/webcontainer/preview-test';
 
 const logger = createScopedLogger('ActionRunner');
 
@@ -23,6 +27,9 @@ export type BaseActionState = smackAction & {
   abort: () => void;
   executed: boolean;
   abortSignal: AbortSignal;
+
+  // steps of `test` actions as they run
+  testResults?: TestStepResult[];
 };
 
 export type FailedActionState = smackAction &
@@ -33,7 +40,7 @@ export type FailedActionState = smackAction &
 
 export type ActionState = BaseActionState | FailedActionState;
 
-type BaseActionUpdate = Partial<Pick<BaseActionState, 'status' | 'abort' | 'executed'>>;
+type BaseActionUpdate = Partial<Pick<BaseActionState, 'status' | 'abort' | 'executed' | 'testResults'>>;
 
 export type ActionStateUpdate =
   | BaseActionUpdate
@@ -70,6 +77,37 @@ class ActionCommandError extends Error {
   }
 }
 
+class PatchConflictError extends Error {
+  readonly filePath: string;
+  readonly details: string;
+
+  constructor(filePath: string, details: string, rejectedCount: number) {
+    super(`Patch rejected: ${rejectedCount} ${rejectedCount === 1 ? 'hunk' : 'hunks'} failed to apply to ${filePath}`);
+
+    this.filePath = filePath;
+    this.details = details;
+
+    Object.setPrototypeOf(this, PatchConflictError.prototype);
+    this.name = 'PatchConflictError';
+  }
+}
+
+class TestFailedError extends Error {
+  readonly results: TestStepResult[];
+
+  constructor(message: string, results: TestStepResult[] = []) {
+    super(message);
+
+    this.results = results;
+
+    Object.setPrototypeOf(this, TestFailedError.prototype);
+    this.name = 'TestFailedError';
+  }
+}
+
+// a test right after a start action waits for the dev server to open its port
+const PREVIEW_WAIT_TIMEOUT = 60_000;
+
 export class ActionRunner {
   #webcontainer: Promise<WebContainer>;
   #currentExecutionPromise: Promise<void> = Promise.resolve();
@@ -79,6 +117,15 @@ export class ActionRunner {
   onAlert?: (alert: ActionAlert) => void;
   onSupabaseAlert?: (alert: SupabaseAlert) => void;
   onDeployAlert?: (alert: DeployAlert) => void;
+
+  /** Returns the current content of a file, keyed by the action's relative `filePath`. */
+  getFileContent?: (filePath: string) => string | undefined;
+
+  /** Returns the URL of the running preview that `test` actions run against. */
+  getPreviewUrl?: () => string | undefined;
+
+  /** Whether the actions belong to a message restored from history, whose tests already ran. */
+  isReloaded?: () => boolean;
   buildOutput?: { path: string; exitCode: number; output: string };
 
   constructor(
@@ -87,12 +134,18 @@ export class ActionRunner {
     onAlert?: (alert: ActionAlert) => void,
     onSupabaseAlert?: (alert: SupabaseAlert) => void,
     onDeployAlert?: (alert: DeployAlert) => void,
+    getFileContent?: (filePath: string) => string | undefined,
+    getPreviewUrl?: () => string | undefined,
+    isReloaded?: () => boolean,
   ) {
     this.#webcontainer = webcontainerPromise;
     this.#shellTerminal = getShellTerminal;
     this.onAlert = onAlert;
     this.onSupabaseAlert = onSupabaseAlert;
     this.onDeployAlert = onDeployAlert;
+    this.getFileContent = getFileContent;
+    this.getPreviewUrl = getPreviewUrl;
+    this.isReloaded = isReloaded;
   }
 
   addAction(data: ActionCallbackData) {
@@ -170,6 +223,26 @@ export class ActionRunner {
           await this.#runFileAction(action);
           break;
         }
+        case 'delete': {
+          await this.#runDeleteAction(action);
+          break;
+        }
+        case 'rename': {
+          await this.#runRenameAction(action);
+          break;
+        }
+        case 'patch': {
+          await this.#runPatchAction(action);
+          break;
+        }
+        case 'mkdir': {
+          await this.#runMkdirAction(action);
+          break;
+        }
+        case 'test': {
+          await this.#runTestAction(actionId, action);
+          break;
+        }
         case 'supabase': {
           try {
             await this.handleSupabaseAction(action as SupabaseAction);
@@ -235,6 +308,38 @@ export class ActionRunner {
         return;
       }
 
+      if (error instanceof PatchConflictError) {
+        this.#updateAction(actionId, { status: 'failed', error: `${error.message}\n\n${error.details}` });
+        logger.warn(`[${action.type}]:${error.message}`);
+
+        this.onAlert?.({
+          type: 'error',
+          title: 'Patch Rejected',
+          description: error.message,
+          content: `File: ${error.filePath}\n\n${error.details}`,
+          source: 'patch',
+        });
+
+        return;
+      }
+
+      if (error instanceof TestFailedError) {
+        const title = action.type === 'test' && action.title ? action.title : 'Preview test';
+
+        this.#updateAction(actionId, { status: 'failed', error: error.message });
+        logger.warn(`[${action.type}]:${error.message}`);
+
+        this.onAlert?.({
+          type: 'error',
+          title: 'Preview Test Failed',
+          description: error.message,
+          content: error.results.length > 0 ? formatTestResults(title, error.results) : `${title}\n${error.message}`,
+          source: 'test',
+        });
+
+        return;
+      }
+
       this.#updateAction(actionId, { status: 'failed', error: 'Action failed' });
       logger.error(`[${action.type}]:Action failed\n\n`, error);
 
@@ -345,6 +450,126 @@ export class ActionRunner {
     }
   }
 
+  async #runDeleteAction(action: ActionState) {
+    if (action.type !== 'delete') {
+      unreachable('Expected delete action');
+    }
+
+    const webcontainer = await this.#webcontainer;
+    const relativePath = nodePath.relative(webcontainer.workdir, action.filePath);
+
+    await webcontainer.fs.rm(relativePath, { recursive: true, force: true });
+    logger.debug(`Deleted ${relativePath}`);
+  }
+
+  async #runRenameAction(action: ActionState) {
+    if (action.type !== 'rename') {
+      unreachable('Expected rename action');
+    }
+
+    const webcontainer = await this.#webcontainer;
+    const fromPath = nodePath.relative(webcontainer.workdir, action.filePath);
+    const toPath = nodePath.relative(webcontainer.workdir, action.newFilePath);
+    const folder = nodePath.dirname(toPath).replace(/\/+$/g, '');
+
+    if (folder !== '.') {
+      await webcontainer.fs.mkdir(folder, { recursive: true });
+    }
+
+    await webcontainer.fs.rename(fromPath, toPath);
+    logger.debug(`Renamed ${fromPath} to ${toPath}`);
+  }
+
+  async #runPatchAction(action: ActionState) {
+    if (action.type !== 'patch') {
+      unreachable('Expected patch action');
+    }
+
+    const webcontainer = await this.#webcontainer;
+    const relativePath = nodePath.relative(webcontainer.workdir, action.filePath);
+
+    // prefer the files store so the patch sees the same content the user sees in the editor
+    const currentContent =
+      this.getFileContent?.(action.filePath) ?? (await webcontainer.fs.readFile(relativePath, 'utf-8'));
+    const result = applyFilePatch(currentContent, action.content);
+
+    if (!result.applied) {
+      throw new PatchConflictError(
+        action.filePath,
+        formatRejectedHunks(action.filePath, result),
+        result.rejected.length,
+      );
+    }
+
+    await webcontainer.fs.writeFile(relativePath, result.content);
+    logger.debug(`File patched ${relativePath} (${result.format}, ${result.hunkCount} hunks)`);
+  }
+
+  async #runMkdirAction(action: ActionState) {
+    if (action.type !== 'mkdir') {
+      unreachable('Expected mkdir action');
+    }
+
+    const webcontainer = await this.#webcontainer;
+    const relativePath = nodePath.relative(webcontainer.workdir, action.filePath);
+
+    await webcontainer.fs.mkdir(relativePath, { recursive: true });
+    logger.debug(`Created folder ${relativePath}`);
+  }
+
+  async #runTestAction(actionId: string, action: ActionState) {
+    if (action.type !== 'test') {
+      unreachable('Expected test action');
+    }
+
+    if (this.isReloaded?.()) {
+      logger.debug('Skipping test of a reloaded message');
+      return;
+    }
+
+    let steps;
+
+    try {
+      steps = parseTestScript(action.content);
+    } catch (error) {
+      throw new TestFailedError(error instanceof Error ? error.message : 'The test could not be parsed');
+    }
+
+    const previewUrl = await this.#waitForPreview(action.abortSignal);
+
+    if (!previewUrl) {
+      throw new TestFailedError('No preview is running, start the dev server before testing it');
+    }
+
+    const results = await runPreviewTest(previewUrl, steps, {
+      signal: action.abortSignal,
+      onProgress: (testResults) => this.#updateAction(actionId, { testResults }),
+    });
+    const failed = results.find((result) => result.status === 'failed');
+
+    if (failed) {
+      throw new TestFailedError(`Line ${failed.line} failed: ${failed.error}`, results);
+    }
+
+    logger.debug(`Test passed (${results.length} steps)`);
+  }
+
+  async #waitForPreview(signal: AbortSignal) {
+    const deadline = Date.now() + PREVIEW_WAIT_TIMEOUT;
+
+    while (!signal.aborted && Date.now() < deadline) {
+      const previewUrl = this.getPreviewUrl?.();
+
+      if (previewUrl) {
+        return previewUrl;
+      }
+
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+
+    return undefined;
+  }
+
   #updateAction(id: string, newState: ActionStateUpdate) {
     const actions = this.actions.get();
 
@@ -0,0 +1,175 @@
+import type { WebContainer } from '@webcontainer/api';
+import { beforeEach, describe, expect, it } from 'vitest';
+import type { FilesStore } from '~/lib/stores/files';
+import { WORK_DIR } from '~/utils/constants';
+import { ArtifactTransaction, RollbackConflictError } from './artifact-transaction';
+
+type Dirent = { type: 'file'; content: string; isBinary: false } | { type: 'folder' };
+
+// a files store and WebContainer file system sharing one in-memory tree
+function createProject(initial: Record<string, string>) {
+  const tree = new Map<string, Dirent>([[WORK_DIR, { type: 'folder' }]]);
+
+  const write = (relativePath: string, content: string) => {
+    const parts = relativePath.split('/');
+
+    for (let i = 1; i < parts.length; i++) {
+      tree.set(`${WORK_DIR}/${parts.slice(0, i).join('/')}`, { type: 'folder' });
+    }
+
+    tree.set(`${WORK_DIR}/${relativePath}`, { type: 'file', content, isBinary: false });
+  };
+
+  const remove = (fullPath: string) => {
+    for (const key of [...tree.keys()]) {
+      if (key === fullPath || key.startsWith(fullPath + '/')) {
+        tree.delete(key);
+      }
+    }
+  };
+
+  const rename = (from: string, to: string) => {
+    for (const [key, dirent] of [...tree.entries()]) {
+      if (key === `${WORK_DIR}/${from}` || key.startsWith(`${WORK_DIR}/${from}/`)) {
+        tree.delete(key);
+        tree.set(`${WORK_DIR}/${to}${key.slice(`${WORK_DIR}/${from}`.length)}`, dirent);
+      }
+    }
+  };
+
+  Object.entries(initial).forEach(([relativePath, content]) => write(relativePath, content));
+
+  const filesStore = {
+    files: { get: () => Object.fromEntries(tree) },
+    getFileOrFolder: (fullPath: string) => tree.get(fullPath),
+    getFile: (fullPath: string) => {
+      const dirent = tree.get(fullPath);
+      return dirent?.type === 'file' ? dirent : undefined;
+    },
+    saveFile: async (fullPath: string, content: string) => write(fullPath.slice(WORK_DIR.length + 1), content),
+    createFile: async (fullPath: string, content: string) => write(fullPath.slice(WORK_DIR.length + 1), content),
+    createFolder: async (fullPath: string) => {
+      tree.set(fullPath, { type: 'folder' });
+    },
+    deleteFile: async (fullPath: string) => remove(fullPath),
+    deleteFolder: async (fullPath: string) => remove(fullPath),
+  } as unknown as FilesStore;
+
+  const webcontainer = {
+    workdir: WORK_DIR,
+    fs: {
+      readFile: async (relativePath: string) => {
+        const dirent = tree.get(`${WORK_DIR}/${relativePath}`);
+
+        if (dirent?.type !== 'file') {
+          throw new Error(`EISDIR or ENOENT: ${relativePath}`);
+        }
+
+        return dirent.content;
+      },
+      readdir: async (relativePath: string) => {
+        if (tree.get(`${WORK_DIR}/${relativePath}`)?.type !== 'folder') {
+          throw new Error(`ENOTDIR or ENOENT: ${relativePath}`);
+        }
+
+        return [];
+      },
+    },
+  } as unknown as WebContainer;
+
+  const snapshot = () =>
+    Object.fromEntries(
+      [...tree.entries()]
+        .filter(([, dirent]) => dirent.type === 'file')
+        .map(([key, dirent]) => [key.slice(WORK_DIR.length + 1), (dirent as { content: string }).content]),
+    );
+
+  return { tree, filesStore, webcontainer, write, remove, rename, snapshot };
+}
+
+describe('ArtifactTransaction', () => {
+  let project: ReturnType<typeof createProject>;
+  let transaction: ArtifactTransaction;
+
+  beforeEach(() => {
+    project = createProject({ 'src/index.ts': 'index', 'src/util.ts': 'util', 'README.md': 'readme' });
+    transaction = new ArtifactTransaction(project.filesStore, Promise.resolve(project.webcontainer));
+  });
+
+  it('removes created files and the folders created for them', async () => {
+    await transaction.captureAction({ type: 'file', filePath: 'src/components/Button.tsx', content: 'button' });
+    project.write('src/components/Button.tsx', 'button');
+    await transaction.recordWrites();
+
+    await transaction.rollback();
+
+    expect(project.snapshot()).toEqual({ 'src/index.ts': 'index', 'src/util.ts': 'util', 'README.md': 'readme' });
+    expect(project.tree.has(`${WORK_DIR}/src/components`)).toBe(false);
+  });
+
+  it('restores modified files', async () => {
+    await transaction.captureAction({ type: 'patch', filePath: 'src/index.ts', content: '' });
+    project.write('src/index.ts', 'patched');
+    await transaction.captureAction({ type: 'file', filePath: 'src/index.ts', content: 'rewritten' });
+    project.write('src/index.ts', 'rewritten');
+    await transaction.recordWrites();
+
+    await transaction.rollback();
+
+    expect(project.snapshot()['src/index.ts']).toBe('index');
+    expect(transaction.getOriginalContent(`${WORK_DIR}/src/index.ts`)).toBe('index');
+  });
+
+  it('restores deleted folders with their files', async () => {
+    await transaction.captureAction({ type: 'delete', filePath: 'src' });
+    project.remove(`${WORK_DIR}/src`);
+    await transaction.recordWrites();
+
+    await transaction.rollback();
+
+    expect(project.snapshot()).toEqual({ 'src/index.ts': 'index', 'src/util.ts': 'util', 'README.md': 'readme' });
+  });
+
+  it('moves renamed folders back', async () => {
+    await transaction.captureAction({ type: 'rename', filePath: 'src', newFilePath: 'lib' });
+    project.rename('src', 'lib');
+    await transaction.recordWrites();
+
+    await transaction.rollback();
+
+    expect(project.snapshot()).toEqual({ 'src/index.ts': 'index', 'src/util.ts': 'util', 'README.md': 'readme' });
+    expect(project.tree.has(`${WORK_DIR}/lib`)).toBe(false);
+  });
+
+  it('refuses to drop changes made after it wrote the files', async () => {
+    await transaction.captureAction({ type: 'file', filePath: 'src/index.ts', content: 'artifact' });
+    project.write('src/index.ts', 'artifact');
+    await transaction.captureAction({ type: 'file', filePath: 'src/new.ts', content: 'new' });
+    project.write('src/new.ts', 'new');
+    await transaction.recordWrites();
+
+    // a later artifact or the user edits one file and deletes the other
+    project.write('src/index.ts', 'edited later');
+    project.remove(`${WORK_DIR}/src/new.ts`);
+
+    const error = await transaction.rollback().catch((rollbackError) => rollbackError);
+
+    expect(error).toBeInstanceOf(RollbackConflictError);
+    expect(error.paths).toEqual([`${WORK_DIR}/src/index.ts`, `${WORK_DIR}/src/new.ts`]);
+    expect(project.snapshot()['src/index.ts']).toBe('edited later');
+
+    await transaction.rollback({ force: true });
+
+    expect(project.snapshot()).toEqual({ 'src/index.ts': 'index', 'src/util.ts': 'util', 'README.md': 'readme' });
+  });
+
+  it('treats files added to folders it created as conflicts', async () => {
+    await transaction.captureAction({ type: 'mkdir', filePath: 'assets' });
+    project.tree.set(`${WORK_DIR}/assets`, { type: 'folder' });
+    await transaction.recordWrites();
+
+    project.write('assets/logo.svg', '<svg />');
+
+    expect(await transaction.findConflicts()).toEqual([`${WORK_DIR}/assets`]);
+  });
+});
@@ -0,0 +1,266 @@
+import type { WebContainer } from '@webcontainer/api';
+import type { FilesStore } from '~/lib/stores/files';
+import type { smackAction } from '~/types/actions';
+import { WORK_DIR } from '~/utils/constants';
+import { createScopedLogger } from '~/utils/logger';
+import { path } from '~/utils/path';
+
+const logger = createScopedLogger('ArtifactTransaction');
+
+type OriginalEntry = { type: 'folder' } | { type: 'file'; content: string | Uint8Array };
+
+// binary files are compared through their decoded text, which is lossy but stable
+type WrittenEntry = { type: 'folder' } | { type: 'file'; content: string };
+
+/**
+ * Thrown when paths changed after the transaction wrote them, rolling back would silently drop those changes.
+ */
+export class RollbackConflictError extends Error {
+  readonly paths: string[];
+
+  constructor(paths: string[]) {
+    super(`Changed since: ${paths.join(', ')}`);
+
+    this.paths = paths;
+
+    Object.setPrototypeOf(this, RollbackConflictError.prototype);
+    this.name = 'RollbackConflictError';
+  }
+}
+
+/**
+ * Records the state of every path an artifact touches, captured right before the first action that
+ * writes to it, so the artifact's file changes can be undone as a unit.
+ *
+ * Only file-level actions (`file`, `patch`, `delete`, `rename`, `mkdir`) are tracked, side effects of
+ * shell commands such as `npm install` are not.
+ */
+export class ArtifactTransaction {
+  #filesStore: FilesStore;
+  #webcontainer: Promise<WebContainer>;
+
+  /**
+   * Original state per full path, `undefined` when the path didn't exist before the artifact ran.
+   */
+  #originals = new Map<string, OriginalEntry | undefined>();
+
+  /**
+   * State the transaction left every path in, as of the last `recordWrites`.
+   */
+  #written = new Map<string, WrittenEntry | undefined>();
+
+  constructor(filesStore: FilesStore, webcontainerPromise: Promise<WebContainer>) {
+    this.#filesStore = filesStore;
+    this.#webcontainer = webcontainerPromise;
+  }
+
+  get touchedPaths() {
+    return [...this.#originals.keys()];
+  }
+
+  get isEmpty() {
+    return this.#originals.size === 0;
+  }
+
+  /**
+   * Text content a file had before the artifact first touched it, `undefined` for new or binary files.
+   */
+  getOriginalContent(filePath: string) {
+    const original = this.#originals.get(filePath);
+
+    return original?.type === 'file' && typeof original.content === 'string' ? original.content : undefined;
+  }
+
+  async captureAction(action: smackAction) {
+    switch (action.type) {
+      case 'file':
+      case 'patch':
+      case 'mkdir': {
+        await this.#capture(this.#toFullPath(action.filePath));
+        break;
+      }
+      case 'delete': {
+        await this.#captureTree(this.#toFullPath(action.filePath));
+        break;
+      }
+      case 'rename': {
+        const fromPath = this.#toFullPath(action.filePath);
+        const toPath = this.#toFullPath(action.newFilePath);
+
+        // every moved path disappears from its source and shows up under the destination
+        for (const filePath of this.#subtree(fromPath)) {
+          await this.#capture(filePath);
+          await this.#capture(toPath + filePath.slice(fromPath.length));
+        }
+
+        break;
+      }
+    }
+  }
+
+  /**
+   * Records the current state of every captured path as what the transaction wrote, call it after its actions ran.
+   */
+  async recordWrites() {
+    for (const filePath of this.#originals.keys()) {
+      this.#written.set(filePath, await this.#read(filePath));
+    }
+  }
+
+  /**
+   * Paths that changed since `recordWrites`, and folders the transaction created that gained other paths since.
+   */
+  async findConflicts() {
+    const conflicts: string[] = [];
+
+    for (const [filePath, written] of this.#written) {
+      const current = await this.#read(filePath);
+      const changed =
+        current?.type !== written?.type ||
+        (current?.type === 'file' && written?.type === 'file' && current.content !== written.content) ||
+        (current?.type === 'folder' &&
+          !this.#originals.get(filePath) &&
+          this.#subtree(filePath).some((childPath) => !this.#originals.has(childPath)));
+
+      if (changed) {
+        conflicts.push(filePath);
+      }
+    }
+
+    return conflicts;
+  }
+
+  /**
+   * Restores every captured path: removes what the artifact created and writes back what it changed or
+   * deleted. Refuses with a `RollbackConflictError` when paths changed since `recordWrites` unless forced.
+   */
+  async rollback({ force = false }: { force?: boolean } = {}) {
+    const conflicts = force ? [] : await this.findConflicts();
+
+    if (conflicts.length > 0) {
+      throw new RollbackConflictError(conflicts);
+    }
+
+    const entries = [...this.#originals.entries()];
+
+    // remove paths the artifact created, deepest first so folders are empty by the time we reach them
+    const created = entries.filter(([, original]) => !original).sort(([a], [b]) => b.length - a.length);
+
+    for (const [filePath] of created) {
+      await this.#remove(filePath);
+    }
+
+    const folders = entries
+      .filter(([, original]) => original?.type === 'folder')
+      .sort(([a], [b]) => a.length - b.length);
+
+    for (const [folderPath] of folders) {
+      const current = this.#filesStore.getFileOrFolder(folderPath);
+
+      if (current?.type === 'file') {
+        await this.#remove(folderPath);
+      }
+
+      if (current?.type !== 'folder') {
+        await this.#filesStore.createFolder(folderPath);
+      }
+    }
+
+    for (const [filePath, original] of entries) {
+      if (original?.type !== 'file') {
+        continue;
+      }
+
+      const current = this.#filesStore.getFileOrFolder(filePath);
+
+      if (current?.type === 'folder') {
+        await this.#remove(filePath);
+      }
+
+      if (current?.type === 'file' && !current.isBinary && typeof original.content === 'string') {
+        if (current.content !== original.content) {
+          await this.#filesStore.saveFile(filePath, original.content);
+        }
+      } else {
+        await this.#filesStore.createFile(filePath, original.content);
+      }
+    }
+
+    logger.debug(`Rolled back ${entries.length} paths`);
+
+    return this.touchedPaths;
+  }
+
+  async #capture(filePath: string) {
+    if (this.#originals.has(filePath)) {
+      return;
+    }
+
+    // writes create missing parent folders, so those have to go away on rollback as well
+    const parent = path.dirname(filePath);
+
+    if (parent.startsWith(WORK_DIR + '/') && !this.#filesStore.getFileOrFolder(parent)) {
+      await this.#capture(parent);
+    }
+
+    const dirent = this.#filesStore.getFileOrFolder(filePath);
+
+    if (!dirent) {
+      this.#originals.set(filePath, undefined);
+    } else if (dirent.type === 'folder') {
+      this.#originals.set(filePath, { type: 'folder' });
+    } else if (dirent.isBinary) {
+      // binary content isn't kept in the files store, read the raw bytes instead
+      const webcontainer = await this.#webcontainer;
+      const content = await webcontainer.fs.readFile(path.relative(webcontainer.workdir, filePath));
+
+      this.#originals.set(filePath, { type: 'file', content });
+    } else {
+      this.#originals.set(filePath, { type: 'file', content: dirent.content });
+    }
+  }
+
+  async #read(filePath: string): Promise<WrittenEntry | undefined> {
+    const webcontainer = await this.#webcontainer;
+    const relativePath = path.relative(webcontainer.workdir, filePath);
+
+    try {
+      return { type: 'file', content: await webcontainer.fs.readFile(relativePath, 'utf-8') };
+    } catch {
+      try {
+        await webcontainer.fs.readdir(relativePath);
+        return { type: 'folder' };
+      } catch {
+        return undefined;
+      }
+    }
+  }
+
+  async #captureTree(folderPath: string) {
+    for (const filePath of this.#subtree(folderPath)) {
+      await this.#capture(filePath);
+    }
+  }
+
+  #subtree(rootPath: string) {
+    const files = this.#filesStore.files.get();
+
+    const children = Object.keys(files).filter((filePath) => filePath.startsWith(rootPath + '/') && files[filePath]);
+
+    return [rootPath, ...children];
+  }
+
+  async #remove(filePath: string) {
+    const current = this.#filesStore.getFileOrFolder(filePath);
+
+    if (current?.type === 'folder') {
+      await this.#filesStore.deleteFolder(filePath);
+    } else if (current?.type === 'file') {
+      await this.#filesStore.deleteFile(filePath);
+    }
+  }
+
+  #toFullPath(filePath: string) {
+    return path.join(WORK_DIR, filePath);
+  }
+}
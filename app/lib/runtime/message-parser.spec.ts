@@ -232,6 +232,114 @@ describe('StreamingMessageParser', () => {
     expect(allCalls.some((call: any) => call[0].action?.filePath === 'file2.txt')).toBe(true);
   });
 
+  it('should parse delete, mkdir and rename actions', () => {
+    const messageId = 'msg1';
+    const input = `<smackArtifact id="art1" title="Refactor" type="bundled"><smackAction type="delete" filePath="src/old.ts"></smackAction><smackAction type="mkdir" filePath="src/lib"></smackAction><smackAction type="rename" filePath="src/a.ts" newFilePath="src/lib/a.ts"></smackAction></smackArtifact>`;
+    parser.parse(messageId, input);
+
+    expect(callbacks.onActionClose).toHaveBeenCalledTimes(3);
+    expect(callbacks.onActionClose).toHaveBeenCalledWith(expect.objectContaining({
+      actionId: '0',
+      action: { type: 'delete', filePath: 'src/old.ts', content: '' },
+    }));
+    expect(callbacks.onActionClose).toHaveBeenCalledWith(expect.objectContaining({
+      actionId: '1',
+      action: { type: 'mkdir', filePath: 'src/lib', content: '' },
+    }));
+    expect(callbacks.onActionClose).toHaveBeenCalledWith(expect.objectContaining({
+      actionId: '2',
+      action: { type: 'rename', filePath: 'src/a.ts', newFilePath: 'src/lib/a.ts', content: '' },
+    }));
+  });
+
+  it('should normalize move actions to rename', () => {
+    const messageId = 'msg1';
+    const input = `<smackArtifact id="art1" title="Move" type="bundled"><smackAction type="move" filePath="a.ts" newFilePath="b.ts"></smackAction></smackArtifact>`;
+    parser.parse(messageId, input);
+
+    expect(callbacks.onActionOpen).toHaveBeenCalledWith(expect.objectContaining({
+      action: { type: 'rename', filePath: 'a.ts', newFilePath: 'b.ts', content: '' },
+    }));
+  });
+
+  it('should not open a rename action without a newFilePath', () => {
+    const messageId = 'msg1';
+    const input = `<smackArtifact id="art1" title="Move" type="bundled"><smackAction type="rename" filePath="a.ts"></smackAction></smackArtifact>`;
+    parser.parse(messageId, input);
+
+    expect(callbacks.onActionOpen).not.toHaveBeenCalled();
+  });
+
+  it('should strip the markdown fence from patch actions', () => {
+    const messageId = 'msg1';
+    const diff = '--- a/index.js\n+++ b/index.js\n@@ -1 +1 @@\n-const a = 1;\n+const a = 2;';
+    const input = `<smackArtifact id="art1" title="Patch" type="bundled"><smackAction type="patch" filePath="index.js">\`\`\`diff\n${diff}\n\`\`\`</smackAction></smackArtifact>`;
+    parser.parse(messageId, input);
+
+    expect(callbacks.onActionClose).toHaveBeenCalledWith(expect.objectContaining({
+      action: { type: 'patch', filePath: 'index.js', content: `${diff}\n` },
+    }));
+  });
+
+  it('should stream action content split across chunks without duplicating it', () => {
+    const messageId = 'msg1';
+    const open = '<smackArtifact id="art1" title="Patch" type="bundled"><smackAction type="file" filePath="a.txt">';
+    parser.parse(messageId, `${open}first `);
+    parser.parse(messageId, `${open}first second</smackAc`);
+    parser.parse(messageId, `${open}first second</smackAction></smackArtifact>`);
+
+    const streamed = (callbacks.onActionStream as any).mock.calls.map((call: any) => call[0].action.content);
+    expect(streamed).toEqual(['first ', 'first second']);
+    expect(callbacks.onActionClose).toHaveBeenCalledWith(expect.objectContaining({
+      action: { type: 'file', filePath: 'a.txt', content: 'first second\n' },
+    }));
+  });
+
+  it('should drop an unfinished action at the resume marker', () => {
+    const messageId = 'msg1';
+    const onActionAbort = vi.fn();
+    parser = new StreamingMessageParser({ callbacks: { ...callbacks, onActionAbort } });
+
+    const open = '<smackArtifact id="art1" title="Resume" type="bundled"><smackAction type="file" filePath="a.txt">';
+    parser.parse(messageId, `${open}trunc`);
+    parser.parse(messageId, `${open}truncated<smackResume/><smackAction type="file" filePath="a.txt">full</smackAction>`);
+
+    expect(onActionAbort).toHaveBeenCalledWith(expect.objectContaining({
+      actionId: '0',
+      action: { type: 'file', filePath: 'a.txt', content: 'trunc' },
+    }));
+    expect(callbacks.onActionClose).toHaveBeenCalledTimes(1);
+    expect(callbacks.onActionClose).toHaveBeenCalledWith(expect.objectContaining({
+      actionId: '1',
+      action: { type: 'file', filePath: 'a.txt', content: 'full\n' },
+    }));
+  });
+
+  it('should drop a cut off action tag at the resume marker', () => {
+    const messageId = 'msg1';
+    const input =
+      '<smackArtifact id="art1" title="Resume" type="bundled"><smackAction type="fi<smackResume/>' +
+      '<smackAction type="shell">npm install</smackAction></smackArtifact>';
+    parser.parse(messageId, input);
+
+    expect(callbacks.onActionOpen).toHaveBeenCalledTimes(1);
+    expect(callbacks.onActionClose).toHaveBeenCalledWith(expect.objectContaining({
+      action: { type: 'shell', content: 'npm install' },
+    }));
+  });
+
+  it('should parse the title of test actions', () => {
+    const messageId = 'msg1';
+    const input =
+      '<smackArtifact id="art1" title="Login" type="bundled">' +
+      '<smackAction type="test" title="Sign in">goto /login\nexpect text Welcome</smackAction></smackArtifact>';
+    parser.parse(messageId, input);
+
+    expect(callbacks.onActionClose).toHaveBeenCalledWith(expect.objectContaining({
+      action: { type: 'test', title: 'Sign in', content: 'goto /login\nexpect text Welcome' },
+    }));
+  });
+
   it('should reset state correctly', () => {
     const messageId = 'msg1';
     parser.parse(messageId, '<smackArtifact id="art1" title="Test" type="bundled">');
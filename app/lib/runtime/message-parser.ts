@@ -5,8 +5,14 @@ import type {
   FileAction,
   ShellAction,
   SupabaseAction,
+  DeleteAction,
+  RenameAction,
+  PatchAction,
+  MkdirAction,
+  TestAction,
 } from '~/types/actions';
 import type { smackArtifactData } from '~/types/artifact';
+import { ACTION_RESUME_MARKER } from '~/utils/constants';
 import { createScopedLogger } from '~/utils/logger';
 import { unreachable } from '~/utils/unreachable';
 
@@ -40,6 +46,12 @@ export interface ParserCallbacks {
   onActionOpen?: ActionCallback;
   onActionStream?: ActionCallback;
   onActionClose?: ActionCallback;
+
+  /**
+   * An unfinished action was dropped because the response resumed after an interruption, it is written
+   * again in full after the resume marker.
+   */
+  onActionAbort?: ActionCallback;
 }
 
 interface ElementFactoryProps {
@@ -60,8 +72,9 @@ interface MessageState {
   insideAction: boolean;
   artifactCounter: number;
   currentArtifact?: smackArtifactData;
-  currentAction: smackActionData;
+  currentAction: smackActionData & { partial?: boolean };
   actionId: number;
+  buffer: string;
 }
 
 function cleanoutMarkdownSyntax(content: string) {
@@ -80,6 +93,19 @@ function cleanoutMarkdownSyntax(content: string) {
 function cleanEscapedTags(content: string) {
   return content.replace(/&lt;/g, '<').replace(/&gt;/g, '>');
 }
+
+/**
+ * Returns the length of the longest suffix of `input` that is a proper prefix of `tag`.
+ */
+function getPartialTagSuffixLength(input: string, tag: string) {
+  for (let length = Math.min(input.length, tag.length - 1); length > 0; length--) {
+    if (tag.startsWith(input.slice(input.length - length))) {
+      return length;
+    }
+  }
+
+  return 0;
+}
 export class StreamingMessageParser {
   #messages = new Map<string, MessageState>();
   #artifactCounter = 0;
@@ -120,9 +146,21 @@ export class StreamingMessageParser {
 
         if (state.insideAction) {
           const closeIndex = remainingInput.indexOf(ARTIFACT_ACTION_TAG_CLOSE);
+          const resumeIndex = remainingInput.indexOf(ACTION_RESUME_MARKER);
           const currentAction = state.currentAction;
 
-          if (closeIndex !== -1) {
+          if (resumeIndex !== -1 && (closeIndex === -1 || resumeIndex < closeIndex)) {
+            this._options.callbacks?.onActionAbort?.({
+              artifactId: currentArtifact.id,
+              messageId,
+              actionId: String(state.actionId - 1),
+              action: currentAction as smackAction,
+            });
+
+            state.insideAction = false;
+            state.currentAction = { content: '' };
+            i += resumeIndex + ACTION_RESUME_MARKER.length;
+          } else if (closeIndex !== -1) {
             const contentChunk = remainingInput.slice(0, closeIndex);
             currentAction.content += contentChunk;
 
@@ -140,6 +178,10 @@ export class StreamingMessageParser {
                   content = cleanEscapedTags(content);
                 }
                 content += '\n';
+              } else if ('type' in currentAction && currentAction.type === 'patch') {
+                content = cleanoutMarkdownSyntax(content);
+                content = cleanEscapedTags(content);
+                content += '\n';
               } else {
                 // Try to parse as JSON for other action types
                 JSON.parse(content);
@@ -161,7 +203,17 @@ export class StreamingMessageParser {
             state.currentAction = { content: '' };
             i += closeIndex + ARTIFACT_ACTION_TAG_CLOSE.length;
           } else {
-            currentAction.content += remainingInput;
+            /*
+             * Hold back a trailing fragment that may be the start of the closing tag, the next
+             * chunk completes it. Everything before it is final and can be streamed.
+             */
+            const heldBackLength = Math.max(
+              getPartialTagSuffixLength(remainingInput, ARTIFACT_ACTION_TAG_CLOSE),
+              getPartialTagSuffixLength(remainingInput, ACTION_RESUME_MARKER),
+            );
+            currentAction.content += remainingInput.slice(0, remainingInput.length - heldBackLength);
+            state.buffer = remainingInput.slice(remainingInput.length - heldBackLength);
+
             if (currentAction.content.length > this.MAX_CONTENT_LENGTH) {
               logger.warn('Action content stream exceeds max length, marking as partial.');
               currentAction.partial = true;
@@ -175,20 +227,31 @@ export class StreamingMessageParser {
               });
               state.insideAction = false;
               state.currentAction = { content: '' };
+              state.buffer = '';
             } else {
               this._options.callbacks?.onActionStream?.({
                 artifactId: currentArtifact.id,
                 messageId,
                 actionId: String(state.actionId - 1),
-                action: { ...currentAction, content: remainingInput } as smackAction,
+                action: { ...currentAction } as smackAction,
               });
-              state.buffer = chunk.slice(i);
             }
             break;
           }
         } else {
           const actionOpenIndex = remainingInput.indexOf(ARTIFACT_ACTION_TAG_OPEN);
           const artifactCloseIndex = remainingInput.indexOf(ARTIFACT_TAG_CLOSE);
+          const resumeIndex = remainingInput.indexOf(ACTION_RESUME_MARKER);
+
+          if (resumeIndex !== -1 && (actionOpenIndex === -1 || actionOpenIndex < resumeIndex)) {
+            // an action tag cut off before it was complete, drop it along with the marker
+            const actionEndIndex = actionOpenIndex === -1 ? -1 : remainingInput.indexOf('>', actionOpenIndex);
+
+            if (actionOpenIndex === -1 || actionEndIndex >= resumeIndex) {
+              i += resumeIndex + ACTION_RESUME_MARKER.length;
+              continue;
+            }
+          }
 
           if (actionOpenIndex !== -1 && (artifactCloseIndex === -1 || actionOpenIndex < artifactCloseIndex)) {
             const actionEndIndex = remainingInput.indexOf('>', actionOpenIndex);
@@ -281,12 +344,16 @@ export class StreamingMessageParser {
 
   #parseActionTag(input: string, actionOpenIndex: number, actionEndIndex: number) {
     const actionTag = input.slice(actionOpenIndex, actionEndIndex + 1);
-    const actionType = this.#extractAttribute(actionTag, 'type') as ActionType;
+    let actionType = this.#extractAttribute(actionTag, 'type') as ActionType | 'move';
 
     if (!actionType) {
       throw new Error('Action type is missing');
     }
 
+    if (actionType === 'move') {
+      actionType = 'rename';
+    }
+
     const actionAttributes: Partial<smackAction> = {
       type: actionType,
       content: '',
@@ -308,7 +375,23 @@ export class StreamingMessageParser {
         const filePath = this.#extractAttribute(actionTag, 'filePath') as string;
         if (!filePath) logger.debug('File path not specified');
         (actionAttributes as FileAction).filePath = filePath;
-      } else if (!['shell', 'start'].includes(actionType)) {
+      } else if (actionType === 'delete' || actionType === 'patch' || actionType === 'mkdir') {
+        const filePath = this.#extractAttribute(actionTag, 'filePath');
+        if (!filePath) throw new Error(`The ${actionType} action requires a filePath`);
+        (actionAttributes as DeleteAction | PatchAction | MkdirAction).filePath = filePath;
+      } else if (actionType === 'rename') {
+        const filePath = this.#extractAttribute(actionTag, 'filePath');
+        const newFilePath = this.#extractAttribute(actionTag, 'newFilePath');
+        if (!filePath || !newFilePath) throw new Error('The rename action requires a filePath and a newFilePath');
+        (actionAttributes as RenameAction).filePath = filePath;
+        (actionAttributes as RenameAction).newFilePath = newFilePath;
+      } else if (actionType === 'test') {
+        const title = this.#extractAttribute(actionTag, 'title');
+
+        if (title) {
+          (actionAttributes as TestAction).title = title;
+        }
+      } else if (!['shell', 'start', 'build'].includes(actionType)) {
         logger.warn(`Unknown action type '${actionType}'`);
       }
     } catch (error) {
@@ -316,7 +399,7 @@ export class StreamingMessageParser {
       throw error; // Re-throw to be caught by the caller
     }
 
-    return actionAttributes as FileAction | ShellAction;
+    return actionAttributes as smackAction;
   }
 
   #extractAttribute(tag: string, attributeName: string): string | undefined {
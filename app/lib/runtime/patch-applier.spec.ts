@@ -0,0 +1,156 @@
+import { describe, expect, it } from 'vitest';
+import { applyFilePatch, formatRejectedHunks } from './patch-applier';
+
+const original = [
+  'import a from "a";',
+  '',
+  'function one() {',
+  '  return 1;',
+  '}',
+  '',
+  'function two() {',
+  '  return 2;',
+  '}',
+  '',
+].join('\n');
+
+describe('applyFilePatch', () => {
+  describe('unified diffs', () => {
+    it('should apply a hunk with file headers', () => {
+      const patch = [
+        '--- a/index.js',
+        '+++ b/index.js',
+        '@@ -3,3 +3,3 @@',
+        ' function one() {',
+        '-  return 1;',
+        '+  return 10;',
+        ' }',
+      ].join('\n');
+
+      const result = applyFilePatch(original, patch);
+
+      expect(result.applied).toBe(true);
+      expect(result.format).toBe('unified');
+      expect(result.content).toContain('  return 10;');
+      expect(result.content).toContain('  return 2;');
+    });
+
+    it('should locate hunks when the line numbers are wrong', () => {
+      const patch = ['@@ -40,3 +40,3 @@', ' function two() {', '-  return 2;', '+  return 20;', ' }'].join('\n');
+
+      const result = applyFilePatch(original, patch);
+
+      expect(result.applied).toBe(true);
+      expect(result.content).toContain('  return 20;');
+    });
+
+    it('should apply multiple hunks in order', () => {
+      const patch = [
+        '@@ -3,3 +3,4 @@',
+        ' function one() {',
+        '+  // one',
+        '   return 1;',
+        ' }',
+        '@@ -7,3 +8,3 @@',
+        ' function two() {',
+        '-  return 2;',
+        '+  return 22;',
+        ' }',
+      ].join('\n');
+
+      const result = applyFilePatch(original, patch);
+
+      expect(result.applied).toBe(true);
+      expect(result.content).toContain('  // one\n  return 1;');
+      expect(result.content).toContain('  return 22;');
+    });
+
+    it('should reject the whole patch when one hunk does not match', () => {
+      const patch = [
+        '@@ -3,3 +3,3 @@',
+        ' function one() {',
+        '-  return 1;',
+        '+  return 10;',
+        ' }',
+        '@@ -7,3 +7,3 @@',
+        ' function three() {',
+        '-  return 3;',
+        '+  return 30;',
+        ' }',
+      ].join('\n');
+
+      const result = applyFilePatch(original, patch);
+
+      expect(result.applied).toBe(false);
+      expect(result.content).toBe(original);
+      expect(result.rejected).toHaveLength(1);
+      expect(result.rejected[0].index).toBe(1);
+      expect(formatRejectedHunks('index.js', result)).toContain('index.js hunk 2/2: context not found');
+    });
+
+    it('should reject a patch without hunks', () => {
+      const result = applyFilePatch(original, 'not a diff');
+
+      expect(result.applied).toBe(false);
+      expect(result.rejected[0].reason).toContain('no @@ hunks');
+    });
+
+    it('should remove lines that start with "-- "', () => {
+      const sql = ['-- users', 'CREATE TABLE users (id int);', '-- posts', 'CREATE TABLE posts (id int);', ''];
+      const patch = [
+        '--- a/schema.sql',
+        '+++ b/schema.sql',
+        '@@ -1,4 +1,3 @@',
+        '--- users',
+        ' CREATE TABLE users (id int);',
+        '--- posts',
+        '-CREATE TABLE posts (id int);',
+      ].join('\n');
+
+      const result = applyFilePatch(sql.join('\n'), patch);
+
+      expect(result.applied).toBe(true);
+      expect(result.content).toBe('CREATE TABLE users (id int);\n');
+    });
+
+    it('should add lines that start with "++"', () => {
+      const patch = ['@@ -3,3 +3,4 @@', ' function one() {', '+++x;', '   return 1;', ' }'].join('\n');
+
+      const result = applyFilePatch(original, patch);
+
+      expect(result.applied).toBe(true);
+      expect(result.content).toContain('function one() {\n++x;\n  return 1;');
+    });
+  });
+
+  describe('search/replace blocks', () => {
+    it('should replace a unique match', () => {
+      const patch = ['<<<<<<< SEARCH', '  return 2;', '=======', '  return 200;', '>>>>>>> REPLACE'].join('\n');
+
+      const result = applyFilePatch(original, patch);
+
+      expect(result.applied).toBe(true);
+      expect(result.format).toBe('search-replace');
+      expect(result.content).toContain('  return 200;');
+    });
+
+    it('should reject an ambiguous match', () => {
+      const patch = ['<<<<<<< SEARCH', '}', '=======', '};', '>>>>>>> REPLACE'].join('\n');
+
+      const result = applyFilePatch(original, patch);
+
+      expect(result.applied).toBe(false);
+      expect(result.content).toBe(original);
+      expect(result.rejected[0].reason).toContain('ambiguous');
+    });
+
+    it('should reject a missing match', () => {
+      const patch = ['<<<<<<< SEARCH', '  return 3;', '=======', '  return 30;', '>>>>>>> REPLACE'].join('\n');
+
+      const result = applyFilePatch(original, patch);
+
+      expect(result.applied).toBe(false);
+      expect(result.rejected[0].reason).toContain('not found');
+    });
+  });
+});
@@ -0,0 +1,360 @@
+/**
+ * Applies `patch` action content to a file. Two formats are accepted:
+ *
+ * - unified diffs (`@@` hunks, file headers optional, line numbers used only as a hint)
+ * - search/replace blocks (`<<<<<<< SEARCH` / `=======` / `>>>>>>> REPLACE`)
+ *
+ * Patches are applied atomically: if any hunk or block can't be located unambiguously the
+ * original content is kept and every rejected hunk is reported so the model can retry.
+ */
+
+export type PatchFormat = 'unified' | 'search-replace';
+
+export interface RejectedHunk {
+  index: number;
+  header: string;
+  reason: string;
+  text: string;
+}
+
+export interface PatchResult {
+  format: PatchFormat;
+  applied: boolean;
+  content: string;
+  hunkCount: number;
+  rejected: RejectedHunk[];
+}
+
+interface Hunk {
+  header: string;
+  oldStart?: number;
+  oldLines: string[];
+  newLines: string[];
+  text: string;
+}
+
+interface SearchReplaceBlock {
+  search: string;
+  replace: string;
+  text: string;
+}
+
+const SEARCH_MARKER = /^<{5,9} SEARCH\s*$/;
+const DIVIDER_MARKER = /^={5,9}\s*$/;
+const REPLACE_MARKER = /^>{5,9} REPLACE\s*$/;
+const HUNK_HEADER = /^@@(?: -(\d+)(?:,\d+)? \+\d+(?:,\d+)?)? ?@@/;
+
+export function isSearchReplacePatch(patch: string) {
+  return patch.split('\n').some((line) => SEARCH_MARKER.test(line));
+}
+
+export function applyFilePatch(original: string, patch: string): PatchResult {
+  if (isSearchReplacePatch(patch)) {
+    return applySearchReplace(original, parseSearchReplaceBlocks(patch));
+  }
+
+  return applyUnifiedDiff(original, parseUnifiedDiff(patch));
+}
+
+export function formatRejectedHunks(filePath: string, result: PatchResult) {
+  const unit = result.format === 'unified' ? 'hunk' : 'block';
+
+  return result.rejected
+    .map((hunk) => `${filePath} ${unit} ${hunk.index + 1}/${result.hunkCount}: ${hunk.reason}\n${hunk.text}`)
+    .join('\n\n');
+}
+
+function parseUnifiedDiff(patch: string): Hunk[] {
+  const hunks: Hunk[] = [];
+  let current: Hunk | undefined;
+  const lines = patch.replace(/\r\n/g, '\n').split('\n');
+
+  for (const [index, line] of lines.entries()) {
+    const header = line.match(HUNK_HEADER);
+
+    if (header) {
+      current = {
+        header: line,
+        oldStart: header[1] !== undefined ? parseInt(header[1], 10) : undefined,
+        oldLines: [],
+        newLines: [],
+        text: line,
+      };
+      hunks.push(current);
+      continue;
+    }
+
+    if (!current) {
+      continue;
+    }
+
+    /*
+     * inside a hunk `--- x` may just remove a line starting with `-- ` (SQL and Lua comments), it only starts the
+     * next file when the `+++` header follows it
+     */
+    const next = lines[index + 1] ?? '';
+    const isFileHeader =
+      (line.startsWith('--- ') && next.startsWith('+++ ')) ||
+      (line.startsWith('diff ') && (next.startsWith('--- ') || next.startsWith('index ')));
+
+    if (isFileHeader) {
+      current = undefined;
+      continue;
+    }
+
+    if (line.startsWith('\\')) {
+      // "\ No newline at end of file"
+      continue;
+    }
+
+    current.text += `\n${line}`;
+
+    const marker = line[0];
+    const body = line.slice(1);
+
+    if (marker === '-') {
+      current.oldLines.push(body);
+    } else if (marker === '+') {
+      current.newLines.push(body);
+    } else {
+      // models frequently drop the leading space of empty context lines
+      const contextLine = marker === ' ' ? body : line;
+      current.oldLines.push(contextLine);
+      current.newLines.push(contextLine);
+    }
+  }
+
+  // a trailing empty context line is almost always an artifact of the closing newline
+  for (const hunk of hunks) {
+    while (
+      hunk.oldLines.length > 0 &&
+      hunk.newLines.length > 0 &&
+      hunk.oldLines[hunk.oldLines.length - 1] === '' &&
+      hunk.newLines[hunk.newLines.length - 1] === ''
+    ) {
+      hunk.oldLines.pop();
+      hunk.newLines.pop();
+    }
+  }
+
+  return hunks;
+}
+
+function findLines(haystack: string[], needle: string[], from: number, hint: number | undefined) {
+  const compare = [(a: string, b: string) => a === b, (a: string, b: string) => a.trimEnd() === b.trimEnd()];
+
+  for (const equals of compare) {
+    const matches: number[] = [];
+
+    for (let start = from; start <= haystack.length - needle.length; start++) {
+      let matched = true;
+
+      for (let offset = 0; offset < needle.length; offset++) {
+        if (!equals(haystack[start + offset], needle[offset])) {
+          matched = false;
+          break;
+        }
+      }
+
+      if (matched) {
+        matches.push(start);
+      }
+    }
+
+    if (matches.length > 0) {
+      return { matches, index: pickClosest(matches, hint) };
+    }
+  }
+
+  return { matches: [], index: -1 };
+}
+
+function pickClosest(matches: number[], hint: number | undefined) {
+  if (hint === undefined) {
+    return matches[0];
+  }
+
+  return matches.reduce((best, candidate) => (Math.abs(candidate - hint) < Math.abs(best - hint) ? candidate : best));
+}
+
+function applyUnifiedDiff(original: string, hunks: Hunk[]): PatchResult {
+  const result: PatchResult = {
+    format: 'unified',
+    applied: false,
+    content: original,
+    hunkCount: hunks.length,
+    rejected: [],
+  };
+
+  if (hunks.length === 0) {
+    result.rejected.push({ index: 0, header: '', reason: 'no @@ hunks found in the patch', text: '' });
+    return result;
+  }
+
+  const lines = original.split('\n');
+  const output: string[] = [];
+  let cursor = 0;
+
+  // line numbers drift once earlier hunks add or remove lines
+  let drift = 0;
+
+  hunks.forEach((hunk, index) => {
+    const hint = hunk.oldStart !== undefined ? Math.max(hunk.oldStart - 1 + drift, 0) : undefined;
+
+    if (hunk.oldLines.length === 0) {
+      // pure insertion, anchored by the header line number or appended to the end
+      const position = Math.min(Math.max(hint ?? lines.length, cursor), lines.length);
+      output.push(...lines.slice(cursor, position), ...hunk.newLines);
+      cursor = position;
+      drift += hunk.newLines.length;
+
+      return;
+    }
+
+    const { matches, index: start } = findLines(lines, hunk.oldLines, cursor, hint);
+
+    if (start === -1) {
+      result.rejected.push({
+        index,
+        header: hunk.header,
+        reason: 'context not found in the current file',
+        text: hunk.text,
+      });
+
+      return;
+    }
+
+    if (matches.length > 1 && hint === undefined) {
+      result.rejected.push({
+        index,
+        header: hunk.header,
+        reason: `context is ambiguous, it matches ${matches.length} locations`,
+        text: hunk.text,
+      });
+
+      return;
+    }
+
+    output.push(...lines.slice(cursor, start), ...hunk.newLines);
+    cursor = start + hunk.oldLines.length;
+    drift += hunk.newLines.length - hunk.oldLines.length;
+  });
+
+  if (result.rejected.length > 0) {
+    return result;
+  }
+
+  output.push(...lines.slice(cursor));
+
+  result.applied = true;
+  result.content = output.join('\n');
+
+  return result;
+}
+
+function parseSearchReplaceBlocks(patch: string): SearchReplaceBlock[] {
+  const blocks: SearchReplaceBlock[] = [];
+  let state: 'outside' | 'search' | 'replace' = 'outside';
+  let search: string[] = [];
+  let replace: string[] = [];
+  let text: string[] = [];
+
+  for (const line of patch.replace(/\r\n/g, '\n').split('\n')) {
+    if (state === 'outside') {
+      if (SEARCH_MARKER.test(line)) {
+        state = 'search';
+        search = [];
+        replace = [];
+        text = [line];
+      }
+
+      continue;
+    }
+
+    text.push(line);
+
+    if (state === 'search' && DIVIDER_MARKER.test(line)) {
+      state = 'replace';
+    } else if (state === 'replace' && REPLACE_MARKER.test(line)) {
+      blocks.push({ search: search.join('\n'), replace: replace.join('\n'), text: text.join('\n') });
+      state = 'outside';
+    } else if (state === 'search') {
+      search.push(line);
+    } else {
+      replace.push(line);
+    }
+  }
+
+  return blocks;
+}
+
+function countOccurrences(haystack: string, needle: string) {
+  let count = 0;
+  let position = haystack.indexOf(needle);
+
+  while (position !== -1) {
+    count++;
+    position = haystack.indexOf(needle, position + needle.length);
+  }
+
+  return count;
+}
+
+function applySearchReplace(original: string, blocks: SearchReplaceBlock[]): PatchResult {
+  const result: PatchResult = {
+    format: 'search-replace',
+    applied: false,
+    content: original,
+    hunkCount: blocks.length,
+    rejected: [],
+  };
+
+  let content = original;
+
+  blocks.forEach((block, index) => {
+    if (block.search.length === 0) {
+      result.rejected.push({ index, header: '', reason: 'the SEARCH section is empty', text: block.text });
+      return;
+    }
+
+    const occurrences = countOccurrences(content, block.search);
+
+    if (occurrences === 0) {
+      result.rejected.push({
+        index,
+        header: '',
+        reason: 'SEARCH text not found in the current file',
+        text: block.text,
+      });
+
+      return;
+    }
+
+    if (occurrences > 1) {
+      result.rejected.push({
+        index,
+        header: '',
+        reason: `SEARCH text is ambiguous, it matches ${occurrences} locations`,
+        text: block.text,
+      });
+
+      return;
+    }
+
+    const position = content.indexOf(block.search);
+    content = content.slice(0, position) + block.replace + content.slice(position + block.search.length);
+  });
+
+  if (blocks.length === 0) {
+    result.rejected.push({ index: 0, header: '', reason: 'no complete SEARCH/REPLACE blocks found', text: '' });
+  }
+
+  if (result.rejected.length > 0) {
+    return result;
+  }
+
+  result.applied = true;
+  result.content = content;
+
+  return result;
+}
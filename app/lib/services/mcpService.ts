@@ -1,14 +1,17 @@
 import {
-  experimental_createMCPClient,
+  dynamicTool,
+  jsonSchema,
   type ToolSet,
   type Message,
   convertToCoreMessages,
 } from 'ai';
 import { formatDataStreamPart } from '@ai-sdk/ui-utils';
-import { Experimental_StdioMCPTransport } from 'ai/mcp-stdio';
+import { Client } from '@modelcontextprotocol/sdk/client/index.js';
+import { SSEClientTransport } from '@modelcontextprotocol/sdk/client/sse.js';
+import { getDefaultEnvironment, StdioClientTransport } from '@modelcontextprotocol/sdk/client/stdio.js';
 import { StreamableHTTPClientTransport } from '@modelcontextprotocol/sdk/client/streamableHttp.js';
 import { z } from 'zod';
-import type { ToolCallAnnotation } from '~/types/context';
+import type { ToolApprover, ToolAuditAnnotation, ToolCallAnnotation } from '~/types/context';
 import {
   TOOL_EXECUTION_APPROVAL,
   TOOL_EXECUTION_DENIED,
@@ -16,9 +19,15 @@ import {
   TOOL_NO_EXECUTE_FUNCTION,
 } from '~/utils/constants';
 import { createScopedLogger } from '~/utils/logger';
+import { resolveToolPolicy, type MCPToolPolicies } from './mcpToolPolicies';
 
 const logger = createScopedLogger('mcp-service');
 
+const HEALTH_CHECK_INTERVAL = 30_000;
+const HEALTH_CHECK_TIMEOUT = 10_000;
+const MIN_RECONNECT_DELAY = 1_000;
+const MAX_RECONNECT_DELAY = 60_000;
+
 export const stdioServerConfigSchema = z
   .object({
     type: z.enum(['stdio']).optional(),
@@ -75,6 +84,9 @@ export type MCPClient = {
   close: () => Promise<void>;
 } & {
   serverName: string;
+
+  // the one connection to the server, also used for its resources and prompts
+  client: Client;
 };
 
 export type ToolCall = {
@@ -97,9 +109,46 @@ export type MCPServerUnavailable = {
   error: string;
   client: MCPClient | null;
   config: MCPServerConfig;
+
+  // set while the supervisor is trying to reconnect, `nextRetryAt` is ms since epoch
+  retry?: {
+    attempt: number;
+    nextRetryAt: number;
+  };
 };
 export type MCPServer = MCPServerAvailable | MCPServerUnavailable;
 
+export type MCPResource = {
+  uri: string;
+  name: string;
+  description?: string;
+  mimeType?: string;
+};
+
+export type MCPPromptArgument = {
+  name: string;
+  description?: string;
+  required?: boolean;
+};
+
+export type MCPPrompt = {
+  name: string;
+  description?: string;
+  arguments?: MCPPromptArgument[];
+};
+
+export type MCPServerContext = {
+  resources: MCPResource[];
+  prompts: MCPPrompt[];
+  error?: string;
+};
+
+export type MCPResourceContent = {
+  uri: string;
+  mimeType?: string;
+  text: string;
+};
+
 export type DataStreamWriter = {
   write: (chunk: string) => void;
   writeData: (data: unknown) => void;
@@ -116,6 +165,13 @@ export class MCPService {
   private _toolsWithoutExecute: ToolSet = {};
   private _mcpToolsPerServer: MCPServerTools = {};
   private _toolNamesToServerNames = new Map<string, string>();
+  private _statusListeners = new Set<(servers: MCPServerTools) => void>();
+  private _healthCheckTimer: ReturnType<typeof setInterval> | undefined;
+  private _reconnectTimers = new Map<string, ReturnType<typeof setTimeout>>();
+  private _reconnectAttempts = new Map<string, number>();
+
+  // bumped whenever the clients are recreated, reconnects scheduled for older clients are dropped
+  private _generation = 0;
   private _config: MCPConfig = {
     mcpServers: {},
   };
@@ -183,25 +239,19 @@ export class MCPService {
   ): Promise<MCPClient> {
     logger.debug(`Creating Streamable-HTTP client for ${serverName} with URL: ${config.url}`);
 
-    const client = await experimental_createMCPClient({
-      transport: new StreamableHTTPClientTransport(new URL(config.url), {
-        requestInit: {
-          headers: config.headers,
-        },
-      }),
-    });
-
-    return Object.assign(client, { serverName });
+    return this._connectClient(
+      serverName,
+      new StreamableHTTPClientTransport(new URL(config.url), { requestInit: { headers: config.headers } }),
+    );
   }
 
   private async _createSSEClient(serverName: string, config: SSEServerConfig): Promise<MCPClient> {
     logger.debug(`Creating SSE client for ${serverName} with URL: ${config.url}`);
 
-    const client = await experimental_createMCPClient({
-      transport: config,
-    });
-
-    return Object.assign(client, { serverName });
+    return this._connectClient(
+      serverName,
+      new SSEClientTransport(new URL(config.url), { requestInit: { headers: config.headers } }),
+    );
   }
 
   private async _createStdioClient(serverName: string, config: STDIOServerConfig): Promise<MCPClient> {
@@ -209,9 +259,57 @@ export class MCPService {
       `Creating STDIO client for '${serverName}' with command: '${config.command}' ${config.args?.join(' ') || ''}`,
     );
 
-    const client = await experimental_createMCPClient({ transport: new Experimental_StdioMCPTransport(config) });
+    return this._connectClient(
+      serverName,
+      new StdioClientTransport({
+        command: config.command,
+        args: config.args,
+        cwd: config.cwd,
+        env: config.env ? { ...getDefaultEnvironment(), ...config.env } : undefined,
+      }),
+    );
+  }
+
+  /**
+   * Connects a client of the MCP SDK. Tools, resources and prompts all go through this one connection, so a
+   * stdio server runs as a single process that the supervisor watches and restarts.
+   */
+  private async _connectClient(serverName: string, transport: Parameters<Client['connect']>[0]): Promise<MCPClient> {
+    const client = new Client({ name: 'smack', version: '1.0.0' });
+
+    await client.connect(transport);
+
+    return {
+      serverName,
+      client,
+      tools: () => this._listTools(client),
+      close: () => client.close(),
+    };
+  }
+
+  private async _listTools(client: Client): Promise<ToolSet> {
+    // servers that only offer resources or prompts have no tools to list
+    if (!client.getServerCapabilities()?.tools) {
+      return {};
+    }
 
-    return Object.assign(client, { serverName });
+    const { tools } = await client.listTools();
+
+    return Object.fromEntries(
+      tools.map(({ name, description, inputSchema }) => [
+        name,
+        dynamicTool({
+          description,
+          inputSchema: jsonSchema({
+            ...inputSchema,
+            properties: inputSchema.properties ?? {},
+            additionalProperties: false,
+          } as Parameters<typeof jsonSchema>[0]),
+          execute: (args, { abortSignal }) =>
+            client.callTool({ name, arguments: args as Record<string, unknown> }, undefined, { signal: abortSignal }),
+        }),
+      ]),
+    );
   }
 
   private _registerTools(serverName: string, tools: ToolSet) {
@@ -230,6 +328,16 @@ export class MCPService {
     }
   }
 
+  private _unregisterTools(serverName: string) {
+    for (const [toolName, owner] of this._toolNamesToServerNames) {
+      if (owner === serverName) {
+        delete this._tools[toolName];
+        delete this._toolsWithoutExecute[toolName];
+        this._toolNamesToServerNames.delete(toolName);
+      }
+    }
+  }
+
   private async _createMCPClient(serverName: string, serverConfig: MCPServerConfig): Promise<MCPClient> {
     const validatedConfig = this._validateServerConfig(serverName, serverConfig);
 
@@ -283,6 +391,15 @@ export class MCPService {
     });
 
     await Promise.allSettled(createClientPromises);
+
+    for (const [serverName, server] of Object.entries(this._mcpToolsPerServer)) {
+      if (server.status === 'unavailable') {
+        this._scheduleReconnect(serverName, server);
+      }
+    }
+
+    this._startHealthChecks();
+    this._notifyStatusChange();
   }
 
   async checkServersAvailabilities() {
@@ -335,10 +452,195 @@ export class MCPService {
 
     await Promise.allSettled(checkPromises);
 
+    for (const [serverName, server] of Object.entries(this._mcpToolsPerServer)) {
+      if (server.status === 'available') {
+        this._cancelReconnect(serverName);
+      } else {
+        this._scheduleReconnect(serverName, server);
+      }
+    }
+
+    this._notifyStatusChange();
+
     return this._mcpToolsPerServer;
   }
 
+  /**
+   * Subscribes to status changes of the servers, the listener is called with every server whenever one of
+   * them goes down or comes back.
+   */
+  onStatusChange(listener: (servers: MCPServerTools) => void) {
+    this._statusListeners.add(listener);
+
+    return () => {
+      this._statusListeners.delete(listener);
+    };
+  }
+
+  private _notifyStatusChange() {
+    for (const listener of this._statusListeners) {
+      try {
+        listener(this._mcpToolsPerServer);
+      } catch (error) {
+        logger.error('MCP status listener failed:', error);
+      }
+    }
+  }
+
+  private _startHealthChecks() {
+    if (this._healthCheckTimer) {
+      return;
+    }
+
+    this._healthCheckTimer = setInterval(() => this._checkHealth(), HEALTH_CHECK_INTERVAL);
+
+    // don't keep the process alive just to watch the servers
+    if (typeof this._healthCheckTimer === 'object' && 'unref' in this._healthCheckTimer) {
+      this._healthCheckTimer.unref();
+    }
+  }
+
+  /**
+   * Lists the tools of every available server, a server that doesn't answer has crashed or lost its
+   * connection and is handed to the reconnect loop.
+   */
+  private async _checkHealth() {
+    const generation = this._generation;
+    const checks = Object.entries(this._mcpToolsPerServer).map(async ([serverName, server]) => {
+      if (server.status !== 'available') {
+        return;
+      }
+
+      try {
+        const tools = await Promise.race([
+          server.client.tools(),
+          new Promise<never>((_, reject) =>
+            setTimeout(() => reject(new Error('health check timed out')), HEALTH_CHECK_TIMEOUT),
+          ),
+        ]);
+
+        if (generation === this._generation && this._mcpToolsPerServer[serverName] === server) {
+          // servers may change their tools while running
+          this._unregisterTools(serverName);
+          this._registerTools(serverName, tools);
+          this._mcpToolsPerServer[serverName] = { ...server, tools };
+        }
+      } catch (error) {
+        if (generation === this._generation && this._mcpToolsPerServer[serverName] === server) {
+          logger.warn(`MCP server "${serverName}" failed its health check:`, error);
+          await this._markUnavailable(serverName, server, (error as Error).message);
+        }
+      }
+    });
+
+    await Promise.allSettled(checks);
+  }
+
+  private async _markUnavailable(serverName: string, server: MCPServer, error: string) {
+    this._unregisterTools(serverName);
+
+    const unavailable: MCPServerUnavailable = { status: 'unavailable', error, client: null, config: server.config };
+    this._mcpToolsPerServer[serverName] = unavailable;
+    this._scheduleReconnect(serverName, unavailable);
+    this._notifyStatusChange();
+
+    // closing a stdio client kills what is left of the process, the reconnect starts a new one
+    try {
+      await server.client?.close();
+    } catch (closeError) {
+      logger.debug(`Error closing client for ${serverName}:`, closeError);
+    }
+  }
+
+  private _scheduleReconnect(serverName: string, server: MCPServerUnavailable) {
+    if (this._reconnectTimers.has(serverName)) {
+      return;
+    }
+
+    const attempt = (this._reconnectAttempts.get(serverName) ?? 0) + 1;
+    const delay = Math.min(MAX_RECONNECT_DELAY, MIN_RECONNECT_DELAY * 2 ** (attempt - 1));
+    const generation = this._generation;
+
+    this._reconnectAttempts.set(serverName, attempt);
+    server.retry = { attempt, nextRetryAt: Date.now() + delay };
+
+    logger.debug(`Reconnecting MCP server "${serverName}" in ${delay}ms (attempt ${attempt})`);
+
+    this._reconnectTimers.set(
+      serverName,
+      setTimeout(() => {
+        this._reconnectTimers.delete(serverName);
+
+        if (generation === this._generation) {
+          this._reconnect(serverName);
+        }
+      }, delay),
+    );
+  }
+
+  private _cancelReconnect(serverName: string) {
+    clearTimeout(this._reconnectTimers.get(serverName));
+    this._reconnectTimers.delete(serverName);
+    this._reconnectAttempts.delete(serverName);
+  }
+
+  private async _reconnect(serverName: string) {
+    const generation = this._generation;
+    const config = this._config.mcpServers[serverName];
+    const current = this._mcpToolsPerServer[serverName];
+
+    if (!config || current?.status !== 'unavailable') {
+      return;
+    }
+
+    let client: MCPClient | null = null;
+
+    try {
+      await current.client?.close().catch(() => undefined);
+
+      client = await this._createMCPClient(serverName, config);
+
+      const tools = await client.tools();
+
+      if (generation !== this._generation) {
+        await client.close();
+        return;
+      }
+
+      this._registerTools(serverName, tools);
+      this._mcpToolsPerServer[serverName] = { status: 'available', client, tools, config };
+      this._cancelReconnect(serverName);
+
+      logger.info(`Reconnected MCP server "${serverName}"`);
+    } catch (error) {
+      if (generation !== this._generation) {
+        return;
+      }
+
+      logger.warn(`Failed to reconnect MCP server "${serverName}":`, error);
+
+      const unavailable: MCPServerUnavailable = {
+        status: 'unavailable',
+        error: (error as Error).message,
+        client,
+        config,
+      };
+      this._mcpToolsPerServer[serverName] = unavailable;
+      this._scheduleReconnect(serverName, unavailable);
+    }
+
+    this._notifyStatusChange();
+  }
+
   private async _closeClients(): Promise<void> {
+    this._generation++;
+
+    for (const serverName of [...this._reconnectTimers.keys()]) {
+      this._cancelReconnect(serverName);
+    }
+
+    this._reconnectAttempts.clear();
+
     const closePromises = Object.entries(this._mcpToolsPerServer).map(async ([serverName, server]) => {
       if (!server.client) {
         return;
@@ -360,6 +662,77 @@ export class MCPService {
     this._toolNamesToServerNames.clear();
   }
 
+  /**
+   * Resources and prompts are read through the same client as the tools, so only servers that are currently
+   * connected can be asked for them.
+   */
+  private _getContextClient(serverName: string): Client {
+    const server = this._mcpToolsPerServer[serverName];
+
+    if (!server?.client) {
+      throw new Error(`MCP server "${serverName}" is not connected`);
+    }
+
+    return server.client.client;
+  }
+
+  async listServerContext(): Promise<Record<string, MCPServerContext>> {
+    const entries = await Promise.all(
+      Object.keys(this._config.mcpServers).map(async (serverName): Promise<[string, MCPServerContext]> => {
+        try {
+          const client = this._getContextClient(serverName);
+          const capabilities = client.getServerCapabilities();
+          const [resources, prompts] = await Promise.all([
+            capabilities?.resources ? client.listResources().then((result) => result.resources) : [],
+            capabilities?.prompts ? client.listPrompts().then((result) => result.prompts) : [],
+          ]);
+
+          return [serverName, { resources, prompts }];
+        } catch (error) {
+          logger.error(`Failed to list resources and prompts of server ${serverName}:`, error);
+          return [serverName, { resources: [], prompts: [], error: (error as Error).message }];
+        }
+      }),
+    );
+
+    return Object.fromEntries(entries);
+  }
+
+  async readResource(serverName: string, uri: string): Promise<MCPResourceContent[]> {
+    const client = this._getContextClient(serverName);
+    const { contents } = await client.readResource({ uri });
+
+    return contents.map((content) => ({
+      uri: content.uri,
+      mimeType: content.mimeType,
+
+      // binary resources can't be given to the model as text, they are described instead
+      text: typeof content.text === 'string' ? content.text : `[binary content, ${content.mimeType ?? 'unknown type'}]`,
+    }));
+  }
+
+  /**
+   * Renders a prompt of a server into the text of a chat message.
+   */
+  async getPrompt(serverName: string, name: string, args: Record<string, string> = {}): Promise<string> {
+    const client = this._getContextClient(serverName);
+    const { messages } = await client.getPrompt({ name, arguments: args });
+
+    return messages
+      .map(({ content }) => {
+        switch (content.type) {
+          case 'text':
+            return content.text;
+          case 'resource':
+            return 'text' in content.resource ? String(content.resource.text) : '';
+          default:
+            return '';
+        }
+      })
+      .filter(Boolean)
+      .join('\n\n');
+  }
+
   isValidToolName(toolName: string): boolean {
     return toolName in this._tools;
   }
@@ -383,7 +756,15 @@ export class MCPService {
     }
   }
 
-  async processToolInvocations(messages: Message[], dataStream: DataStreamWriter): Promise<Message[]> {
+  /**
+   * Settles the tool calls the user answered in the last message: approved calls are executed unless a policy
+   * denies them, and every settled call is reported to the client as a `toolAudit` annotation.
+   */
+  async processToolInvocations(
+    messages: Message[],
+    dataStream: DataStreamWriter,
+    policies?: MCPToolPolicies,
+  ): Promise<Message[]> {
     const lastMessage = messages[messages.length - 1];
     const parts = lastMessage.parts;
 
@@ -406,11 +787,19 @@ export class MCPService {
           return part;
         }
 
+        const serverName = this._toolNamesToServerNames.get(toolName) ?? 'unknown';
+        const decision = resolveToolPolicy(policies, serverName, toolName, toolInvocation.args);
+        const startTime = Date.now();
         let result;
+        let outcome: ToolAuditAnnotation['outcome'];
+        let approver: ToolApprover;
 
-        if (toolInvocation.result === TOOL_EXECUTION_APPROVAL.APPROVE) {
+        if (toolInvocation.result === TOOL_EXECUTION_APPROVAL.APPROVE && decision !== 'deny') {
           const toolInstance = this._tools[toolName];
 
+          // allowed calls are approved by the client without asking
+          approver = decision === 'allow' ? 'policy' : 'user';
+
           if (toolInstance && typeof toolInstance.execute === 'function') {
             logger.debug(`calling tool "${toolName}" with args: ${JSON.stringify(toolInvocation.args)}`);
 
@@ -419,15 +808,25 @@ export class MCPService {
                 messages: convertToCoreMessages(messages),
                 toolCallId,
               });
+              outcome = 'executed';
             } catch (error) {
               logger.error(`error while calling tool "${toolName}":`, error);
               result = TOOL_EXECUTION_ERROR;
+              outcome = 'failed';
             }
           } else {
             result = TOOL_NO_EXECUTE_FUNCTION;
+            outcome = 'failed';
           }
+        } else if (toolInvocation.result === TOOL_EXECUTION_APPROVAL.APPROVE) {
+          logger.warn(`tool "${toolName}" of "${serverName}" was approved but is denied by policy`);
+          result = TOOL_EXECUTION_DENIED;
+          outcome = 'denied';
+          approver = 'policy';
         } else if (toolInvocation.result === TOOL_EXECUTION_APPROVAL.REJECT) {
           result = TOOL_EXECUTION_DENIED;
+          outcome = 'denied';
+          approver = decision === 'deny' ? 'policy' : 'user';
         } else {
           // For any unhandled responses, return the original part.
           return part;
@@ -441,6 +840,19 @@ export class MCPService {
           }),
         );
 
+        dataStream.writeMessageAnnotation({
+          type: 'toolAudit',
+          toolCallId,
+          serverName,
+          toolName,
+          args: toolInvocation.args ?? {},
+          outcome,
+          approver,
+          resultSize: JSON.stringify(result ?? null).length,
+          duration: Date.now() - startTime,
+          timestamp: startTime,
+        } satisfies ToolAuditAnnotation);
+
         // Return updated toolInvocation with the actual result.
         return {
           ...part,
@@ -460,6 +872,10 @@ export class MCPService {
     return this._tools;
   }
 
+  get servers() {
+    return this._mcpToolsPerServer;
+  }
+
   get toolsWithoutExecute() {
     return this._toolsWithoutExecute;
   }
@@ -0,0 +1,49 @@
+import { describe, expect, it } from 'vitest';
+import { resolveToolPolicy, type MCPToolPolicies } from './mcpToolPolicies';
+
+const policies: MCPToolPolicies = {
+  filesystem: {
+    default: { mode: 'deny' },
+    tools: {
+      read_file: { mode: 'allow' },
+      write_file: { mode: 'allow-matching', argPatterns: { path: '/tmp/*' } },
+      list_directory: { mode: 'allow-matching', argPatterns: { path: '/home/?/*.md' } },
+      search_files: { mode: 'allow-matching', argPatterns: { pattern: '(a+)+' } },
+    },
+  },
+};
+
+describe('resolveToolPolicy', () => {
+  it('asks when no rule applies', () => {
+    expect(resolveToolPolicy(policies, 'github', 'create_issue')).toBe('ask');
+    expect(resolveToolPolicy(undefined, 'filesystem', 'read_file')).toBe('ask');
+  });
+
+  it('prefers tool rules over the server default', () => {
+    expect(resolveToolPolicy(policies, 'filesystem', 'read_file')).toBe('allow');
+    expect(resolveToolPolicy(policies, 'filesystem', 'delete_file')).toBe('deny');
+  });
+
+  it('only allows calls whose arguments match the whole pattern', () => {
+    expect(resolveToolPolicy(policies, 'filesystem', 'write_file', { path: '/tmp/notes.txt' })).toBe('allow');
+    expect(resolveToolPolicy(policies, 'filesystem', 'write_file', { path: '/etc/tmp/notes.txt' })).toBe('ask');
+    expect(resolveToolPolicy(policies, 'filesystem', 'write_file', {})).toBe('ask');
+  });
+
+  it('matches globs with * and ? and nothing else', () => {
+    expect(resolveToolPolicy(policies, 'filesystem', 'list_directory', { path: '/home/a/b/c.md' })).toBe('allow');
+    expect(resolveToolPolicy(policies, 'filesystem', 'list_directory', { path: '/home/ab/readme.md' })).toBe('ask');
+    expect(resolveToolPolicy(policies, 'filesystem', 'search_files', { pattern: 'aaa' })).toBe('ask');
+    expect(resolveToolPolicy(policies, 'filesystem', 'search_files', { pattern: '(a+)+' })).toBe('allow');
+  });
+
+  it('stays fast on patterns that would backtrack as regular expressions', () => {
+    const slow: MCPToolPolicies = {
+      filesystem: { tools: { write_file: { mode: 'allow-matching', argPatterns: { path: '*a*a*a*a*a*a*b' } } } },
+    };
+
+    const start = Date.now();
+    expect(resolveToolPolicy(slow, 'filesystem', 'write_file', { path: 'a'.repeat(5000) })).toBe('ask');
+    expect(Date.now() - start).toBeLessThan(1000);
+  });
+});
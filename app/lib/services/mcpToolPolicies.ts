@@ -0,0 +1,97 @@
+import { z } from 'zod';
+
+export const MAX_TOOL_PATTERN_LENGTH = 256;
+
+export const mcpToolPolicySchema = z.object({
+  mode: z.enum(['allow', 'ask', 'deny', 'allow-matching']),
+
+  // argument name to glob pattern, only used by `allow-matching`
+  argPatterns: z.record(z.string().max(MAX_TOOL_PATTERN_LENGTH)).optional(),
+});
+export type MCPToolPolicy = z.infer<typeof mcpToolPolicySchema>;
+export type MCPToolPolicyMode = MCPToolPolicy['mode'];
+
+export const mcpToolPoliciesSchema = z.record(
+  z.string(),
+  z.object({
+    default: mcpToolPolicySchema.optional(),
+    tools: z.record(z.string(), mcpToolPolicySchema).optional(),
+  }),
+);
+
+/**
+ * Approval rules per MCP server, a rule for a tool takes precedence over the server default.
+ */
+export type MCPToolPolicies = z.infer<typeof mcpToolPoliciesSchema>;
+
+export type MCPToolDecision = 'allow' | 'ask' | 'deny';
+
+/**
+ * Matches a glob where `*` stands for any run of characters and `?` for one character. Policies arrive with
+ * every chat request, so they are never compiled to regular expressions; this runs in O(pattern × value) at worst.
+ */
+function matchesPattern(pattern: string, value: unknown) {
+  const text = typeof value === 'string' ? value : JSON.stringify(value ?? '');
+  let patternIndex = 0;
+  let textIndex = 0;
+  let starIndex = -1;
+  let starTextIndex = 0;
+
+  while (textIndex < text.length) {
+    const char = pattern[patternIndex];
+
+    if (char === '*') {
+      starIndex = patternIndex++;
+      starTextIndex = textIndex;
+    } else if (char !== undefined && (char === '?' || char === text[textIndex])) {
+      patternIndex++;
+      textIndex++;
+    } else if (starIndex !== -1) {
+      // let the last star swallow one more character and retry from there
+      patternIndex = starIndex + 1;
+      textIndex = ++starTextIndex;
+    } else {
+      return false;
+    }
+  }
+
+  while (pattern[patternIndex] === '*') {
+    patternIndex++;
+  }
+
+  return patternIndex === pattern.length;
+}
+
+export function getToolPolicy(policies: MCPToolPolicies | undefined, serverName: string, toolName: string) {
+  const serverPolicies = policies?.[serverName];
+
+  return serverPolicies?.tools?.[toolName] ?? serverPolicies?.default;
+}
+
+/**
+ * Decides whether a tool call runs without asking, needs the user's approval or is refused. Calls without a
+ * matching rule are asked for, `allow-matching` only allows calls whose arguments all match their pattern.
+ */
+export function resolveToolPolicy(
+  policies: MCPToolPolicies | undefined,
+  serverName: string,
+  toolName: string,
+  args: Record<string, unknown> = {},
+): MCPToolDecision {
+  const policy = getToolPolicy(policies, serverName, toolName);
+
+  switch (policy?.mode) {
+    case 'allow':
+    case 'deny':
+    case 'ask':
+      return policy.mode;
+    case 'allow-matching': {
+      const patterns = Object.entries(policy.argPatterns ?? {});
+      const matches = patterns.every(([name, pattern]) => matchesPattern(pattern, args[name]));
+
+      return patterns.length > 0 && matches ? 'allow' : 'ask';
+    }
+    default:
+      return 'ask';
+  }
+}
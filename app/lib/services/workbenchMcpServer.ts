@@ -0,0 +1,340 @@
+import { workbenchStore } from '~/lib/stores/workbench';
+import type { ActionCallbackData } from '~/lib/runtime/message-parser';
+import { capturePreview } from '~/lib/webcontainer/preview-capture';
+import type { smackAction } from '~/types/actions';
+import { WORK_DIR } from '~/utils/constants';
+import { createScopedLogger } from '~/utils/logger';
+import { path } from '~/utils/path';
+import {
+  WORKBENCH_BRIDGE_TOKEN_HEADER,
+  type WorkbenchBridgeCall,
+  type WorkbenchBridgeResponse,
+  type WorkbenchToolName,
+} from './workbenchMcpTools';
+
+const logger = createScopedLogger('workbench-mcp-server');
+
+const MAX_RETRY_DELAY = 30_000;
+
+export type WorkbenchServerStatus = 'stopped' | 'connecting' | 'connected' | 'error';
+
+type ToolResult = {
+  content: ({ type: 'text'; text: string } | { type: 'image'; data: string; mimeType: string })[];
+  isError?: boolean;
+};
+
+type ActionInput = {
+  type: 'file' | 'patch' | 'delete' | 'rename' | 'mkdir' | 'shell' | 'start';
+  filePath?: string;
+  newFilePath?: string;
+  content?: string;
+};
+
+function text(value: string, isError = false): ToolResult {
+  return { content: [{ type: 'text', text: value }], isError: isError || undefined };
+}
+
+/**
+ * Resolves a path relative to the project root, refusing anything that points outside of it.
+ */
+function toFullPath(filePath = '') {
+  const fullPath = path.join(WORK_DIR, filePath.replace(/^\/+/, ''));
+
+  if (fullPath !== WORK_DIR && !fullPath.startsWith(WORK_DIR + '/')) {
+    throw new Error(`Path is outside of the project: ${filePath}`);
+  }
+
+  return fullPath;
+}
+
+function toRelativePath(fullPath: string) {
+  return fullPath.slice(WORK_DIR.length + 1);
+}
+
+function assertUnlocked(fullPath: string) {
+  const lock = workbenchStore.isFileLocked(fullPath);
+
+  if (lock.locked) {
+    throw new Error(`${toRelativePath(fullPath)} is locked${lock.lockedBy !== fullPath ? ` by ${lock.lockedBy}` : ''}`);
+  }
+}
+
+function waitForExecutionQueue() {
+  return new Promise<void>((resolve) => workbenchStore.addToExecutionQueue(async () => resolve()));
+}
+
+let artifactCounter = 0;
+
+/**
+ * Runs actions through the action runner of a dedicated artifact, like an artifact streamed by the chat.
+ */
+async function runActions(inputs: ActionInput[], title = 'MCP actions') {
+  const artifactId = `mcp-${Date.now()}-${++artifactCounter}`;
+
+  workbenchStore.addArtifact({ id: artifactId, messageId: artifactId, title, type: 'mcp' });
+
+  inputs.forEach((input, index) => {
+    if (input.filePath !== undefined) {
+      const fullPath = toFullPath(input.filePath);
+
+      if (input.type !== 'mkdir') {
+        assertUnlocked(fullPath);
+      }
+    }
+
+    const action = {
+      ...input,
+      content: input.content ?? '',
+      filePath: input.filePath && toRelativePath(toFullPath(input.filePath)),
+      newFilePath: input.newFilePath && toRelativePath(toFullPath(input.newFilePath)),
+    } as smackAction;
+    const data: ActionCallbackData = { artifactId, messageId: artifactId, actionId: String(index), action };
+
+    workbenchStore.addAction(data);
+    workbenchStore.runAction(data);
+  });
+
+  await waitForExecutionQueue();
+  workbenchStore.updateArtifact({ id: artifactId, messageId: artifactId, title, artifactId }, { closed: true });
+
+  const actions = workbenchStore.artifacts.get()[artifactId]?.runner.actions.get() ?? {};
+
+  return inputs.map((input, index) => {
+    const action = actions[String(index)];
+    const target = input.filePath ? ` ${input.filePath}` : ` ${input.content ?? ''}`.trimEnd();
+
+    return {
+      summary: `${input.type}${target}: ${action?.status ?? 'skipped'}`,
+      error: action?.status === 'failed' ? action.error : undefined,
+    };
+  });
+}
+
+async function callTool(name: WorkbenchToolName, args: any): Promise<ToolResult> {
+  switch (name) {
+    case 'list_files': {
+      const root = toFullPath(args?.path);
+      const entries = Object.entries(workbenchStore.files.get())
+        .filter(([filePath, dirent]) => dirent && filePath.startsWith(root + '/'))
+        .map(([filePath, dirent]) => toRelativePath(filePath) + (dirent?.type === 'folder' ? '/' : ''))
+        .sort();
+
+      return text(entries.join('\n') || '(empty)');
+    }
+    case 'read_file': {
+      const file = workbenchStore.files.get()[toFullPath(args?.path)];
+
+      if (file?.type !== 'file') {
+        return text(`File not found: ${args?.path}`, true);
+      }
+
+      return file.isBinary ? text(`${args.path} is a binary file`, true) : text(file.content);
+    }
+    case 'write_file': {
+      const [result] = await runActions([{ type: 'file', filePath: args?.path, content: args?.content ?? '' }]);
+
+      return text(result.error ?? result.summary, !!result.error);
+    }
+    case 'delete_file': {
+      const [result] = await runActions([{ type: 'delete', filePath: args?.path }]);
+
+      return text(result.error ?? result.summary, !!result.error);
+    }
+    case 'run_actions': {
+      const results = await runActions(args?.actions ?? [], args?.title);
+      const failed = results.some((result) => result.error);
+
+      return text(
+        results.map((result) => result.summary + (result.error ? `\n${result.error}` : '')).join('\n'),
+        failed,
+      );
+    }
+    case 'run_command': {
+      const shell = workbenchStore.smackTerminal;
+      await shell.ready();
+
+      // share the execution queue with the action runner so commands never interleave
+      const result = await new Promise<Awaited<ReturnType<typeof shell.executeCommand>>>((resolve, reject) => {
+        workbenchStore.addToExecutionQueue(() =>
+          shell.executeCommand(`mcp-${Date.now()}`, String(args?.command ?? '')).then(resolve, reject),
+        );
+      });
+
+      if (!result) {
+        return text('The terminal is not ready', true);
+      }
+
+      return text(`${result.output}\n\nExit code: ${result.exitCode}`, result.exitCode !== 0);
+    }
+    case 'list_previews': {
+      const previews = workbenchStore.previews.get();
+
+      return text(
+        previews.map((preview) => `${preview.port}: ${preview.baseUrl}`).join('\n') || 'No preview is running',
+      );
+    }
+    case 'screenshot_preview': {
+      const previews = workbenchStore.previews.get();
+      const preview = args?.port ? previews.find((p) => p.port === args.port) : previews[0];
+
+      if (!preview) {
+        return text(args?.port ? `No preview on port ${args.port}` : 'No preview is running', true);
+      }
+
+      const dataUrl = await capturePreview(new URL(args?.path || '/', preview.baseUrl).href, {
+        width: args?.width,
+        height: args?.height,
+      });
+
+      return {
+        content: [{ type: 'image', data: dataUrl.replace(/^data:image\/png;base64,/, ''), mimeType: 'image/png' }],
+      };
+    }
+  }
+}
+
+function listResources() {
+  const resources = Object.entries(workbenchStore.files.get())
+    .filter(([, dirent]) => dirent?.type === 'file' && !dirent.isBinary)
+    .map(([filePath]) => ({ uri: `file://${filePath}`, name: toRelativePath(filePath), mimeType: 'text/plain' }));
+
+  return { resources };
+}
+
+function readResource(uri: string) {
+  const filePath = toFullPath(toRelativePath(decodeURI(new URL(uri).pathname)));
+  const file = workbenchStore.files.get()[filePath];
+
+  if (file?.type !== 'file' || file.isBinary) {
+    throw new Error(`Resource not found: ${uri}`);
+  }
+
+  return { contents: [{ uri, mimeType: 'text/plain', text: file.content }] };
+}
+
+async function handleCall(call: WorkbenchBridgeCall): Promise<unknown> {
+  switch (call.method) {
+    case 'tools/call':
+      try {
+        return await callTool(call.params.name, call.params.arguments);
+      } catch (error) {
+        return text(error instanceof Error ? error.message : String(error), true);
+      }
+    case 'resources/list':
+      return listResources();
+    case 'resources/read':
+      return readResource(call.params?.uri);
+  }
+}
+
+/**
+ * Asks the server whether it can relay calls, the bridge answers 501 on deployments without shared memory.
+ */
+export async function isWorkbenchServerSupported() {
+  try {
+    const response = await fetch('/api/mcp-bridge', { method: 'GET' });
+    return response.status !== 501;
+  } catch {
+    return true;
+  }
+}
+
+/**
+ * Shares the workbench with external MCP clients: long-polls the server for the calls they make against
+ * `/api/mcp-workbench` and answers them from this tab.
+ */
+export class WorkbenchMcpServer {
+  #controller: AbortController | undefined;
+  #status: WorkbenchServerStatus = 'stopped';
+
+  constructor(private _onStatusChange: (status: WorkbenchServerStatus) => void) {}
+
+  get status() {
+    return this.#status;
+  }
+
+  start(token: string) {
+    this.stop();
+
+    const controller = new AbortController();
+    this.#controller = controller;
+    this.#setStatus('connecting');
+    this.#poll(token, controller.signal);
+  }
+
+  stop() {
+    this.#controller?.abort();
+    this.#controller = undefined;
+    this.#setStatus('stopped');
+  }
+
+  #setStatus(status: WorkbenchServerStatus) {
+    if (this.#status !== status) {
+      this.#status = status;
+      this._onStatusChange(status);
+    }
+  }
+
+  async #poll(token: string, signal: AbortSignal) {
+    let failures = 0;
+
+    while (!signal.aborted) {
+      try {
+        const response = await fetch('/api/mcp-bridge', {
+          headers: { [WORKBENCH_BRIDGE_TOKEN_HEADER]: token },
+          signal,
+        });
+
+        if (response.status === 501) {
+          logger.warn('The server cannot share the workbench over MCP');
+          this.#setStatus('stopped');
+
+          return;
+        }
+
+        if (!response.ok) {
+          throw new Error(`Server responded with ${response.status}: ${response.statusText}`);
+        }
+
+        const { calls } = (await response.json()) as { calls: WorkbenchBridgeCall[] };
+
+        failures = 0;
+        this.#setStatus('connected');
+
+        for (const call of calls) {
+          this.#answer(token, call);
+        }
+      } catch (error) {
+        if (signal.aborted) {
+          return;
+        }
+
+        failures++;
+        logger.warn('Workbench MCP bridge poll failed:', error);
+        this.#setStatus('error');
+
+        await new Promise((resolve) => setTimeout(resolve, Math.min(MAX_RETRY_DELAY, 1000 * 2 ** failures)));
+      }
+    }
+  }
+
+  async #answer(token: string, call: WorkbenchBridgeCall) {
+    let response: WorkbenchBridgeResponse;
+
+    try {
+      response = { id: call.id, result: await handleCall(call) };
+    } catch (error) {
+      response = { id: call.id, error: error instanceof Error ? error.message : String(error) };
+    }
+
+    try {
+      await fetch('/api/mcp-bridge', {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json', [WORKBENCH_BRIDGE_TOKEN_HEADER]: token },
+        body: JSON.stringify(response),
+      });
+    } catch (error) {
+      logger.error(`Failed to answer ${call.method}:`, error);
+    }
+  }
+}
@@ -0,0 +1,120 @@
+/**
+ * Tools the workbench exposes to external MCP clients. The server endpoint lists them, the browser tab
+ * that owns the workspace executes them, see `workbenchMcpServer.ts`.
+ */
+export const WORKBENCH_MCP_TOOLS = [
+  {
+    name: 'list_files',
+    description: 'Lists the files and folders of the project, folders end with a slash.',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        path: { type: 'string', description: 'Folder relative to the project root, defaults to the root' },
+      },
+    },
+  },
+  {
+    name: 'read_file',
+    description: 'Reads a text file of the project.',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        path: { type: 'string', description: 'File path relative to the project root' },
+      },
+      required: ['path'],
+    },
+  },
+  {
+    name: 'write_file',
+    description: 'Creates or overwrites a text file of the project, locked files are refused.',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        path: { type: 'string', description: 'File path relative to the project root' },
+        content: { type: 'string', description: 'The complete new content of the file' },
+      },
+      required: ['path', 'content'],
+    },
+  },
+  {
+    name: 'delete_file',
+    description: 'Deletes a file or folder of the project, locked files are refused.',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        path: { type: 'string', description: 'Path relative to the project root' },
+      },
+      required: ['path'],
+    },
+  },
+  {
+    name: 'run_command',
+    description: 'Runs a shell command in the workspace terminal and returns its output and exit code.',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        command: { type: 'string', description: 'The command to run, e.g. `npm test`' },
+      },
+      required: ['command'],
+    },
+  },
+  {
+    name: 'run_actions',
+    description:
+      'Runs actions through the action runner, the same way an artifact from the chat does. Supported types ' +
+      'are file, patch, delete, rename, mkdir, shell and start; `start` keeps a dev server running.',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        title: { type: 'string', description: 'Title of the artifact shown in the workbench' },
+        actions: {
+          type: 'array',
+          items: {
+            type: 'object',
+            properties: {
+              type: { type: 'string', enum: ['file', 'patch', 'delete', 'rename', 'mkdir', 'shell', 'start'] },
+              filePath: { type: 'string' },
+              newFilePath: { type: 'string', description: 'Destination of a rename' },
+              content: { type: 'string', description: 'File content, unified diff or command' },
+            },
+            required: ['type'],
+          },
+        },
+      },
+      required: ['actions'],
+    },
+  },
+  {
+    name: 'list_previews',
+    description: 'Lists the ports of the dev servers running in the workspace.',
+    inputSchema: { type: 'object', properties: {} },
+  },
+  {
+    name: 'screenshot_preview',
+    description: 'Takes a PNG screenshot of a running preview.',
+    inputSchema: {
+      type: 'object',
+      properties: {
+        port: { type: 'number', description: 'Port of the preview, defaults to the first one' },
+        path: { type: 'string', description: 'Path to open, defaults to /' },
+        width: { type: 'number', description: 'Viewport width in pixels, defaults to 1280' },
+        height: { type: 'number', description: 'Viewport height in pixels, defaults to 800' },
+      },
+    },
+  },
+] as const;
+
+export type WorkbenchToolName = (typeof WORKBENCH_MCP_TOOLS)[number]['name'];
+
+/**
+ * A request the server relays to the browser tab, `params` are the params of the MCP request.
+ */
+export interface WorkbenchBridgeCall {
+  id: string;
+  method: 'tools/call' | 'resources/list' | 'resources/read';
+  params: any;
+}
+
+export type WorkbenchBridgeResponse = { id: string; result: unknown } | { id: string; error: string };
+
+export const WORKBENCH_BRIDGE_TOKEN_HEADER = 'x-workbench-token';
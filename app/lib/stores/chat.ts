@@ -1,7 +1,17 @@
 import { map } from 'nanostores';
+import type { ProviderInfo } from '~/types/model';
+import { DEFAULT_MODEL, DEFAULT_PROVIDER } from '~/utils/constants';
 
 export const chatStore = map({
   started: false,
   aborted: false,
   showChat: true,
 });
+
+/**
+ * The model picked in the chat, for features outside the chat that call the LLM.
+ */
+export const chatModelStore = map<{ model: string; provider: ProviderInfo }>({
+  model: DEFAULT_MODEL,
+  provider: DEFAULT_PROVIDER,
+});
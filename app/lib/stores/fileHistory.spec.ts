@@ -0,0 +1,95 @@
+import 'fake-indexeddb/auto';
+import { describe, expect, it, vi } from 'vitest';
+import { chatId, db, getFileVersions } from '~/lib/persistence';
+import { fileHistoryStore } from './fileHistory';
+
+vi.mock('~/lib/persistence', async () => {
+  const { atom } = await import('nanostores');
+  const persistence = await import('~/lib/persistence/db');
+
+  return { ...persistence, db: await persistence.openDatabase(), chatId: atom<string | undefined>(undefined) };
+});
+
+const FILE_PATH = '/home/project/src/index.ts';
+
+let chatCount = 0;
+
+function openChat() {
+  const id = `chat-${++chatCount}`;
+  chatId.set(id);
+
+  return id;
+}
+
+function contentsInMemory() {
+  return (fileHistoryStore.versions.get()[FILE_PATH] ?? []).map((version) => version.content);
+}
+
+async function contentsInDatabase(id: string) {
+  return (await getFileVersions(db!, id, FILE_PATH)).map((version) => version.content);
+}
+
+describe('fileHistoryStore', () => {
+  it('records the original content before the first write', async () => {
+    openChat();
+
+    await fileHistoryStore.record(FILE_PATH, 'updated', 'llm', 'initial');
+
+    const versions = fileHistoryStore.versions.get()[FILE_PATH];
+    expect(versions.map(({ source, content }) => [source, content])).toEqual([
+      ['original', 'initial'],
+      ['llm', 'updated'],
+    ]);
+  });
+
+  it('skips writes that leave the file unchanged', async () => {
+    openChat();
+
+    await fileHistoryStore.record(FILE_PATH, 'a', 'user');
+    await fileHistoryStore.record(FILE_PATH, 'a', 'llm');
+
+    expect(contentsInMemory()).toEqual(['a']);
+  });
+
+  it('keeps only the latest 50 versions of a file', async () => {
+    const id = openChat();
+
+    for (let i = 0; i < 55; i++) {
+      await fileHistoryStore.record(FILE_PATH, `v${i}`, 'user');
+    }
+
+    const expected = Array.from({ length: 50 }, (_, i) => `v${i + 5}`);
+
+    expect(contentsInMemory()).toEqual(expected);
+    expect(await contentsInDatabase(id)).toEqual(expected);
+  });
+
+  it('restores stored versions oldest first when the chat is reopened', async () => {
+    const id = openChat();
+
+    await fileHistoryStore.record(FILE_PATH, 'v1', 'llm');
+    await fileHistoryStore.record(FILE_PATH, 'v2', 'user');
+
+    openChat();
+    chatId.set(id);
+
+    expect(contentsInMemory()).toEqual([]);
+
+    await fileHistoryStore.load(FILE_PATH);
+    await fileHistoryStore.record(FILE_PATH, 'v3', 'llm');
+
+    expect(contentsInMemory()).toEqual(['v1', 'v2', 'v3']);
+    expect(await contentsInDatabase(id)).toEqual(['v1', 'v2', 'v3']);
+  });
+
+  it('persists versions recorded before the chat had an id', async () => {
+    chatId.set(undefined);
+
+    await fileHistoryStore.record(FILE_PATH, 'draft', 'llm');
+
+    const id = openChat();
+
+    expect(contentsInMemory()).toEqual(['draft']);
+    await vi.waitFor(async () => expect(await contentsInDatabase(id)).toEqual(['draft']));
+  });
+});
@@ -0,0 +1,132 @@
+import { map } from 'nanostores';
+import { addFileVersion, chatId, db, deleteFileVersions, getFileVersions } from '~/lib/persistence';
+import type { FileVersion, FileVersionSource } from '~/lib/persistence/types';
+import { createScopedLogger } from '~/utils/logger';
+
+const logger = createScopedLogger('FileHistoryStore');
+
+const MAX_VERSIONS_PER_FILE = 50;
+
+/**
+ * Keeps every LLM and user write of a file as a version, persisted per chat in IndexedDB.
+ *
+ * Writes that happen before the chat has an id (the first response of a new chat) are buffered and
+ * flushed as soon as the id is allocated.
+ */
+class FileHistoryStore {
+  versions = map<Record<string, FileVersion[]>>({});
+
+  #chatId: string | undefined;
+  #pending: FileVersion[] = [];
+  #loaded = new Set<string>();
+
+  constructor() {
+    chatId.subscribe((id) => {
+      if (id === this.#chatId) {
+        return;
+      }
+
+      const previousChatId = this.#chatId;
+      this.#chatId = id;
+
+      if (previousChatId === undefined && id) {
+        // the chat was just created, the buffered versions belong to it
+        this.#flushPending(id);
+        return;
+      }
+
+      this.#pending = [];
+      this.#loaded.clear();
+      this.versions.set({});
+    });
+  }
+
+  async record(filePath: string, content: string, source: FileVersionSource, previousContent?: string) {
+    const versions = await this.load(filePath);
+
+    if (versions.length === 0 && previousContent !== undefined && previousContent !== content) {
+      await this.#add(filePath, previousContent, 'original');
+    }
+
+    const current = this.versions.get()[filePath] ?? [];
+
+    if (current[current.length - 1]?.content === content) {
+      return;
+    }
+
+    await this.#add(filePath, content, source);
+  }
+
+  /**
+   * Loads the versions of a file from IndexedDB once, later calls are served from memory.
+   */
+  async load(filePath: string) {
+    const chatIdValue = this.#chatId;
+
+    if (!chatIdValue || !db || this.#loaded.has(filePath)) {
+      return this.versions.get()[filePath] ?? [];
+    }
+
+    try {
+      const stored = await getFileVersions(db, chatIdValue, filePath);
+
+      // keep versions recorded while the request was in flight
+      const inMemory = (this.versions.get()[filePath] ?? []).filter((version) => version.id === undefined);
+
+      this.#loaded.add(filePath);
+      this.versions.setKey(filePath, [...stored, ...inMemory].slice(-MAX_VERSIONS_PER_FILE));
+    } catch (error) {
+      logger.error('Failed to load file versions', error);
+    }
+
+    return this.versions.get()[filePath] ?? [];
+  }
+
+  async #add(filePath: string, content: string, source: FileVersionSource) {
+    const version: FileVersion = {
+      chatId: this.#chatId ?? '',
+      filePath,
+      content,
+      timestamp: Date.now(),
+      source,
+    };
+
+    this.versions.setKey(filePath, [...(this.versions.get()[filePath] ?? []), version].slice(-MAX_VERSIONS_PER_FILE));
+
+    if (!this.#chatId || !db) {
+      this.#pending.push(version);
+      return;
+    }
+
+    await this.#persist(version);
+  }
+
+  async #persist(version: FileVersion) {
+    if (!db) {
+      return;
+    }
+
+    try {
+      version.id = await addFileVersion(db, version);
+
+      const stored = await getFileVersions(db, version.chatId, version.filePath);
+      const expired = stored.slice(0, Math.max(stored.length - MAX_VERSIONS_PER_FILE, 0));
+
+      await deleteFileVersions(db, expired.map((entry) => entry.id!));
+    } catch (error) {
+      logger.error('Failed to persist file version', error);
+    }
+  }
+
+  async #flushPending(id: string) {
+    const pending = this.#pending;
+    this.#pending = [];
+
+    for (const version of pending) {
+      version.chatId = id;
+      await this.#persist(version);
+    }
+  }
+}
+
+export const fileHistoryStore = new FileHistoryStore();
@@ -782,6 +782,8 @@ export class FilesStore {
         await webcontainer.fs.mkdir(dirPath, { recursive: true });
       }
 
+      this.#restoreDeletedPath(filePath);
+
       const isBinary = content instanceof Uint8Array;
 
       if (isBinary) {
@@ -831,6 +833,7 @@ export class FilesStore {
 
       await webcontainer.fs.mkdir(relativePath, { recursive: true });
 
+      this.#restoreDeletedPath(folderPath);
       this.files.setKey(folderPath, { type: 'folder' });
 
       logger.info(`Folder created: ${folderPath}`);
@@ -919,6 +922,13 @@ export class FilesStore {
     }
   }
 
+  // recreating a previously deleted path must stop it from being cleaned up on the next load
+  #restoreDeletedPath(filePath: string) {
+    if (this.#deletedPaths.delete(filePath)) {
+      this.#persistDeletedPaths();
+    }
+  }
+
   // method to persist deleted paths to localStorage
   #persistDeletedPaths() {
     try {
@@ -23,7 +23,8 @@ export interface LogEntry {
     | 'settings'
     | 'task'
     | 'update'
-    | 'feature';
+    | 'feature'
+    | 'mcp';
   subCategory?: string;
   duration?: number;
   statusCode?: number;
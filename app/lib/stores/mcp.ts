@@ -1,5 +1,7 @@
 import { create } from 'zustand';
-import type { MCPConfig, MCPServerTools } from '~/lib/services/mcpService';
+import type { MCPConfig, MCPResourceContent, MCPServerContext, MCPServerTools } from '~/lib/services/mcpService';
+import type { MCPToolPolicies } from '~/lib/services/mcpToolPolicies';
+import type { WorkbenchMcpServer, WorkbenchServerStatus } from '~/lib/services/workbenchMcpServer';
 
 const MCP_SETTINGS_KEY = 'mcp_settings';
 const isBrowser = typeof window !== 'undefined';
@@ -7,6 +9,11 @@ const isBrowser = typeof window !== 'undefined';
 type MCPSettings = {
   mcpConfig: MCPConfig;
   maxLLMSteps: number;
+  toolPolicies?: MCPToolPolicies;
+  workbenchServer?: {
+    enabled: boolean;
+    token: string;
+  };
 };
 
 const defaultSettings = {
@@ -20,22 +27,44 @@ type Store = {
   isInitialized: boolean;
   settings: MCPSettings;
   serverTools: MCPServerTools;
+  serverContext: Record<string, MCPServerContext>;
   error: string | null;
   isUpdatingConfig: boolean;
+  workbenchServerStatus: WorkbenchServerStatus;
 };
 
 type Actions = {
   initialize: () => Promise<void>;
   updateSettings: (settings: MCPSettings) => Promise<void>;
   checkServersAvailabilities: () => Promise<void>;
+  loadServerContext: () => Promise<void>;
+  readResource: (serverName: string, uri: string) => Promise<MCPResourceContent[]>;
+  getPrompt: (serverName: string, name: string, args?: Record<string, string>) => Promise<string>;
+  setWorkbenchServerEnabled: (enabled: boolean) => Promise<void>;
+  updateToolPolicies: (toolPolicies: MCPToolPolicies) => void;
 };
 
+let workbenchServer: WorkbenchMcpServer | undefined;
+let statusSource: EventSource | undefined;
+
+// the workbench server pulls in the workbench store, so it is only loaded once it is enabled
+async function getWorkbenchServer(onStatusChange: (status: WorkbenchServerStatus) => void) {
+  if (!workbenchServer) {
+    const { WorkbenchMcpServer } = await import('~/lib/services/workbenchMcpServer');
+    workbenchServer = new WorkbenchMcpServer(onStatusChange);
+  }
+
+  return workbenchServer;
+}
+
 export const useMCPStore = create<Store & Actions>((set, get) => ({
   isInitialized: false,
   settings: defaultSettings,
   serverTools: {},
+  serverContext: {},
   error: null,
   isUpdatingConfig: false,
+  workbenchServerStatus: 'stopped',
   initialize: async () => {
     if (get().isInitialized) {
       return;
@@ -61,6 +90,25 @@ export const useMCPStore = create<Store & Actions>((set, get) => ({
     }
 
     set(() => ({ isInitialized: true }));
+
+    if (isBrowser && !statusSource) {
+      // the server reconnects crashed servers on its own, follow along so the lists show live state
+      statusSource = new EventSource('/api/mcp-status');
+      statusSource.onmessage = (event) => {
+        try {
+          set(() => ({ serverTools: JSON.parse(event.data) as MCPServerTools }));
+        } catch (error) {
+          console.error('Error parsing mcp status:', error);
+        }
+      };
+    }
+
+    const { workbenchServer: workbenchServerSettings } = get().settings;
+
+    if (workbenchServerSettings?.enabled) {
+      const server = await getWorkbenchServer((status) => set(() => ({ workbenchServerStatus: status })));
+      server.start(workbenchServerSettings.token);
+    }
   },
   updateSettings: async (newSettings: MCPSettings) => {
     if (get().isUpdatingConfig) {
@@ -71,12 +119,13 @@ export const useMCPStore = create<Store & Actions>((set, get) => ({
       set(() => ({ isUpdatingConfig: true }));
 
       const serverTools = await updateServerConfig(newSettings.mcpConfig);
+      const settings = { ...get().settings, ...newSettings };
 
       if (isBrowser) {
-        localStorage.setItem(MCP_SETTINGS_KEY, JSON.stringify(newSettings));
+        localStorage.setItem(MCP_SETTINGS_KEY, JSON.stringify(settings));
       }
 
-      set(() => ({ settings: newSettings, serverTools }));
+      set(() => ({ settings, serverTools }));
     } catch (error) {
       throw error;
     } finally {
@@ -96,6 +145,70 @@ export const useMCPStore = create<Store & Actions>((set, get) => ({
 
     set(() => ({ serverTools }));
   },
+  loadServerContext: async () => {
+    const response = await fetch('/api/mcp-context');
+
+    if (!response.ok) {
+      throw new Error(`Server responded with ${response.status}: ${response.statusText}`);
+    }
+
+    const serverContext = (await response.json()) as Record<string, MCPServerContext>;
+
+    set(() => ({ serverContext }));
+  },
+  readResource: async (serverName: string, uri: string) => {
+    const { contents } = await requestServerContext<{ contents: MCPResourceContent[] }>({
+      type: 'resource',
+      serverName,
+      uri,
+    });
+
+    return contents;
+  },
+  getPrompt: async (serverName: string, name: string, args?: Record<string, string>) => {
+    const { text } = await requestServerContext<{ text: string }>({
+      type: 'prompt',
+      serverName,
+      name,
+      arguments: args,
+    });
+
+    return text;
+  },
+  updateToolPolicies: (toolPolicies: MCPToolPolicies) => {
+    // policies are sent along with every chat request, the servers don't need to be reconfigured
+    const settings = { ...get().settings, toolPolicies };
+
+    if (isBrowser) {
+      localStorage.setItem(MCP_SETTINGS_KEY, JSON.stringify(settings));
+    }
+
+    set(() => ({ settings }));
+  },
+  setWorkbenchServerEnabled: async (enabled: boolean) => {
+    const current = get().settings;
+    const workbenchServerSettings = {
+      enabled,
+
+      // the token is what MCP clients authenticate with, keep it stable across sessions
+      token: current.workbenchServer?.token ?? (crypto.randomUUID() + crypto.randomUUID()).replace(/-/g, ''),
+    };
+    const settings = { ...current, workbenchServer: workbenchServerSettings };
+
+    if (isBrowser) {
+      localStorage.setItem(MCP_SETTINGS_KEY, JSON.stringify(settings));
+    }
+
+    set(() => ({ settings }));
+
+    const server = await getWorkbenchServer((status) => set(() => ({ workbenchServerStatus: status })));
+
+    if (enabled) {
+      server.start(workbenchServerSettings.token);
+    } else {
+      server.stop();
+    }
+  },
 }));
 
 async function updateServerConfig(config: MCPConfig) {
@@ -113,3 +226,18 @@ async function updateServerConfig(config: MCPConfig) {
 
   return data;
 }
+
+async function requestServerContext<T>(body: Record<string, unknown>) {
+  const response = await fetch('/api/mcp-context', {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(body),
+  });
+
+  if (!response.ok) {
+    const { error } = (await response.json().catch(() => ({}))) as { error?: string };
+    throw new Error(error ?? `Server responded with ${response.status}: ${response.statusText}`);
+  }
+
+  return (await response.json()) as T;
+}
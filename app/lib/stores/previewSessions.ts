@@ -0,0 +1,119 @@
+import { atom } from 'nanostores';
+
+export interface DevicePreset {
+  // name of one of the window sizes of the preview
+  device: string;
+  width: number;
+  height: number;
+  landscape: boolean;
+  enabled: boolean;
+}
+
+export interface PreviewSession {
+  id: string;
+  name: string;
+  port: number;
+  path: string;
+  preset?: DevicePreset;
+}
+
+const PORT_LABELS_KEY = 'smack_preview_port_labels';
+const PORT_PRESETS_KEY = 'smack_preview_port_presets';
+const SESSIONS_KEY = 'smack_preview_sessions';
+
+// labels of the usual dev server ports until users name them
+const KNOWN_PORTS: Record<number, string> = {
+  3000: 'frontend',
+  4173: 'frontend',
+  4200: 'frontend',
+  5173: 'frontend',
+  5174: 'frontend',
+  8080: 'frontend',
+  3001: 'api',
+  4000: 'api',
+  5000: 'api',
+  8000: 'api',
+  8787: 'api',
+  6006: 'storybook',
+};
+
+function load<T>(key: string, fallback: T): T {
+  const stored = typeof window !== 'undefined' ? localStorage.getItem(key) : null;
+
+  try {
+    return stored ? JSON.parse(stored) : fallback;
+  } catch {
+    return fallback;
+  }
+}
+
+function save(key: string, value: unknown) {
+  if (typeof window !== 'undefined') {
+    localStorage.setItem(key, JSON.stringify(value));
+  }
+}
+
+export const portLabelsStore = atom<Record<number, string>>(load(PORT_LABELS_KEY, {}));
+export const portPresetsStore = atom<Record<number, DevicePreset>>(load(PORT_PRESETS_KEY, {}));
+export const previewSessionsStore = atom<PreviewSession[]>(load(SESSIONS_KEY, []));
+
+export function getPortLabel(port: number, labels = portLabelsStore.get()): string | undefined {
+  return labels[port] ?? KNOWN_PORTS[port];
+}
+
+export function setPortLabel(port: number, label: string) {
+  const labels = { ...portLabelsStore.get() };
+
+  // clearing a label brings back the default one
+  if (label.trim()) {
+    labels[port] = label.trim();
+  } else {
+    delete labels[port];
+  }
+
+  portLabelsStore.set(labels);
+  save(PORT_LABELS_KEY, portLabelsStore.get());
+}
+
+export function setPortPreset(port: number, preset: DevicePreset) {
+  portPresetsStore.set({ ...portPresetsStore.get(), [port]: preset });
+  save(PORT_PRESETS_KEY, portPresetsStore.get());
+}
+
+export function savePreviewSession(session: Omit<PreviewSession, 'id'>) {
+  const existing = previewSessionsStore.get().find((item) => item.name === session.name);
+  const saved = { ...session, id: existing?.id ?? Math.random().toString(36).slice(2) };
+
+  previewSessionsStore.set([...previewSessionsStore.get().filter((item) => item.id !== saved.id), saved]);
+  save(SESSIONS_KEY, previewSessionsStore.get());
+
+  return saved;
+}
+
+export function removePreviewSession(id: string) {
+  previewSessionsStore.set(previewSessionsStore.get().filter((session) => session.id !== id));
+  save(SESSIONS_KEY, previewSessionsStore.get());
+}
+
+/**
+ * The address of the standalone preview page of a session, other tabs open it on the same path and viewport and
+ * reload it with the editor. Undefined for previews that are not served by WebContainer.
+ */
+export function getSessionUrl(baseUrl: string, session: Pick<PreviewSession, 'name' | 'path' | 'preset'>) {
+  const match = baseUrl.match(/^https?:\/\/([^.]+)\.local-credentialless\.webcontainer-api\.io/);
+
+  if (!match) {
+    return undefined;
+  }
+
+  const params = new URLSearchParams({ name: session.name, path: session.path });
+
+  if (session.preset?.enabled) {
+    const { width, height, landscape } = session.preset;
+
+    params.set('width', String(landscape ? height : width));
+    params.set('height', String(landscape ? width : height));
+  }
+
+  return `${window.location.origin}/webcontainer/preview/${match[1]}?${params}`;
+}
@@ -0,0 +1,184 @@
+import { map } from 'nanostores';
+import { z } from 'zod';
+import { db, deleteCustomPrompt as deleteStoredPrompt, getCustomPrompts, putCustomPrompts } from '~/lib/persistence';
+import { PromptLibrary, type CustomPrompt } from '~/lib/common/prompt-library';
+import { promptStore, updatePromptId } from './settings';
+import { createScopedLogger } from '~/utils/logger';
+
+const logger = createScopedLogger('PromptsStore');
+
+const EXPORT_TYPE = 'smack-prompts';
+
+const customPromptSchema = z.object({
+  id: z.string().optional(),
+  label: z.string().min(1),
+  description: z.string().default(''),
+  template: z.string().min(1),
+  version: z.number().int().positive().default(1),
+  history: z
+    .array(z.object({ version: z.number().int().positive(), template: z.string(), updatedAt: z.string() }))
+    .default([]),
+  createdAt: z.string().optional(),
+  updatedAt: z.string().optional(),
+});
+
+const promptExportSchema = z.object({
+  type: z.literal(EXPORT_TYPE),
+  prompts: z.array(customPromptSchema),
+});
+
+/**
+ * Imported ids are only kept when they cannot shadow a built-in prompt of the library.
+ */
+function isCustomPromptId(id: string | undefined): id is string {
+  return !!id && id.startsWith('custom-') && !Object.hasOwn(PromptLibrary.library, id);
+}
+
+/**
+ * Custom system prompts by id, loaded from IndexedDB.
+ */
+export const customPromptsStore = map<Record<string, CustomPrompt>>({});
+
+let loading: Promise<void> | undefined;
+
+export function loadCustomPrompts() {
+  if (!db) {
+    return Promise.resolve();
+  }
+
+  loading ??= getCustomPrompts(db)
+    .then((prompts) => {
+      customPromptsStore.set(Object.fromEntries(prompts.map((prompt) => [prompt.id, prompt])));
+    })
+    .catch((error) => {
+      logger.error('Failed to load custom prompts', error);
+      loading = undefined;
+    });
+
+  return loading;
+}
+
+async function persist(prompts: CustomPrompt[]) {
+  if (db) {
+    await putCustomPrompts(db, prompts);
+  }
+
+  for (const prompt of prompts) {
+    customPromptsStore.setKey(prompt.id, prompt);
+  }
+}
+
+/**
+ * Creates a prompt, or updates it when `id` is given. A changed template becomes a new version and the previous one
+ * is kept in the history.
+ */
+export async function saveCustomPrompt(input: { id?: string; label: string; description: string; template: string }) {
+  const now = new Date().toISOString();
+  const existing = input.id ? customPromptsStore.get()[input.id] : undefined;
+
+  if (!existing) {
+    const prompt: CustomPrompt = {
+      id: `custom-${crypto.randomUUID()}`,
+      label: input.label,
+      description: input.description,
+      template: input.template,
+      version: 1,
+      history: [],
+      createdAt: now,
+      updatedAt: now,
+    };
+    await persist([prompt]);
+
+    return prompt;
+  }
+
+  const templateChanged = existing.template !== input.template;
+  const prompt: CustomPrompt = {
+    ...existing,
+    label: input.label,
+    description: input.description,
+    template: input.template,
+    version: templateChanged ? existing.version + 1 : existing.version,
+    history: templateChanged
+      ? [...existing.history, { version: existing.version, template: existing.template, updatedAt: existing.updatedAt }]
+      : existing.history,
+    updatedAt: now,
+  };
+  await persist([prompt]);
+
+  return prompt;
+}
+
+/**
+ * Makes an earlier version current again, as a new version so that no history is lost.
+ */
+export async function restorePromptVersion(id: string, version: number) {
+  const prompt = customPromptsStore.get()[id];
+  const restored = prompt?.history.find((entry) => entry.version === version);
+
+  if (!prompt || !restored) {
+    throw new Error(`Version ${version} of the prompt was not found`);
+  }
+
+  return saveCustomPrompt({ ...prompt, template: restored.template });
+}
+
+export async function deleteCustomPrompt(id: string) {
+  if (db) {
+    await deleteStoredPrompt(db, id);
+  }
+
+  const { [id]: _deleted, ...rest } = customPromptsStore.get();
+  customPromptsStore.set(rest);
+
+  if (promptStore.get() === id) {
+    updatePromptId('default');
+  }
+}
+
+/**
+ * Serializes prompts to share them with a team, all prompts when no ids are given.
+ */
+export function exportCustomPrompts(ids?: string[]) {
+  const prompts = Object.values(customPromptsStore.get()).filter((prompt) => !ids || ids.includes(prompt.id));
+
+  return JSON.stringify({ type: EXPORT_TYPE, prompts }, null, 2);
+}
+
+/**
+ * Imports prompts exported with `exportCustomPrompts`. Prompts that already exist become a new version of the
+ * existing prompt when their template differs, the others are added. Prompts whose id is not a custom prompt id get
+ * a new one.
+ */
+export async function importCustomPrompts(json: string) {
+  const parsed = promptExportSchema.safeParse(JSON.parse(json));
+
+  if (!parsed.success) {
+    throw new Error('The file does not contain exported prompts');
+  }
+
+  const now = new Date().toISOString();
+  const existingPrompts = customPromptsStore.get();
+  const imported: CustomPrompt[] = [];
+
+  for (const { id, ...prompt } of parsed.data.prompts) {
+    const promptId = isCustomPromptId(id) ? id : `custom-${crypto.randomUUID()}`;
+    const existing = existingPrompts[promptId];
+
+    if (existing) {
+      imported.push(await saveCustomPrompt({ ...prompt, id: existing.id }));
+      continue;
+    }
+
+    const created: CustomPrompt = {
+      ...prompt,
+      id: promptId,
+      createdAt: prompt.createdAt ?? now,
+      updatedAt: prompt.updatedAt ?? now,
+    };
+    await persist([created]);
+    imported.push(created);
+  }
+
+  return imported;
+}
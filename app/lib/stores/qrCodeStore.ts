@@ -1,3 +1,6 @@
 import { atom } from 'nanostores';
 
 export const expoUrlAtom = atom<string | null>(null);
+
+// the preview that the desktop app exposes on the local network and its address there
+export const previewTunnelAtom = atom<{ port: number; url: string; token: string } | null>(null);
@@ -255,11 +255,25 @@ const SETTINGS_KEYS = {
   LATEST_BRANCH: 'isLatestBranch',
   AUTO_SELECT_TEMPLATE: 'autoSelectTemplate',
   CONTEXT_OPTIMIZATION: 'contextOptimizationEnabled',
+  LEXICAL_CONTEXT: 'lexicalContextEnabled',
   EVENT_LOGS: 'isEventLogsEnabled',
   PROMPT_ID: 'promptId',
   DEVELOPER_MODE: 'isDeveloperMode',
+  VISUAL_SNAPSHOTS: 'visualSnapshotsEnabled',
+  VISUAL_SNAPSHOT_VIEWPORTS: 'visualSnapshotViewports',
 } as const;
 
+export interface VisualSnapshotViewport {
+  name: string;
+  width: number;
+  height: number;
+}
+
+export const DEFAULT_VISUAL_SNAPSHOT_VIEWPORTS: VisualSnapshotViewport[] = [
+  { name: 'Desktop', width: 1280, height: 800 },
+  { name: 'Mobile', width: 390, height: 844 },
+];
+
 // Initialize settings from localStorage or defaults
 const getInitialSettings = () => {
   const getStoredBoolean = (key: string, defaultValue: boolean): boolean => {
@@ -284,20 +298,36 @@ const getInitialSettings = () => {
     latestBranch: getStoredBoolean(SETTINGS_KEYS.LATEST_BRANCH, false),
     autoSelectTemplate: getStoredBoolean(SETTINGS_KEYS.AUTO_SELECT_TEMPLATE, true),
     contextOptimization: getStoredBoolean(SETTINGS_KEYS.CONTEXT_OPTIMIZATION, true),
+    lexicalContext: getStoredBoolean(SETTINGS_KEYS.LEXICAL_CONTEXT, false),
     eventLogs: getStoredBoolean(SETTINGS_KEYS.EVENT_LOGS, true),
     promptId: isBrowser ? localStorage.getItem(SETTINGS_KEYS.PROMPT_ID) || 'default' : 'default',
     developerMode: getStoredBoolean(SETTINGS_KEYS.DEVELOPER_MODE, false),
+    visualSnapshots: getStoredBoolean(SETTINGS_KEYS.VISUAL_SNAPSHOTS, false),
+    visualSnapshotViewports: getStoredViewports(),
   };
 };
 
+function getStoredViewports(): VisualSnapshotViewport[] {
+  const stored = isBrowser ? localStorage.getItem(SETTINGS_KEYS.VISUAL_SNAPSHOT_VIEWPORTS) : null;
+
+  try {
+    return stored ? JSON.parse(stored) : DEFAULT_VISUAL_SNAPSHOT_VIEWPORTS;
+  } catch {
+    return DEFAULT_VISUAL_SNAPSHOT_VIEWPORTS;
+  }
+}
+
 // Initialize stores with persisted values
 const initialSettings = getInitialSettings();
 
 export const latestBranchStore = atom<boolean>(initialSettings.latestBranch);
 export const autoSelectStarterTemplate = atom<boolean>(initialSettings.autoSelectTemplate);
 export const enableContextOptimizationStore = atom<boolean>(initialSettings.contextOptimization);
+export const enableLexicalContextStore = atom<boolean>(initialSettings.lexicalContext);
 export const isEventLogsEnabled = atom<boolean>(initialSettings.eventLogs);
 export const promptStore = atom<string>(initialSettings.promptId);
+export const enableVisualSnapshotsStore = atom<boolean>(initialSettings.visualSnapshots);
+export const visualSnapshotViewportsStore = atom<VisualSnapshotViewport[]>(initialSettings.visualSnapshotViewports);
 
 // Helper functions to update settings with persistence
 export const updateLatestBranch = (enabled: boolean) => {
@@ -315,11 +345,26 @@ export const updateContextOptimization = (enabled: boolean) => {
   localStorage.setItem(SETTINGS_KEYS.CONTEXT_OPTIMIZATION, JSON.stringify(enabled));
 };
 
+export const updateLexicalContext = (enabled: boolean) => {
+  enableLexicalContextStore.set(enabled);
+  localStorage.setItem(SETTINGS_KEYS.LEXICAL_CONTEXT, JSON.stringify(enabled));
+};
+
 export const updateEventLogs = (enabled: boolean) => {
   isEventLogsEnabled.set(enabled);
   localStorage.setItem(SETTINGS_KEYS.EVENT_LOGS, JSON.stringify(enabled));
 };
 
+export const updateVisualSnapshots = (enabled: boolean) => {
+  enableVisualSnapshotsStore.set(enabled);
+  localStorage.setItem(SETTINGS_KEYS.VISUAL_SNAPSHOTS, JSON.stringify(enabled));
+};
+
+export const updateVisualSnapshotViewports = (viewports: VisualSnapshotViewport[]) => {
+  visualSnapshotViewportsStore.set(viewports);
+  localStorage.setItem(SETTINGS_KEYS.VISUAL_SNAPSHOT_VIEWPORTS, JSON.stringify(viewports));
+};
+
 export const updatePromptId = (id: string) => {
   promptStore.set(id);
   localStorage.setItem(SETTINGS_KEYS.PROMPT_ID, id);
@@ -0,0 +1,140 @@
+import { atom } from 'nanostores';
+import { chatId, db, getSnapshot, setVisualSnapshots } from '~/lib/persistence';
+import type { VisualCapture, VisualSnapshot } from '~/lib/persistence/types';
+import { capturePreview } from '~/lib/webcontainer/preview-capture';
+import { createScopedLogger } from '~/utils/logger';
+import type { FileMap } from './files';
+import { visualSnapshotViewportsStore } from './settings';
+
+const logger = createScopedLogger('VisualSnapshotsStore');
+
+// every capture is a full PNG per viewport, older artifacts are dropped first
+const MAX_VISUAL_SNAPSHOTS = 20;
+
+/**
+ * Captures of the preview taken after each completed artifact, persisted with the snapshot of the chat.
+ *
+ * Captures taken before the chat has an id (the first response of a new chat) are kept in memory and persisted as
+ * soon as the id is allocated.
+ */
+class VisualSnapshotsStore {
+  snapshots = atom<VisualSnapshot[]>([]);
+  capturing = atom(false);
+
+  #chatId: string | undefined;
+  #captureQueue = Promise.resolve();
+
+  // files of the last capture, for chats that have no snapshot yet when their captures are persisted
+  #files: FileMap = {};
+
+  constructor() {
+    chatId.subscribe((id) => {
+      if (id === this.#chatId) {
+        return;
+      }
+
+      const previousChatId = this.#chatId;
+      this.#chatId = id;
+
+      if (previousChatId === undefined && id && this.snapshots.get().length > 0) {
+        // the chat was just created, the captures belong to it
+        this.#persist();
+        return;
+      }
+
+      this.snapshots.set([]);
+
+      if (id) {
+        this.#load(id);
+      }
+    });
+  }
+
+  /**
+   * Captures the preview at every configured viewport. Captures are queued so that two artifacts completing
+   * back to back don't load the preview side by side.
+   */
+  capture(artifact: { id: string; title: string }, previewUrl: string, files: FileMap) {
+    const capture = this.#captureQueue.then(() => this.#capture(artifact, previewUrl, files));
+    this.#captureQueue = capture.then(
+      () => undefined,
+      () => undefined,
+    );
+
+    return capture;
+  }
+
+  async #capture(artifact: { id: string; title: string }, previewUrl: string, files: FileMap) {
+    const captures: VisualCapture[] = [];
+
+    this.capturing.set(true);
+
+    try {
+      for (const viewport of visualSnapshotViewportsStore.get()) {
+        const { width, height } = viewport;
+
+        try {
+          const dataUrl = await capturePreview(previewUrl, { width, height });
+          captures.push({ viewport: viewport.name, width, height, dataUrl });
+        } catch (error) {
+          logger.warn(`Failed to capture the ${viewport.name} viewport`, error);
+        }
+      }
+    } finally {
+      this.capturing.set(false);
+    }
+
+    if (captures.length === 0) {
+      return undefined;
+    }
+
+    const snapshot: VisualSnapshot = {
+      artifactId: artifact.id,
+      title: artifact.title,
+      timestamp: new Date().toISOString(),
+      captures,
+    };
+
+    // an artifact captured again, from the preview toolbar, replaces its previous capture
+    const snapshots = [...this.snapshots.get().filter((item) => item.artifactId !== artifact.id), snapshot];
+
+    this.#files = files;
+    this.snapshots.set(snapshots.slice(-MAX_VISUAL_SNAPSHOTS));
+    await this.#persist();
+
+    return snapshot;
+  }
+
+  async #load(id: string) {
+    if (!db) {
+      return;
+    }
+
+    try {
+      const snapshot = await getSnapshot(db, id);
+
+      // the chat may have changed while the snapshot was loading
+      if (this.#chatId === id) {
+        this.snapshots.set(snapshot?.visualSnapshots ?? []);
+      }
+    } catch (error) {
+      logger.error('Failed to load visual snapshots', error);
+    }
+  }
+
+  async #persist() {
+    const id = this.#chatId;
+
+    if (!id || !db) {
+      return;
+    }
+
+    try {
+      await setVisualSnapshots(db, id, this.snapshots.get(), this.#files);
+    } catch (error) {
+      logger.error('Failed to save visual snapshots', error);
+    }
+  }
+}
+
+export const visualSnapshotsStore = new VisualSnapshotsStore();
@@ -1,11 +1,14 @@
 import { atom, map, type MapStore, type ReadableAtom, type WritableAtom } from 'nanostores';
 import type { EditorDocument, ScrollPosition } from '~/components/editor/codemirror/CodeMirrorEditor';
 import { ActionRunner } from '~/lib/runtime/action-runner';
+import { ArtifactTransaction, RollbackConflictError } from '~/lib/runtime/artifact-transaction';
+import type { FileVersionSource } from '~/lib/persistence/types';
 import type { ActionCallbackData, ArtifactCallbackData } from '~/lib/runtime/message-parser';
 import { webcontainer } from '~/lib/webcontainer';
 import type { ITerminal } from '~/types/terminal';
 import { unreachable } from '~/utils/unreachable';
 import { EditorStore } from './editor';
+import { fileHistoryStore } from './fileHistory';
 import { FilesStore, type FileMap } from './files';
 import { PreviewsStore } from './previews';
 import { TerminalStore } from './terminal';
@@ -14,10 +17,15 @@ import fileSaver from 'file-saver';
 import { Octokit, type RestEndpointMethodTypes } from '@octokit/rest';
 import { path } from '~/utils/path';
 import { extractRelativePath } from '~/utils/diff';
-import { description } from '~/lib/persistence';
+import { chatId, db, description, recordArtifactRollback } from '~/lib/persistence';
 import Cookies from 'js-cookie';
 import { createSampler } from '~/utils/sampler';
-import type { ActionAlert, DeployAlert, SupabaseAlert } from '~/types/actions';
+import { WORK_DIR } from '~/utils/constants';
+import type { ActionAlert, DeployAlert, PreviewError, SupabaseAlert } from '~/types/actions';
+import { formatPreviewError } from '~/utils/previewErrors';
+import { addSourcePluginToConfig, SOURCE_PLUGIN, SOURCE_PLUGIN_PATH } from '~/utils/jsxSource';
+import { enableVisualSnapshotsStore } from './settings';
+import { visualSnapshotsStore } from './visualSnapshots';
 
 export interface ArtifactState {
   id: string;
@@ -25,12 +33,38 @@ export interface ArtifactState {
   type?: string;
   closed: boolean;
   runner: ActionRunner;
+  transaction: ArtifactTransaction;
+  rollbackStatus?: 'rolling-back' | 'rolled-back';
 }
 
 export type ArtifactUpdateState = Pick<ArtifactState, 'title' | 'closed'>;
 
+/**
+ * Files changed together by a project-wide replace, undone as a unit.
+ */
+export interface ReplaceChangeSet {
+  id: string;
+  description: string;
+  files: string[];
+
+  /**
+   * Content the replace wrote per file, undo refuses to drop changes made to the files after it.
+   */
+  contents: Record<string, string>;
+  timestamp: number;
+  transaction: ArtifactTransaction;
+}
+
 type Artifacts = MapStore<Record<string, ArtifactState>>;
 
+// errors kept for one alert, the oldest are dropped first
+const MAX_PREVIEW_ERRORS = 10;
+
+// lets the dev server pick up the files of an artifact before its preview is captured
+const VISUAL_SNAPSHOT_DELAY = 3000;
+
+const VITE_CONFIG_FILES = ['vite.config.ts', 'vite.config.js', 'vite.config.mts', 'vite.config.mjs'];
+
 export type WorkbenchViewType = 'code' | 'diff' | 'preview';
 
 export class WorkbenchStore {
@@ -40,6 +74,7 @@ export class WorkbenchStore {
   #terminalStore = new TerminalStore(webcontainer);
 
   #reloadedMessages = new Set<string>();
+  #rolledBackArtifacts = new Set<string>();
 
   artifacts: Artifacts = import.meta.hot?.data.artifacts ?? map({});
 
@@ -52,6 +87,11 @@ export class WorkbenchStore {
     import.meta.hot?.data.supabaseAlert ?? atom<SupabaseAlert | undefined>(undefined);
   deployAlert: WritableAtom<DeployAlert | undefined> =
     import.meta.hot?.data.deployAlert ?? atom<DeployAlert | undefined>(undefined);
+  replaceChangeSets: WritableAtom<ReplaceChangeSet[]> =
+    import.meta.hot?.data.replaceChangeSets ?? atom<ReplaceChangeSet[]>([]);
+  previewErrors: WritableAtom<PreviewError[]> = import.meta.hot?.data.previewErrors ?? atom<PreviewError[]>([]);
+  attachedPreviewErrors: WritableAtom<PreviewError[]> =
+    import.meta.hot?.data.attachedPreviewErrors ?? atom<PreviewError[]>([]);
   modifiedFiles = new Set<string>();
   artifactIdList: string[] = [];
   #globalExecutionQueue = Promise.resolve();
@@ -64,6 +104,9 @@ export class WorkbenchStore {
       import.meta.hot.data.actionAlert = this.actionAlert;
       import.meta.hot.data.supabaseAlert = this.supabaseAlert;
       import.meta.hot.data.deployAlert = this.deployAlert;
+      import.meta.hot.data.replaceChangeSets = this.replaceChangeSets;
+      import.meta.hot.data.previewErrors = this.previewErrors;
+      import.meta.hot.data.attachedPreviewErrors = this.attachedPreviewErrors;
 
       // Ensure binary files are properly preserved across hot reloads
       const filesMap = this.files.get();
@@ -115,9 +158,46 @@ export class WorkbenchStore {
     return this.actionAlert;
   }
   clearAlert() {
+    if (this.actionAlert.get()?.source === 'preview') {
+      this.previewErrors.set([]);
+    }
+
     this.actionAlert.set(undefined);
   }
 
+  /**
+   * Collects an error reported by the runtime probe of the preview and shows all collected errors in one alert.
+   */
+  reportPreviewError(error: PreviewError) {
+    const errors = this.previewErrors.get();
+
+    // the same error tends to be thrown on every render
+    if (errors.some((item) => item.kind === error.kind && item.message === error.message)) {
+      return;
+    }
+
+    const nextErrors = [...errors, error].slice(-MAX_PREVIEW_ERRORS);
+    this.previewErrors.set(nextErrors);
+
+    this.actionAlert.set({
+      type: 'preview',
+      title: nextErrors.length > 1 ? `${nextErrors.length} Preview Errors` : 'Preview Error',
+      description: error.message,
+      content: nextErrors.map(formatPreviewError).join('\n\n'),
+      source: 'preview',
+    });
+  }
+
+  /**
+   * Moves the collected preview errors to the next chat message and dismisses their alert.
+   */
+  attachPreviewErrors() {
+    const errors = [...this.attachedPreviewErrors.get(), ...this.previewErrors.get()];
+
+    this.attachedPreviewErrors.set(errors.slice(-MAX_PREVIEW_ERRORS));
+    this.clearAlert();
+  }
+
   get SupabaseAlert() {
     return this.supabaseAlert;
   }
@@ -220,7 +300,11 @@ export class WorkbenchStore {
     this.#editorStore.setSelectedFile(filePath);
   }
 
-  async saveFile(filePath: string) {
+  async saveFile(
+    filePath: string,
+    source: FileVersionSource = 'user',
+    previousContent = this.#filesStore.getFile(filePath)?.content,
+  ) {
     const documents = this.#editorStore.documents.get();
     const document = documents[filePath];
 
@@ -235,6 +319,7 @@ export class WorkbenchStore {
      */
 
     await this.#filesStore.saveFile(filePath, document.value);
+    fileHistoryStore.record(filePath, document.value, source, previousContent);
 
     const newUnsavedFiles = new Set(this.unsavedFiles.get());
     newUnsavedFiles.delete(filePath);
@@ -275,6 +360,135 @@ export class WorkbenchStore {
     }
   }
 
+  /**
+   * Writes the new content of several files as one change set that `undoReplace` restores as a unit. Locked files
+   * and files with unsaved changes in the editor are refused.
+   */
+  replaceInFiles(contents: Record<string, string>, description: string) {
+    return new Promise<ReplaceChangeSet>((resolve, reject) => {
+      this.addToExecutionQueue(() => this.#replaceInFiles(contents, description).then(resolve, reject));
+    });
+  }
+
+  async #replaceInFiles(contents: Record<string, string>, description: string) {
+    const filePaths = Object.keys(contents);
+    const lockedFiles = filePaths.filter((filePath) => this.isFileLocked(filePath).locked);
+    const unsavedFiles = filePaths.filter((filePath) => this.unsavedFiles.get().has(filePath));
+
+    if (lockedFiles.length > 0) {
+      throw new Error(`Locked files can't be changed: ${lockedFiles.map(extractRelativePath).join(', ')}`);
+    }
+
+    if (unsavedFiles.length > 0) {
+      throw new Error(`Save or discard the changes to ${unsavedFiles.map(extractRelativePath).join(', ')} first`);
+    }
+
+    const transaction = new ArtifactTransaction(this.#filesStore, webcontainer);
+
+    try {
+      for (const filePath of filePaths) {
+        const previousContent = this.#filesStore.getFile(filePath)?.content;
+
+        await transaction.captureAction({ type: 'file', filePath: extractRelativePath(filePath), content: '' });
+        await this.#filesStore.saveFile(filePath, contents[filePath]);
+        fileHistoryStore.record(filePath, contents[filePath], 'user', previousContent);
+      }
+    } catch (error) {
+      // leave no file half replaced
+      await transaction.rollback();
+      throw error;
+    }
+
+    const changeSet: ReplaceChangeSet = {
+      id: `replace-${Date.now()}`,
+      description,
+      files: filePaths,
+      contents,
+      timestamp: Date.now(),
+      transaction,
+    };
+    this.replaceChangeSets.set([...this.replaceChangeSets.get(), changeSet]);
+
+    return changeSet;
+  }
+
+  /**
+   * Restores the files of a change set. Throws a `RollbackConflictError` listing the files that changed after the
+   * replace unless forced.
+   */
+  undoReplace(changeSetId: string, options: { force?: boolean } = {}) {
+    return new Promise<void>((resolve, reject) => {
+      this.addToExecutionQueue(() => this.#undoReplace(changeSetId, options).then(resolve, reject));
+    });
+  }
+
+  async #undoReplace(changeSetId: string, { force }: { force?: boolean }) {
+    const changeSet = this.replaceChangeSets.get().find((item) => item.id === changeSetId);
+
+    if (!changeSet) {
+      return;
+    }
+
+    const conflicts = changeSet.files.filter(
+      (filePath) => !force && this.#filesStore.getFile(filePath)?.content !== changeSet.contents[filePath],
+    );
+
+    if (conflicts.length > 0) {
+      throw new RollbackConflictError(conflicts.map((filePath) => extractRelativePath(filePath)));
+    }
+
+    await changeSet.transaction.rollback({ force: true });
+
+    for (const filePath of changeSet.files) {
+      const content = this.#filesStore.getFile(filePath)?.content;
+
+      if (content !== undefined) {
+        fileHistoryStore.record(filePath, content, 'user');
+      }
+    }
+
+    this.replaceChangeSets.set(this.replaceChangeSets.get().filter((item) => item.id !== changeSetId));
+  }
+
+  /**
+   * Adds the plugin that maps preview elements to their JSX to the Vite config of the project, the dev server
+   * restarts by itself. Returns `false` for projects without a config it knows how to change.
+   */
+  async enableSourceMapping() {
+    const files = this.files.get();
+    const configPath = VITE_CONFIG_FILES.map((name) => `${WORK_DIR}/${name}`).find((filePath) => files[filePath]);
+    const configFile = configPath ? files[configPath] : undefined;
+
+    if (!configPath || configFile?.type !== 'file' || configFile.isBinary) {
+      return false;
+    }
+
+    const config = addSourcePluginToConfig(configFile.content);
+
+    if (config === undefined) {
+      return false;
+    }
+
+    if (config === configFile.content) {
+      return true;
+    }
+
+    if (this.isFileLocked(configPath).locked) {
+      throw new Error(`${extractRelativePath(configPath)} is locked, unlock it to edit the preview visually`);
+    }
+
+    const pluginPath = `${WORK_DIR}/${SOURCE_PLUGIN_PATH}`;
+
+    if (!files[pluginPath]) {
+      await this.#filesStore.createFile(pluginPath, SOURCE_PLUGIN);
+    }
+
+    await this.#filesStore.saveFile(configPath, config);
+    fileHistoryStore.record(configPath, config, 'user', configFile.content);
+
+    return true;
+  }
+
   getFileModifcations() {
     return this.#filesStore.getFileModifications();
   }
@@ -463,6 +677,10 @@ export class WorkbenchStore {
     this.#reloadedMessages = new Set(messages);
   }
 
+  setRolledBackArtifacts(artifactIds: string[]) {
+    this.#rolledBackArtifacts = new Set(artifactIds);
+  }
+
   addArtifact({ messageId, title, id, type }: ArtifactCallbackData) {
     const artifact = this.#getArtifact(id);
 
@@ -479,6 +697,8 @@ export class WorkbenchStore {
       title,
       closed: false,
       type,
+      transaction: new ArtifactTransaction(this.#filesStore, webcontainer),
+      rollbackStatus: this.#rolledBackArtifacts.has(id) ? 'rolled-back' : undefined,
       runner: new ActionRunner(
         webcontainer,
         () => this.smackTerminal,
@@ -503,11 +723,18 @@ export class WorkbenchStore {
 
           this.deployAlert.set(alert);
         },
+        (filePath) => {
+          const file = this.#filesStore.getFile(path.join(WORK_DIR, filePath));
+
+          return file?.isBinary ? undefined : file?.content;
+        },
+        () => this.previews.get().find((preview) => preview.ready)?.baseUrl,
+        () => this.#reloadedMessages.has(messageId),
       ),
     });
   }
 
-  updateArtifact({ artifactId }: ArtifactCallbackData, state: Partial<ArtifactUpdateState>) {
+  updateArtifact({ artifactId, messageId }: ArtifactCallbackData, state: Partial<ArtifactUpdateState>) {
     if (!artifactId) {
       return;
     }
@@ -519,6 +746,34 @@ export class WorkbenchStore {
     }
 
     this.artifacts.setKey(artifactId, { ...artifact, ...state });
+
+    const completed = state.closed && !artifact.closed && !this.#reloadedMessages.has(messageId);
+
+    if (completed && enableVisualSnapshotsStore.get()) {
+      // after the file actions of the artifact, without holding up the actions of the next one
+      this.addToExecutionQueue(async () => {
+        setTimeout(() => this.captureVisualSnapshot(artifactId), VISUAL_SNAPSHOT_DELAY);
+      });
+    }
+  }
+
+  /**
+   * Captures the first running preview for the visual snapshot of an artifact, does nothing without a preview.
+   */
+  async captureVisualSnapshot(artifactId: string) {
+    const artifact = this.#getArtifact(artifactId);
+    const preview = this.previews.get().find((item) => item.ready);
+
+    if (!artifact || !preview) {
+      return undefined;
+    }
+
+    try {
+      return await visualSnapshotsStore.capture(artifact, preview.baseUrl, this.files.get());
+    } catch (error) {
+      console.error('Failed to capture visual snapshot:', error);
+      return undefined;
+    }
   }
   addAction(data: ActionCallbackData) {
     // this._addAction(data);
@@ -537,6 +792,18 @@ export class WorkbenchStore {
     return artifact.runner.addAction(data);
   }
 
+  /**
+   * Drops an action whose output was cut off by an interrupted response, the resumed response writes it
+   * again in full.
+   */
+  abortAction(data: ActionCallbackData) {
+    this.addToExecutionQueue(async () => {
+      const artifact = this.#getArtifact(data.artifactId);
+
+      artifact?.runner.actions.get()[data.actionId]?.abort();
+    });
+  }
+
   runAction(data: ActionCallbackData, isStreaming: boolean = false) {
     if (isStreaming) {
       this.actionStreamSampler(data, isStreaming);
@@ -555,10 +822,19 @@ export class WorkbenchStore {
 
     const action = artifact.runner.actions.get()[data.actionId];
 
-    if (!action || action.executed) {
+    // aborted actions may still have a sampled stream update pending
+    if (!action || action.executed || action.abortSignal.aborted) {
       return;
     }
 
+    if (artifact.rollbackStatus) {
+      // the artifact's changes were rolled back, don't re-apply them when the chat is replayed
+      action.abort();
+      return;
+    }
+
+    await artifact.transaction.captureAction(data.action);
+
     if (data.action.type === 'file') {
       const wc = await webcontainer;
       const fullPath = path.join(wc.workdir, data.action.filePath);
@@ -586,16 +862,119 @@ export class WorkbenchStore {
       this.#editorStore.updateFile(fullPath, data.action.content);
 
       if (!isStreaming && data.action.content) {
-        await this.saveFile(fullPath);
+        await this.saveFile(fullPath, 'llm', artifact.transaction.getOriginalContent(fullPath));
       }
 
       if (!isStreaming) {
         await artifact.runner.runAction(data);
         this.resetAllFileModifications();
       }
+    } else if (data.action.type === 'patch') {
+      const wc = await webcontainer;
+      const fullPath = path.join(wc.workdir, data.action.filePath);
+
+      if (this.selectedFile.value !== fullPath) {
+        this.setSelectedFile(fullPath);
+      }
+
+      if (this.currentView.value !== 'code') {
+        this.currentView.set('code');
+      }
+
+      await artifact.runner.runAction(data);
+
+      if (artifact.runner.actions.get()[data.actionId]?.status === 'complete') {
+        const content = await wc.fs.readFile(path.relative(wc.workdir, fullPath), 'utf-8');
+        fileHistoryStore.record(fullPath, content, 'llm', artifact.transaction.getOriginalContent(fullPath));
+      }
+    } else if (data.action.type === 'delete' || data.action.type === 'rename') {
+      const wc = await webcontainer;
+      const fromPath = path.join(wc.workdir, data.action.filePath);
+      const selectedFile = this.selectedFile.value;
+
+      await artifact.runner.runAction(data);
+
+      // keep the editor pointed at a file that still exists
+      if (selectedFile && (selectedFile === fromPath || selectedFile.startsWith(fromPath + '/'))) {
+        this.setSelectedFile(
+          data.action.type === 'rename'
+            ? path.join(wc.workdir, data.action.newFilePath) + selectedFile.slice(fromPath.length)
+            : undefined,
+        );
+      }
     } else {
       await artifact.runner.runAction(data);
     }
+
+    if (!isStreaming) {
+      // what the artifact left behind, a rollback refuses to drop later changes to it
+      await artifact.transaction.recordWrites();
+    }
+  }
+
+  /**
+   * Restores every file written, deleted or created by the artifact and records the rollback in the
+   * chat snapshot. Runs on the execution queue so it never interleaves with pending actions. Rejects with a
+   * `RollbackConflictError` when later artifacts or the user changed those files, unless forced.
+   */
+  rollbackArtifact(artifactId: string, options: { force?: boolean } = {}) {
+    return new Promise<void>((resolve, reject) => {
+      this.addToExecutionQueue(() => this.#rollbackArtifact(artifactId, options).then(resolve, reject));
+    });
+  }
+
+  async #rollbackArtifact(artifactId: string, { force }: { force?: boolean }) {
+    const artifact = this.#getArtifact(artifactId);
+
+    if (!artifact || artifact.rollbackStatus || artifact.transaction.isEmpty) {
+      return;
+    }
+
+    const conflicts = force ? [] : await artifact.transaction.findConflicts();
+
+    if (conflicts.length > 0) {
+      throw new RollbackConflictError(conflicts.map((filePath) => extractRelativePath(filePath)));
+    }
+
+    this.artifacts.setKey(artifactId, { ...artifact, rollbackStatus: 'rolling-back' });
+
+    for (const action of Object.values(artifact.runner.actions.get())) {
+      if (action.status === 'pending' || action.status === 'running') {
+        action.abort();
+      }
+    }
+
+    try {
+      const touchedPaths = await artifact.transaction.rollback({ force: true });
+
+      this.#rolledBackArtifacts.add(artifactId);
+      this.artifacts.setKey(artifactId, { ...artifact, rollbackStatus: 'rolled-back' });
+
+      const selectedFile = this.selectedFile.value;
+
+      if (selectedFile && !this.#filesStore.getFile(selectedFile)) {
+        this.setSelectedFile(undefined);
+      }
+
+      const currentChatId = chatId.get();
+
+      if (db && currentChatId) {
+        await recordArtifactRollback(
+          db,
+          currentChatId,
+          {
+            artifactId,
+            title: artifact.title,
+            files: touchedPaths.map((filePath) => extractRelativePath(filePath)),
+            timestamp: new Date().toISOString(),
+          },
+          this.files.get(),
+        );
+      }
+    } catch (error) {
+      this.artifacts.setKey(artifactId, { ...artifact, rollbackStatus: undefined });
+      throw error;
+    }
   }
 
   actionStreamSampler = createSampler(async (data: ActionCallbackData, isStreaming: boolean = false) => {
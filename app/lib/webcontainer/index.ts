@@ -1,6 +1,6 @@
 import { WebContainer } from '@webcontainer/api';
 import { WORK_DIR_NAME } from '~/utils/constants';
-import { cleanStackTrace } from '~/utils/stacktrace';
+import { isPreviewError } from '~/utils/previewErrors';
 
 interface WebContainerContext {
   loaded: boolean;
@@ -26,7 +26,6 @@ if (!import.meta.env.SSR) {
         return WebContainer.boot({
           coep: 'credentialless',
           workdirName: WORK_DIR_NAME,
-          forwardPreviewErrors: true, // Enable error forwarding from iframes
         });
       })
       .then(async (webcontainer) => {
@@ -34,25 +33,29 @@ if (!import.meta.env.SSR) {
 
         const { workbenchStore } = await import('~/lib/stores/workbench');
 
-        const response = await fetch('/inspector-script.js');
-        const inspectorScript = await response.text();
-        await webcontainer.setPreviewScript(inspectorScript);
+        /*
+         * the runtime probe reports errors of the preview, the inspector lets users pick elements in it, the test
+         * runner runs the steps of test actions and the tunnel answers phones from the desktop app
+         */
+        const scripts = await Promise.all(
+          ['/preview-tunnel.js', '/inspector-script.js', '/preview-probe.js', '/preview-test-runner.js'].map((url) =>
+            fetch(url).then((response) => response.text()),
+          ),
+        );
+        await webcontainer.setPreviewScript(scripts.join('\n'));
 
-        // Listen for preview errors
-        webcontainer.on('preview-message', (message) => {
-          console.log('WebContainer preview message:', message);
+        window.addEventListener('message', (event) => {
+          if (event.data?.type !== 'PREVIEW_RUNTIME_ERROR' || !isPreviewError(event.data.error)) {
+            return;
+          }
+
+          // any page can post messages, only the previews report their errors
+          const isPreview = workbenchStore.previews
+            .get()
+            .some((preview) => new URL(preview.baseUrl).origin === event.origin);
 
-          // Handle both uncaught exceptions and unhandled promise rejections
-          if (message.type === 'PREVIEW_UNCAUGHT_EXCEPTION' || message.type === 'PREVIEW_UNHANDLED_REJECTION') {
-            const isPromise = message.type === 'PREVIEW_UNHANDLED_REJECTION';
-            const title = isPromise ? 'Unhandled Promise Rejection' : 'Uncaught Exception';
-            workbenchStore.actionAlert.set({
-              type: 'preview',
-              title,
-              description: 'message' in message ? message.message : 'Unknown error',
-              content: `Error occurred at ${message.pathname}${message.search}${message.hash}\nPort: ${message.port}\n\nStack trace:\n${cleanStackTrace(message.stack || '')}`,
-              source: 'preview',
-            });
+          if (isPreview) {
+            workbenchStore.reportPreviewError(event.data.error);
           }
         });
 
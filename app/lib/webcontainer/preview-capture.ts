@@ -0,0 +1,76 @@
+const CAPTURE_TIMEOUT = 30_000;
+
+// give the page a moment to settle after the inspector script reported ready
+const RENDER_DELAY = 500;
+
+export interface PreviewCaptureOptions {
+  width?: number;
+  height?: number;
+}
+
+/**
+ * Creates an iframe for previews that is rendered at the given size but kept out of sight, not yet attached.
+ */
+export function createOffscreenFrame({ width = 1280, height = 800 }: PreviewCaptureOptions = {}) {
+  const iframe = document.createElement('iframe');
+
+  Object.assign(iframe.style, {
+    position: 'fixed',
+    left: '-10000px',
+    top: '0',
+    width: `${width}px`,
+    height: `${height}px`,
+    border: '0',
+    pointerEvents: 'none',
+  });
+  iframe.setAttribute('sandbox', 'allow-scripts allow-forms allow-same-origin');
+  iframe.setAttribute('allow', 'cross-origin-isolated');
+
+  return iframe;
+}
+
+/**
+ * Loads a preview in an offscreen iframe and asks the injected inspector script for a PNG of the viewport,
+ * resolves with a `data:image/png` URL. Works whether or not the preview is open in the workbench.
+ */
+export function capturePreview(url: string, { width = 1280, height = 800 }: PreviewCaptureOptions = {}) {
+  return new Promise<string>((resolve, reject) => {
+    const requestId = Math.random().toString(36).slice(2);
+    const iframe = createOffscreenFrame({ width, height });
+
+    const cleanup = () => {
+      clearTimeout(timeout);
+      window.removeEventListener('message', handleMessage);
+      iframe.remove();
+    };
+
+    const timeout = setTimeout(() => {
+      cleanup();
+      reject(new Error('Timed out waiting for the preview to render'));
+    }, CAPTURE_TIMEOUT);
+
+    function handleMessage(event: MessageEvent) {
+      if (event.source !== iframe.contentWindow) {
+        return;
+      }
+
+      if (event.data?.type === 'INSPECTOR_READY') {
+        setTimeout(() => {
+          iframe.contentWindow?.postMessage({ type: 'PREVIEW_SCREENSHOT_REQUEST', requestId }, '*');
+        }, RENDER_DELAY);
+      } else if (event.data?.type === 'PREVIEW_SCREENSHOT_RESPONSE' && event.data.requestId === requestId) {
+        cleanup();
+
+        if (event.data.error) {
+          reject(new Error(event.data.error));
+        } else {
+          resolve(event.data.dataUrl);
+        }
+      }
+    }
+
+    window.addEventListener('message', handleMessage);
+    iframe.src = url;
+    document.body.appendChild(iframe);
+  });
+}
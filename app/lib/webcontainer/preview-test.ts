@@ -0,0 +1,124 @@
+import type { TestStepResult } from '~/types/actions';
+import type { TestStep } from '~/utils/previewTest';
+import { createOffscreenFrame } from './preview-capture';
+
+// how long a step waits for the element or text it needs, and a page for its scripts
+const STEP_TIMEOUT = 5_000;
+const LOAD_TIMEOUT = 30_000;
+
+interface PreviewTestOptions {
+  signal?: AbortSignal;
+  onProgress?: (results: TestStepResult[]) => void;
+}
+
+/**
+ * Runs the steps of a test against a preview in an offscreen iframe. `goto` steps load the page here, the other
+ * steps run inside the preview with the injected test runner. Steps after the first failure are skipped.
+ */
+export async function runPreviewTest(baseUrl: string, steps: TestStep[], { signal, onProgress }: PreviewTestOptions) {
+  const iframe = createOffscreenFrame();
+  const results: TestStepResult[] = steps.map((step) => ({ line: step.line, step: step.source, status: 'skipped' }));
+
+  let onReady: (() => void) | undefined;
+  let pending: { requestId: string; step: TestStep; resolve: (error?: string) => void } | undefined;
+
+  const send = () => {
+    if (pending) {
+      const { requestId, step } = pending;
+      iframe.contentWindow?.postMessage({ type: 'PREVIEW_TEST_STEP', requestId, step, timeout: STEP_TIMEOUT }, '*');
+    }
+  };
+
+  const handleMessage = (event: MessageEvent) => {
+    if (event.source !== iframe.contentWindow) {
+      return;
+    }
+
+    if (event.data?.type === 'INSPECTOR_READY') {
+      onReady?.();
+
+      // a click may have navigated away before the next step arrived, the new page gets it again
+      send();
+    } else if (event.data?.type === 'PREVIEW_TEST_RESULT' && event.data.requestId === pending?.requestId) {
+      pending.resolve(event.data.ok ? undefined : String(event.data.error));
+    }
+  };
+
+  const withTimeout = <T>(promise: Promise<T>, timeout: number, message: string) => {
+    let timer: ReturnType<typeof setTimeout>;
+    let onAbort: () => void;
+
+    return Promise.race([
+      promise,
+      new Promise<never>((_, reject) => {
+        onAbort = () => reject(new Error('The test was aborted'));
+        timer = setTimeout(() => reject(new Error(message)), timeout);
+        signal?.addEventListener('abort', onAbort, { once: true });
+      }),
+    ]).finally(() => {
+      clearTimeout(timer);
+      signal?.removeEventListener('abort', onAbort);
+    });
+  };
+
+  const load = (path: string) => {
+    const url = new URL(path, baseUrl).href;
+    const ready = new Promise<void>((resolve) => {
+      onReady = resolve;
+    });
+
+    iframe.src = url;
+
+    return withTimeout(ready, LOAD_TIMEOUT, `${path} did not load`);
+  };
+
+  const run = (step: TestStep) => {
+    const result = new Promise<string | undefined>((resolve) => {
+      pending = { requestId: Math.random().toString(36).slice(2), step, resolve };
+    });
+
+    send();
+
+    return withTimeout(result, STEP_TIMEOUT * 2, 'The preview did not answer');
+  };
+
+  window.addEventListener('message', handleMessage);
+  document.body.appendChild(iframe);
+
+  try {
+    if (steps[0]?.command !== 'goto') {
+      await load('/');
+    }
+
+    for (const [index, step] of steps.entries()) {
+      let error: string | undefined;
+
+      try {
+        if (step.command === 'goto') {
+          await load(step.args[0]);
+        } else {
+          error = await run(step);
+        }
+      } catch (stepError) {
+        error = stepError instanceof Error ? stepError.message : String(stepError);
+      }
+
+      pending = undefined;
+      results[index] = { ...results[index], status: error ? 'failed' : 'passed', error };
+      onProgress?.([...results]);
+
+      if (error) {
+        break;
+      }
+    }
+  } catch (error) {
+    // the first page did not load
+    results[0] = { ...results[0], status: 'failed', error: error instanceof Error ? error.message : String(error) };
+    onProgress?.([...results]);
+  } finally {
+    window.removeEventListener('message', handleMessage);
+    iframe.remove();
+  }
+
+  return results;
+}
@@ -0,0 +1,114 @@
+import { createOffscreenFrame } from './preview-capture';
+
+interface TunnelRequest {
+  requestId: string;
+  method: string;
+  path: string;
+  headers: Record<string, string>;
+  body?: Uint8Array;
+}
+
+declare global {
+  interface Window {
+    // exposed by the preload script of the desktop app
+    previewTunnel?: {
+      start(): Promise<{ url: string; token: string }>;
+      stop(): Promise<void>;
+      onRequest(callback: (request: TunnelRequest) => void): () => void;
+      respond(response: unknown): void;
+    };
+  }
+}
+
+const READY_TIMEOUT = 30_000;
+
+// read by the desktop app, which answers requests without it with 403
+const TOKEN_PARAM = 'smack_tunnel';
+
+// removes the hidden preview that answers the requests
+let stopRelay: (() => void) | undefined;
+
+/**
+ * The link phones open, it carries the token of the tunnel the first time.
+ */
+export function getPreviewTunnelLink(tunnel: { url: string; token: string }, path = '/') {
+  const link = new URL(path, tunnel.url);
+  link.searchParams.set(TOKEN_PARAM, tunnel.token);
+
+  return link.href;
+}
+
+export function isPreviewTunnelAvailable() {
+  return typeof window !== 'undefined' && !!window.previewTunnel;
+}
+
+function createRelay(baseUrl: string) {
+  const tunnel = window.previewTunnel!;
+  const iframe = createOffscreenFrame();
+  let onReady: () => void;
+
+  const ready = new Promise<void>((resolve, reject) => {
+    onReady = resolve;
+    setTimeout(() => reject(new Error('The preview did not load')), READY_TIMEOUT);
+  });
+
+  // the requests that arrive report it
+  ready.catch(() => undefined);
+
+  const handleMessage = (event: MessageEvent) => {
+    if (event.source !== iframe.contentWindow) {
+      return;
+    }
+
+    if (event.data?.type === 'INSPECTOR_READY') {
+      onReady();
+    } else if (event.data?.type === 'PREVIEW_TUNNEL_RESPONSE') {
+      const { requestId, status, headers, body, error } = event.data;
+      tunnel.respond({ requestId, status, headers, body: body ? new Uint8Array(body) : undefined, error });
+    }
+  };
+
+  const unsubscribe = tunnel.onRequest((request) => {
+    ready
+      .then(() => iframe.contentWindow?.postMessage({ type: 'PREVIEW_TUNNEL_REQUEST', ...request }, '*'))
+      .catch((error) => tunnel.respond({ requestId: request.requestId, error: String(error) }));
+  });
+
+  window.addEventListener('message', handleMessage);
+  iframe.src = baseUrl;
+  document.body.appendChild(iframe);
+
+  return () => {
+    unsubscribe();
+    window.removeEventListener('message', handleMessage);
+    iframe.remove();
+  };
+}
+
+/**
+ * Exposes a preview on the local network through the desktop app and resolves with its address and token. Requests are
+ * answered by a hidden copy of the preview, changes show up on phones when they reload as live reload does not reach
+ * them. Starting it again switches the exposed preview.
+ */
+export async function startPreviewTunnel(baseUrl: string) {
+  if (!window.previewTunnel) {
+    throw new Error('Sharing the preview on the network needs the desktop app');
+  }
+
+  stopRelay?.();
+  stopRelay = createRelay(baseUrl);
+
+  try {
+    return await window.previewTunnel.start();
+  } catch (error) {
+    stopPreviewTunnel();
+    throw error;
+  }
+}
+
+export function stopPreviewTunnel() {
+  stopRelay?.();
+  stopRelay = undefined;
+
+  return window.previewTunnel?.stop();
+}
@@ -2,18 +2,25 @@ import { type ActionFunctionArgs } from '@remix-run/cloudflare';
 import { generateId, type ToolSet, type TextStreamPart } from 'ai';
 import { formatDataStreamPart } from '@ai-sdk/ui-utils';
 import { MAX_RESPONSE_SEGMENTS, MAX_TOKENS, type FileMap } from '~/lib/llm/constants';
-import { CONTINUE_PROMPT } from '~/lib/common/prompts/prompts';
+import { CONTINUE_PROMPT, getResumePrompt } from '~/lib/common/prompts/prompts';
 import { streamText, type Messages, type StreamingOptions } from '~/lib/.server/llm/stream-text';
 import SwitchableStream from '~/lib/.server/llm/switchable-stream';
+import { StreamRecoveryManager } from '~/lib/.server/llm/stream-recovery';
 import type { IProviderSetting } from '~/types/model';
 import { createScopedLogger } from '~/utils/logger';
 import { getFilePaths, selectContext } from '~/lib/.server/llm/select-context';
-import type { ContextAnnotation, ProgressAnnotation } from '~/types/context';
-import { WORK_DIR } from '~/utils/constants';
+import type { ContextAnnotation, ModelAnswerAnnotation, ProgressAnnotation } from '~/types/context';
+import { ACTION_RESUME_MARKER, WORK_DIR } from '~/utils/constants';
 import { createSummary } from '~/lib/.server/llm/create-summary';
 import { extractPropertiesFromMessage } from '~/lib/.server/llm/utils';
+import { UsageTracker } from '~/lib/.server/llm/usage';
+import { classifyLlmError, getFallbackChain, shouldFallback, type ModelTarget } from '~/lib/.server/llm/fallback-chain';
+import { LLMManager } from '~/lib/modules/llm/manager';
 import type { DesignScheme } from '~/types/design-scheme';
 import { MCPService } from '~/lib/services/mcpService';
+import { mcpToolPoliciesSchema, type MCPToolPolicies } from '~/lib/services/mcpToolPolicies';
+import { DEFAULT_USER_ID, requestManager, type QueueUpdate } from '~/lib/modules/smack/request-manager';
+import { getCurrentUserId } from '~/utils/auth.server';
 
 type DataStreamWriterCompat = {
   write: (chunk: string) => void;
@@ -63,13 +70,25 @@ function createDataStreamCompat({
   return stream;
 }
 
+interface MergeHooks {
+  onText?: (text: string) => void;
+
+  // takes over an error part by returning `true`, which ends the merge without writing it or the finish message
+  onError?: (error: unknown) => boolean;
+}
+
+/**
+ * Writes a streamText result as data stream parts. An aborted stream ends the merge without a finish message.
+ */
 async function mergeResultIntoDataStream(
   result: { fullStream: AsyncIterable<TextStreamPart<ToolSet>>; finishReason: Promise<string>; totalUsage: Promise<any> },
-  writer: DataStreamWriterCompat,
+  writer: Pick<DataStreamWriterCompat, 'write'>,
+  hooks: MergeHooks = {},
 ) {
   for await (const part of result.fullStream) {
     switch (part.type) {
       case 'text-delta':
+        hooks.onText?.(part.text);
         writer.write(formatDataStreamPart('text', part.text));
         break;
       case 'reasoning-delta':
@@ -123,7 +142,13 @@ async function mergeResultIntoDataStream(
           }),
         );
         break;
+      case 'abort':
+        return;
       case 'error': {
+        if (hooks.onError?.(part.error)) {
+          return;
+        }
+
         const message = (part.error as any)?.message ?? String(part.error);
         writer.write(formatDataStreamPart('error', message));
         break;
@@ -143,6 +168,27 @@ async function mergeResultIntoDataStream(
   );
 }
 
+type AttemptOutcome =
+  | { type: 'complete' }
+  | { type: 'fallback'; error: unknown }
+  | { type: 'interrupted'; error: unknown };
+
+// a response that writes nothing for this long is considered dead and gets resumed
+const STREAM_STALL_TIMEOUT = 45000;
+
+async function forwardStream(stream: ReadableStream<string>, writer: Pick<DataStreamWriterCompat, 'write'>) {
+  const reader = stream.getReader();
+
+  while (true) {
+    const { done, value } = await reader.read();
+
+    if (done) {
+      break;
+    }
+
+    writer.write(value);
+  }
+}
 
 export async function action(args: ActionFunctionArgs) {
   return chatAction(args);
@@ -174,15 +220,48 @@ function parseCookies(cookieHeader: string): Record<string, string> {
   return cookies;
 }
 
-async function chatAction({ context, request }: ActionFunctionArgs) {
-  
+/**
+ * Identifies who a chat request is scheduled for on shared model servers, the signed in user or else the
+ * client address.
+ */
+async function getAuthenticatedUserId(args: ActionFunctionArgs): Promise<string | undefined> {
+  try {
+    return (await getCurrentUserId(args as any)) || undefined;
+  } catch {
+    // authentication isn't configured
+    return undefined;
+  }
+}
+
+function getSchedulingUserId(request: Request, userId?: string): string {
+  if (userId) {
+    return userId;
+  }
+
+  const clientIp =
+    request.headers.get('CF-Connecting-IP') || request.headers.get('X-Forwarded-For')?.split(',')[0].trim();
+
+  return clientIp ? `ip_${clientIp}` : DEFAULT_USER_ID;
+}
+
+function formatQueueMessage({ position, queueLength, estimatedWaitTime }: QueueUpdate) {
+  const seconds = Math.ceil(estimatedWaitTime / 1000);
+
+  return `Waiting in queue (${position} of ${queueLength}${seconds > 0 ? `, ~${seconds}s` : ''})`;
+}
+
+async function chatAction(args: ActionFunctionArgs) {
+  const { context, request } = args;
 
   try {
     const body = await request.json<{
+      id?: string;
       messages: Messages;
       files: any;
       promptId?: string;
+      promptTemplate?: string;
       contextOptimization: boolean;
+      lexicalContext?: boolean;
       chatMode: 'discuss' | 'build';
       designScheme?: DesignScheme;
       supabase?: {
@@ -194,6 +273,7 @@ async function chatAction({ context, request }: ActionFunctionArgs) {
         };
       };
       maxLLMSteps: number;
+      toolPolicies?: MCPToolPolicies;
     }>();
 
     if (
@@ -214,7 +294,23 @@ async function chatAction({ context, request }: ActionFunctionArgs) {
       );
     }
 
-    const { messages, files, promptId, contextOptimization, supabase, chatMode, designScheme, maxLLMSteps } = body;
+    const {
+      messages,
+      files,
+      promptId,
+      promptTemplate,
+      contextOptimization,
+      lexicalContext,
+      supabase,
+      chatMode,
+      designScheme,
+      maxLLMSteps,
+    } = body;
+    const toolPolicies = mcpToolPoliciesSchema.safeParse(body.toolPolicies ?? {});
+
+    if (!toolPolicies.success) {
+      logger.warn('Ignoring invalid MCP tool policies', toolPolicies.error.message);
+    }
 
     const cookieHeader = request.headers.get('Cookie');
     const cookies = parseCookies(cookieHeader || '');
@@ -237,26 +333,30 @@ async function chatAction({ context, request }: ActionFunctionArgs) {
       logger.error('Failed to parse providers cookie', e);
     }
 
-    const cumulativeUsage = {
-      completionTokens: 0,
-      promptTokens: 0,
-      totalTokens: 0,
-    };
+    const usageTracker = new UsageTracker();
     const encoder: TextEncoder = new TextEncoder();
     let progressCounter: number = 1;
     let lastChunk: string | undefined = undefined;
 
+    const userId = await getAuthenticatedUserId(args);
+    const schedulingUserId = getSchedulingUserId(request, userId);
+
     const dataStream = createDataStreamCompat({
       async execute(dataStream) {
-        
-
         const mcpService = MCPService.getInstance();
         const filePaths = getFilePaths(files || {});
         let filteredFiles: FileMap | undefined = undefined;
         let summary: string | undefined = undefined;
         let messageSliceId = 0;
 
-        const processedMessages = await mcpService.processToolInvocations(messages, dataStream);
+        const processedMessages = await mcpService.processToolInvocations(
+          messages,
+          dataStream,
+          toolPolicies.success ? toolPolicies.data : undefined,
+        );
+
+        // the model the user picked, summaries and context selection run on it as well
+        const requested = extractPropertiesFromMessage(processedMessages.filter((x) => x.role === 'user').slice(-1)[0]);
 
         if (processedMessages.length > 3) {
           messageSliceId = processedMessages.length - 3;
@@ -271,7 +371,7 @@ async function chatAction({ context, request }: ActionFunctionArgs) {
             message: 'Analysing Request',
           } satisfies ProgressAnnotation);
 
-          summary = await createSummary({
+          const { summary: chatSummary, summaryTree } = await createSummary({
             messages: [...processedMessages],
             env: context.cloudflare?.env,
             apiKeys,
@@ -279,13 +379,10 @@ async function chatAction({ context, request }: ActionFunctionArgs) {
             promptId,
             contextOptimization,
             onFinish(resp) {
-              if (resp.usage) {
-                cumulativeUsage.completionTokens += resp.usage.completionTokens || 0;
-                cumulativeUsage.promptTokens += resp.usage.promptTokens || 0;
-                cumulativeUsage.totalTokens += resp.usage.totalTokens || 0;
-              }
+              usageTracker.add(requested, resp.usage);
             },
           });
+          summary = chatSummary;
           dataStream.writeData({
             type: 'progress',
             label: 'summary',
@@ -298,6 +395,7 @@ async function chatAction({ context, request }: ActionFunctionArgs) {
             summary,
             chatId: processedMessages.slice(-1)?.[0]?.id,
           } as ContextAnnotation);
+          dataStream.writeMessageAnnotation({ type: 'summaryTree', nodes: summaryTree } as ContextAnnotation);
 
           dataStream.writeData({
             type: 'progress',
@@ -309,19 +407,17 @@ async function chatAction({ context, request }: ActionFunctionArgs) {
 
           filteredFiles = await selectContext({
             messages: [...processedMessages],
+            chatId: body.id,
             env: context.cloudflare?.env,
             apiKeys,
             files,
             providerSettings,
             promptId,
             contextOptimization,
+            lexicalContext,
             summary,
             onFinish(resp) {
-              if (resp.usage) {
-                cumulativeUsage.completionTokens += resp.usage.completionTokens || 0;
-                cumulativeUsage.promptTokens += resp.usage.promptTokens || 0;
-                cumulativeUsage.totalTokens += resp.usage.totalTokens || 0;
-              }
+              usageTracker.add(requested, resp.usage);
             },
           });
 
@@ -347,112 +443,345 @@ async function chatAction({ context, request }: ActionFunctionArgs) {
           } satisfies ProgressAnnotation);
         }
 
-        const options: StreamingOptions = {
-          supabaseConnection: supabase,
-          toolChoice: 'auto',
-          tools: mcpService.toolsWithoutExecute,
-          maxSteps: maxLLMSteps,
-          onStepFinish: ({ toolCalls }) => {
-            toolCalls.forEach((toolCall) => mcpService.processToolCall(toolCall, dataStream));
-          },
-          onFinish: async ({ text: content, finishReason, usage }) => {
-            if (usage) {
-              cumulativeUsage.completionTokens += usage.completionTokens || 0;
-              cumulativeUsage.promptTokens += usage.promptTokens || 0;
-              cumulativeUsage.totalTokens += usage.totalTokens || 0;
-            }
+        // queued requests only start generating once they leave the queue, the others with their first chunk
+        let isResponseAnnounced = false;
+        const announceResponse = () => {
+          if (isResponseAnnounced) {
+            return;
+          }
 
-            if (finishReason !== 'length') {
-              dataStream.writeMessageAnnotation({
-                type: 'usage',
-                value: { ...cumulativeUsage },
-              });
+          isResponseAnnounced = true;
+          dataStream.writeData({
+            type: 'progress',
+            label: 'response',
+            status: 'in-progress',
+            order: progressCounter++,
+            message: 'Generating Response',
+          } satisfies ProgressAnnotation);
+        };
+
+        const scheduling = requestManager.registerClient({
+          userId: schedulingUserId,
+
+          // queued model requests are dropped when the client disconnects
+          signal: request.signal,
+          onQueueUpdate: (update) => {
+            if (update.status === 'queued') {
               dataStream.writeData({
                 type: 'progress',
-                label: 'response',
+                label: 'queue',
+                status: 'in-progress',
+                order: progressCounter++,
+                message: formatQueueMessage(update),
+              } satisfies ProgressAnnotation);
+            } else if (update.status === 'started') {
+              dataStream.writeData({
+                type: 'progress',
+                label: 'queue',
                 status: 'complete',
                 order: progressCounter++,
-                message: 'Response Generated',
+                message: 'Left the queue',
               } satisfies ProgressAnnotation);
-              await new Promise((resolve) => setTimeout(resolve, 0));
-              return;
+              announceResponse();
+            }
+          },
+        });
+
+        // the model that answered, continuations of a truncated response stay on it
+        let answeringTarget: ModelTarget | undefined;
+
+        // responses cut off by the token limit are continued, up to MAX_RESPONSE_SEGMENTS parts
+        let segments = 1;
+        let continuationMessages: typeof processedMessages | undefined;
+
+        try {
+          const options: StreamingOptions = {
+            supabaseConnection: supabase,
+            toolChoice: 'auto',
+            tools: mcpService.toolsWithoutExecute,
+            maxSteps: maxLLMSteps,
+            headers: scheduling.headers,
+            abortSignal: request.signal,
+            onChunk: announceResponse,
+            onStepFinish: ({ toolCalls }) => {
+              toolCalls.forEach((toolCall) => mcpService.processToolCall(toolCall, dataStream));
+            },
+            onFinish: async ({ text: content, finishReason, usage }) => {
+              usageTracker.add(answeringTarget ?? requested, usage);
+
+              if (finishReason !== 'length') {
+                dataStream.writeMessageAnnotation(usageTracker.toAnnotation(userId));
+                dataStream.writeData({
+                  type: 'progress',
+                  label: 'response',
+                  status: 'complete',
+                  order: progressCounter++,
+                  message: 'Response Generated',
+                } satisfies ProgressAnnotation);
+                await new Promise((resolve) => setTimeout(resolve, 0));
+                return;
+              }
+
+              if (segments >= MAX_RESPONSE_SEGMENTS) {
+                throw new Error('Cannot continue message: Maximum segments reached');
+              }
+
+              const lastUserMessage = processedMessages.filter((x) => x.role === 'user').slice(-1)[0];
+              if (!lastUserMessage) {
+                throw new Error('Cannot continue: No user message found to extract properties from.');
+              }
+              const { model, provider } = answeringTarget ?? extractPropertiesFromMessage(lastUserMessage);
+
+              // picked up by the attempt loop once this attempt settles, so the continuation streams like any attempt
+              segments++;
+              continuationMessages = [
+                ...attemptMessages,
+                { id: generateId(), role: 'assistant', content },
+                {
+                  id: generateId(),
+                  role: 'user',
+                  content: `[Model: ${model}]\n\n[Provider: ${provider}]\n\n${CONTINUE_PROMPT}`,
+                },
+              ];
+            },
+          };
+
+          const primary: ModelTarget = { provider: requested.provider, model: requested.model };
+          const targets = [primary, ...getFallbackChain(context.cloudflare?.env, primary)];
+
+          /*
+           * Each attempt becomes a source of the switchable stream: a model that fails with a rate limit or
+           * quota error before writing anything is swapped for the next one in the chain, and a response that
+           * dies in the middle of an artifact is resumed from its last complete action.
+           */
+          const switchableStream = new SwitchableStream();
+          const forwarding = forwardStream(switchableStream.readable, dataStream);
+
+          let attemptController = new AbortController();
+          const streamRecovery = new StreamRecoveryManager({
+            timeout: STREAM_STALL_TIMEOUT,
+            onTimeout: () => attemptController.abort(),
+          });
+
+          const runAttempt = (
+            result: Awaited<ReturnType<typeof streamText>>,
+            canFallBack: boolean,
+            prefix?: string,
+          ): Promise<AttemptOutcome> =>
+            new Promise((resolve) => {
+              const source = new ReadableStream<string>({
+                async start(controller) {
+                  let written = false;
+                  let outcome: AttemptOutcome = { type: 'complete' };
+
+                  const reportError = (error: unknown) => {
+                    logger.error('Streaming error:', error);
+
+                    const { type, message, retryable, httpCode } = getErrorDetails((error as any)?.message);
+                    dataStream.writeData({ type: 'error', error: { type, message, retryable, httpCode } });
+                  };
+
+                  if (prefix) {
+                    controller.enqueue(formatDataStreamPart('text', prefix));
+                  }
+
+                  streamRecovery.startMonitoring();
+
+                  try {
+                    await mergeResultIntoDataStream(
+                      result,
+                      {
+                        write(chunk) {
+                          written = true;
+                          streamRecovery.updateActivity();
+                          controller.enqueue(chunk);
+                        },
+                      },
+                      {
+                        onText: (text) => streamRecovery.appendText(text),
+                        onError: (error) => {
+                          if (!written && canFallBack && shouldFallback(classifyLlmError(error))) {
+                            outcome = { type: 'fallback', error };
+                            return true;
+                          }
+
+                          if (!request.signal.aborted && streamRecovery.canResume()) {
+                            outcome = { type: 'interrupted', error };
+                            return true;
+                          }
+
+                          reportError(error);
+
+                          return false;
+                        },
+                      },
+                    );
+
+                    if (attemptController.signal.aborted && !request.signal.aborted && outcome.type === 'complete') {
+                      // the stream stalled and was aborted by the recovery manager
+                      const error = new Error('The response stream stalled (network timeout)');
+
+                      if (streamRecovery.canResume()) {
+                        outcome = { type: 'interrupted', error };
+                      } else {
+                        reportError(error);
+                        controller.enqueue(formatDataStreamPart('error', error.message));
+                      }
+                    }
+
+                    controller.close();
+                  } catch (error) {
+                    controller.error(error);
+                  } finally {
+                    streamRecovery.stop();
+                  }
+
+                  resolve(outcome);
+                },
+              });
+
+              switchableStream.switchSource(source);
+            });
+
+          let fallbackFrom: ModelAnswerAnnotation['fallbackFrom'];
+          let unansweredError: unknown;
+          let attemptMessages = processedMessages;
+          let resumeMarker: string | undefined;
+          let index = 0;
+
+          while (index < targets.length) {
+            const target = targets[index];
+            const hasNext = index < targets.length - 1;
+
+            if (index > 0 && !LLMManager.getInstance().getProvider(target.provider)) {
+              logger.warn(`Skipping fallback ${target.provider}/${target.model}: provider is not registered`);
+              index++;
+              continue;
+            }
+
+            attemptController = new AbortController();
+
+            let result: Awaited<ReturnType<typeof streamText>>;
+
+            try {
+              result = await streamText({
+                messages: [...attemptMessages],
+                env: context.cloudflare?.env,
+                options: { ...options, abortSignal: AbortSignal.any([request.signal, attemptController.signal]) },
+                apiKeys,
+                files,
+                providerSettings,
+                promptId,
+                promptTemplate,
+                contextOptimization,
+                contextFiles: filteredFiles,
+                chatMode,
+                designScheme,
+                summary,
+                messageSliceId,
+                modelOverride: index > 0 ? target : undefined,
+              });
+            } catch (error) {
+              // a fallback that can't be set up (e.g. missing API key) is skipped
+              if (index === 0 || !hasNext) {
+                throw error;
+              }
+
+              logger.warn(`Skipping fallback ${target.provider}/${target.model}:`, error);
+              index++;
+              continue;
             }
 
-            if (new SwitchableStream().switches >= MAX_RESPONSE_SEGMENTS) {
-              throw new Error('Cannot continue message: Maximum segments reached');
+            // set up front as onFinish may continue a truncated response before the attempt settles
+            answeringTarget = target;
+            continuationMessages = undefined;
+
+            const outcome = await runAttempt(result, hasNext, resumeMarker);
+            resumeMarker = undefined;
+
+            if (outcome.type === 'complete' && continuationMessages) {
+              attemptMessages = continuationMessages;
+              continuationMessages = undefined;
+              continue;
             }
 
-            const lastUserMessage = processedMessages.filter((x) => x.role === 'user').slice(-1)[0];
-            if (!lastUserMessage) {
-              throw new Error('Cannot continue: No user message found to extract properties from.');
+            if (outcome.type === 'complete') {
+              unansweredError = undefined;
+              dataStream.writeMessageAnnotation({
+                type: 'modelAnswer',
+                provider: target.provider,
+                model: target.model,
+                fallbackFrom,
+              } satisfies ModelAnswerAnnotation);
+              break;
             }
-            const { model, provider } = extractPropertiesFromMessage(lastUserMessage);
-            processedMessages.push({ id: generateId(), role: 'assistant', content });
-            processedMessages.push({
-              id: generateId(),
-              role: 'user',
-              content: `[Model: ${model}]\n\n[Provider: ${provider}]\n\n${CONTINUE_PROMPT}`,
-            });
 
-            const result = await streamText({
-              messages: [...processedMessages],
-              env: context.cloudflare?.env,
-              options,
-              apiKeys,
-              files,
-              providerSettings,
-              promptId,
-              contextOptimization,
-              contextFiles: filteredFiles,
-              chatMode,
-              designScheme,
-              summary,
-              messageSliceId,
-            });
+            if (outcome.type === 'interrupted') {
+              const resumePoint = streamRecovery.beginResume();
+
+              logger.warn(`Response from ${target.provider}/${target.model} was interrupted, resuming`, outcome.error);
+
+              // the client drops the unfinished action when it reaches the marker
+              resumeMarker = resumePoint.discardedText.trim() ? ACTION_RESUME_MARKER : undefined;
+              attemptMessages = [
+                ...processedMessages,
+                { id: generateId(), role: 'assistant', content: resumePoint.completedText },
+                {
+                  id: generateId(),
+                  role: 'user',
+                  content: `[Model: ${target.model}]\n\n[Provider: ${target.provider}]\n\n${getResumePrompt(
+                    resumePoint.lastAction,
+                    resumePoint.interruptedAction,
+                  )}`,
+                },
+              ];
 
-            await mergeResultIntoDataStream(result, dataStream);
-          },
-        };
+              dataStream.writeData({
+                type: 'progress',
+                label: 'response',
+                status: 'in-progress',
+                order: progressCounter++,
+                message: 'Connection lost, resuming the response',
+              } satisfies ProgressAnnotation);
+              continue;
+            }
 
-        dataStream.writeData({
-          type: 'progress',
-          label: 'response',
-          status: 'in-progress',
-          order: progressCounter++,
-          message: 'Generating Response',
-        } satisfies ProgressAnnotation);
-
-        const result = await streamText({
-          messages: [...processedMessages],
-          env: context.cloudflare?.env,
-          options,
-          apiKeys,
-          files,
-          providerSettings,
-          promptId,
-          contextOptimization,
-          contextFiles: filteredFiles,
-          chatMode,
-          designScheme,
-          summary,
-          messageSliceId,
-        });
+            answeringTarget = undefined;
+            unansweredError = outcome.error;
 
-        (async () => {
-          for await (const part of result.fullStream) {
-            
-            if (part.type === 'error') {
-              const error: any = part.error;
-              logger.error('Streaming error:', error);
+            const reason = classifyLlmError(outcome.error);
+            const next = targets[index + 1];
 
-              const { type, message, retryable, httpCode } = getErrorDetails(error.message);
-              dataStream.writeData({ type: 'error', error: { type, message, retryable, httpCode } });
-              return;
+            logger.warn(
+              `${target.provider}/${target.model} failed (${reason}), falling back to ${next.provider}/${next.model}`,
+            );
+
+            if (!fallbackFrom) {
+              fallbackFrom = { ...primary, reason };
             }
+
+            dataStream.writeData({
+              type: 'progress',
+              label: 'response',
+              status: 'in-progress',
+              order: progressCounter++,
+              message: `${target.provider} is unavailable (${reason.replace('_', ' ')}), trying ${next.provider}`,
+            } satisfies ProgressAnnotation);
+            index++;
+          }
+
+          if (!answeringTarget && unansweredError) {
+            // the remaining fallbacks couldn't be used
+            const { type, message, retryable, httpCode } = getErrorDetails((unansweredError as any)?.message);
+            dataStream.writeData({ type: 'error', error: { type, message, retryable, httpCode } });
+            dataStream.write(formatDataStreamPart('error', message));
           }
-          streamRecovery.stop();
-        })();
-        await mergeResultIntoDataStream(result, dataStream);
+
+          switchableStream.close();
+          await forwarding;
+        } finally {
+          scheduling.dispose();
+        }
       },
       onError: (error: any) => {
         const { message } = getErrorDetails(error.message);
@@ -0,0 +1,62 @@
+import { type ActionFunctionArgs, type LoaderFunctionArgs } from '@remix-run/cloudflare';
+import {
+  isWorkbenchBridgeSupported,
+  workbenchBridge,
+  WORKBENCH_BRIDGE_UNSUPPORTED_MESSAGE,
+} from '~/lib/.server/mcp/workbench-bridge';
+import { WORKBENCH_BRIDGE_TOKEN_HEADER, type WorkbenchBridgeResponse } from '~/lib/services/workbenchMcpTools';
+import { createScopedLogger } from '~/utils/logger';
+
+const logger = createScopedLogger('api.mcp-bridge');
+
+const unsupported = () => Response.json({ error: WORKBENCH_BRIDGE_UNSUPPORTED_MESSAGE }, { status: 501 });
+
+function getToken(request: Request) {
+  const token = request.headers.get(WORKBENCH_BRIDGE_TOKEN_HEADER);
+
+  return token && token.length >= 32 ? token : undefined;
+}
+
+// long-polled by the browser tab that shares its workspace over MCP
+export async function loader({ request }: LoaderFunctionArgs) {
+  if (!isWorkbenchBridgeSupported()) {
+    return unsupported();
+  }
+
+  const token = getToken(request);
+
+  if (!token) {
+    return Response.json({ error: 'Missing workspace token' }, { status: 401 });
+  }
+
+  const calls = await workbenchBridge.poll(token, request.signal);
+
+  return Response.json({ calls });
+}
+
+export async function action({ request }: ActionFunctionArgs) {
+  if (!isWorkbenchBridgeSupported()) {
+    return unsupported();
+  }
+
+  const token = getToken(request);
+
+  if (!token) {
+    return Response.json({ error: 'Missing workspace token' }, { status: 401 });
+  }
+
+  try {
+    const response = (await request.json()) as WorkbenchBridgeResponse;
+
+    if (!response || typeof response.id !== 'string') {
+      return Response.json({ error: 'Invalid bridge response' }, { status: 400 });
+    }
+
+    workbenchBridge.respond(token, response);
+
+    return Response.json({ ok: true });
+  } catch (error) {
+    logger.error('Error handling bridge response:', error);
+    return Response.json({ error: 'Failed to handle bridge response' }, { status: 500 });
+  }
+}
@@ -0,0 +1,44 @@
+import { type ActionFunctionArgs } from '@remix-run/cloudflare';
+import { createScopedLogger } from '~/utils/logger';
+import { MCPService } from '~/lib/services/mcpService';
+
+const logger = createScopedLogger('api.mcp-context');
+
+type ContextRequest =
+  | { type: 'resource'; serverName: string; uri: string }
+  | { type: 'prompt'; serverName: string; name: string; arguments?: Record<string, string> };
+
+// resources and prompts of every configured server
+export async function loader() {
+  try {
+    const mcpService = MCPService.getInstance();
+    const serverContext = await mcpService.listServerContext();
+
+    return Response.json(serverContext);
+  } catch (error) {
+    logger.error('Error listing MCP resources and prompts:', error);
+    return Response.json({ error: 'Failed to list MCP resources and prompts' }, { status: 500 });
+  }
+}
+
+export async function action({ request }: ActionFunctionArgs) {
+  try {
+    const body = (await request.json()) as ContextRequest;
+    const mcpService = MCPService.getInstance();
+
+    if (body.type === 'resource') {
+      const contents = await mcpService.readResource(body.serverName, body.uri);
+      return Response.json({ contents });
+    }
+
+    if (body.type === 'prompt') {
+      const text = await mcpService.getPrompt(body.serverName, body.name, body.arguments);
+      return Response.json({ text });
+    }
+
+    return Response.json({ error: 'Unknown request type' }, { status: 400 });
+  } catch (error) {
+    logger.error('Error reading MCP resource or prompt:', error);
+    return Response.json({ error: error instanceof Error ? error.message : String(error) }, { status: 500 });
+  }
+}
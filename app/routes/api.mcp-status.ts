@@ -0,0 +1,59 @@
+import { type LoaderFunctionArgs } from '@remix-run/cloudflare';
+import { MCPService, type MCPServerTools } from '~/lib/services/mcpService';
+
+// keeps proxies from closing an idle stream
+const KEEP_ALIVE_INTERVAL = 20_000;
+
+function serialize(servers: MCPServerTools) {
+  // clients hold transports and processes, the browser only needs the state
+  const state = Object.fromEntries(
+    Object.entries(servers).map(([serverName, server]) => [serverName, { ...server, client: null }]),
+  );
+
+  return `data: ${JSON.stringify(state)}\n\n`;
+}
+
+/**
+ * Streams the state of the MCP servers as server-sent events, one event with every server on each change.
+ */
+export async function loader({ request }: LoaderFunctionArgs) {
+  const mcpService = MCPService.getInstance();
+  const encoder = new TextEncoder();
+
+  const stream = new ReadableStream({
+    start(controller) {
+      const send = (chunk: string) => {
+        try {
+          controller.enqueue(encoder.encode(chunk));
+        } catch {
+          close();
+        }
+      };
+
+      const unsubscribe = mcpService.onStatusChange((servers) => send(serialize(servers)));
+      const keepAlive = setInterval(() => send(': keep-alive\n\n'), KEEP_ALIVE_INTERVAL);
+
+      function close() {
+        unsubscribe();
+        clearInterval(keepAlive);
+
+        try {
+          controller.close();
+        } catch {
+          // already closed
+        }
+      }
+
+      request.signal.addEventListener('abort', close);
+      send(serialize(mcpService.servers));
+    },
+  });
+
+  return new Response(stream, {
+    headers: {
+      'Content-Type': 'text/event-stream',
+      'Cache-Control': 'no-cache',
+      Connection: 'keep-alive',
+    },
+  });
+}
@@ -0,0 +1,136 @@
+import { type ActionFunctionArgs } from '@remix-run/cloudflare';
+import {
+  isWorkbenchBridgeSupported,
+  workbenchBridge,
+  WORKBENCH_BRIDGE_UNSUPPORTED_MESSAGE,
+} from '~/lib/.server/mcp/workbench-bridge';
+import { WORKBENCH_MCP_TOOLS } from '~/lib/services/workbenchMcpTools';
+import { createScopedLogger } from '~/utils/logger';
+
+const logger = createScopedLogger('api.mcp-workbench');
+
+const SUPPORTED_PROTOCOL_VERSIONS = ['2025-06-18', '2025-03-26', '2024-11-05'];
+
+interface JsonRpcRequest {
+  jsonrpc: '2.0';
+  id?: string | number | null;
+  method: string;
+  params?: any;
+}
+
+class JsonRpcError extends Error {
+  constructor(
+    readonly code: number,
+    message: string,
+  ) {
+    super(message);
+  }
+}
+
+async function handleRequest(token: string, { method, params }: JsonRpcRequest): Promise<unknown> {
+  switch (method) {
+    case 'initialize': {
+      const requested = params?.protocolVersion;
+
+      return {
+        protocolVersion: SUPPORTED_PROTOCOL_VERSIONS.includes(requested) ? requested : SUPPORTED_PROTOCOL_VERSIONS[0],
+        capabilities: { tools: {}, resources: {} },
+        serverInfo: { name: 'smack-workbench', version: '1.0.0' },
+        instructions: 'Drives a smack workspace: its files, terminal, action runner and previews.',
+      };
+    }
+    case 'ping':
+      return {};
+    case 'tools/list':
+      return { tools: WORKBENCH_MCP_TOOLS };
+    case 'tools/call': {
+      if (!WORKBENCH_MCP_TOOLS.some((tool) => tool.name === params?.name)) {
+        throw new JsonRpcError(-32602, `Unknown tool: ${params?.name}`);
+      }
+
+      try {
+        return await workbenchBridge.call(token, 'tools/call', params);
+      } catch (error) {
+        // tool failures are reported to the model rather than as protocol errors
+        return { content: [{ type: 'text', text: (error as Error).message }], isError: true };
+      }
+    }
+    case 'resources/list':
+    case 'resources/read':
+      try {
+        return await workbenchBridge.call(token, method, params);
+      } catch (error) {
+        throw new JsonRpcError(-32603, (error as Error).message);
+      }
+    case 'resources/templates/list':
+      return { resourceTemplates: [] };
+    default:
+      throw new JsonRpcError(-32601, `Method not found: ${method}`);
+  }
+}
+
+async function handleMessage(token: string, message: JsonRpcRequest) {
+  // notifications don't get a response
+  if (message.id === undefined || message.id === null) {
+    return undefined;
+  }
+
+  try {
+    const result = await handleRequest(token, message);
+
+    return { jsonrpc: '2.0', id: message.id, result };
+  } catch (error) {
+    const code = error instanceof JsonRpcError ? error.code : -32603;
+
+    return { jsonrpc: '2.0', id: message.id, error: { code, message: (error as Error).message } };
+  }
+}
+
+/**
+ * MCP endpoint (streamable HTTP, JSON responses only) for a workspace shared from the MCP settings tab,
+ * clients authenticate with the workspace token as a bearer token.
+ */
+export async function action({ request }: ActionFunctionArgs) {
+  if (!isWorkbenchBridgeSupported()) {
+    return Response.json(
+      { jsonrpc: '2.0', id: null, error: { code: -32000, message: WORKBENCH_BRIDGE_UNSUPPORTED_MESSAGE } },
+      { status: 501 },
+    );
+  }
+
+  const token = request.headers.get('Authorization')?.replace(/^Bearer\s+/i, '');
+
+  if (!token || !workbenchBridge.isConnected(token)) {
+    return Response.json(
+      { jsonrpc: '2.0', id: null, error: { code: -32001, message: 'Unknown or disconnected workspace' } },
+      { status: 401 },
+    );
+  }
+
+  let body: JsonRpcRequest | JsonRpcRequest[];
+
+  try {
+    body = await request.json();
+  } catch {
+    return Response.json(
+      { jsonrpc: '2.0', id: null, error: { code: -32700, message: 'Parse error' } },
+      { status: 400 },
+    );
+  }
+
+  const messages = Array.isArray(body) ? body : [body];
+  const responses = (await Promise.all(messages.map((message) => handleMessage(token, message)))).filter(Boolean);
+
+  if (responses.length === 0) {
+    return new Response(null, { status: 202 });
+  }
+
+  logger.debug(`Handled ${messages.map((message) => message.method).join(', ')}`);
+
+  return Response.json(Array.isArray(body) ? responses : responses[0]);
+}
+
+// the server doesn't push messages, so there is no SSE stream to open
+export async function loader() {
+  return new Response('Method Not Allowed', { status: 405, headers: { Allow: 'POST' } });
+}
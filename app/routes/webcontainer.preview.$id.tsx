@@ -1,21 +1,37 @@
-import { json, type LoaderFunctionArgs } from '@remix-run/cloudflare';
+import { json, type LoaderFunctionArgs, type MetaFunction } from '@remix-run/cloudflare';
 import { useLoaderData } from '@remix-run/react';
 import { useCallback, useEffect, useRef, useState } from 'react';
 
 const PREVIEW_CHANNEL = 'preview-updates';
 
-export async function loader({ params }: LoaderFunctionArgs) {
+export async function loader({ params, request }: LoaderFunctionArgs) {
   const previewId = params.id;
 
   if (!previewId) {
     throw new Response('Preview ID is required', { status: 400 });
   }
 
-  return json({ previewId });
+  // preview sessions open this page on their path and viewport, see `getSessionUrl`
+  const { searchParams } = new URL(request.url);
+  const path = searchParams.get('path') ?? '/';
+  const width = Number(searchParams.get('width')) || undefined;
+  const height = Number(searchParams.get('height')) || undefined;
+
+  return json({
+    previewId,
+    name: searchParams.get('name') ?? undefined,
+    path: path.startsWith('/') ? path : `/${path}`,
+    width,
+    height,
+  });
 }
 
+export const meta: MetaFunction<typeof loader> = ({ data }) => [
+  { title: data?.name ? `${data.name} · Preview` : 'Preview' },
+];
+
 export default function WebContainerPreview() {
-  const { previewId } = useLoaderData<typeof loader>();
+  const { previewId, path, width, height } = useLoaderData<typeof loader>();
   const iframeRef = useRef<HTMLIFrameElement>(null);
   const broadcastChannelRef = useRef<BroadcastChannel>();
   const [previewUrl, setPreviewUrl] = useState('');
@@ -59,7 +75,7 @@ export default function WebContainerPreview() {
     };
 
     // Construct the WebContainer preview URL
-    const url = `https://${previewId}.local-credentialless.webcontainer-api.io`;
+    const url = `https://${previewId}.local-credentialless.webcontainer-api.io${path}`;
     setPreviewUrl(url);
 
     // Set the iframe src
@@ -74,14 +90,15 @@ export default function WebContainerPreview() {
     return () => {
       broadcastChannelRef.current?.close();
     };
-  }, [previewId, handleRefresh, notifyPreviewReady]);
+  }, [previewId, path, handleRefresh, notifyPreviewReady]);
 
   return (
-    <div className="w-full h-full">
+    <div className="w-full h-full flex items-center justify-center overflow-auto">
       <iframe
         ref={iframeRef}
         title="WebContainer Preview"
-        className="w-full h-full border-none"
+        className="w-full h-full border-none shrink-0"
+        style={width && height ? { width, height } : undefined}
         sandbox="allow-scripts allow-forms allow-popups allow-modals allow-storage-access-by-user-activation allow-same-origin"
         allow="cross-origin-isolated"
         loading="eager"
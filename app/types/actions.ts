@@ -1,6 +1,6 @@
 import type { Change } from 'diff';
 
-export type ActionType = 'file' | 'shell' | 'supabase';
+export type ActionType = 'file' | 'shell' | 'supabase' | 'delete' | 'rename' | 'patch' | 'mkdir' | 'test';
 
 export interface BaseAction {
   content: string;
@@ -30,7 +30,61 @@ export interface SupabaseAction extends BaseAction {
   projectId?: string;
 }
 
-export type smackAction = FileAction | ShellAction | StartAction | BuildAction | SupabaseAction;
+export interface DeleteAction extends BaseAction {
+  type: 'delete';
+  filePath: string;
+}
+
+/**
+ * Moves a file or folder. The parser also accepts `type="move"` and normalizes it to `rename`.
+ */
+export interface RenameAction extends BaseAction {
+  type: 'rename';
+  filePath: string;
+  newFilePath: string;
+}
+
+/**
+ * Edits an existing file in place; `content` is either a unified diff or `<<<<<<< SEARCH` / `=======` /
+ * `>>>>>>> REPLACE` blocks, applied against the current file.
+ */
+export interface PatchAction extends BaseAction {
+  type: 'patch';
+  filePath: string;
+}
+
+export interface MkdirAction extends BaseAction {
+  type: 'mkdir';
+  filePath: string;
+}
+
+/**
+ * Checks the running preview, `content` is a script of steps like `click "text=Add to cart"` parsed by
+ * `parseTestScript`.
+ */
+export interface TestAction extends BaseAction {
+  type: 'test';
+  title?: string;
+}
+
+export interface TestStepResult {
+  line: number;
+  step: string;
+  status: 'passed' | 'failed' | 'skipped';
+  error?: string;
+}
+
+export type smackAction =
+  | FileAction
+  | ShellAction
+  | StartAction
+  | BuildAction
+  | SupabaseAction
+  | DeleteAction
+  | RenameAction
+  | PatchAction
+  | MkdirAction
+  | TestAction;
 
 export type smackActionData = smackAction | BaseAction;
 
@@ -39,7 +93,19 @@ export interface ActionAlert {
   title: string;
   description: string;
   content: string;
-  source?: 'terminal' | 'preview'; // Add source to differentiate between terminal and preview errors
+  source?: 'terminal' | 'preview' | 'patch' | 'test'; // Add source to differentiate between terminal, preview, patch and test errors
+}
+
+export interface PreviewError {
+  kind: 'console' | 'exception' | 'rejection' | 'network';
+  message: string;
+
+  // mapped back to the project sources where the dev server provides source maps
+  stack?: string;
+
+  // path, query and hash of the preview page
+  url: string;
+  timestamp: number;
 }
 
 export interface SupabaseAlert {
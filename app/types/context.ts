@@ -7,8 +7,66 @@ export type ContextAnnotation =
       type: 'chatSummary';
       summary: string;
       chatId: string;
+    }
+  | {
+      type: 'summaryTree';
+      nodes: SummaryNode[];
     };
 
+/**
+ * Summary of a range of messages, leaves cover a fixed window of messages and the nodes above merge the nodes of the
+ * level below. Nodes are never recomputed once written, the chat record keeps them in the annotations of its messages.
+ */
+export type SummaryNode = {
+  level: number;
+
+  // ids of the first and last message covered
+  from: string;
+  to: string;
+  messageCount: number;
+  summary: string;
+};
+
+export type ModelAnswerAnnotation = {
+  type: 'modelAnswer';
+  provider: string;
+  model: string;
+
+  // set when the requested model failed and a model from the fallback chain answered instead
+  fallbackFrom?: {
+    provider: string;
+    model: string;
+    reason: string;
+  };
+};
+
+export type TokenUsage = {
+  promptTokens: number;
+  completionTokens: number;
+  totalTokens: number;
+
+  // prompt tokens served from the provider's prompt cache, included in promptTokens
+  cachedTokens: number;
+};
+
+export type ModelUsage = TokenUsage & {
+  provider: string;
+  model: string;
+
+  // USD, missing when the model has no pricing
+  cost?: number;
+};
+
+export type UsageAnnotation = {
+  type: 'usage';
+  value: TokenUsage & { cost?: number };
+
+  // one entry per model that took part in the response, summaries and context selection included
+  models?: ModelUsage[];
+  userId?: string;
+  timestamp?: number;
+};
+
 export type ProgressAnnotation = {
   type: 'progress';
   label: string;
@@ -24,3 +82,23 @@ export type ToolCallAnnotation = {
   toolName: string;
   toolDescription: string;
 };
+
+export type ToolApprover = 'user' | 'policy';
+
+/**
+ * Written for every MCP tool call the server settled, whether it ran or was refused.
+ */
+export type ToolAuditAnnotation = {
+  type: 'toolAudit';
+  toolCallId: string;
+  serverName: string;
+  toolName: string;
+  args: Record<string, unknown>;
+  outcome: 'executed' | 'denied' | 'failed';
+  approver: ToolApprover;
+
+  // length of the JSON serialized result
+  resultSize: number;
+  duration: number;
+  timestamp: number;
+};
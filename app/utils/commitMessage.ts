@@ -0,0 +1,65 @@
+import type { FileMap } from '~/lib/stores/files';
+import type { ProviderInfo } from '~/types/model';
+import { computeFileModifications } from './diff';
+import { WORK_DIR } from './constants';
+
+const commitMessagePrompt = `
+You write git commit messages. You get the staged changes of a commit as unified diffs, or as the full file when
+that is shorter, each in a <file path="..."> tag.
+
+Reply with the commit message only: a subject line in the imperative mood of at most 72 characters, and when the
+changes need it, a blank line followed by a short body that explains what changed and why. No markdown, no quotes.
+`;
+
+export interface StagedChange {
+  // path relative to the repository root
+  filepath: string;
+  before: string;
+  after: string;
+}
+
+/**
+ * Asks the model picked in the chat for a commit message that describes the staged changes.
+ */
+export async function generateCommitMessage(options: {
+  changes: StagedChange[];
+  model: string;
+  provider: ProviderInfo;
+}) {
+  const files: FileMap = {};
+  const modifiedFiles = new Map<string, string>();
+
+  for (const change of options.changes) {
+    const fullPath = `${WORK_DIR}/${change.filepath}`;
+    files[fullPath] = { type: 'file', content: change.after, isBinary: false };
+    modifiedFiles.set(fullPath, change.before);
+  }
+
+  const modifications = computeFileModifications(files, modifiedFiles);
+
+  if (!modifications) {
+    throw new Error('There are no staged changes to describe');
+  }
+
+  const message = Object.entries(modifications)
+    .map(([path, { content }]) => `<file path="${path.slice(WORK_DIR.length + 1)}">\n${content}\n</file>`)
+    .join('\n\n');
+
+  const response = await fetch('/api/llmcall', {
+    method: 'POST',
+    body: JSON.stringify({
+      system: commitMessagePrompt,
+      message,
+      model: options.model,
+      provider: options.provider,
+    }),
+  });
+
+  const result: { text?: string; message?: string } = await response.json();
+
+  if (!response.ok || !result.text) {
+    throw new Error(result.message || 'The model returned no commit message');
+  }
+
+  return result.text.trim();
+}
@@ -4,6 +4,9 @@ import type { ProviderInfo } from '~/types/model';
 export const WORK_DIR_NAME = 'project';
 export const WORK_DIR = `/home/${WORK_DIR_NAME}`;
 export const MODIFICATIONS_TAG_NAME = 'smack_file_modifications';
+
+// written into a resumed response, the client drops the unfinished action output that precedes it
+export const ACTION_RESUME_MARKER = '<smackResume/>';
 export const MODEL_REGEX = /^\[Model: (.*?)\]\n\n/;
 export const PROVIDER_REGEX = /\[Provider: (.*?)\]\n\n/;
 export const DEFAULT_MODEL = process.env.DEFAULT_MODEL || 'gemini-2.0-flash';
@@ -20,16 +23,52 @@ export const TOOL_EXECUTION_ERROR = 'Error: An error occured while calling tool'
  * Provider list will be loaded from API in client builds
  * Static fallback for build-time safety - matches what LLMManager would provide
  */
-const FALLBACK_PROVIDER_LIST: ProviderInfo[] = [
-  {
-    name: 'Google',
+function clientProvider(
+  name: string,
+  config: { baseUrlKey?: string; apiTokenKey?: string },
+  getApiKeyLink?: string,
+): ProviderInfo {
+  return {
+    name,
     staticModels: [],
-    config: { baseUrlKey: 'GEMINI_LIKE_API_BASE_URL', apiTokenKey: 'GEMINI_LIKE_API_KEY' },
-    getApiKeyLink: 'https://aistudio.google.com/app/apikey',
+    config,
+    getApiKeyLink,
+    icon: `/icons/${name}.svg`,
     getModelInstance: () => {
       throw new Error('Not implemented in client');
     },
-  } as ProviderInfo,
+  } as ProviderInfo;
+}
+
+const FALLBACK_PROVIDER_LIST: ProviderInfo[] = [
+  clientProvider(
+    'Google',
+    { baseUrlKey: 'GEMINI_LIKE_API_BASE_URL', apiTokenKey: 'GEMINI_LIKE_API_KEY' },
+    'https://aistudio.google.com/app/apikey',
+  ),
+  clientProvider('AmazonBedrock', { apiTokenKey: 'AWS_BEDROCK_CONFIG' }, 'https://console.aws.amazon.com/bedrock'),
+  clientProvider('Anthropic', { apiTokenKey: 'ANTHROPIC_API_KEY' }, 'https://console.anthropic.com/settings/keys'),
+  clientProvider('Cohere', { apiTokenKey: 'COHERE_API_KEY' }, 'https://dashboard.cohere.com/api-keys'),
+  clientProvider('Deepseek', { apiTokenKey: 'DEEPSEEK_API_KEY' }, 'https://platform.deepseek.com/apiKeys'),
+  clientProvider('Groq', { apiTokenKey: 'GROQ_API_KEY' }, 'https://console.groq.com/keys'),
+  clientProvider('HuggingFace', { apiTokenKey: 'HUGGINGFACE_API_KEY' }, 'https://huggingface.co/settings/tokens'),
+  clientProvider(
+    'Hyperbolic',
+    { baseUrlKey: 'HYPERBOLIC_API_BASE_URL', apiTokenKey: 'HYPERBOLIC_API_KEY' },
+    'https://app.hyperbolic.xyz/settings',
+  ),
+  clientProvider('LMStudio', { baseUrlKey: 'LMSTUDIO_API_BASE_URL' }, 'https://lmstudio.ai/'),
+  clientProvider('Mistral', { apiTokenKey: 'MISTRAL_API_KEY' }, 'https://console.mistral.ai/api-keys/'),
+  clientProvider('Ollama', { baseUrlKey: 'OLLAMA_API_BASE_URL' }, 'https://ollama.com/download'),
+  clientProvider('OpenAI', { apiTokenKey: 'OPENAI_API_KEY' }, 'https://platform.openai.com/api-keys'),
+  clientProvider('OpenRouter', { apiTokenKey: 'OPEN_ROUTER_API_KEY' }, 'https://openrouter.ai/settings/keys'),
+  clientProvider('Perplexity', { apiTokenKey: 'PERPLEXITY_API_KEY' }, 'https://www.perplexity.ai/settings/api'),
+  clientProvider(
+    'Together',
+    { baseUrlKey: 'TOGETHER_API_BASE_URL', apiTokenKey: 'TOGETHER_API_KEY' },
+    'https://api.together.xyz/settings/api-keys',
+  ),
+  clientProvider('xAI', { apiTokenKey: 'XAI_API_KEY' }, 'https://console.x.ai'),
 ];
 
 /*
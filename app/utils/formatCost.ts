@@ -0,0 +1,14 @@
+/**
+ * Formats a USD amount, keeping enough precision for the fractions of a cent a single response costs.
+ */
+export function formatCost(cost: number): string {
+  if (cost === 0) {
+    return '$0.00';
+  }
+
+  if (cost < 0.01) {
+    return `$${cost.toFixed(4)}`;
+  }
+
+  return `$${cost.toFixed(2)}`;
+}
@@ -0,0 +1,33 @@
+import { describe, expect, it } from 'vitest';
+import { applyHunks, diffHunks } from './gitHunks';
+
+const lines = (count: number) => Array.from({ length: count }, (_, i) => `line ${i + 1}\n`);
+
+describe('applyHunks', () => {
+  const oldContent = lines(20).join('');
+  const changed = lines(20);
+  changed[1] = 'changed 2\n';
+  changed[17] = 'changed 18\n';
+
+  const newContent = changed.join('');
+  const hunks = diffHunks(oldContent, newContent);
+
+  it('applies only the selected hunks', () => {
+    expect(hunks).toHaveLength(2);
+
+    const partial = applyHunks(oldContent, [hunks[1]]);
+
+    expect(partial).toContain('line 2\n');
+    expect(partial).toContain('changed 18\n');
+    expect(applyHunks(oldContent, hunks)).toBe(newContent);
+    expect(applyHunks(oldContent, [])).toBe(oldContent);
+  });
+
+  it('keeps a missing newline at the end of the file', () => {
+    const before = 'a\nb';
+    const after = 'a\nb\nc';
+
+    expect(applyHunks(before, diffHunks(before, after))).toBe(after);
+    expect(applyHunks('', diffHunks('', 'new\n'))).toBe('new\n');
+  });
+});
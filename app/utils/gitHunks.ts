@@ -0,0 +1,61 @@
+import { structuredPatch } from 'diff';
+
+export interface GitHunk {
+  // `@@ -a,b +c,d @@`, unique within the diff of a file
+  header: string;
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  lines: string[];
+}
+
+/**
+ * Splits the changes between two versions of a file into hunks with three lines of context, like `git diff`.
+ */
+export function diffHunks(oldContent: string, newContent: string): GitHunk[] {
+  const { hunks } = structuredPatch('a', 'b', oldContent, newContent, '', '', { context: 3 });
+
+  return hunks.map((hunk) => ({
+    header: `@@ -${hunk.oldStart},${hunk.oldLines} +${hunk.newStart},${hunk.newLines} @@`,
+    oldStart: hunk.oldStart,
+    oldLines: hunk.oldLines,
+    newStart: hunk.newStart,
+    newLines: hunk.newLines,
+    lines: hunk.lines,
+  }));
+}
+
+/**
+ * Applies some of the hunks of `diffHunks(base, ...)` to `base`. Staging a hunk applies it to the index version of a
+ * file, unstaging one applies all other staged hunks to the HEAD version.
+ */
+export function applyHunks(base: string, hunks: GitHunk[]) {
+  // keep the line endings so that a missing newline at the end of the file survives
+  const baseLines = base === '' ? [] : base.split(/(?<=\n)/);
+  const result: string[] = [];
+  let cursor = 0;
+
+  for (const hunk of [...hunks].sort((a, b) => a.oldStart - b.oldStart)) {
+    // a hunk that only adds lines starts after `oldStart` instead of at it
+    const start = hunk.oldLines === 0 ? hunk.oldStart : hunk.oldStart - 1;
+
+    result.push(...baseLines.slice(cursor, start));
+    cursor = start;
+
+    hunk.lines.forEach((line, index) => {
+      if (line.startsWith(' ')) {
+        result.push(baseLines[cursor++]);
+      } else if (line.startsWith('-')) {
+        cursor++;
+      } else if (line.startsWith('+')) {
+        const noNewline = hunk.lines[index + 1]?.startsWith('\\');
+        result.push(noNewline ? line.slice(1) : `${line.slice(1)}\n`);
+      }
+    });
+  }
+
+  result.push(...baseLines.slice(cursor));
+
+  return result.join('');
+}
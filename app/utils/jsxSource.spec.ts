@@ -0,0 +1,61 @@
+import { describe, expect, it } from 'vitest';
+import { addSourcePluginToConfig, setJsxStyles, setJsxText } from './jsxSource';
+
+const component = `export function Card() {
+  return (
+    <div className="card" onClick={() => open({ id: '>' })}>
+      <h1>Hello world</h1>
+      <img src={logo} />
+    </div>
+  );
+}
+`;
+
+describe('addSourcePluginToConfig', () => {
+  it('adds the plugin after the imports and first in the plugins', () => {
+    const imports = "import { defineConfig } from 'vite';\nimport react from '@vitejs/plugin-react';\n";
+
+    expect(addSourcePluginToConfig(`${imports}\nexport default defineConfig({ plugins: [react()] });\n`)).toBe(
+      `${imports}import smackSource from './.smack/vite-source-plugin.mjs';\n\n` +
+        'export default defineConfig({ plugins: [smackSource(), react()] });\n',
+    );
+  });
+
+  it('leaves patched configs alone and gives up without plugins', () => {
+    const patched = addSourcePluginToConfig('export default { plugins: [] };\n')!;
+
+    expect(addSourcePluginToConfig(patched)).toBe(patched);
+    expect(addSourcePluginToConfig('export default {};\n')).toBeUndefined();
+  });
+});
+
+describe('setJsxStyles', () => {
+  it('adds a style prop after the attributes, skipping braces and strings', () => {
+    expect(setJsxStyles(component, { line: 3, column: 4 }, { color: 'red' })).toContain(
+      `<div className="card" onClick={() => open({ id: '>' })} style={{ color: 'red' }}>`,
+    );
+    expect(setJsxStyles(component, { line: 5, column: 6 }, { 'border-radius': '4px' })).toContain(
+      `<img src={logo} style={{ borderRadius: '4px' }} />`,
+    );
+  });
+
+  it('updates existing properties and appends new ones', () => {
+    const content = `<p style={{ color: 'red', margin: 0 }}>Hi</p>`;
+
+    expect(setJsxStyles(content, { line: 1, column: 0 }, { color: 'blue', fontSize: '12px' })).toBe(
+      `<p style={{ color: 'blue', margin: 0, fontSize: '12px' }}>Hi</p>`,
+    );
+  });
+});
+
+describe('setJsxText', () => {
+  it('replaces plain text children', () => {
+    expect(setJsxText(component, { line: 4, column: 6 }, 'Hello world', 'Hi {there}')).toContain(
+      `<h1>Hi {'{'}there{'}'}</h1>`,
+    );
+  });
+
+  it('refuses elements with other children', () => {
+    expect(() => setJsxText(component, { line: 3, column: 4 }, 'Hello world', 'Hi')).toThrow();
+  });
+});
@@ -0,0 +1,247 @@
+/**
+ * Position of a JSX element as stamped by the source plugin, `line` starts at 1 and `column` at 0 like in Babel.
+ */
+export interface JsxSourcePosition {
+  line: number;
+  column: number;
+}
+
+export const SOURCE_PLUGIN_PATH = '.smack/vite-source-plugin.mjs';
+
+/**
+ * Vite plugin written into projects for the visual editor. It stamps every DOM element created from JSX with the
+ * file, line and column of its JSX so that elements picked in the preview can be edited in the source. It runs
+ * before the React plugin and only on the dev server, with the Babel the React plugin brings along.
+ */
+export const SOURCE_PLUGIN = `// Added by smack.sh for the visual editor, only used by the dev server.
+import path from 'node:path';
+
+export default function smackSource() {
+  let babel;
+  let root = process.cwd();
+
+  return {
+    name: 'smack-source',
+    apply: 'serve',
+    enforce: 'pre',
+    configResolved(config) {
+      root = config.root;
+    },
+    async transform(code, id) {
+      const file = id.split('?')[0];
+
+      if (!/\\.[jt]sx$/.test(file) || file.includes('/node_modules/')) {
+        return null;
+      }
+
+      babel ??= await import('@babel/core').catch(() => null);
+
+      if (!babel) {
+        return null;
+      }
+
+      const t = babel.types;
+      const source = path.relative(root, file);
+      const stamp = (name, value) => t.jsxAttribute(t.jsxIdentifier(name), t.stringLiteral(String(value)));
+
+      const result = await babel.transformAsync(code, {
+        filename: file,
+        babelrc: false,
+        configFile: false,
+        sourceMaps: true,
+        parserOpts: { plugins: file.endsWith('.tsx') ? ['jsx', 'typescript'] : ['jsx'] },
+        plugins: [
+          () => ({
+            visitor: {
+              JSXOpeningElement({ node }) {
+                const stamped = node.attributes.some((attribute) => attribute.name?.name === 'data-source-file');
+
+                // components pass the attributes on to who knows where, only DOM elements are stamped
+                if (!node.loc || stamped || node.name.type !== 'JSXIdentifier' || !/^[a-z]/.test(node.name.name)) {
+                  return;
+                }
+
+                node.attributes.push(
+                  stamp('data-source-file', source),
+                  stamp('data-source-line', node.loc.start.line),
+                  stamp('data-source-column', node.loc.start.column),
+                );
+              },
+            },
+          }),
+        ],
+      });
+
+      return result && { code: result.code, map: result.map };
+    },
+  };
+}
+`;
+
+/**
+ * Adds the source plugin to a Vite config, returns `undefined` when the config has no `plugins` array to add it to.
+ */
+export function addSourcePluginToConfig(config: string) {
+  if (config.includes(SOURCE_PLUGIN_PATH)) {
+    return config;
+  }
+
+  const plugins = /plugins\s*:\s*\[/.exec(config);
+
+  if (!plugins) {
+    return undefined;
+  }
+
+  const pluginsEnd = plugins.index + plugins[0].length;
+  const withPlugin = `${config.slice(0, pluginsEnd)}smackSource(), ${config.slice(pluginsEnd)}`;
+
+  // after the last import, which may span several lines
+  const imports = [...withPlugin.matchAll(/^(?:.*\sfrom\s+|import\s+)['"][^'"]+['"];?[ \t]*$/gm)];
+  const lastImport = imports[imports.length - 1];
+  const importLine = `import smackSource from './${SOURCE_PLUGIN_PATH}';`;
+
+  if (!lastImport) {
+    return `${importLine}\n${withPlugin}`;
+  }
+
+  const importsEnd = (lastImport.index ?? 0) + lastImport[0].length;
+
+  return `${withPlugin.slice(0, importsEnd)}\n${importLine}${withPlugin.slice(importsEnd)}`;
+}
+
+function getOffset(content: string, { line, column }: JsxSourcePosition) {
+  let offset = 0;
+
+  for (let i = 1; i < line; i++) {
+    offset = content.indexOf('\n', offset) + 1;
+
+    if (offset === 0) {
+      return -1;
+    }
+  }
+
+  return offset + column;
+}
+
+// index after the bracket that closes the one at `start`, skipping strings
+function skipBraces(content: string, start: number) {
+  let depth = 0;
+  let quote: string | undefined;
+
+  for (let i = start; i < content.length; i++) {
+    const char = content[i];
+
+    if (quote) {
+      if (char === '\\') {
+        i++;
+      } else if (char === quote) {
+        quote = undefined;
+      }
+    } else if (char === '"' || char === "'" || char === '`') {
+      quote = char;
+    } else if (char === '{') {
+      depth++;
+    } else if (char === '}' && --depth === 0) {
+      return i + 1;
+    }
+  }
+
+  return -1;
+}
+
+/**
+ * Finds the opening tag of the element at a stamped position.
+ */
+export function findOpeningTag(content: string, position: JsxSourcePosition) {
+  const start = getOffset(content, position);
+
+  if (start < 0 || content[start] !== '<') {
+    throw new Error('The element is no longer where the preview says it is, wait for the preview to reload');
+  }
+
+  for (let i = start + 1; i < content.length; i++) {
+    const char = content[i];
+
+    if (char === '"' || char === "'") {
+      i = content.indexOf(char, i + 1);
+    } else if (char === '{') {
+      i = skipBraces(content, i) - 1;
+    } else if (char === '>') {
+      return { start, end: i + 1, selfClosing: content[i - 1] === '/' };
+    }
+
+    if (i < 0) {
+      break;
+    }
+  }
+
+  throw new Error('The opening tag of the element could not be parsed');
+}
+
+const toCamelCase = (property: string) => property.replace(/-([a-z])/g, (_, char: string) => char.toUpperCase());
+
+const quote = (value: string) => `'${value.replace(/\\/g, '\\\\').replace(/'/g, "\\'")}'`;
+
+/**
+ * Sets inline styles of the element at a position, in its `style={{ ... }}` prop which is added when missing.
+ */
+export function setJsxStyles(content: string, position: JsxSourcePosition, styles: Record<string, string>) {
+  const tag = findOpeningTag(content, position);
+  const entries = Object.entries(styles).map(([property, value]) => [toCamelCase(property), value.trim()] as const);
+  const styleProp = /\sstyle=\{\{/.exec(content.slice(tag.start, tag.end));
+
+  if (!styleProp) {
+    // right after the last attribute, so that `<img />` becomes `<img style={{ ... }} />`
+    let insertAt = tag.end - (tag.selfClosing ? 2 : 1);
+
+    while (/\s/.test(content[insertAt - 1])) {
+      insertAt--;
+    }
+
+    const object = entries.map(([property, value]) => `${property}: ${quote(value)}`).join(', ');
+
+    return `${content.slice(0, insertAt)} style={{ ${object} }}${content.slice(insertAt)}`;
+  }
+
+  const objectStart = tag.start + styleProp.index + styleProp[0].length - 1;
+  const objectEnd = skipBraces(content, objectStart) - 1;
+  let object = content.slice(objectStart + 1, objectEnd);
+
+  for (const [property, value] of entries) {
+    const existing = new RegExp(`(^|[,\\s])(${property}\\s*:\\s*)('[^']*'|"[^"]*"|[^,]+)`).exec(object);
+
+    if (existing) {
+      const valueStart = existing.index + existing[1].length + existing[2].length;
+      object = `${object.slice(0, valueStart)}${quote(value)}${object.slice(valueStart + existing[3].length)}`;
+    } else {
+      const trimmed = object.trimEnd();
+      const separator = trimmed === '' || trimmed.endsWith(',') ? ' ' : ', ';
+      object = `${trimmed}${separator}${property}: ${quote(value)} `;
+    }
+  }
+
+  return `${content.slice(0, objectStart + 1)}${object}${content.slice(objectEnd)}`;
+}
+
+const normalizeText = (text: string) => text.replace(/\s+/g, ' ').trim();
+
+const escapeJsxText = (text: string) => text.replace(/[{}<>]/g, (char) => `{'${char}'}`);
+
+/**
+ * Replaces the text of the element at a position, as long as its children are that text alone.
+ */
+export function setJsxText(content: string, position: JsxSourcePosition, oldText: string, newText: string) {
+  const tag = findOpeningTag(content, position);
+  const childrenEnd = content.slice(tag.end).search(/[<{]/);
+  const children = content.slice(tag.end, childrenEnd < 0 ? undefined : tag.end + childrenEnd);
+
+  if (tag.selfClosing || normalizeText(children) !== normalizeText(oldText) || content[tag.end + childrenEnd] !== '<') {
+    throw new Error('Only elements that contain plain text can be edited here, ask smack for other changes');
+  }
+
+  const leading = /^\s*/.exec(children)![0];
+  const trailing = /\s*$/.exec(children)![0];
+  const after = content.slice(tag.end + children.length);
+
+  return `${content.slice(0, tag.end)}${leading}${escapeJsxText(newText)}${trailing}${after}`;
+}
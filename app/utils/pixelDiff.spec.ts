@@ -0,0 +1,38 @@
+import { describe, expect, it } from 'vitest';
+import { diffPixels, type PixelImage } from './pixelDiff';
+
+function image(width: number, height: number, pixels: number[][]): PixelImage {
+  return { width, height, data: new Uint8ClampedArray(pixels.flat()) };
+}
+
+const white = [255, 255, 255, 255];
+const black = [0, 0, 0, 255];
+
+describe('diffPixels', () => {
+  it('counts the pixels that changed beyond the threshold', () => {
+    const before = image(2, 1, [white, white]);
+    const after = image(2, 1, [[250, 250, 250, 255], black]);
+    const diff = diffPixels(before, after);
+
+    expect(diff.changedPixels).toBe(1);
+    expect(diff.changedRatio).toBe(0.5);
+    expect(Array.from(diff.data.subarray(4, 8))).toEqual([255, 0, 64, 255]);
+  });
+
+  it('compares transparent pixels by what they look like on white', () => {
+    expect(diffPixels(image(1, 1, [[0, 0, 0, 0]]), image(1, 1, [white])).changedPixels).toBe(0);
+  });
+
+  it('counts pixels outside the smaller image as changed', () => {
+    const diff = diffPixels(image(1, 1, [white]), image(1, 2, [white, white]));
+
+    expect(diff.height).toBe(2);
+    expect(diff.changedPixels).toBe(1);
+  });
+
+  it('fades unchanged pixels to gray', () => {
+    const diff = diffPixels(image(1, 1, [black]), image(1, 1, [black]));
+
+    expect(Array.from(diff.data)).toEqual([191, 191, 191, 255]);
+  });
+});
@@ -0,0 +1,79 @@
+export interface PixelImage {
+  width: number;
+  height: number;
+
+  // RGBA, as in `ImageData`
+  data: Uint8ClampedArray;
+}
+
+export interface PixelDiff extends PixelImage {
+  changedPixels: number;
+
+  // share of the pixels that changed, between 0 and 1
+  changedRatio: number;
+}
+
+// faded gray for unchanged pixels so the changes stand out, red for changed ones
+const UNCHANGED_OPACITY = 0.25;
+const CHANGED_COLOR = [255, 0, 64];
+
+/**
+ * Compares two images pixel by pixel, `threshold` is the color distance between 0 and 1 below which pixels count as
+ * unchanged so that anti-aliasing noise is ignored. Images of different sizes are compared over the larger size and
+ * the pixels only one of them has count as changed.
+ *
+ * Runs on the main thread over every pixel of a viewport, so the loop works on offsets and allocates nothing.
+ */
+export function diffPixels(before: PixelImage, after: PixelImage, threshold = 0.1): PixelDiff {
+  const width = Math.max(before.width, after.width);
+  const height = Math.max(before.height, after.height);
+  const data = new Uint8ClampedArray(width * height * 4);
+  const maxDistance = threshold * threshold * 3 * 255 * 255;
+  let changedPixels = 0;
+
+  for (let y = 0; y < height; y++) {
+    for (let x = 0; x < width; x++) {
+      const offset = (y * width + x) * 4;
+      const beforeOffset = (y * before.width + x) * 4;
+      const afterOffset = (y * after.width + x) * 4;
+      const inBefore = x < before.width && y < before.height;
+      const inAfter = x < after.width && y < after.height;
+      let changed = !inBefore || !inAfter;
+
+      if (!changed) {
+        // composite on white so that transparent pixels compare by what is actually shown
+        const red = blend(before.data, beforeOffset, 0) - blend(after.data, afterOffset, 0);
+        const green = blend(before.data, beforeOffset, 1) - blend(after.data, afterOffset, 1);
+        const blue = blend(before.data, beforeOffset, 2) - blend(after.data, afterOffset, 2);
+
+        changed = red * red + green * green + blue * blue > maxDistance;
+      }
+
+      if (changed) {
+        changedPixels++;
+        data[offset] = CHANGED_COLOR[0];
+        data[offset + 1] = CHANGED_COLOR[1];
+        data[offset + 2] = CHANGED_COLOR[2];
+      } else {
+        const gray =
+          0.299 * blend(after.data, afterOffset, 0) +
+          0.587 * blend(after.data, afterOffset, 1) +
+          0.114 * blend(after.data, afterOffset, 2);
+        const faded = 255 - (255 - gray) * UNCHANGED_OPACITY;
+
+        data[offset] = faded;
+        data[offset + 1] = faded;
+        data[offset + 2] = faded;
+      }
+
+      data[offset + 3] = 255;
+    }
+  }
+
+  return { width, height, data, changedPixels, changedRatio: width * height ? changedPixels / (width * height) : 0 };
+}
+
+function blend(data: Uint8ClampedArray, offset: number, channel: number) {
+  const alpha = data[offset + 3] / 255;
+  return data[offset + channel] * alpha + 255 * (1 - alpha);
+}
@@ -0,0 +1,25 @@
+import { describe, expect, it } from 'vitest';
+import { formatPreviewError, isPreviewError } from './previewErrors';
+
+const error = {
+  kind: 'exception' as const,
+  message: 'Cannot read properties of undefined',
+  stack: 'TypeError: x\n    at App (/home/project/src/App.tsx:12:5)\n    at render (/node_modules/react-dom.js:1:1)',
+  url: '/cart',
+  timestamp: 1,
+};
+
+describe('previewErrors', () => {
+  it('rejects messages that are not probe errors', () => {
+    expect(isPreviewError(error)).toBe(true);
+    expect(isPreviewError({ ...error, kind: 'other' })).toBe(false);
+    expect(isPreviewError({ ...error, kind: 'toString' })).toBe(false);
+    expect(isPreviewError(null)).toBe(false);
+  });
+
+  it('keeps the project frames relative to the project', () => {
+    expect(formatPreviewError(error)).toBe(
+      'Uncaught exception at /cart: Cannot read properties of undefined\nTypeError: x\n    at App (src/App.tsx:12:5)',
+    );
+  });
+});
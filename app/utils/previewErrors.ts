@@ -0,0 +1,44 @@
+import type { PreviewError } from '~/types/actions';
+import { WORK_DIR } from './constants';
+import { cleanStackTrace } from './stacktrace';
+
+const KIND_LABELS: Record<PreviewError['kind'], string> = {
+  console: 'console.error',
+  exception: 'Uncaught exception',
+  rejection: 'Unhandled promise rejection',
+  network: 'Failed request',
+};
+
+/**
+ * Checks the shape of an error posted by the runtime probe, any page can post messages to the editor.
+ */
+export function isPreviewError(value: unknown): value is PreviewError {
+  const error = value as PreviewError | undefined;
+
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    Object.hasOwn(KIND_LABELS, error.kind) &&
+    typeof error.message === 'string' &&
+    typeof error.url === 'string' &&
+    typeof error.timestamp === 'number'
+  );
+}
+
+export function formatPreviewError(error: PreviewError) {
+  const stack = cleanStackTrace(error.stack ?? '')
+    .replaceAll(`${WORK_DIR}/`, '')
+    .split('\n')
+    .filter((line) => line.trim())
+    .slice(0, 10)
+    .join('\n');
+
+  return `${KIND_LABELS[error.kind]} at ${error.url}: ${error.message}${stack ? `\n${stack}` : ''}`;
+}
+
+/**
+ * Formats the errors of the preview for the model, as the message that asks it to fix them.
+ */
+export function formatPreviewErrors(errors: PreviewError[]) {
+  return `*Fix these preview errors*\n\`\`\`js\n${errors.map(formatPreviewError).join('\n\n')}\n\`\`\`\n`;
+}
@@ -0,0 +1,40 @@
+import { describe, expect, it } from 'vitest';
+import { formatTestResults, parseTestScript } from './previewTest';
+
+describe('parseTestScript', () => {
+  it('parses quoted and trailing unquoted arguments', () => {
+    const steps = parseTestScript(`// sign in
+goto /login
+fill "#email" ada@example.com
+click "text=Sign in"
+expect text Welcome back, Ada
+wait 200`);
+
+    expect(steps.map(({ line, command, args }) => ({ line, command, args }))).toEqual([
+      { line: 2, command: 'goto', args: ['/login'] },
+      { line: 3, command: 'fill', args: ['#email', 'ada@example.com'] },
+      { line: 4, command: 'click', args: ['text=Sign in'] },
+      { line: 5, command: 'expect-text', args: ['Welcome back, Ada'] },
+      { line: 6, command: 'wait', args: ['200'] },
+    ]);
+  });
+
+  it('reports the line of invalid steps', () => {
+    expect(() => parseTestScript('goto /\nhover .menu')).toThrow('Line 2: unknown step "hover .menu"');
+    expect(() => parseTestScript('fill "#email')).toThrow('Line 1: unterminated "');
+    expect(() => parseTestScript('click "a" "b"')).toThrow('Line 1: unexpected ""b""');
+    expect(() => parseTestScript('// nothing')).toThrow('The test has no steps');
+  });
+});
+
+describe('formatTestResults', () => {
+  it('marks every step and shows the errors', () => {
+    expect(
+      formatTestResults('Checkout', [
+        { line: 1, step: 'goto /cart', status: 'passed' },
+        { line: 2, step: 'click "text=Pay"', status: 'failed', error: 'No element matches "text=Pay"' },
+        { line: 3, step: 'expect text Paid', status: 'skipped' },
+      ]),
+    ).toBe('Checkout\n✓ goto /cart\n✗ click "text=Pay"\n    No element matches "text=Pay"\n- expect text Paid');
+  });
+});
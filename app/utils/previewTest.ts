@@ -0,0 +1,161 @@
+import type { TestStepResult } from '~/types/actions';
+
+export type TestCommand =
+  | 'goto'
+  | 'click'
+  | 'fill'
+  | 'press'
+  | 'wait'
+  | 'expect-text'
+  | 'expect-no-text'
+  | 'expect-visible'
+  | 'expect-hidden'
+  | 'expect-url';
+
+export interface TestStep {
+  line: number;
+  source: string;
+  command: TestCommand;
+  args: string[];
+}
+
+// number of arguments of each command, `expect` commands are written `expect text ...`
+const ARITY: Record<TestCommand, number> = {
+  goto: 1,
+  click: 1,
+  fill: 2,
+  press: 1,
+  wait: 1,
+  'expect-text': 1,
+  'expect-no-text': 1,
+  'expect-visible': 1,
+  'expect-hidden': 1,
+  'expect-url': 1,
+};
+
+function readQuoted(text: string, start: number) {
+  const quote = text[start];
+  let value = '';
+
+  for (let i = start + 1; i < text.length; i++) {
+    if (text[i] === '\\' && i + 1 < text.length) {
+      value += text[++i];
+    } else if (text[i] === quote) {
+      return { value, end: i + 1 };
+    } else {
+      value += text[i];
+    }
+  }
+
+  throw new Error(`unterminated ${quote}`);
+}
+
+// quoted arguments can contain spaces, the last argument can also be left unquoted
+function parseArgs(text: string, arity: number) {
+  const args: string[] = [];
+  let position = 0;
+
+  while (args.length < arity) {
+    while (/\s/.test(text[position] ?? '')) {
+      position++;
+    }
+
+    if (position >= text.length) {
+      break;
+    }
+
+    if (text[position] === '"' || text[position] === "'") {
+      const { value, end } = readQuoted(text, position);
+      args.push(value);
+      position = end;
+    } else if (args.length === arity - 1) {
+      args.push(text.slice(position).trim());
+      position = text.length;
+    } else {
+      const end = text.slice(position).search(/\s|$/) + position;
+      args.push(text.slice(position, end));
+      position = end;
+    }
+  }
+
+  if (args.length < arity) {
+    throw new Error(`expected ${arity} ${arity === 1 ? 'argument' : 'arguments'}`);
+  }
+
+  if (text.slice(position).trim()) {
+    throw new Error(`unexpected "${text.slice(position).trim()}"`);
+  }
+
+  return args;
+}
+
+/**
+ * Parses the script of a `test` action, one step per line:
+ *
+ * ```
+ * goto /login
+ * fill "#email" ada@example.com
+ * click "text=Sign in"
+ * expect text Welcome back
+ * expect url /dashboard
+ * ```
+ *
+ * Selectors are CSS or `text=...` for the element whose text contains it. Lines starting with `//` are comments.
+ */
+export function parseTestScript(script: string): TestStep[] {
+  const steps: TestStep[] = [];
+
+  script.split('\n').forEach((rawLine, index) => {
+    const source = rawLine.trim();
+    const line = index + 1;
+
+    if (!source || source.startsWith('//')) {
+      return;
+    }
+
+    const [word, ...rest] = source.split(/\s+/);
+    let command = word.toLowerCase();
+    let argsText = source.slice(word.length);
+
+    if (command === 'expect') {
+      const kind = rest[0]?.toLowerCase();
+
+      command = `expect-${kind}`;
+      argsText = argsText.trimStart().slice(kind?.length ?? 0);
+    }
+
+    if (!Object.keys(ARITY).includes(command)) {
+      throw new Error(`Line ${line}: unknown step "${source}"`);
+    }
+
+    try {
+      const args = parseArgs(argsText, ARITY[command as TestCommand]);
+
+      if (command === 'wait' && !/^\d+$/.test(args[0])) {
+        throw new Error('wait takes milliseconds');
+      }
+
+      steps.push({ line, source, command: command as TestCommand, args });
+    } catch (error) {
+      throw new Error(`Line ${line}: ${error instanceof Error ? error.message : String(error)} in "${source}"`);
+    }
+  });
+
+  if (steps.length === 0) {
+    throw new Error('The test has no steps');
+  }
+
+  return steps;
+}
+
+/**
+ * Formats the results of a test for the model, failed steps with their error.
+ */
+export function formatTestResults(title: string, results: TestStepResult[]) {
+  const lines = results.map((result) => {
+    const mark = result.status === 'passed' ? '✓' : result.status === 'failed' ? '✗' : '-';
+    return `${mark} ${result.step}${result.error ? `\n    ${result.error}` : ''}`;
+  });
+
+  return `${title}\n${lines.join('\n')}`;
+}
@@ -0,0 +1,38 @@
+import { describe, expect, it } from 'vitest';
+import type { FileMap } from '~/lib/stores/files';
+import { applyReplacements, findReplaceMatches } from './searchReplace';
+
+const files: FileMap = {
+  '/home/project/src/a.ts': { type: 'file', content: 'const foo = 1;\nfoo += fooBar;\n', isBinary: false },
+  '/home/project/node_modules/lib/index.js': { type: 'file', content: 'foo', isBinary: false },
+};
+
+const query = { search: 'foo', replace: 'baz', isRegex: false, caseSensitive: true, isWordMatch: false };
+
+describe('findReplaceMatches', () => {
+  it('finds matches with their line and skips excluded folders', () => {
+    const matches = findReplaceMatches(files, query);
+
+    expect(matches.map((match) => [match.lineNumber, match.column])).toEqual([
+      [1, 6],
+      [2, 0],
+      [2, 7],
+    ]);
+  });
+
+  it('expands regex groups in the replacement', () => {
+    const matches = findReplaceMatches(files, { ...query, search: 'foo(\\w+)', replace: '$1Foo', isRegex: true });
+
+    expect(matches.map((match) => match.replacement)).toEqual(['BarFoo']);
+  });
+});
+
+describe('applyReplacements', () => {
+  it('only replaces the given matches', () => {
+    const content = (files['/home/project/src/a.ts'] as { content: string }).content;
+    const matches = findReplaceMatches(files, { ...query, isWordMatch: true });
+
+    expect(applyReplacements(content, matches.slice(1))).toBe('const foo = 1;\nbaz += fooBar;\n');
+    expect(applyReplacements(content, matches)).toBe('const baz = 1;\nbaz += fooBar;\n');
+  });
+});
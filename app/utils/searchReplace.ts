@@ -0,0 +1,146 @@
+import type { FileMap } from '~/lib/stores/files';
+
+export interface ReplaceQuery {
+  search: string;
+  replace: string;
+  isRegex: boolean;
+  caseSensitive: boolean;
+  isWordMatch: boolean;
+}
+
+export interface ReplaceMatch {
+  // `<path>:<offset>`, stable as long as the file doesn't change
+  id: string;
+  path: string;
+  start: number;
+  end: number;
+  lineNumber: number;
+  lineText: string;
+
+  // column of the match in `lineText`
+  column: number;
+  matchText: string;
+  replacement: string;
+}
+
+// same folders the search leaves out
+const EXCLUDED_PATHS = /\/(?:node_modules|\.git|dist)\/|\/package-lock\.json$|\.lock$/;
+
+const MAX_MATCHES = 5000;
+
+function escapeRegExp(text: string) {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+}
+
+function countLines(text: string, from: number, to: number) {
+  let count = 0;
+
+  for (let i = from; i < to; i++) {
+    if (text.charCodeAt(i) === 10) {
+      count++;
+    }
+  }
+
+  return count;
+}
+
+/**
+ * Expands `$&`, `$1` and `$<name>` in a regex replacement the way `String.prototype.replace` does.
+ */
+function expandReplacement(template: string, match: RegExpMatchArray) {
+  return template.replace(/\$(\$|&|\d{1,2}|<([^>]+)>)/g, (token, reference: string, name?: string) => {
+    if (reference === '$') {
+      return '$';
+    }
+
+    if (reference === '&') {
+      return match[0];
+    }
+
+    if (name !== undefined) {
+      return match.groups?.[name] ?? '';
+    }
+
+    const group = Number(reference);
+
+    return group > 0 && group < match.length ? (match[group] ?? '') : token;
+  });
+}
+
+/**
+ * Builds the pattern for a query, throws when the regular expression is invalid.
+ */
+export function buildSearchPattern(query: Omit<ReplaceQuery, 'replace'>) {
+  let source = query.isRegex ? query.search : escapeRegExp(query.search);
+
+  if (query.isWordMatch) {
+    source = `\\b(?:${source})\\b`;
+  }
+
+  return new RegExp(source, query.caseSensitive ? 'gm' : 'gim');
+}
+
+/**
+ * Finds every match of the query in the text files of the project, with the text each one is replaced with. In regex
+ * mode the replacement may reference groups as `$1` or `$<name>`.
+ */
+export function findReplaceMatches(files: FileMap, query: ReplaceQuery): ReplaceMatch[] {
+  if (!query.search) {
+    return [];
+  }
+
+  const pattern = buildSearchPattern(query);
+  const matches: ReplaceMatch[] = [];
+
+  for (const [path, dirent] of Object.entries(files)) {
+    if (dirent?.type !== 'file' || dirent.isBinary || EXCLUDED_PATHS.test(path)) {
+      continue;
+    }
+
+    const content = dirent.content;
+    let lineNumber = 1;
+    let lineCountedTo = 0;
+
+    for (const match of content.matchAll(pattern)) {
+      if (match[0].length === 0) {
+        // empty matches (e.g. `^`) have nothing to preview or replace
+        continue;
+      }
+
+      const start = match.index ?? 0;
+      const end = start + match[0].length;
+      const lineStart = start === 0 ? 0 : content.lastIndexOf('\n', start - 1) + 1;
+      const lineEnd = content.indexOf('\n', start);
+
+      lineNumber += countLines(content, lineCountedTo, start);
+      lineCountedTo = start;
+
+      matches.push({
+        id: `${path}:${start}`,
+        path,
+        start,
+        end,
+        lineNumber,
+        lineText: content.slice(lineStart, lineEnd === -1 ? undefined : lineEnd),
+        column: start - lineStart,
+        matchText: match[0],
+        replacement: query.isRegex ? expandReplacement(query.replace, match) : query.replace,
+      });
+
+      if (matches.length >= MAX_MATCHES) {
+        return matches;
+      }
+    }
+  }
+
+  return matches;
+}
+
+/**
+ * Applies matches of one file, in any order, to its content.
+ */
+export function applyReplacements(content: string, matches: ReplaceMatch[]) {
+  return [...matches]
+    .sort((a, b) => b.start - a.start)
+    .reduce((result, match) => result.slice(0, match.start) + match.replacement + result.slice(match.end), content);
+}
@@ -12,6 +12,7 @@ import { createWindow } from './ui/window';
 import { initCookies, storeCookies } from './utils/cookie';
 import { loadServerBuild, serveAsset } from './utils/serve';
 import { reloadOnChange } from './utils/reload';
+import { setupPreviewTunnel } from './utils/tunnel';
 
 Object.assign(console, log.functions);
 
@@ -169,6 +170,8 @@ declare global {
 
   console.log('Using renderer URL:', rendererURL);
 
+  setupPreviewTunnel();
+
   const win = await createWindow(rendererURL);
 
   app.on('activate', async () => {
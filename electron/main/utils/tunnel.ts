@@ -0,0 +1,217 @@
+import { BrowserWindow, ipcMain, type WebContents } from 'electron';
+import crypto from 'node:crypto';
+import http from 'node:http';
+import os from 'node:os';
+
+interface TunnelRequest {
+  requestId: string;
+  method: string;
+  path: string;
+  headers: Record<string, string>;
+  body?: Uint8Array;
+}
+
+interface TunnelResponse {
+  requestId: string;
+  status: number;
+  headers: [string, string][];
+  body?: Uint8Array;
+  error?: string;
+}
+
+interface Tunnel {
+  server: http.Server;
+  url: string;
+  token: string;
+
+  // the renderer that answers the requests
+  webContents: WebContents;
+}
+
+const REQUEST_TIMEOUT = 30_000;
+
+// phones open the QR code link with the token once, the cookie keeps them in afterwards
+const TOKEN_PARAM = 'smack_tunnel';
+const TOKEN_COOKIE = 'smack_tunnel';
+
+// the relay fetches decoded bodies, the length and encoding of the preview server do not apply anymore
+const SKIPPED_HEADERS = ['content-encoding', 'content-length', 'transfer-encoding', 'connection'];
+
+let tunnel: Tunnel | undefined;
+const pending = new Map<string, (response: TunnelResponse) => void>();
+
+function getLanAddress() {
+  for (const addresses of Object.values(os.networkInterfaces())) {
+    const address = addresses?.find((item) => item.family === 'IPv4' && !item.internal);
+
+    if (address) {
+      return address.address;
+    }
+  }
+
+  return undefined;
+}
+
+function readBody(req: http.IncomingMessage) {
+  return new Promise<Uint8Array | undefined>((resolve, reject) => {
+    const chunks: Buffer[] = [];
+
+    req.on('data', (chunk: Buffer) => chunks.push(chunk));
+    req.on('end', () => resolve(chunks.length > 0 ? new Uint8Array(Buffer.concat(chunks)) : undefined));
+    req.on('error', reject);
+  });
+}
+
+function matchesToken(value: string | undefined, token: string) {
+  return (
+    value !== undefined &&
+    value.length === token.length &&
+    crypto.timingSafeEqual(Buffer.from(value), Buffer.from(token))
+  );
+}
+
+function readCookies(req: http.IncomingMessage) {
+  return (req.headers.cookie ?? '')
+    .split(';')
+    .map((cookie) => cookie.trim().split('='))
+    .filter(([name]) => name)
+    .map(([name, ...value]) => [name, value.join('=')] as const);
+}
+
+/**
+ * Lets requests with the token through. The first request carries it in the query, it gets the cookie and is
+ * redirected to the same address without the token so that the preview never sees it.
+ */
+function authorize(req: http.IncomingMessage, res: http.ServerResponse, token: string) {
+  const url = new URL(req.url ?? '/', 'http://tunnel');
+
+  if (matchesToken(url.searchParams.get(TOKEN_PARAM) ?? undefined, token)) {
+    url.searchParams.delete(TOKEN_PARAM);
+    res.writeHead(302, {
+      location: url.pathname + url.search,
+      'set-cookie': `${TOKEN_COOKIE}=${token}; Path=/; HttpOnly; SameSite=Lax`,
+    });
+    res.end();
+
+    return false;
+  }
+
+  if (readCookies(req).some(([name, value]) => name === TOKEN_COOKIE && matchesToken(value, token))) {
+    return true;
+  }
+
+  res.writeHead(403, { 'content-type': 'text/plain' });
+  res.end('Open the preview with the QR code of the desktop app');
+
+  return false;
+}
+
+function stopTunnel() {
+  tunnel?.server.close();
+  tunnel = undefined;
+}
+
+async function startTunnel(webContents: WebContents): Promise<Tunnel> {
+  const address = getLanAddress();
+
+  if (!address) {
+    throw new Error('This computer is not connected to a local network');
+  }
+
+  const token = crypto.randomBytes(24).toString('hex');
+
+  const relay = async (req: http.IncomingMessage, res: http.ServerResponse) => {
+    if (!authorize(req, res, token)) {
+      return;
+    }
+
+    const requestId = crypto.randomUUID();
+    const headers: Record<string, string> = {};
+
+    // browsers refuse to send a cookie header from fetch, and the preview has no use for the token anyway
+    for (const [name, value] of Object.entries(req.headers)) {
+      if (typeof value === 'string' && name !== 'host' && name !== 'cookie') {
+        headers[name] = value;
+      }
+    }
+
+    try {
+      const body = await readBody(req);
+      const response = await new Promise<TunnelResponse>((resolve, reject) => {
+        const timer = setTimeout(() => {
+          pending.delete(requestId);
+          reject(new Error('The preview did not answer'));
+        }, REQUEST_TIMEOUT);
+
+        pending.set(requestId, (value) => {
+          clearTimeout(timer);
+          resolve(value);
+        });
+
+        const request: TunnelRequest = { requestId, method: req.method ?? 'GET', path: req.url ?? '/', headers, body };
+        webContents.send('preview-tunnel:request', request);
+      });
+
+      if (response.error) {
+        throw new Error(response.error);
+      }
+
+      const responseHeaders = response.headers
+        .filter(([name]) => !SKIPPED_HEADERS.includes(name.toLowerCase()))
+        .flat();
+
+      res.writeHead(response.status, responseHeaders);
+      res.end(response.body ? Buffer.from(response.body) : undefined);
+    } catch (error) {
+      res.writeHead(502, { 'content-type': 'text/plain' });
+      res.end(error instanceof Error ? error.message : String(error));
+    }
+  };
+
+  const server = http.createServer(relay);
+
+  const port = await new Promise<number>((resolve, reject) => {
+    server.once('error', reject);
+    server.listen(0, '0.0.0.0', () => resolve((server.address() as { port: number }).port));
+  });
+
+  return { server, url: `http://${address}:${port}`, token, webContents };
+}
+
+/**
+ * Serves the preview of a renderer on the local network so that phones can open it. WebContainer servers only
+ * exist inside the renderer, so every request is relayed over IPC to the window that started the tunnel and
+ * answered from inside the preview. The tunnel closes with that window.
+ *
+ * Cookies do not cross the tunnel: the relay answers with a browser `fetch`, which never exposes `set-cookie`
+ * and cannot send a `cookie` header, so previews that sign users in with cookies only work on the desktop.
+ */
+export function setupPreviewTunnel() {
+  ipcMain.handle('preview-tunnel:start', async (event) => {
+    let current = tunnel;
+
+    if (!current || current.webContents !== event.sender) {
+      stopTunnel();
+
+      const started = await startTunnel(event.sender);
+      current = tunnel = started;
+
+      BrowserWindow.fromWebContents(event.sender)?.once('closed', () => {
+        if (tunnel === started) {
+          stopTunnel();
+        }
+      });
+
+      console.log('Preview tunnel listening on', started.url);
+    }
+
+    return { url: current.url, token: current.token };
+  });
+
+  ipcMain.handle('preview-tunnel:stop', () => stopTunnel());
+
+  ipcMain.on('preview-tunnel:response', (_event, response: TunnelResponse) => {
+    pending.get(response.requestId)?.(response);
+    pending.delete(response.requestId);
+  });
+}
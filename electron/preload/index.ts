@@ -19,4 +19,26 @@ const ipc = {
   },
 };
 
+// relays requests of phones on the local network to the preview, see `setupPreviewTunnel`
+const previewTunnel = {
+  start(): Promise<{ url: string; token: string }> {
+    return ipcRenderer.invoke('preview-tunnel:start');
+  },
+  stop(): Promise<void> {
+    return ipcRenderer.invoke('preview-tunnel:stop');
+  },
+  onRequest(callback: (request: unknown) => void) {
+    const listener = (_event: IpcRendererEvent, request: unknown) => callback(request);
+    ipcRenderer.on('preview-tunnel:request', listener);
+
+    return () => {
+      ipcRenderer.removeListener('preview-tunnel:request', listener);
+    };
+  },
+  respond(response: unknown) {
+    ipcRenderer.send('preview-tunnel:response', response);
+  },
+};
+
 contextBridge.exposeInMainWorld('ipc', ipc);
+contextBridge.exposeInMainWorld('previewTunnel', previewTunnel);
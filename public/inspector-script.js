@@ -128,7 +128,24 @@
       // Add new readable formats
       selector: createReadableSelector(element),
       displayText: createElementDisplayText(element),
-      elementPath: getElementPath(element)
+      elementPath: getElementPath(element),
+      source: getElementSource(element)
+    };
+  }
+
+  // JSX location stamped by the source plugin of the visual editor, from the closest stamped element
+  function getElementSource(element) {
+    const stamped = element.closest('[data-source-file]');
+    if (!stamped) return undefined;
+
+    const onlyText = Array.from(element.childNodes).every(node => node.nodeType === Node.TEXT_NODE);
+
+    return {
+      file: stamped.getAttribute('data-source-file'),
+      line: Number(stamped.getAttribute('data-source-line')),
+      column: Number(stamped.getAttribute('data-source-column')),
+      exact: stamped === element,
+      text: onlyText ? element.textContent : undefined
     };
   }
 
@@ -283,10 +300,202 @@
     }
   }
 
+  // Function to inline the page's stylesheets, cross-origin sheets can't be read and are skipped
+  const CSS_URL_PATTERN = /url\(\s*(['"]?)([^'")]+)\1\s*\)/g;
+
+  // an SVG image can't load anything itself, so subresources are embedded as data URLs
+  function toDataUrl(url, cache) {
+    if (url.startsWith('data:')) return Promise.resolve(url);
+
+    if (!cache.has(url)) {
+      const dataUrl = fetch(url)
+        .then(response => (response.ok ? response.blob() : Promise.reject(new Error(response.statusText))))
+        .then(blob => new Promise((resolve, reject) => {
+          const reader = new FileReader();
+          reader.onload = () => resolve(reader.result);
+          reader.onerror = () => reject(reader.error);
+          reader.readAsDataURL(blob);
+        }))
+        // resources that can't be fetched, e.g. without CORS, stay missing from the capture
+        .catch(() => url);
+
+      cache.set(url, dataUrl);
+    }
+
+    return cache.get(url);
+  }
+
+  // embeds backgrounds, fonts and other url() references, relative ones are resolved against `baseUrl`
+  async function inlineCssUrls(css, baseUrl, cache) {
+    const urls = new Set();
+
+    for (const match of css.matchAll(CSS_URL_PATTERN)) {
+      // references to SVG elements such as url(#gradient) are not resources
+      if (!match[2].startsWith('#')) urls.add(match[2]);
+    }
+
+    const dataUrls = new Map(
+      await Promise.all(Array.from(urls, async url => [url, await toDataUrl(new URL(url, baseUrl).href, cache)])),
+    );
+
+    return css.replace(CSS_URL_PATTERN, (match, quote, url) => (dataUrls.has(url) ? `url("${dataUrls.get(url)}")` : match));
+  }
+
+  async function collectStyles(cache) {
+    const sheets = await Promise.all(Array.from(document.styleSheets).map(sheet => {
+      let css = '';
+
+      try {
+        for (const rule of Array.from(sheet.cssRules)) {
+          css += rule.cssText + '\n';
+        }
+      } catch (e) {
+        // cross-origin stylesheet
+      }
+
+      return inlineCssUrls(css, sheet.href || document.baseURI, cache);
+    }));
+
+    return sheets.join('');
+  }
+
+  // images and canvas contents are embedded into the clone, styles with url() references are rewritten
+  async function inlineResources(clone, cache) {
+    const liveImages = document.querySelectorAll('img');
+    const liveCanvases = document.querySelectorAll('canvas');
+
+    // the SVG image would pick a <source> of a <picture> that it then can't load
+    clone.querySelectorAll('picture source').forEach(el => el.remove());
+
+    const images = Array.from(clone.querySelectorAll('img')).map(async (el, index) => {
+      const live = liveImages[index];
+      const src = live && (live.currentSrc || live.src);
+
+      el.removeAttribute('srcset');
+      el.removeAttribute('sizes');
+      el.removeAttribute('loading');
+
+      if (src) el.setAttribute('src', await toDataUrl(src, cache));
+    });
+
+    clone.querySelectorAll('canvas').forEach((el, index) => {
+      const live = liveCanvases[index];
+
+      if (!live) return;
+
+      const image = document.createElement('img');
+
+      for (const attr of Array.from(el.attributes)) {
+        image.setAttribute(attr.name, attr.value);
+      }
+
+      image.style.width = `${live.offsetWidth}px`;
+      image.style.height = `${live.offsetHeight}px`;
+
+      try {
+        image.setAttribute('src', live.toDataURL());
+      } catch (e) {
+        // a canvas that drew images from other origins can't be read
+      }
+
+      el.replaceWith(image);
+    });
+
+    const inlineStyles = Array.from(clone.querySelectorAll('[style*="url("]')).map(async el => {
+      el.setAttribute('style', await inlineCssUrls(el.getAttribute('style'), document.baseURI, cache));
+    });
+
+    await Promise.all([...images, ...inlineStyles]);
+  }
+
+  // Function to render the page into a PNG data URL through an SVG foreignObject
+  async function captureScreenshot() {
+    const width = window.innerWidth;
+    const height = window.innerHeight;
+    const clone = document.documentElement.cloneNode(true);
+    const cache = new Map();
+
+    // scripts don't run in the snapshot and inspector highlights shouldn't show up in it
+    clone.querySelectorAll('script').forEach(el => el.remove());
+    clone.querySelectorAll('.inspector-highlight').forEach(el => el.classList.remove('inspector-highlight'));
+
+    // form state lives in properties, not attributes
+    const liveInputs = document.querySelectorAll('input, textarea');
+    clone.querySelectorAll('input, textarea').forEach((el, index) => {
+      const live = liveInputs[index];
+
+      if (!live) return;
+
+      if (el.tagName === 'TEXTAREA') {
+        el.textContent = live.value;
+      } else if (live.type === 'checkbox' || live.type === 'radio') {
+        if (live.checked) el.setAttribute('checked', '');
+      } else {
+        el.setAttribute('value', live.value);
+      }
+    });
+
+    await inlineResources(clone, cache);
+
+    const style = document.createElement('style');
+    style.textContent = await collectStyles(cache);
+    clone.querySelector('head')?.appendChild(style);
+
+    const body = clone.querySelector('body');
+
+    if (body) {
+      body.style.margin = getComputedStyle(document.body).margin;
+      body.style.transform = `translate(${-window.scrollX}px, ${-window.scrollY}px)`;
+    }
+
+    const html = new XMLSerializer().serializeToString(clone);
+    const svg =
+      `<svg xmlns="http://www.w3.org/2000/svg" width="${width}" height="${height}">` +
+      `<foreignObject width="100%" height="100%">${html}</foreignObject></svg>`;
+
+    return new Promise((resolve, reject) => {
+      const image = new Image();
+
+      image.onload = () => {
+        const canvas = document.createElement('canvas');
+        canvas.width = width;
+        canvas.height = height;
+
+        const ctx = canvas.getContext('2d');
+        ctx.fillStyle = getComputedStyle(document.body).backgroundColor || '#ffffff';
+        ctx.fillRect(0, 0, width, height);
+        ctx.drawImage(image, 0, 0);
+
+        try {
+          resolve(canvas.toDataURL('image/png'));
+        } catch (error) {
+          // images from other origins taint the canvas
+          reject(error);
+        }
+      };
+      image.onerror = () => reject(new Error('Failed to render the page'));
+      image.src = 'data:image/svg+xml;charset=utf-8,' + encodeURIComponent(svg);
+    });
+  }
+
   // Listen for messages from parent
   window.addEventListener('message', function(event) {
     if (event.data.type === 'INSPECTOR_ACTIVATE') {
       setInspectorActive(event.data.active);
+    } else if (event.data.type === 'PREVIEW_SCREENSHOT_REQUEST') {
+      const requestId = event.data.requestId;
+
+      captureScreenshot()
+        .then(dataUrl => {
+          window.parent.postMessage({ type: 'PREVIEW_SCREENSHOT_RESPONSE', requestId, dataUrl }, '*');
+        })
+        .catch(error => {
+          window.parent.postMessage({
+            type: 'PREVIEW_SCREENSHOT_RESPONSE',
+            requestId,
+            error: error && error.message ? error.message : String(error)
+          }, '*');
+        });
     }
   });
 
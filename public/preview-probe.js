@@ -0,0 +1,254 @@
+(function() {
+  // Forwards console errors, uncaught exceptions, unhandled rejections and failed fetches to the editor, with the
+  // stack traces mapped back to the project sources through the source maps of the dev server.
+  if (window.__smackPreviewProbe) {
+    return;
+  }
+
+  window.__smackPreviewProbe = true;
+
+  const MAX_REPORTS = 50;
+  const BASE64 = 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/';
+  const originalFetch = window.fetch.bind(window);
+  const originalConsoleError = console.error.bind(console);
+  const sourceMaps = new Map();
+  let reportCount = 0;
+
+  function decodeVlq(segment) {
+    const values = [];
+    let value = 0;
+    let shift = 0;
+
+    for (const char of segment) {
+      const digit = BASE64.indexOf(char);
+
+      value += (digit & 31) << shift;
+
+      if (digit & 32) {
+        shift += 5;
+      } else {
+        values.push(value & 1 ? -(value >> 1) : value >> 1);
+        value = 0;
+        shift = 0;
+      }
+    }
+
+    return values;
+  }
+
+  // turns `mappings` into `[generatedColumn, sourceIndex, sourceLine, sourceColumn]` segments per generated line
+  function decodeMappings(mappings) {
+    let sourceIndex = 0;
+    let sourceLine = 0;
+    let sourceColumn = 0;
+
+    return mappings.split(';').map(line => {
+      let generatedColumn = 0;
+
+      return line
+        .split(',')
+        .filter(Boolean)
+        .map(segment => {
+          const values = decodeVlq(segment);
+
+          generatedColumn += values[0];
+
+          if (values.length < 4) {
+            return [generatedColumn];
+          }
+
+          sourceIndex += values[1];
+          sourceLine += values[2];
+          sourceColumn += values[3];
+
+          return [generatedColumn, sourceIndex, sourceLine, sourceColumn];
+        });
+    });
+  }
+
+  function decodeDataUrl(url) {
+    const data = url.slice(url.indexOf(',') + 1);
+
+    if (!/;base64,/.test(url)) {
+      return decodeURIComponent(data);
+    }
+
+    const bytes = Uint8Array.from(atob(data), char => char.charCodeAt(0));
+
+    return new TextDecoder().decode(bytes);
+  }
+
+  function loadSourceMap(fileUrl) {
+    if (!sourceMaps.has(fileUrl)) {
+      const promise = originalFetch(fileUrl)
+        .then(response => response.text())
+        .then(code => {
+          const match = /\/\/# sourceMappingURL=(\S+)\s*$/.exec(code);
+
+          if (!match) {
+            return null;
+          }
+
+          if (match[1].startsWith('data:')) {
+            return JSON.parse(decodeDataUrl(match[1]));
+          }
+
+          return originalFetch(new URL(match[1], fileUrl)).then(response => response.json());
+        })
+        .then(map => map && {
+          sources: map.sources,
+          sourceRoot: map.sourceRoot || '',
+          lines: decodeMappings(map.mappings),
+        })
+        .catch(() => null);
+
+      sourceMaps.set(fileUrl, promise);
+    }
+
+    return sourceMaps.get(fileUrl);
+  }
+
+  function lookup(map, line, column) {
+    const segments = map.lines[line - 1];
+
+    if (!segments) {
+      return null;
+    }
+
+    let found = null;
+
+    for (const segment of segments) {
+      if (segment[0] > column - 1) {
+        break;
+      }
+
+      if (segment.length === 4) {
+        found = segment;
+      }
+    }
+
+    if (!found) {
+      return null;
+    }
+
+    return { source: map.sourceRoot + map.sources[found[1]], line: found[2] + 1, column: found[3] + 1 };
+  }
+
+  async function mapStack(stack) {
+    if (!stack) {
+      return '';
+    }
+
+    const lines = await Promise.all(
+      stack.split('\n').map(async line => {
+        const match = /(https?:\/\/[^\s()]+?):(\d+):(\d+)/.exec(line);
+
+        if (!match || new URL(match[1]).origin !== location.origin) {
+          return line;
+        }
+
+        const map = await loadSourceMap(match[1]);
+        const position = map && lookup(map, Number(match[2]), Number(match[3]));
+
+        if (!position) {
+          return line.replace(match[1], match[1].replace(location.origin, '').replace(/\?.*$/, ''));
+        }
+
+        return line.replace(match[0], `${position.source}:${position.line}:${position.column}`);
+      }),
+    );
+
+    return lines.join('\n');
+  }
+
+  function stringify(value) {
+    if (value instanceof Error) {
+      return value.message;
+    }
+
+    if (typeof value === 'string') {
+      return value;
+    }
+
+    try {
+      return JSON.stringify(value);
+    } catch {
+      return String(value);
+    }
+  }
+
+  function report(kind, message, stack) {
+    if (reportCount >= MAX_REPORTS) {
+      return;
+    }
+
+    reportCount++;
+
+    mapStack(stack).then(mappedStack => {
+      window.parent.postMessage(
+        {
+          type: 'PREVIEW_RUNTIME_ERROR',
+          error: {
+            kind,
+            message: String(message).slice(0, 2000),
+            stack: mappedStack,
+            url: location.pathname + location.search + location.hash,
+            timestamp: Date.now(),
+          },
+        },
+        '*',
+      );
+    });
+  }
+
+  // drops the message and the frames of the probe from a stack taken in one of its wrappers
+  function callerStack() {
+    const stack = new Error().stack || '';
+    return stack.split('\n').slice(3).join('\n');
+  }
+
+  console.error = function(...args) {
+    originalConsoleError(...args);
+
+    const error = args.find(arg => arg instanceof Error);
+    report('console', args.map(stringify).join(' '), error ? error.stack : callerStack());
+  };
+
+  window.addEventListener('error', event => {
+    // resource loading errors have no message and are reported by the fetch wrapper or the browser
+    if (!event.message) {
+      return;
+    }
+
+    report('exception', event.message, event.error && event.error.stack);
+  });
+
+  window.addEventListener('unhandledrejection', event => {
+    const reason = event.reason;
+    report('rejection', stringify(reason), reason instanceof Error ? reason.stack : '');
+  });
+
+  window.fetch = function(input, init) {
+    const stack = callerStack();
+    const url = typeof input === 'string' ? input : input instanceof URL ? input.href : input.url;
+    const method = (init && init.method) || (input instanceof Request ? input.method : 'GET');
+
+    return originalFetch(input, init).then(
+      response => {
+        // client errors are often expected, server errors rarely are
+        if (response.status >= 500) {
+          report('network', `${method} ${url} failed with ${response.status} ${response.statusText}`, stack);
+        }
+
+        return response;
+      },
+      error => {
+        if (!error || error.name !== 'AbortError') {
+          report('network', `${method} ${url} failed: ${stringify(error)}`, stack);
+        }
+
+        throw error;
+      },
+    );
+  };
+})();
@@ -0,0 +1,166 @@
+(function() {
+  // Runs the steps of `test` actions inside the preview, the editor drives it step by step and handles navigation.
+  if (window.__smackPreviewTestRunner) {
+    return;
+  }
+
+  window.__smackPreviewTestRunner = true;
+
+  const POLL_INTERVAL = 50;
+
+  function isVisible(element) {
+    const style = getComputedStyle(element);
+    return style.visibility !== 'hidden' && style.display !== 'none' && element.getClientRects().length > 0;
+  }
+
+  // `text=...` picks the innermost visible element whose text contains it, like in Playwright
+  function findElement(selector) {
+    if (!selector.startsWith('text=')) {
+      return document.querySelector(selector);
+    }
+
+    const text = selector.slice(5).trim().toLowerCase();
+    const matches = Array.from(document.body.querySelectorAll('*')).filter(element => {
+      const content = (element.innerText || element.value || element.getAttribute('aria-label') || '').toLowerCase();
+      return content.includes(text) && isVisible(element);
+    });
+
+    return matches.find(element => !matches.some(other => other !== element && element.contains(other))) || null;
+  }
+
+  function waitFor(check, timeout, message) {
+    const deadline = Date.now() + timeout;
+
+    return new Promise((resolve, reject) => {
+      function poll() {
+        const result = check();
+
+        if (result) {
+          resolve(result);
+        } else if (Date.now() > deadline) {
+          reject(new Error(message));
+        } else {
+          setTimeout(poll, POLL_INTERVAL);
+        }
+      }
+
+      poll();
+    });
+  }
+
+  function waitForElement(selector, timeout) {
+    return waitFor(
+      () => {
+        const element = findElement(selector);
+        return element && isVisible(element) ? element : null;
+      },
+      timeout,
+      `No visible element matches "${selector}"`,
+    );
+  }
+
+  // React tracks input values through the native setter, assigning `value` directly is not seen as a change
+  function setValue(element, value) {
+    if (element.isContentEditable) {
+      element.textContent = value;
+    } else {
+      const descriptor = Object.getOwnPropertyDescriptor(Object.getPrototypeOf(element), 'value');
+
+      if (descriptor && descriptor.set) {
+        descriptor.set.call(element, value);
+      } else {
+        element.value = value;
+      }
+    }
+
+    element.dispatchEvent(new Event('input', { bubbles: true }));
+    element.dispatchEvent(new Event('change', { bubbles: true }));
+  }
+
+  function press(key) {
+    const target = document.activeElement || document.body;
+    const init = { key, bubbles: true, cancelable: true };
+    const proceed = target.dispatchEvent(new KeyboardEvent('keydown', init));
+
+    target.dispatchEvent(new KeyboardEvent('keyup', init));
+
+    if (proceed && key === 'Enter' && target.form) {
+      target.form.requestSubmit();
+    }
+  }
+
+  async function runStep(step, timeout) {
+    const [first, second] = step.args;
+
+    switch (step.command) {
+      case 'click': {
+        const element = await waitForElement(first, timeout);
+
+        element.scrollIntoView({ block: 'center' });
+        element.click();
+        break;
+      }
+      case 'fill': {
+        const element = await waitForElement(first, timeout);
+
+        element.focus();
+        setValue(element, second);
+        break;
+      }
+      case 'press': {
+        press(first);
+        break;
+      }
+      case 'wait': {
+        await new Promise(resolve => setTimeout(resolve, Number(first)));
+        break;
+      }
+      case 'expect-text': {
+        await waitFor(() => document.body.innerText.includes(first), timeout, `The page does not show "${first}"`);
+        break;
+      }
+      case 'expect-no-text': {
+        await waitFor(() => !document.body.innerText.includes(first), timeout, `The page still shows "${first}"`);
+        break;
+      }
+      case 'expect-visible': {
+        await waitForElement(first, timeout);
+        break;
+      }
+      case 'expect-hidden': {
+        await waitFor(
+          () => {
+            const element = findElement(first);
+            return !element || !isVisible(element);
+          },
+          timeout,
+          `"${first}" is still visible`,
+        );
+        break;
+      }
+      case 'expect-url': {
+        const current = () => (first.includes('?') ? location.pathname + location.search : location.pathname);
+        await waitFor(() => current() === first, timeout, `The page did not get to ${first}`);
+        break;
+      }
+      default:
+        throw new Error(`Unknown step ${step.command}`);
+    }
+  }
+
+  window.addEventListener('message', event => {
+    if (event.source !== window.parent || !event.data || event.data.type !== 'PREVIEW_TEST_STEP') {
+      return;
+    }
+
+    const { requestId, step, timeout } = event.data;
+
+    runStep(step, timeout).then(
+      () => window.parent.postMessage({ type: 'PREVIEW_TEST_RESULT', requestId, ok: true }, '*'),
+      error => {
+        const message = error instanceof Error ? error.message : String(error);
+        window.parent.postMessage({ type: 'PREVIEW_TEST_RESULT', requestId, ok: false, error: message }, '*');
+      },
+    );
+  });
+})();
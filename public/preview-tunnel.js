@@ -0,0 +1,39 @@
+(function() {
+  // Answers the requests that phones send to the preview tunnel of the desktop app from inside the preview, where the
+  // service worker of WebContainer serves them. It runs before the probe so that relayed requests are not reported.
+  if (window.__smackPreviewTunnel) {
+    return;
+  }
+
+  window.__smackPreviewTunnel = true;
+
+  const originalFetch = window.fetch.bind(window);
+
+  window.addEventListener('message', event => {
+    if (event.source !== window.parent || !event.data || event.data.type !== 'PREVIEW_TUNNEL_REQUEST') {
+      return;
+    }
+
+    const { requestId, method, path, headers, body } = event.data;
+
+    originalFetch(path, { method, headers, body: method === 'GET' || method === 'HEAD' ? undefined : body })
+      .then(response =>
+        response.arrayBuffer().then(buffer => {
+          const message = {
+            type: 'PREVIEW_TUNNEL_RESPONSE',
+            requestId,
+            status: response.status,
+            // set-cookie is hidden from scripts and repeated headers arrive joined with commas
+            headers: Array.from(response.headers.entries()),
+            body: buffer,
+          };
+
+          window.parent.postMessage(message, '*', [buffer]);
+        }),
+      )
+      .catch(error => {
+        const message = error instanceof Error ? error.message : String(error);
+        window.parent.postMessage({ type: 'PREVIEW_TUNNEL_RESPONSE', requestId, error: message }, '*');
+      });
+  });
+})();
@@ -2,6 +2,10 @@ interface Env {
   RUNNING_IN_DOCKER: boolean;
   DEFAULT_NUM_CTX: number;
   ANTHROPIC_API_KEY: string;
+  OPENAI_API_KEY: string;
+  COHERE_API_KEY: string;
+  HYPERBOLIC_API_KEY: string;
+  HYPERBOLIC_API_BASE_URL: string;
   GEMINI_API_KEY: string;
   GROQ_API_KEY: string;
   HUGGINGFACE_API_KEY: string;
@@ -19,4 +23,13 @@ interface Env {
   XAI_API_KEY: string;
   PERPLEXITY_API_KEY: string;
   AWS_BEDROCK_CONFIG: string;
+  LLM_FALLBACK_CHAIN: string;
+  SMACK_SERVER_MODE: string;
+  SMACK_SERVER_URL: string;
+  SMACK_SERVER_COMMAND: string;
+  SMACK_SERVER_ARGS: string;
+  SMACK_MODEL_PATH: string;
+  SMACK_CONTEXT_SIZE: string;
+  SMACK_STARTUP_TIMEOUT_MS: string;
+  SMACK_FALLBACK_MODEL: string;
 }
\ No newline at end of file